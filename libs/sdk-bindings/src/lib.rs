@@ -14,20 +14,20 @@ use breez_sdk_core::{
     LnUrlCallbackStatus, LnUrlErrorData, LnUrlPayError, LnUrlPayErrorData, LnUrlPayRequest,
     LnUrlPayRequestData, LnUrlWithdrawError, LnUrlWithdrawRequest, LnUrlWithdrawRequestData,
     LnUrlWithdrawResult, LnUrlWithdrawSuccessData, LocaleOverrides, LocalizedName, LogEntry,
-    LogStream, LspInformation, MessageSuccessActionData, MetadataFilter, MetadataItem, Network,
-    NodeState, OnchainPaymentLimitsResponse, OpenChannelFeeRequest, OpenChannelFeeResponse,
-    OpeningFeeParams, OpeningFeeParamsMenu, PayOnchainRequest, PayOnchainResponse, Payment,
-    PaymentDetails, PaymentFailedData, PaymentStatus, PaymentType, PaymentTypeFilter,
-    PrepareOnchainPaymentRequest, PrepareOnchainPaymentResponse, PrepareRedeemOnchainFundsRequest,
-    PrepareRedeemOnchainFundsResponse, PrepareRefundRequest, PrepareRefundResponse, Rate,
-    ReceiveOnchainRequest, ReceivePaymentRequest, ReceivePaymentResponse, RecommendedFees,
-    RedeemOnchainFundsRequest, RedeemOnchainFundsResponse, RefundRequest, RefundResponse,
-    ReportIssueRequest, ReportPaymentFailureDetails, ReverseSwapFeesRequest, ReverseSwapInfo,
-    ReverseSwapPairInfo, ReverseSwapStatus, RouteHint, RouteHintHop, SendPaymentRequest,
-    SendPaymentResponse, SendSpontaneousPaymentRequest, ServiceHealthCheckResponse,
-    SignMessageRequest, SignMessageResponse, StaticBackupRequest, StaticBackupResponse,
-    SuccessActionProcessed, SwapAmountType, SwapInfo, SwapStatus, Symbol, TlvEntry,
-    UnspentTransactionOutput, UrlSuccessActionData,
+    LogStream, LogStreamFilter, LspInformation, MessageSuccessActionData, MetadataFilter,
+    MetadataItem, Network, NodeState, OnchainPaymentLimitsResponse, OpenChannelFeeRequest,
+    OpenChannelFeeResponse, OpeningFeeParams, OpeningFeeParamsMenu, PayOnchainRequest,
+    PayOnchainResponse, Payment, PaymentDetails, PaymentFailedData, PaymentStatus, PaymentType,
+    PaymentTypeFilter, PrepareOnchainPaymentRequest, PrepareOnchainPaymentResponse,
+    PrepareRedeemOnchainFundsRequest, PrepareRedeemOnchainFundsResponse, PrepareRefundRequest,
+    PrepareRefundResponse, Rate, ReceiveOnchainRequest, ReceivePaymentRequest,
+    ReceivePaymentResponse, RecommendedFees, RedeemOnchainFundsRequest, RedeemOnchainFundsResponse,
+    RefundRequest, RefundResponse, ReportIssueRequest, ReportPaymentFailureDetails,
+    ReverseSwapFeesRequest, ReverseSwapInfo, ReverseSwapPairInfo, ReverseSwapStatus, RouteHint,
+    RouteHintHop, SendPaymentRequest, SendPaymentResponse, SendSpontaneousPaymentRequest,
+    ServiceHealthCheckResponse, SignMessageRequest, SignMessageResponse, StaticBackupRequest,
+    StaticBackupResponse, SuccessActionProcessed, SwapAmountType, SwapInfo, SwapStatus, Symbol,
+    TlvEntry, UnspentTransactionOutput, UrlSuccessActionData,
 };
 use log::{Level, LevelFilter, Metadata, Record};
 use once_cell::sync::{Lazy, OnceCell};
@@ -37,11 +37,12 @@ static LOG_INIT: OnceCell<bool> = OnceCell::new();
 
 struct BindingLogger {
     log_stream: Box<dyn LogStream>,
+    filter: Option<LogStreamFilter>,
 }
 
 impl BindingLogger {
-    fn init(log_stream: Box<dyn LogStream>) {
-        let binding_logger = BindingLogger { log_stream };
+    fn init(log_stream: Box<dyn LogStream>, filter: Option<LogStreamFilter>) {
+        let binding_logger = BindingLogger { log_stream, filter };
         log::set_boxed_logger(Box::new(binding_logger)).unwrap();
         log::set_max_level(LevelFilter::Trace);
     }
@@ -50,14 +51,18 @@ impl BindingLogger {
 impl log::Log for BindingLogger {
     fn enabled(&self, m: &Metadata) -> bool {
         // ignore the internal uniffi log to prevent infinite loop.
-        m.level() <= Level::Trace && *m.target() != *"breez_sdk_bindings::uniffi_binding"
+        m.level() <= Level::Trace
+            && *m.target() != *"breez_sdk_bindings::uniffi_binding"
+            && self.filter.as_ref().is_none_or(|filter| filter.enabled(m))
     }
 
     fn log(&self, record: &Record) {
-        self.log_stream.log(LogEntry {
-            line: record.args().to_string(),
-            level: record.level().as_str().to_string(),
-        });
+        if self.enabled(record.metadata()) {
+            self.log_stream.log(LogEntry {
+                line: record.args().to_string(),
+                level: record.level().as_str().to_string(),
+            });
+        }
     }
     fn flush(&self) {}
 }
@@ -103,11 +108,13 @@ pub fn connect(
 }
 
 /// If used, this must be called before `connect`
-pub fn set_log_stream(log_stream: Box<dyn LogStream>) -> SdkResult<()> {
+///
+/// The records can be filtered with `env_logger` directives, see [LogStreamFilter].
+pub fn set_log_stream(log_stream: Box<dyn LogStream>, filter: Option<String>) -> SdkResult<()> {
     LOG_INIT.set(true).map_err(|_| SdkError::Generic {
         err: "Log stream already created".into(),
     })?;
-    BindingLogger::init(log_stream);
+    BindingLogger::init(log_stream, filter.as_deref().map(LogStreamFilter::new));
     Ok(())
 }
 
@@ -423,6 +423,9 @@ pub fn parse_bolt12_offer(input: &str) -> Result<LNOffer, Bolt12ParseError> {
 
 #[cfg(test)]
 mod tests {
+    use lightning::bitcoin::hashes::{sha256, Hash};
+    use lightning::bitcoin::secp256k1::{Secp256k1, SecretKey};
+
     use crate::invoice::*;
 
     #[cfg(all(target_family = "wasm", target_os = "unknown"))]
@@ -481,6 +484,87 @@ mod tests {
         print!("{encoded:?}");
     }
 
+    fn build_signed_invoice(
+        description: Bolt11InvoiceDescription,
+        amount_msat: Option<u64>,
+        route_hints: Vec<RouteHint>,
+    ) -> (Bolt11Invoice, PublicKey) {
+        let secp = Secp256k1::new();
+        let secret_key = SecretKey::from_slice(&[0x2a; 32]).unwrap();
+        let mut builder = InvoiceBuilder::new(Currency::Bitcoin)
+            .invoice_description(description)
+            .payment_hash(sha256::Hash::hash(&[0x01; 32]))
+            .payment_secret(PaymentSecret([0x02; 32]))
+            .current_timestamp()
+            .expiry_time(std::time::Duration::from_secs(600))
+            .min_final_cltv_expiry_delta(144);
+        if let Some(amount_msat) = amount_msat {
+            builder = builder.amount_milli_satoshis(amount_msat);
+        }
+        for hint in route_hints {
+            builder = builder.private_route(hint.to_ldk_hint().unwrap());
+        }
+        let invoice = builder
+            .build_signed(|m| secp.sign_ecdsa_recoverable(m, &secret_key))
+            .unwrap();
+        (invoice, PublicKey::from_secret_key(&secp, &secret_key))
+    }
+
+    #[sdk_macros::test_all]
+    fn test_parse_invoice_fixed_amount() {
+        let hint = self::RouteHint {
+            hops: vec![self::RouteHintHop {
+                src_node_id: "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+                    .to_string(),
+                short_channel_id: "851x1234x1".to_string(),
+                fees_base_msat: 1000,
+                fees_proportional_millionths: 100,
+                cltv_expiry_delta: 144,
+                htlc_minimum_msat: None,
+                htlc_maximum_msat: None,
+            }],
+        };
+        let (invoice, payee) = build_signed_invoice(
+            Bolt11InvoiceDescription::Direct(Description::new("coffee".to_string()).unwrap()),
+            Some(21_000),
+            vec![hint.clone()],
+        );
+
+        let res = parse_invoice(&invoice.to_string()).unwrap();
+        assert_eq!(res.network, Network::Bitcoin);
+        assert_eq!(res.amount_msat, Some(21_000));
+        assert_eq!(res.description, Some("coffee".to_string()));
+        assert_eq!(res.description_hash, None);
+        assert_eq!(res.payee_pubkey, payee.to_string());
+        assert_eq!(
+            res.payment_hash,
+            invoice.payment_hash().encode_hex::<String>()
+        );
+        assert_eq!(res.payment_secret, vec![0x02; 32]);
+        assert_eq!(res.expiry, 600);
+        assert_eq!(res.min_final_cltv_expiry_delta, 144);
+        assert_eq!(res.routing_hints, vec![hint]);
+    }
+
+    #[sdk_macros::test_all]
+    fn test_parse_invoice_amountless() {
+        let description_hash = sha256::Hash::hash(b"a long description");
+        let (invoice, payee) = build_signed_invoice(
+            Bolt11InvoiceDescription::Hash(Sha256(description_hash)),
+            None,
+            vec![],
+        );
+
+        let res = parse_invoice(&format!("lightning:{invoice}")).unwrap();
+        assert_eq!(res.bolt11, invoice.to_string());
+        assert_eq!(res.amount_msat, None);
+        assert_eq!(res.description, None);
+        assert_eq!(res.description_hash, Some(description_hash.to_string()));
+        assert_eq!(res.payee_pubkey, payee.to_string());
+        assert_eq!(res.expiry, 600);
+        assert!(res.routing_hints.is_empty());
+    }
+
     #[sdk_macros::test_all]
     fn test_parse_invoice_invalid_bitcoin_network() {
         let payreq = String::from("lnbc110n1p38q3gtpp5ypz09jrd8p993snjwnm68cph4ftwp22le34xd4r8ftspwshxhmnsdqqxqyjw5qcqpxsp5htlg8ydpywvsa7h3u4hdn77ehs4z4e844em0apjyvmqfkzqhhd2q9qgsqqqyssqszpxzxt9uuqzymr7zxcdccj5g69s8q7zzjs7sgxn9ejhnvdh6gqjcy22mss2yexunagm5r2gqczh8k24cwrqml3njskm548aruhpwssq9nvrvz");
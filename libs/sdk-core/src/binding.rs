@@ -454,11 +454,13 @@ pub fn breez_events_stream(s: StreamSink<BreezEvent>) -> Result<()> {
 }
 
 /// If used, this must be called before `connect`. It can only be called once.
-pub fn breez_log_stream(s: StreamSink<LogEntry>) -> Result<()> {
+///
+/// The records can be filtered with `env_logger` directives, see [LogStreamFilter].
+pub fn breez_log_stream(s: StreamSink<LogEntry>, filter: Option<String>) -> Result<()> {
     LOG_INIT
         .set(true)
         .map_err(|_| anyhow!("Log stream already created"))?;
-    BindingLogger::init(s);
+    BindingLogger::init(s, filter.as_deref().map(LogStreamFilter::new));
     Ok(())
 }
 
@@ -815,11 +817,12 @@ impl EventListener for BindingEventListener {
 
 struct BindingLogger {
     log_stream: StreamSink<LogEntry>,
+    filter: Option<LogStreamFilter>,
 }
 
 impl BindingLogger {
-    fn init(log_stream: StreamSink<LogEntry>) {
-        let binding_logger = BindingLogger { log_stream };
+    fn init(log_stream: StreamSink<LogEntry>, filter: Option<LogStreamFilter>) {
+        let binding_logger = BindingLogger { log_stream, filter };
         log::set_boxed_logger(Box::new(binding_logger)).unwrap();
         log::set_max_level(LevelFilter::Trace);
     }
@@ -827,7 +830,10 @@ impl BindingLogger {
 
 impl log::Log for BindingLogger {
     fn enabled(&self, m: &Metadata) -> bool {
-        m.level() <= Level::Trace
+        match &self.filter {
+            Some(filter) => filter.enabled(m),
+            None => m.level() <= Level::Trace,
+        }
     }
 
     fn log(&self, record: &Record) {
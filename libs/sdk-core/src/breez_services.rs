@@ -16,17 +16,21 @@ use sdk_common::prelude::*;
 use serde_json::{json, Value};
 use tokio::sync::{mpsc, watch, Mutex};
 use tokio::time::{sleep, MissedTickBehavior};
+use tokio_util::sync::CancellationToken;
 
 use crate::backup::{BackupRequest, BackupTransport, BackupWatcher};
 use crate::buy::{BuyBitcoinApi, BuyBitcoinService};
 use crate::chain::{
-    ChainService, RecommendedFees, RedundantChainService, RedundantChainServiceTrait,
-    DEFAULT_MEMPOOL_SPACE_URL,
+    ChainService, OnchainTx, RecommendedFees, RedundantChainService, RedundantChainServiceTrait,
+    TxStatus, DEFAULT_MEMPOOL_SPACE_URL,
 };
 use crate::error::{
     ConnectError, ReceiveOnchainError, ReceiveOnchainResult, ReceivePaymentError,
-    RedeemOnchainResult, SdkError, SdkResult, SendOnchainError, SendPaymentError,
+    RedeemOnchainError, RedeemOnchainResult, SdkError, SdkResult, SendOnchainError,
+    SendPaymentError,
 };
+use crate::export::export_payments;
+use crate::ldk::sat_to_msat;
 use crate::lnurl::auth::SdkLnurlAuthSigner;
 use crate::lnurl::pay::*;
 use crate::lsp::LspInformation;
@@ -35,7 +39,10 @@ use crate::models::{
     ReverseSwapPairInfo, ReverseSwapServiceAPI, SwapInfo, SwapperAPI,
     INVOICE_PAYMENT_FEE_EXPIRY_SECONDS,
 };
-use crate::node_api::NodeAPI;
+use crate::node_api::{
+    split_node_address, NodeAPI, RemoteLockEvent, ReorgEvent, RestoreEvent, RestoreEventListener,
+    SpliceEvent,
+};
 use crate::persist::db::SqliteStorage;
 use crate::persist::swap::SwapStorage;
 use crate::persist::transactions::PaymentStorage;
@@ -79,6 +86,109 @@ pub enum BreezEvent {
     /// Indicates that a swap has been updated which may also
     /// include a status change
     SwapUpdated { details: SwapInfo },
+    /// Indicates that this instance holds the exclusive lock on the node storage
+    RemoteLockAcquired,
+    /// Indicates that the exclusive lock on the node storage could not be refreshed
+    RemoteLockRefreshFailed,
+    /// Indicates that the exclusive lock on the node storage was lost, which means
+    /// that the node may be running on another device
+    RemoteLockLost,
+    /// Indicates that a splice of a channel was negotiated and its transaction broadcast.
+    /// The amount is positive for splice-ins and negative for splice-outs
+    SplicePending {
+        channel_id: String,
+        amount_sat: i64,
+        funding_txid: String,
+    },
+    /// Indicates that a splice of a channel was confirmed and the channel is usable again
+    SpliceCompleted { channel_id: String, amount_sat: i64 },
+    /// Indicates that a splice of a channel failed and the channel was left unchanged
+    SpliceFailed {
+        channel_id: String,
+        amount_sat: i64,
+        reason: String,
+    },
+    /// Indicates that the download of the node state from the remote storage started, as when
+    /// the node is restored on a new device
+    RestoreDownloadStarted,
+    /// Indicates that the node state was downloaded from the remote storage
+    RestoreDownloadCompleted,
+    /// Indicates that the first sync of the restored node with the chain started
+    RestoreChainSyncStarted,
+    /// Indicates that the first sync of the restored node with the chain completed
+    RestoreChainSyncCompleted,
+    /// Indicates that the previous tip of the chain was replaced by a block of another branch.
+    /// The transactions confirmed in the replaced blocks may be unconfirmed again
+    ChainReorg {
+        old_tip_height: u32,
+        old_tip_hash: String,
+        new_tip_height: u32,
+        new_tip_hash: String,
+    },
+}
+
+impl From<RemoteLockEvent> for BreezEvent {
+    fn from(event: RemoteLockEvent) -> Self {
+        match event {
+            RemoteLockEvent::Acquired => Self::RemoteLockAcquired,
+            RemoteLockEvent::RefreshFailed => Self::RemoteLockRefreshFailed,
+            RemoteLockEvent::Lost => Self::RemoteLockLost,
+        }
+    }
+}
+
+impl From<RestoreEvent> for BreezEvent {
+    fn from(event: RestoreEvent) -> Self {
+        match event {
+            RestoreEvent::DownloadStarted => Self::RestoreDownloadStarted,
+            RestoreEvent::DownloadCompleted => Self::RestoreDownloadCompleted,
+            RestoreEvent::ChainSyncStarted => Self::RestoreChainSyncStarted,
+            RestoreEvent::ChainSyncCompleted => Self::RestoreChainSyncCompleted,
+        }
+    }
+}
+
+impl From<SpliceEvent> for BreezEvent {
+    fn from(event: SpliceEvent) -> Self {
+        match event {
+            SpliceEvent::Pending {
+                channel_id,
+                amount_sat,
+                funding_txid,
+            } => Self::SplicePending {
+                channel_id,
+                amount_sat,
+                funding_txid,
+            },
+            SpliceEvent::Completed {
+                channel_id,
+                amount_sat,
+            } => Self::SpliceCompleted {
+                channel_id,
+                amount_sat,
+            },
+            SpliceEvent::Failed {
+                channel_id,
+                amount_sat,
+                reason,
+            } => Self::SpliceFailed {
+                channel_id,
+                amount_sat,
+                reason,
+            },
+        }
+    }
+}
+
+impl From<ReorgEvent> for BreezEvent {
+    fn from(event: ReorgEvent) -> Self {
+        Self::ChainReorg {
+            old_tip_height: event.old_tip_height,
+            old_tip_hash: event.old_tip_hash,
+            new_tip_height: event.new_tip_height,
+            new_tip_hash: event.new_tip_hash,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -105,6 +215,24 @@ pub trait LogStream: Send + Sync {
     fn log(&self, l: LogEntry);
 }
 
+/// Selects the records sent to a log stream by their target and level, using `env_logger`
+/// directives such as `debug,hyper=warn,reqwest=warn`.
+pub struct LogStreamFilter {
+    filter: env_logger::filter::Filter,
+}
+
+impl LogStreamFilter {
+    pub fn new(directives: &str) -> Self {
+        Self {
+            filter: env_logger::filter::Builder::new().parse(directives).build(),
+        }
+    }
+
+    pub fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.enabled(metadata)
+    }
+}
+
 /// Request to sign a message with the node's private key.
 #[derive(Clone, Debug, PartialEq)]
 pub struct SignMessageRequest {
@@ -154,7 +282,7 @@ pub struct BreezServices {
     rest_client: Arc<dyn RestClient>,
     btc_receive_swapper: Arc<BTCReceiveSwap>,
     btc_send_swapper: Arc<BTCSendSwap>,
-    event_listener: Option<Box<dyn EventListener>>,
+    event_listener: Option<Arc<dyn EventListener>>,
     backup_watcher: Arc<BackupWatcher>,
     shutdown_sender: watch::Sender<()>,
 }
@@ -256,38 +384,26 @@ impl BreezServices {
     ///
     /// Calling `send_payment` ensures that the payment is not already completed; if so, it will result in an error.
     /// If the invoice doesn't specify an amount, the amount is taken from the `amount_msat` arg.
+    /// An amount above [NodeState::max_payable_msat] fails with [SendPaymentError::InsufficientBalance].
     pub async fn send_payment(
         &self,
         req: SendPaymentRequest,
     ) -> Result<SendPaymentResponse, SendPaymentError> {
-        let parsed_invoice = parse_invoice(req.bolt11.as_str())?;
-        let invoice_expiration = parsed_invoice.timestamp + parsed_invoice.expiry;
-        let current_time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
-        if invoice_expiration < current_time {
-            return Err(SendPaymentError::InvoiceExpired {
-                err: format!("Invoice expired at {invoice_expiration}"),
-            });
-        }
-        let invoice_amount_msat = parsed_invoice.amount_msat.unwrap_or_default();
-        let provided_amount_msat = req.amount_msat.unwrap_or_default();
-
-        // Valid the invoice network against the config network
-        validate_network(parsed_invoice.clone(), self.config.network)?;
+        self.send_payment_with_cancellation(req, CancellationToken::new())
+            .await
+    }
 
-        let amount_msat = match (provided_amount_msat, invoice_amount_msat) {
-            (0, 0) => {
-                return Err(SendPaymentError::InvalidAmount {
-                    err: "Amount must be provided when paying a zero invoice".into(),
-                })
-            }
-            (0, amount_msat) => amount_msat,
-            (amount_msat, 0) => amount_msat,
-            (_amount_1, _amount_2) => {
-                return Err(SendPaymentError::InvalidAmount {
-                    err: "Amount should not be provided when paying a non zero invoice".into(),
-                })
-            }
-        };
+    /// Pay a bolt11 invoice as [BreezServices::send_payment] does, until `cancel` is cancelled.
+    ///
+    /// A cancelled payment fails with [SendPaymentError::Cancelled] and is left pending, without
+    /// a [BreezEvent::PaymentFailed]. If the node already dispatched it, the payment may still
+    /// complete, which is picked up by the next sync.
+    pub async fn send_payment_with_cancellation(
+        &self,
+        req: SendPaymentRequest,
+        cancel: CancellationToken,
+    ) -> Result<SendPaymentResponse, SendPaymentError> {
+        let (parsed_invoice, amount_msat) = self.validate_invoice(&req.bolt11, req.amount_msat)?;
 
         if self
             .persister
@@ -297,16 +413,41 @@ impl BreezServices {
             return Err(SendPaymentError::AlreadyPaid);
         }
 
+        // Fail fast when the channels can't carry the amount. The node still rejects the payment
+        // if the liquidity changes in the meantime.
+        let max_payable_msat = self.node_info().await.max_payable_msat;
+        if amount_msat > max_payable_msat {
+            return Err(SendPaymentError::InsufficientBalance {
+                err: format!(
+                    "Amount {amount_msat} msat exceeds the maximum payable {max_payable_msat} msat"
+                ),
+            });
+        }
+
+        ensure_sdk!(
+            !cancel.is_cancelled(),
+            SendPaymentError::Cancelled {
+                err: "The payment was cancelled before it was sent".into()
+            }
+        );
         self.persist_pending_payment(&parsed_invoice, amount_msat)?;
 
         debug!("attempting normal payment");
         let payment_res = self
             .node_api
-            .send_payment(parsed_invoice.bolt11.clone(), req.amount_msat)
+            .send_payment(
+                parsed_invoice.bolt11.clone(),
+                req.amount_msat,
+                req.max_fee_msat,
+                cancel,
+            )
             .map_err(Into::into)
             .await;
 
         debug!("payment returned {payment_res:?}");
+        if let Err(e @ SendPaymentError::Cancelled { .. }) = payment_res {
+            return Err(e);
+        }
         let payment = self
             .on_payment_completed(
                 parsed_invoice.payee_pubkey.clone(),
@@ -317,14 +458,82 @@ impl BreezServices {
         Ok(SendPaymentResponse { payment })
     }
 
+    /// Checks that a bolt11 invoice can be paid, without paying it.
+    ///
+    /// The invoice is validated as in [BreezServices::send_payment]. The routing fee is estimated
+    /// by the limit the payment would use, and a route is only looked for if
+    /// [PrepareSendPaymentRequest::probe] is set.
+    pub async fn prepare_send_payment(
+        &self,
+        req: PrepareSendPaymentRequest,
+    ) -> Result<PrepareSendPaymentResponse, SendPaymentError> {
+        let (invoice, amount_msat) = self.validate_invoice(&req.bolt11, req.amount_msat)?;
+        let max_fee_msat = self.config.max_routing_fee_msat(amount_msat);
+        let route_found = match req.probe.unwrap_or(false) {
+            true => Some(
+                self.node_api
+                    .probe_payment(invoice.bolt11.clone(), req.amount_msat, max_fee_msat)
+                    .await?,
+            ),
+            false => None,
+        };
+        let max_payable_msat = self.node_info().await.max_payable_msat;
+        Ok(PrepareSendPaymentResponse {
+            invoice,
+            amount_msat,
+            max_fee_msat,
+            route_found,
+            is_payable: amount_msat <= max_payable_msat && route_found != Some(false),
+        })
+    }
+
+    /// Checks that the invoice is not expired and is for the configured network, returning it
+    /// parsed along with the amount to pay.
+    fn validate_invoice(
+        &self,
+        bolt11: &str,
+        amount_msat: Option<u64>,
+    ) -> Result<(LNInvoice, u64), SendPaymentError> {
+        let parsed_invoice = parse_invoice(bolt11)?;
+        let invoice_expiration = parsed_invoice.timestamp + parsed_invoice.expiry;
+        let current_time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        if invoice_expiration < current_time {
+            return Err(SendPaymentError::InvoiceExpired {
+                err: format!("Invoice expired at {invoice_expiration}"),
+            });
+        }
+        let invoice_amount_msat = parsed_invoice.amount_msat.unwrap_or_default();
+        let provided_amount_msat = amount_msat.unwrap_or_default();
+
+        // Valid the invoice network against the config network
+        validate_network(parsed_invoice.clone(), self.config.network)?;
+
+        let amount_msat = match (provided_amount_msat, invoice_amount_msat) {
+            (0, 0) => {
+                return Err(SendPaymentError::InvalidAmount {
+                    err: "Amount must be provided when paying a zero invoice".into(),
+                })
+            }
+            (0, amount_msat) => amount_msat,
+            (amount_msat, 0) => amount_msat,
+            (_amount_1, _amount_2) => {
+                return Err(SendPaymentError::InvalidAmount {
+                    err: "Amount should not be provided when paying a non zero invoice".into(),
+                })
+            }
+        };
+        Ok((parsed_invoice, amount_msat))
+    }
+
     /// Pay directly to a node id using keysend
     pub async fn send_spontaneous_payment(
         &self,
         req: SendSpontaneousPaymentRequest,
     ) -> Result<SendPaymentResponse, SendPaymentError> {
+        let extra_tlvs = keysend_tlvs(req.extra_tlvs, req.comment)?;
         let payment_res = self
             .node_api
-            .send_spontaneous_payment(req.node_id.clone(), req.amount_msat, req.extra_tlvs)
+            .send_spontaneous_payment(req.node_id.clone(), req.amount_msat, extra_tlvs)
             .map_err(Into::into)
             .await;
         let payment = self
@@ -333,6 +542,54 @@ impl BreezServices {
         Ok(SendPaymentResponse { payment })
     }
 
+    /// Pay a node given by its `node_id@host:port` address, connecting to it first.
+    ///
+    /// The amount is paid using keysend, unless [PayNodeAddressRequest::open_channel_sat] is set,
+    /// in which case a channel is opened to the node and the amount is pushed to it.
+    pub async fn pay_node_address(
+        &self,
+        req: PayNodeAddressRequest,
+    ) -> Result<PayNodeAddressResponse, SendPaymentError> {
+        let (node_id, address) = split_node_address(&req.address)?;
+        self.node_api
+            .connect_peer(node_id.to_string(), address.to_string())
+            .await?;
+        match req.open_channel_sat {
+            Some(amount_sat) => {
+                ensure_sdk!(
+                    req.amount_msat <= sat_to_msat(amount_sat),
+                    SendPaymentError::InvalidAmount {
+                        err: format!(
+                            "Amount {} msat exceeds the channel capacity of {amount_sat} sat",
+                            req.amount_msat
+                        )
+                    }
+                );
+                self.node_api
+                    .open_channel(
+                        node_id.to_string(),
+                        address.to_string(),
+                        amount_sat,
+                        req.amount_msat,
+                    )
+                    .await?;
+                Ok(PayNodeAddressResponse::ChannelOpened)
+            }
+            None => {
+                let payment = self
+                    .send_spontaneous_payment(SendSpontaneousPaymentRequest {
+                        node_id: node_id.to_string(),
+                        amount_msat: req.amount_msat,
+                        extra_tlvs: None,
+                        comment: None,
+                    })
+                    .await?
+                    .payment;
+                Ok(PayNodeAddressResponse::Paid { payment })
+            }
+        }
+    }
+
     /// Second step of LNURL-pay. The first step is `parse()`, which also validates the LNURL destination
     /// and generates the `LnUrlPayRequest` payload needed here.
     ///
@@ -359,6 +616,7 @@ impl BreezServices {
                 let pay_req = SendPaymentRequest {
                     bolt11: cb.pr.clone(),
                     amount_msat: None,
+                    max_fee_msat: None,
                 };
                 let invoice = parse_invoice(cb.pr.as_str())?;
 
@@ -505,11 +763,92 @@ impl BreezServices {
     /// This also works when the node doesn't have any channels and need inbound liquidity.
     /// In such case when the invoice is paid a new zero-conf channel will be open by the LSP,
     /// providing inbound liquidity and the payment will be routed via this new channel.
+    ///
+    /// With [ReceivePaymentRequest::register_notifications] the LSP is asked to notify the
+    /// registered webhook of the payment, until the invoice is deleted with
+    /// [BreezServices::delete_invoice]. The invoice is cancelled if the LSP does not accept
+    /// the registration.
     pub async fn receive_payment(
         &self,
         req: ReceivePaymentRequest,
     ) -> Result<ReceivePaymentResponse, ReceivePaymentError> {
-        self.receiver.receive_payment(req).await
+        self.receive_payment_with_cancellation(req, CancellationToken::new())
+            .await
+    }
+
+    /// Creates a bolt11 payment request as [BreezServices::receive_payment] does, failing with
+    /// [ReceivePaymentError::Cancelled] if `cancel` is cancelled while waiting for the node or
+    /// the LSP. An invoice created meanwhile is not returned and is left unpaid.
+    pub async fn receive_payment_with_cancellation(
+        &self,
+        req: ReceivePaymentRequest,
+        cancel: CancellationToken,
+    ) -> Result<ReceivePaymentResponse, ReceivePaymentError> {
+        let webhook_url = match req.register_notifications {
+            Some(true) => Some(self.persister.get_webhook_url()?.ok_or(
+                ReceivePaymentError::Generic {
+                    err: "No webhook registered to notify of the payment".into(),
+                },
+            )?),
+            _ => None,
+        };
+        let res = tokio::select! {
+            biased;
+            _ = cancel.cancelled() => Err(ReceivePaymentError::Cancelled {
+                err: "Receiving the payment was cancelled".into(),
+            }),
+            res = self.receiver.receive_payment(req) => res,
+        }?;
+        if let Some(webhook_url) = webhook_url {
+            // Registered once the invoice is created, as the node picks its preimage.
+            let payment_hash = &res.ln_invoice.payment_hash;
+            let invoice_webhook_url = invoice_webhook_url(&webhook_url, payment_hash)?;
+            if let Err(e) = self
+                .register_payment_notifications(invoice_webhook_url.clone())
+                .await
+            {
+                if let Err(e) = self.node_api.cancel_jit_invoice(payment_hash.clone()).await {
+                    warn!("Failed to cancel the invoice {payment_hash}: {e}");
+                }
+                return Err(e.into());
+            }
+            self.persister
+                .set_invoice_webhook_url(payment_hash, invoice_webhook_url)?;
+        }
+        Ok(res)
+    }
+
+    /// Deletes an invoice created by [BreezServices::receive_payment] that has not been paid yet,
+    /// so that payments for it are rejected.
+    ///
+    /// The payment notifications registered for the invoice are removed as well.
+    pub async fn delete_invoice(&self, bolt11: String) -> SdkResult<()> {
+        let invoice = parse_invoice(&bolt11)?;
+        self.node_api.delete_invoice(bolt11).await?;
+        self.remove_invoice_notifications(&invoice.payment_hash)
+            .await
+    }
+
+    /// Cancels a JIT invoice created by [BreezServices::receive_payment] that is no longer
+    /// going to be paid, identified by its payment hash, so that payments for it are rejected.
+    ///
+    /// The payment notifications the LSP holds for the invoice are removed as well. Calling it
+    /// for an unknown or already paid invoice does nothing.
+    pub async fn cancel_jit_invoice(&self, payment_hash: String) -> SdkResult<()> {
+        self.node_api
+            .cancel_jit_invoice(payment_hash.clone())
+            .await?;
+        self.remove_invoice_notifications(&payment_hash).await
+    }
+
+    /// Unregisters the payment notifications registered for the invoice, if any.
+    async fn remove_invoice_notifications(&self, payment_hash: &str) -> SdkResult<()> {
+        if let Some(invoice_webhook_url) = self.persister.get_invoice_webhook_url(payment_hash)? {
+            self.unregister_payment_notifications(invoice_webhook_url)
+                .await?;
+            self.persister.remove_invoice_webhook_url(payment_hash)?;
+        }
+        Ok(())
     }
 
     /// Report an issue.
@@ -543,6 +882,164 @@ impl BreezServices {
         self.node_api.get_node_state().await
     }
 
+    /// Connects to the configured LSP if the node is not connected to it, e.g. after the
+    /// connectivity of the device was restored, and returns the node id of the LSP.
+    ///
+    /// The LSP is reconnected to automatically afterwards. Fails if no connection could be
+    /// established within the connect timeout.
+    pub async fn ensure_lsp_connected(&self) -> SdkResult<String> {
+        Ok(self.node_api.ensure_lsp_connected().await?)
+    }
+
+    /// Reads the app data stored under `key` with [BreezServices::app_kv_set], if any.
+    pub async fn app_kv_get(&self, key: String) -> SdkResult<Option<Vec<u8>>> {
+        Ok(self.node_api.app_kv_get(key).await?)
+    }
+
+    /// Stores a small piece of data of the app, e.g. the id of the last seen notification.
+    ///
+    /// The data is kept in the node store, so it is backed up and synced across devices along
+    /// with the node state. Keys consist of up to 120 alphanumeric characters, `-` or `_`, and
+    /// values are limited to 8 KiB.
+    pub async fn app_kv_set(&self, key: String, value: Vec<u8>) -> SdkResult<()> {
+        Ok(self.node_api.app_kv_set(key, value).await?)
+    }
+
+    /// Deletes the app data stored under `key`. Deleting a missing key does nothing.
+    pub async fn app_kv_delete(&self, key: String) -> SdkResult<()> {
+        Ok(self.node_api.app_kv_delete(key).await?)
+    }
+
+    /// Disconnects from the given peer.
+    ///
+    /// Fails if the node is not connected to the peer.
+    pub async fn disconnect_peer(&self, node_id: String) -> SdkResult<()> {
+        Ok(self.node_api.disconnect_peer(node_id).await?)
+    }
+
+    /// Lists the channels of the node, including the ones which are not ready yet.
+    pub async fn list_channels(&self) -> SdkResult<Vec<ChannelInfo>> {
+        Ok(self.node_api.list_channels().await?)
+    }
+
+    /// Lists the channels whose funding transaction has not reached the required
+    /// confirmations yet. See [ChannelInfo::blocks_until_ready] for when they become usable.
+    pub async fn list_pending_channels(&self) -> SdkResult<Vec<ChannelInfo>> {
+        let channels = self.node_api.list_channels().await?;
+        Ok(channels
+            .into_iter()
+            .filter(|c| !c.is_channel_ready)
+            .collect())
+    }
+
+    /// Sums the fees of the payments completed from `from_ts` (inclusive) to `to_ts`
+    /// (exclusive), both in epoch seconds, e.g. to report the fees paid in a month.
+    pub async fn fee_summary(&self, from_ts: i64, to_ts: i64) -> SdkResult<FeeSummary> {
+        Ok(self.node_api.fee_summary(from_ts, to_ts).await?)
+    }
+
+    /// Aborts the opening of the pending channel with the given id, e.g. when its funding
+    /// transaction never confirms.
+    ///
+    /// The channel is force-closed, so the funds return to the on-chain wallet only after
+    /// the funding and the commitment transactions are resolved on-chain.
+    pub async fn abort_channel_open(&self, channel_id: String) -> SdkResult<()> {
+        Ok(self.node_api.abort_channel_open(channel_id).await?)
+    }
+
+    /// Returns how far the funds of the closed channel with the given id are from being
+    /// spendable on-chain, or `None` if there are no funds left to claim from it.
+    pub async fn pending_close_status(
+        &self,
+        channel_id: String,
+    ) -> SdkResult<Option<PendingCloseStatus>> {
+        Ok(self.node_api.pending_close_status(channel_id).await?)
+    }
+
+    /// Lists the funds from closed channels which are being swept to the on-chain wallet,
+    /// along with the stage of their sweep.
+    ///
+    /// The sweeps are made by the node on its own, so the funds only need to be waited for.
+    pub async fn list_pending_sweeps(&self) -> SdkResult<Vec<PendingSweep>> {
+        Ok(self.node_api.list_pending_sweeps().await?)
+    }
+
+    /// Broadcasts again the unconfirmed transactions of the node, like on-chain sends and
+    /// sweeps, in case they were dropped from the mempools. Returns the ids of the
+    /// transactions rebroadcast.
+    pub async fn rebroadcast_pending(&self) -> SdkResult<Vec<String>> {
+        Ok(self.node_api.rebroadcast_pending().await?)
+    }
+
+    /// Returns the number of nodes and channels in the routing graph of the node.
+    ///
+    /// The graph is empty until it is synced from the Rapid Gossip Sync server after the
+    /// start, so payments may fail to find a route until then.
+    pub async fn graph_status(&self) -> SdkResult<GraphStatus> {
+        Ok(self.node_api.graph_status().await?)
+    }
+
+    /// Probes the external dependencies of the node, reporting for each one whether it is
+    /// reachable and how long the probe took.
+    ///
+    /// The VSS server is checked as with [BreezServices::check_vss], the chain source is asked
+    /// for its tip, the Rapid Gossip Sync server for its latest snapshot and the LSP peer is
+    /// connected to if it is not already. Meant for diagnostics, the probes run concurrently
+    /// and a failure of one of them does not fail the check.
+    pub async fn connectivity_check(&self) -> SdkResult<ConnectivityCheckResult> {
+        Ok(self.node_api.connectivity_check().await?)
+    }
+
+    /// Discards the local cache of the node state and downloads it again from the remote
+    /// storage, for when the local cache is suspected to be corrupted.
+    ///
+    /// Changes not yet synced to the remote storage are lost. Writes of the node wait until
+    /// the download is done, so none of them is lost meanwhile.
+    pub async fn resync_from_remote(&self) -> SdkResult<ResyncResult> {
+        Ok(self.node_api.resync_from_remote().await?)
+    }
+
+    /// Returns the current on-chain fee rates for transactions to confirm in the next block,
+    /// within 6 blocks and within a day, e.g. to show the expected cost of channel operations.
+    pub async fn fee_estimates(&self) -> SdkResult<FeeEstimates> {
+        Ok(self.node_api.fee_estimates().await?)
+    }
+
+    /// Tops up the channel with the given id with `amount_sat` from the on-chain wallet
+    /// of the node, without closing the channel.
+    ///
+    /// The capacity of the channel increases once the splice transaction confirms.
+    pub async fn splice_in(&self, channel_id: String, amount_sat: u64) -> SdkResult<()> {
+        Ok(self.node_api.splice_in(channel_id, amount_sat).await?)
+    }
+
+    /// Moves `amount_sat` from the channel with the given id to `to_address` on-chain,
+    /// without closing the channel.
+    ///
+    /// The amount is limited by the spendable balance of the channel.
+    pub async fn splice_out(
+        &self,
+        channel_id: String,
+        amount_sat: u64,
+        to_address: String,
+    ) -> SdkResult<()> {
+        Ok(self
+            .node_api
+            .splice_out(channel_id, amount_sat, to_address)
+            .await?)
+    }
+
+    /// Generates a new address of the on-chain wallet of the node, which can be used
+    /// to fund a [BreezServices::splice_in].
+    pub async fn new_onchain_address(&self) -> SdkResult<String> {
+        Ok(self.node_api.new_onchain_address().await?)
+    }
+
+    /// Lists the peers known to the node, including the disconnected ones.
+    pub async fn list_peers(&self) -> SdkResult<Vec<PeerInfo>> {
+        Ok(self.node_api.list_peers().await?)
+    }
+
     /// Sign given message with the private key of the node id. Returns a zbase
     /// encoded signature.
     pub async fn sign_message(&self, req: SignMessageRequest) -> SdkResult<SignMessageResponse> {
@@ -587,15 +1084,54 @@ impl BreezServices {
     }
 
     /// List payments matching the given filters, as retrieved from persistent storage
+    ///
+    /// The probes sent by [BreezServices::prepare_send_payment] are not recorded by the node,
+    /// so they are never listed.
     pub async fn list_payments(&self, req: ListPaymentsRequest) -> SdkResult<Vec<Payment>> {
         Ok(self.persister.list_payments(req)?)
     }
 
+    /// Exports the payments matching the filters of `req`, e.g. for accounting.
+    pub async fn export_payments(
+        &self,
+        format: PaymentExportFormat,
+        req: ListPaymentsRequest,
+    ) -> SdkResult<String> {
+        let payments = self.persister.list_payments(req)?;
+        export_payments(&payments, format)
+    }
+
+    /// Prunes the payments with one of the given statuses, which were last updated before
+    /// `older_than_ts`, along with their metadata. Pending payments cannot be pruned.
+    ///
+    /// Returns the number of pruned payments.
+    pub async fn prune_payments(
+        &self,
+        older_than_ts: u64,
+        statuses: Vec<PaymentStatus>,
+    ) -> SdkResult<u32> {
+        let payment_ids = self
+            .node_api
+            .prune_payments(older_than_ts, statuses)
+            .await?;
+        self.persister.delete_payments(&payment_ids)?;
+        Ok(payment_ids.len() as u32)
+    }
+
     /// Fetch a specific payment by its hash.
     pub async fn payment_by_hash(&self, hash: String) -> SdkResult<Option<Payment>> {
         Ok(self.persister.get_payment_by_hash(&hash)?)
     }
 
+    /// Returns the status of the payment with the given hash as known to the node, without
+    /// waiting for a sync, e.g. to poll whether an invoice was paid.
+    ///
+    /// An invoice awaiting payment is reported as [PaymentStatus::Pending], `None` is returned
+    /// for an unknown hash.
+    pub async fn payment_status(&self, payment_hash: String) -> SdkResult<Option<PaymentStatus>> {
+        Ok(self.node_api.payment_status(payment_hash).await?)
+    }
+
     /// Set the external metadata of a payment as a valid JSON string
     pub async fn set_payment_metadata(&self, hash: String, metadata: String) -> SdkResult<()> {
         Ok(self
@@ -608,6 +1144,7 @@ impl BreezServices {
         &self,
         req: RedeemOnchainFundsRequest,
     ) -> RedeemOnchainResult<RedeemOnchainFundsResponse> {
+        self.validate_sat_per_vbyte(req.sat_per_vbyte).await?;
         let txid = self
             .node_api
             .redeem_onchain_funds(req.to_address, req.sat_per_vbyte)
@@ -620,10 +1157,34 @@ impl BreezServices {
         &self,
         req: PrepareRedeemOnchainFundsRequest,
     ) -> RedeemOnchainResult<PrepareRedeemOnchainFundsResponse> {
+        self.validate_sat_per_vbyte(req.sat_per_vbyte).await?;
         let response = self.node_api.prepare_redeem_onchain_funds(req).await?;
         Ok(response)
     }
 
+    /// Checks that the fee rate is within the bounds of the config, raising the floor to the
+    /// minimum fee of the chain service when it is available.
+    async fn validate_sat_per_vbyte(&self, sat_per_vbyte: u32) -> RedeemOnchainResult<()> {
+        let config_min_sat_per_vbyte = u64::from(self.config.min_sat_per_vbyte);
+        let min_sat_per_vbyte = match self.chain_service.recommended_fees().await {
+            Ok(fees) => fees.minimum_fee.max(config_min_sat_per_vbyte),
+            Err(e) => {
+                warn!("Failed to fetch the minimum fee rate: {e}");
+                config_min_sat_per_vbyte
+            }
+        };
+        let max_sat_per_vbyte = u64::from(self.config.max_sat_per_vbyte);
+        ensure_sdk!(
+            (min_sat_per_vbyte..=max_sat_per_vbyte).contains(&u64::from(sat_per_vbyte)),
+            RedeemOnchainError::InvalidFeeRate {
+                err: format!(
+                    "Fee rate of {sat_per_vbyte} sat/vbyte is outside of the accepted range of {min_sat_per_vbyte} to {max_sat_per_vbyte} sat/vbyte"
+                )
+            }
+        );
+        Ok(())
+    }
+
     /// Fetch live rates of fiat currencies, sorted by name
     pub async fn fetch_fiat_rates(&self) -> SdkResult<Vec<Rate>> {
         self.fiat_api.fetch_fiat_rates().await.map_err(Into::into)
@@ -672,6 +1233,11 @@ impl BreezServices {
         get_lsp_by_id(self.node_info().await.id, self.lsp_api.clone(), id.as_str()).await
     }
 
+    /// Gets the opening fee menu of the current LSP, with all the fee tiers and their validity
+    pub async fn lsp_fee_menu(&self) -> SdkResult<OpeningFeeParamsMenu> {
+        Ok(self.lsp_info().await?.opening_fee_params_list)
+    }
+
     /// Gets the fees required to open a channel for a given amount.
     /// If no channel is needed, returns 0. If a channel is needed, returns the required opening fees.
     pub async fn open_channel_fee(
@@ -699,11 +1265,117 @@ impl BreezServices {
         })
     }
 
+    /// Estimates the inbound liquidity and the opening fee needed to receive
+    /// the given amount in a single payment.
+    pub async fn estimate_liquidity_for(
+        &self,
+        target_receivable_msat: u64,
+    ) -> SdkResult<LiquidityEstimate> {
+        Ok(self
+            .node_api
+            .estimate_liquidity_for(target_receivable_msat)
+            .await?)
+    }
+
+    /// Pre-opens a channel with the LSP providing at least `inbound_msat` of inbound liquidity,
+    /// so that later receives up to that amount do not wait for a channel to be opened.
+    ///
+    /// This is not an upfront open: no channel exists until the returned invoice is funded.
+    /// LSPS2 only opens a channel for an incoming payment, so the channel is opened once the
+    /// returned invoice is paid, e.g. by a top-up of the wallet. The LSP sizes the channel
+    /// above the payment by its [LspInformation::channel_over_provisioning_ppm], which is what
+    /// provides the inbound liquidity. The reserve the LSP keeps in the channel cannot be
+    /// received, so the inbound liquidity ends up slightly lower.
+    pub async fn preopen_channel(
+        &self,
+        inbound_msat: u64,
+    ) -> Result<PreopenChannelResponse, ReceivePaymentError> {
+        let lsp_info = self.lsp_info().await?;
+        let over_provisioning_ppm = lsp_info.channel_over_provisioning_ppm;
+        ensure_sdk!(
+            over_provisioning_ppm > 0,
+            ReceivePaymentError::Generic {
+                err: "The LSP does not over-provision its channels".into()
+            }
+        );
+        let fee_params = lsp_info
+            .cheapest_open_channel_fee(INVOICE_PAYMENT_FEE_EXPIRY_SECONDS)?
+            .clone();
+        let amount_msat = preopen_amount_msat(inbound_msat, over_provisioning_ppm, &fee_params)?;
+        let max_receivable_msat = self
+            .node_info()
+            .await
+            .max_receivable_single_payment_amount_msat;
+        ensure_sdk!(
+            max_receivable_msat < amount_msat,
+            ReceivePaymentError::Generic {
+                err: format!(
+                    "{max_receivable_msat} msat can already be received without a new channel"
+                )
+            }
+        );
+
+        let res = self
+            .receive_payment(ReceivePaymentRequest {
+                amount_msat,
+                description: "Channel pre-open".to_string(),
+                opening_fee_params: Some(fee_params.clone()),
+                ..Default::default()
+            })
+            .await?;
+        let opening_fee_msat = res
+            .opening_fee_msat
+            .unwrap_or_else(|| fee_params.get_channel_fees_msat_for(amount_msat));
+        let forward_msat = amount_msat - opening_fee_msat;
+        let channel_size_msat = forward_msat
+            .checked_mul(u64::from(over_provisioning_ppm))
+            .and_then(|over_provisioned| forward_msat.checked_add(over_provisioned / 1_000_000))
+            .ok_or_else(|| ReceivePaymentError::Generic {
+                err: format!("The channel for {forward_msat} msat overflows"),
+            })?;
+        Ok(PreopenChannelResponse {
+            ln_invoice: res.ln_invoice,
+            opening_fee_msat,
+            channel_size_msat,
+        })
+    }
+
     /// Close all channels.
     ///
     /// Should be called when the user wants to close all the channels.
     pub async fn close_lsp_channels(&self) -> SdkResult<()> {
-        self.node_api.close_all_channels().await?;
+        self.close_all_channels(None, false).await
+    }
+
+    /// Close all channels, force-closing them if `force` is set.
+    ///
+    /// If a `close_to_address` is given, the funds of each closed channel are sent to it
+    /// once the channel is resolved on-chain. The address must belong to the network of the
+    /// node.
+    pub async fn close_all_channels(
+        &self,
+        close_to_address: Option<String>,
+        force: bool,
+    ) -> SdkResult<()> {
+        self.node_api
+            .close_all_channels(close_to_address, force)
+            .await?;
+        self.sync().await
+    }
+
+    /// Close the channel with the given id, force-closing it if `force` is set.
+    ///
+    /// If a `close_to_address` is given, the funds of the channel are sent to it once the
+    /// channel is resolved on-chain. The address must belong to the network of the node.
+    pub async fn close_channel(
+        &self,
+        channel_id: String,
+        close_to_address: Option<String>,
+        force: bool,
+    ) -> SdkResult<()> {
+        self.node_api
+            .close_channel(channel_id, close_to_address, force)
+            .await?;
         self.sync().await
     }
 
@@ -1127,6 +1799,8 @@ impl BreezServices {
                         lnurl_withdraw_endpoint: None,
                         swap_info: None,
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -1219,6 +1893,27 @@ impl BreezServices {
         self.chain_service.recommended_fees().await
     }
 
+    /// Get the height of the current chain tip
+    pub async fn chain_tip(&self) -> SdkResult<u32> {
+        self.chain_service.current_tip().await
+    }
+
+    /// Wait until the node has synced to the current chain tip, for apps that do not listen
+    /// for [BreezEvent::Synced]. Fails if the node is not synced within `timeout`.
+    pub async fn wait_until_synced(&self, timeout: Duration) -> SdkResult<()> {
+        Ok(self.node_api.wait_until_synced(timeout).await?)
+    }
+
+    /// Get the confirmation status of an onchain transaction, e.g. to track a deposit
+    pub async fn onchain_tx_status(&self, txid: String) -> SdkResult<TxStatus> {
+        self.chain_service.transaction_status(txid).await
+    }
+
+    /// Get the onchain and mempool transactions associated with an address
+    pub async fn address_transactions(&self, address: String) -> SdkResult<Vec<OnchainTx>> {
+        self.chain_service.address_transactions(address).await
+    }
+
     /// Get the full default config for a specific environment type
     pub fn default_config(env_type: EnvironmentType, api_key: String) -> Config {
         match env_type {
@@ -1228,6 +1923,40 @@ impl BreezServices {
         }
     }
 
+    /// Get a fingerprint identifying the seed the node state is backed up for.
+    ///
+    /// The fingerprint is deterministic for a seed and does not reveal it, so it can be used
+    /// to confirm that the right seed is used before restoring a node.
+    pub fn recovery_fingerprint(network: Network, seed: Vec<u8>) -> SdkResult<String> {
+        Ok(node_builder::recovery_fingerprint(network, &seed)?)
+    }
+
+    /// Checks that the VSS server of the config is reachable and accepts the credentials
+    /// derived from the seed, without building the node.
+    ///
+    /// A throwaway key is written, read back and deleted in a store separate from the
+    /// node state.
+    pub async fn check_vss(config: Config, seed: Vec<u8>) -> SdkResult<VssCheckResult> {
+        Ok(node_builder::check_vss(&config, &seed).await?)
+    }
+
+    /// Copies the node state from the VSS server of the config to the one of `new_config`,
+    /// after which the node can connect with `new_config`.
+    ///
+    /// Keys already copied are skipped, so the migration can be run again if interrupted.
+    /// Must not be called while connected.
+    pub async fn migrate_vss(config: Config, new_config: Config, seed: Vec<u8>) -> SdkResult<()> {
+        Ok(node_builder::migrate_vss(&config, &new_config, &seed).await?)
+    }
+
+    /// Clears the local copy of the node state in the working dir of the config, so that it
+    /// is downloaded again from VSS on the next [BreezServices::connect].
+    ///
+    /// Must not be called while connected.
+    pub fn reset_local_cache(config: Config) -> SdkResult<()> {
+        Ok(node_builder::reset_local_cache(&config)?)
+    }
+
     /// Get the static backup data from the persistent storage.
     /// This data enables the user to recover the node in an external core lightning node.
     /// See here for instructions on how to recover using this data: <https://docs.corelightning.org/docs/backup-and-recovery#backing-up-using-static-channel-backup>
@@ -1290,6 +2019,11 @@ impl BreezServices {
         //track swap events
         self.track_swap_events().await;
 
+        // track remote lock events
+        self.track_remote_lock_events().await;
+        self.track_splice_events().await;
+        self.track_reorg_events().await;
+
         // track paid invoices
         self.track_invoices().await;
 
@@ -1408,6 +2142,99 @@ impl BreezServices {
         });
     }
 
+    async fn track_remote_lock_events(self: &Arc<BreezServices>) {
+        let cloned = self.clone();
+        tokio::spawn(async move {
+            let mut events_stream = match cloned.node_api.stream_remote_lock_events().await {
+                Ok(events_stream) => events_stream,
+                Err(e) => {
+                    error!("Failed to stream remote lock events: {e:?}");
+                    return;
+                }
+            };
+            let mut shutdown_receiver = cloned.shutdown_sender.subscribe();
+            loop {
+                tokio::select! {
+                    lock_event = events_stream.next() => {
+                        let Some(e) = lock_event else {
+                            debug!("Remote lock events stream ended");
+                            break;
+                        };
+                        if let Err(err) = cloned.notify_event_listeners(e.into()).await {
+                            error!("error handling remote lock event: {err:?}");
+                        }
+                    },
+                    _ = shutdown_receiver.changed() => {
+                        debug!("Remote lock events handling task completed");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    async fn track_splice_events(self: &Arc<BreezServices>) {
+        let cloned = self.clone();
+        tokio::spawn(async move {
+            let mut events_stream = match cloned.node_api.stream_splice_events().await {
+                Ok(events_stream) => events_stream,
+                Err(e) => {
+                    error!("Failed to stream splice events: {e:?}");
+                    return;
+                }
+            };
+            let mut shutdown_receiver = cloned.shutdown_sender.subscribe();
+            loop {
+                tokio::select! {
+                    splice_event = events_stream.next() => {
+                        let Some(e) = splice_event else {
+                            debug!("Splice events stream ended");
+                            break;
+                        };
+                        if let Err(err) = cloned.notify_event_listeners(e.into()).await {
+                            error!("error handling splice event: {err:?}");
+                        }
+                    },
+                    _ = shutdown_receiver.changed() => {
+                        debug!("Splice events handling task completed");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    async fn track_reorg_events(self: &Arc<BreezServices>) {
+        let cloned = self.clone();
+        tokio::spawn(async move {
+            let mut events_stream = match cloned.node_api.stream_reorg_events().await {
+                Ok(events_stream) => events_stream,
+                Err(e) => {
+                    error!("Failed to stream reorg events: {e:?}");
+                    return;
+                }
+            };
+            let mut shutdown_receiver = cloned.shutdown_sender.subscribe();
+            loop {
+                tokio::select! {
+                    reorg_event = events_stream.next() => {
+                        let Some(e) = reorg_event else {
+                            debug!("Reorg events stream ended");
+                            break;
+                        };
+                        if let Err(err) = cloned.notify_event_listeners(e.into()).await {
+                            error!("error handling reorg event: {err:?}");
+                        }
+                    },
+                    _ = shutdown_receiver.changed() => {
+                        debug!("Reorg events handling task completed");
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
     async fn track_invoices(self: &Arc<BreezServices>) {
         let cloned = self.clone();
         tokio::spawn(async move {
@@ -2020,15 +2847,20 @@ impl BreezServicesBuilder {
                 })?,
         );
 
+        let event_listener: Option<Arc<dyn EventListener>> = event_listener.map(Arc::from);
         let mut node_api = self.node_api.clone();
         let mut backup_transport = self.backup_transport.clone();
         let mut lsp_api = self.lsp_api.clone();
         if node_api.is_none() {
+            let restore_event_listener = event_listener.clone().map(|listener| {
+                Arc::new(move |e: RestoreEvent| listener.on_event(e.into())) as RestoreEventListener
+            });
             let node_impls = node_builder::build_node(
                 self.config.clone(),
                 self.seed.clone().unwrap(),
                 restore_only,
                 persister.clone(),
+                restore_event_listener,
             )
             .await?;
             node_api = Some(node_impls.node);
@@ -2077,7 +2909,7 @@ impl BreezServicesBuilder {
         };
 
         // mempool space is used to monitor the chain
-        let mempoolspace_urls = match self.config.mempoolspace_url.clone() {
+        let mut mempoolspace_urls = match self.config.mempoolspace_url.clone() {
             None => {
                 let cached = persister.get_mempoolspace_base_urls()?;
                 match cached.len() {
@@ -2096,6 +2928,11 @@ impl BreezServicesBuilder {
             }
             Some(mempoolspace_url_from_config) => vec![mempoolspace_url_from_config],
         };
+        // The esplora API is compatible with the mempool.space one, except for the fees
+        // estimation, so use it as a last resort when no mempool.space URL is configured
+        if self.config.mempoolspace_url.is_none() && !self.config.esplora_url.is_empty() {
+            mempoolspace_urls.push(self.config.esplora_url.clone());
+        }
         let chain_service = Arc::new(RedundantChainService::from_base_urls(
             rest_client.clone(),
             mempoolspace_urls,
@@ -2104,6 +2941,7 @@ impl BreezServicesBuilder {
         let receiver: Arc<dyn Receiver> = Arc::new(PaymentReceiver::new(
             unwrapped_node_api.clone(),
             lsp_api.clone(),
+            self.config.max_jit_channels,
         ));
 
         let btc_receive_swapper = Arc::new(BTCReceiveSwap::new(BTCReceiveSwapParameters {
@@ -2253,17 +3091,101 @@ async fn get_notification_lsps(
     Ok(notification_lsps)
 }
 
+/// Adds the comment of a keysend payment to its extra TLVs.
+fn keysend_tlvs(
+    extra_tlvs: Option<Vec<TlvEntry>>,
+    comment: Option<String>,
+) -> Result<Option<Vec<TlvEntry>>, SendPaymentError> {
+    let Some(comment) = comment else {
+        return Ok(extra_tlvs);
+    };
+    ensure_sdk!(
+        comment.len() <= MAX_KEYSEND_COMMENT_LEN,
+        SendPaymentError::Generic {
+            err: format!("Comment is longer than {MAX_KEYSEND_COMMENT_LEN} bytes")
+        }
+    );
+    let mut tlvs = extra_tlvs.unwrap_or_default();
+    ensure_sdk!(
+        tlvs.iter()
+            .all(|tlv| tlv.field_number != KEYSEND_MESSAGE_TLV_TYPE),
+        SendPaymentError::Generic {
+            err: format!("Both a comment and a TLV of type {KEYSEND_MESSAGE_TLV_TYPE} are set")
+        }
+    );
+    tlvs.push(TlvEntry {
+        field_number: KEYSEND_MESSAGE_TLV_TYPE,
+        value: comment.into_bytes(),
+    });
+    Ok(Some(tlvs))
+}
+
+/// The number of times the amount of a pre-open payment is raised to cover its own fee,
+/// before giving up on an amount the fee keeps growing with.
+const PREOPEN_AMOUNT_MAX_ITERATIONS: usize = 100;
+
+/// The amount of a JIT payment for which the LSP opens a channel with at least `inbound_msat`
+/// of inbound liquidity. The channel is over-provisioned by a share of the amount forwarded
+/// after the opening fee is deduced.
+///
+/// Fails if the amount overflows, or does not settle within [PREOPEN_AMOUNT_MAX_ITERATIONS]
+/// as the proportional fee is too high.
+fn preopen_amount_msat(
+    inbound_msat: u64,
+    over_provisioning_ppm: u32,
+    fee_params: &OpeningFeeParams,
+) -> Result<u64, ReceivePaymentError> {
+    let unsupported = || ReceivePaymentError::InvalidAmount {
+        err: format!("Cannot pre-open a channel for {inbound_msat} msat of inbound liquidity"),
+    };
+    let forward_msat = inbound_msat
+        .checked_mul(1_000_000)
+        .ok_or_else(unsupported)?
+        .div_ceil(u64::from(over_provisioning_ppm));
+    // The fee grows with the amount, so the amount is raised until it covers its own fee.
+    let mut amount_msat = forward_msat;
+    for _ in 0..PREOPEN_AMOUNT_MAX_ITERATIONS {
+        // The proportional fee is computed on the amount without overflow checks.
+        amount_msat
+            .checked_mul(u64::from(fee_params.proportional))
+            .ok_or_else(unsupported)?;
+        let next_msat = forward_msat
+            .checked_add(fee_params.get_channel_fees_msat_for(amount_msat))
+            .ok_or_else(unsupported)?;
+        if next_msat == amount_msat {
+            return Ok(amount_msat);
+        }
+        amount_msat = next_msat;
+    }
+    Err(unsupported())
+}
+
+/// The webhook url registered for the payment notifications of a single invoice, which
+/// identifies the invoice by its payment hash.
+fn invoice_webhook_url(webhook_url: &str, payment_hash: &str) -> SdkResult<String> {
+    let mut url = reqwest::Url::parse(webhook_url)
+        .map_err(|e| SdkError::generic(&format!("Invalid webhook url: {e}")))?;
+    url.query_pairs_mut()
+        .append_pair("payment_hash", payment_hash);
+    Ok(url.to_string())
+}
+
 #[cfg(test)]
 pub(crate) mod tests {
     use std::collections::HashMap;
     use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
 
     use anyhow::{anyhow, Result};
     use regex::Regex;
     use reqwest::Url;
     use sdk_common::prelude::Rate;
+    use tokio_util::sync::CancellationToken;
 
-    use crate::breez_services::{BreezServices, BreezServicesBuilder};
+    use crate::breez_services::{
+        keysend_tlvs, preopen_amount_msat, BreezServices, BreezServicesBuilder, LogStreamFilter,
+    };
+    use crate::lightning_invoice::Currency;
     use crate::models::{LnPaymentDetails, NodeState, Payment, PaymentDetails, PaymentTypeFilter};
     use crate::node_api::NodeAPI;
     use crate::persist::swap::SwapStorage;
@@ -2375,6 +3297,8 @@ pub(crate) mod tests {
                         lnurl_withdraw_endpoint: None,
                         swap_info: None,
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -2403,6 +3327,8 @@ pub(crate) mod tests {
                         lnurl_withdraw_endpoint: Some(test_lnurl_withdraw_endpoint.to_string()),
                         swap_info: None,
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -2431,6 +3357,8 @@ pub(crate) mod tests {
                         lnurl_withdraw_endpoint: None,
                         swap_info: None,
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -2459,6 +3387,8 @@ pub(crate) mod tests {
                         lnurl_withdraw_endpoint: None,
                         swap_info: Some(swap_info.clone()),
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -2487,6 +3417,8 @@ pub(crate) mod tests {
                         lnurl_withdraw_endpoint: None,
                         swap_info: None,
                         reverse_swap_info: Some(rev_swap_info.clone()),
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -2621,6 +3553,396 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_receive_payment_min_final_cltv() -> Result<()> {
+        let requested_cltv = Arc::new(std::sync::Mutex::new(None));
+        let mut node_api = MockNodeAPI::new(get_dummy_node_state());
+        let requested = Arc::clone(&requested_cltv);
+        node_api.set_on_create_invoice(Box::new(move |req| {
+            *requested.lock().unwrap() = Some(req.min_accepted_cltv);
+            Ok(())
+        }));
+        let breez_services = breez_services_with(Some(Arc::new(node_api)), None, vec![]).await?;
+        let req = ReceivePaymentRequest {
+            amount_msat: 1_000_000,
+            description: "cltv".to_string(),
+            ..Default::default()
+        };
+
+        // The requested delta is passed to the node as the minimum it accepts.
+        for cltv in [18, 2016] {
+            breez_services
+                .receive_payment(ReceivePaymentRequest {
+                    cltv: Some(cltv),
+                    ..req.clone()
+                })
+                .await?;
+            assert_eq!(*requested_cltv.lock().unwrap(), Some(Some(cltv)));
+        }
+
+        *requested_cltv.lock().unwrap() = None;
+        for cltv in [17, 2017] {
+            let res = breez_services
+                .receive_payment(ReceivePaymentRequest {
+                    cltv: Some(cltv),
+                    ..req.clone()
+                })
+                .await;
+            assert!(matches!(res, Err(ReceivePaymentError::Generic { .. })));
+        }
+        // Invalid deltas do not reach the node.
+        assert_eq!(*requested_cltv.lock().unwrap(), None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_payment_description_length() -> Result<()> {
+        let breez_services = breez_services().await?;
+        let req = ReceivePaymentRequest {
+            amount_msat: 1_000_000,
+            description: "a".repeat(639),
+            ..Default::default()
+        };
+        let res = breez_services.receive_payment(req.clone()).await?;
+        assert_eq!(res.ln_invoice.description, Some("a".repeat(639)));
+
+        let req = ReceivePaymentRequest {
+            description: format!("{}€", "a".repeat(638)),
+            ..req
+        };
+        let res = breez_services.receive_payment(req.clone()).await;
+        assert!(matches!(
+            res,
+            Err(ReceivePaymentError::InvalidDescription { err }) if err.contains("639")
+        ));
+
+        // The multi-byte char crossing the limit is dropped as a whole.
+        let res = breez_services
+            .receive_payment(ReceivePaymentRequest {
+                truncate_description: Some(true),
+                ..req
+            })
+            .await?;
+        assert_eq!(res.ln_invoice.description, Some("a".repeat(638)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_payment_register_notifications() -> Result<()> {
+        let breez_services = breez_services().await?;
+        let req = ReceivePaymentRequest {
+            amount_msat: 1_000_000,
+            description: "notifications".to_string(),
+            register_notifications: Some(true),
+            ..Default::default()
+        };
+        let res = breez_services.receive_payment(req.clone()).await;
+        assert!(matches!(res, Err(ReceivePaymentError::Generic { .. })));
+
+        breez_services
+            .persister
+            .set_webhook_url("https://example.com/webhook?token=1".to_string())?;
+        let res = breez_services.receive_payment(req).await?;
+        let payment_hash = res.ln_invoice.payment_hash;
+        assert_eq!(
+            breez_services
+                .persister
+                .get_invoice_webhook_url(&payment_hash)?,
+            Some(format!(
+                "https://example.com/webhook?token=1&payment_hash={payment_hash}"
+            ))
+        );
+
+        breez_services.delete_invoice(res.ln_invoice.bolt11).await?;
+        assert_eq!(
+            breez_services
+                .persister
+                .get_invoice_webhook_url(&payment_hash)?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_log_stream_filter() {
+        let filter = LogStreamFilter::new("debug,hyper=warn");
+        let metadata = |target, level| log::Metadata::builder().target(target).level(level).build();
+        assert!(filter.enabled(&metadata("breez_sdk_core::ldk", log::Level::Debug)));
+        assert!(!filter.enabled(&metadata("breez_sdk_core::ldk", log::Level::Trace)));
+        assert!(!filter.enabled(&metadata("hyper::proto", log::Level::Debug)));
+        assert!(filter.enabled(&metadata("hyper::proto", log::Level::Warn)));
+    }
+
+    #[test]
+    fn test_keysend_tlvs() {
+        let podcast_tlv = TlvEntry {
+            field_number: 7629169,
+            value: b"podcast".to_vec(),
+        };
+        let comment_tlv = |comment: &str| TlvEntry {
+            field_number: KEYSEND_MESSAGE_TLV_TYPE,
+            value: comment.as_bytes().to_vec(),
+        };
+
+        assert_eq!(keysend_tlvs(None, None).unwrap(), None);
+        assert_eq!(
+            keysend_tlvs(None, Some("thanks".to_string())).unwrap(),
+            Some(vec![comment_tlv("thanks")])
+        );
+        assert_eq!(
+            keysend_tlvs(Some(vec![podcast_tlv.clone()]), Some("thanks".to_string())).unwrap(),
+            Some(vec![podcast_tlv, comment_tlv("thanks")])
+        );
+
+        let longest = "a".repeat(MAX_KEYSEND_COMMENT_LEN);
+        assert!(keysend_tlvs(None, Some(longest.clone())).is_ok());
+        assert!(keysend_tlvs(None, Some(format!("{longest}a"))).is_err());
+        assert!(keysend_tlvs(Some(vec![comment_tlv("tlv")]), Some("thanks".to_string())).is_err());
+    }
+
+    #[test]
+    fn test_preopen_amount_msat() {
+        let fee_params = OpeningFeeParams {
+            min_msat: 1_000_000,
+            proportional: 40_000,
+            valid_until: "2030-01-01T00:00:00Z".to_string(),
+            max_idle_time: 0,
+            max_client_to_self_delay: 10_000,
+            promise: "promise".to_string(),
+        };
+        // The minimum fee is charged on top of the forwarded amount.
+        assert_eq!(
+            preopen_amount_msat(5_000_000, 1_000_000, &fee_params).unwrap(),
+            6_000_000
+        );
+        // The proportional fee is charged on the whole amount, including the fee itself.
+        let amount_msat = preopen_amount_msat(50_000_000, 1_000_000, &fee_params).unwrap();
+        assert_eq!(amount_msat, 52_083_000);
+        assert_eq!(
+            amount_msat - fee_params.get_channel_fees_msat_for(amount_msat),
+            50_000_000
+        );
+        // Only half of the forwarded amount is over-provisioned.
+        assert_eq!(
+            preopen_amount_msat(5_000_000, 500_000, &fee_params).unwrap(),
+            11_000_000
+        );
+
+        assert!(preopen_amount_msat(u64::MAX, 1_000_000, &fee_params).is_err());
+        // A fee of the whole amount is never covered.
+        let fee_params = OpeningFeeParams {
+            proportional: 1_000_000,
+            ..fee_params
+        };
+        assert!(preopen_amount_msat(5_000_000, 1_000_000, &fee_params).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_prepare_send_payment() -> Result<()> {
+        let breez_services = breez_services().await?;
+        let invoice = create_invoice_for(Currency::Bitcoin, SystemTime::now(), 50);
+        let res = breez_services
+            .prepare_send_payment(PrepareSendPaymentRequest {
+                bolt11: invoice.bolt11.clone(),
+                probe: Some(true),
+                ..Default::default()
+            })
+            .await?;
+        assert_eq!(res.amount_msat, 50);
+        assert_eq!(res.max_fee_msat, breez_services.config.exemptfee_msat);
+        assert_eq!(res.route_found, Some(true));
+        assert!(res.is_payable);
+
+        let res = breez_services
+            .prepare_send_payment(PrepareSendPaymentRequest {
+                bolt11: invoice.bolt11,
+                amount_msat: Some(50),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(res, Err(SendPaymentError::InvalidAmount { .. })));
+
+        let two_hours_ago = SystemTime::now() - Duration::from_secs(7200);
+        let expired = create_invoice_for(Currency::Bitcoin, two_hours_ago, 50);
+        let res = breez_services
+            .prepare_send_payment(PrepareSendPaymentRequest {
+                bolt11: expired.bolt11,
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(res, Err(SendPaymentError::InvoiceExpired { .. })));
+
+        let regtest = create_invoice_for(Currency::Regtest, SystemTime::now(), 50);
+        let res = breez_services
+            .prepare_send_payment(PrepareSendPaymentRequest {
+                bolt11: regtest.bolt11,
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(res, Err(SendPaymentError::InvalidNetwork { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_payment_insufficient_balance() -> Result<()> {
+        let breez_services = breez_services().await?;
+        let max_payable_msat = get_dummy_node_state().max_payable_msat;
+        let invoice =
+            create_invoice_for(Currency::Bitcoin, SystemTime::now(), max_payable_msat + 1);
+        let res = breez_services
+            .send_payment(SendPaymentRequest {
+                bolt11: invoice.bolt11.clone(),
+                amount_msat: None,
+                max_fee_msat: None,
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(SendPaymentError::InsufficientBalance { .. })
+        ));
+        // Rejected before the payment is dispatched or persisted as pending.
+        assert!(breez_services
+            .persister
+            .get_payment_by_hash(&invoice.payment_hash)?
+            .is_none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_send_payment_cancelled() -> Result<()> {
+        let mut node_api = MockNodeAPI::new(get_dummy_node_state());
+        node_api.set_payments_in_flight(true);
+        let breez_services = breez_services_with(Some(Arc::new(node_api)), None, vec![]).await?;
+        let invoice = create_invoice_for(Currency::Bitcoin, SystemTime::now(), 1_000_000);
+        let req = SendPaymentRequest {
+            bolt11: invoice.bolt11.clone(),
+            amount_msat: None,
+            max_fee_msat: None,
+        };
+
+        // Cancelled before it was sent, nothing is persisted.
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let res = breez_services
+            .send_payment_with_cancellation(req.clone(), cancel)
+            .await;
+        assert!(matches!(res, Err(SendPaymentError::Cancelled { .. })));
+        assert!(breez_services
+            .persister
+            .get_payment_by_hash(&invoice.payment_hash)?
+            .is_none());
+
+        // Cancelled while in flight, the call returns promptly and the payment is left pending.
+        let cancel = CancellationToken::new();
+        let services = Arc::clone(&breez_services);
+        let send = tokio::spawn({
+            let cancel = cancel.clone();
+            async move { services.send_payment_with_cancellation(req, cancel).await }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(!send.is_finished());
+        cancel.cancel();
+        let res = tokio::time::timeout(Duration::from_secs(1), send).await??;
+        assert!(matches!(res, Err(SendPaymentError::Cancelled { .. })));
+        let payment = breez_services
+            .persister
+            .get_payment_by_hash(&invoice.payment_hash)?
+            .unwrap();
+        assert_eq!(payment.status, PaymentStatus::Pending);
+        assert_eq!(payment.error, None);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_redeem_onchain_funds_fee_rate_bounds() -> Result<()> {
+        let breez_services = breez_services().await?;
+        for sat_per_vbyte in [0, 100_000] {
+            let res = breez_services
+                .redeem_onchain_funds(RedeemOnchainFundsRequest {
+                    to_address: "bcrt1qxvnh8jtghmwq9qsy4z3xd0gz5jym6ymqkkzgtq".to_string(),
+                    sat_per_vbyte,
+                })
+                .await;
+            assert!(matches!(
+                res,
+                Err(RedeemOnchainError::InvalidFeeRate { .. })
+            ));
+
+            let res = breez_services
+                .prepare_redeem_onchain_funds(PrepareRedeemOnchainFundsRequest {
+                    to_address: "bcrt1qxvnh8jtghmwq9qsy4z3xd0gz5jym6ymqkkzgtq".to_string(),
+                    sat_per_vbyte,
+                })
+                .await;
+            assert!(matches!(
+                res,
+                Err(RedeemOnchainError::InvalidFeeRate { .. })
+            ));
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pay_node_address() -> Result<()> {
+        let breez_services = breez_services().await?;
+        let node_id = "03e7156ae33b0a208d0744199163177e909e80176e55d97a2f221ede0f934dd9ad";
+
+        let res = breez_services
+            .pay_node_address(PayNodeAddressRequest {
+                address: node_id.to_string(),
+                amount_msat: 1_000,
+                open_channel_sat: None,
+            })
+            .await;
+        assert!(res.is_err());
+
+        let res = breez_services
+            .pay_node_address(PayNodeAddressRequest {
+                address: format!("{node_id}@127.0.0.1:9735"),
+                amount_msat: 1_000,
+                open_channel_sat: None,
+            })
+            .await?;
+        assert!(matches!(res, PayNodeAddressResponse::Paid { .. }));
+
+        let res = breez_services
+            .pay_node_address(PayNodeAddressRequest {
+                address: format!("{node_id}@127.0.0.1:9735"),
+                amount_msat: 1_000,
+                open_channel_sat: Some(100_000),
+            })
+            .await?;
+        assert!(matches!(res, PayNodeAddressResponse::ChannelOpened));
+
+        let res = breez_services
+            .pay_node_address(PayNodeAddressRequest {
+                address: format!("{node_id}@127.0.0.1:9735"),
+                amount_msat: 100_001_000,
+                open_channel_sat: Some(100_000),
+            })
+            .await;
+        assert!(matches!(res, Err(SendPaymentError::InvalidAmount { .. })));
+
+        // The capacity does not overflow when converted to msat.
+        let res = breez_services
+            .pay_node_address(PayNodeAddressRequest {
+                address: format!("{node_id}@127.0.0.1:9735"),
+                amount_msat: 1_000,
+                open_channel_sat: Some(u64::MAX),
+            })
+            .await?;
+        assert!(matches!(res, PayNodeAddressResponse::ChannelOpened));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_fetch_rates() -> Result<(), Box<dyn std::error::Error>> {
         let breez_services = breez_services().await?;
@@ -2717,17 +4039,18 @@ pub(crate) mod tests {
         NodeState {
             id: "tx1".to_string(),
             block_height: 1,
-            channels_balance_msat: 100,
+            channels_balance_msat: 100_000,
             onchain_balance_msat: 1_000,
             pending_onchain_balance_msat: 100,
             utxos: vec![],
-            max_payable_msat: 95,
+            max_payable_msat: 95_000,
             max_receivable_msat: 4_000_000_000,
             max_single_payment_amount_msat: 1_000,
             max_chan_reserve_msats: 0,
             connected_peers: vec!["1111".to_string()],
             max_receivable_single_payment_amount_msat: 2_000,
             total_inbound_liquidity_msats: 10_000,
+            sync_warning: None,
         }
     }
 }
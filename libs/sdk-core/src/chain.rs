@@ -17,6 +17,22 @@ pub trait ChainService: Send + Sync {
     /// See <https://mempool.space/docs/api/rest#get-address-transactions>
     async fn address_transactions(&self, address: String) -> SdkResult<Vec<OnchainTx>>;
     async fn current_tip(&self) -> SdkResult<u32>;
+    /// Gets the hash of the block at the given height of the best chain.
+    ///
+    /// See <https://mempool.space/docs/api/rest#get-block-height>
+    async fn block_hash(&self, height: u32) -> SdkResult<String>;
+    /// Gets the confirmation status of a transaction.
+    ///
+    /// See <https://mempool.space/docs/api/rest#get-transaction-status>
+    async fn transaction_status(&self, txid: String) -> SdkResult<TxStatus>;
+    /// Gets the spending status of all the outputs of a transaction.
+    ///
+    /// See <https://mempool.space/docs/api/rest#get-transaction-outspends>
+    async fn transaction_outspends(&self, txid: String) -> SdkResult<Vec<Outspend>>;
+    /// Gets the serialized transaction.
+    ///
+    /// See <https://mempool.space/docs/api/rest#get-transaction-hex>
+    async fn transaction_hex(&self, txid: String) -> SdkResult<Vec<u8>>;
     /// If successful, it returns the transaction ID. Otherwise returns an `Err` describing the error.
     async fn broadcast_transaction(&self, tx: Vec<u8>) -> SdkResult<String>;
 }
@@ -85,6 +101,62 @@ impl ChainService for RedundantChainService {
         ))
     }
 
+    async fn block_hash(&self, height: u32) -> SdkResult<String> {
+        for inst in &self.instances {
+            match inst.block_hash(height).await {
+                Ok(res) => {
+                    return Ok(res);
+                }
+                Err(e) => error!("Call to chain service {} failed: {e}", inst.base_url),
+            }
+        }
+        Err(SdkError::service_connectivity(
+            "All chain service instances failed",
+        ))
+    }
+
+    async fn transaction_status(&self, txid: String) -> SdkResult<TxStatus> {
+        for inst in &self.instances {
+            match inst.transaction_status(txid.clone()).await {
+                Ok(res) => {
+                    return Ok(res);
+                }
+                Err(e) => error!("Call to chain service {} failed: {e}", inst.base_url),
+            }
+        }
+        Err(SdkError::service_connectivity(
+            "All chain service instances failed",
+        ))
+    }
+
+    async fn transaction_outspends(&self, txid: String) -> SdkResult<Vec<Outspend>> {
+        for inst in &self.instances {
+            match inst.transaction_outspends(txid.clone()).await {
+                Ok(res) => {
+                    return Ok(res);
+                }
+                Err(e) => error!("Call to chain service {} failed: {e}", inst.base_url),
+            }
+        }
+        Err(SdkError::service_connectivity(
+            "All chain service instances failed",
+        ))
+    }
+
+    async fn transaction_hex(&self, txid: String) -> SdkResult<Vec<u8>> {
+        for inst in &self.instances {
+            match inst.transaction_hex(txid.clone()).await {
+                Ok(res) => {
+                    return Ok(res);
+                }
+                Err(e) => error!("Call to chain service {} failed: {e}", inst.base_url),
+            }
+        }
+        Err(SdkError::service_connectivity(
+            "All chain service instances failed",
+        ))
+    }
+
     async fn broadcast_transaction(&self, tx: Vec<u8>) -> SdkResult<String> {
         for inst in &self.instances {
             match inst.broadcast_transaction(tx.clone()).await {
@@ -201,6 +273,18 @@ pub struct RecommendedFees {
     pub minimum_fee: u64,
 }
 
+impl RecommendedFees {
+    /// Returns the fee rate, in sat/vbyte, for a transaction to confirm within `target_blocks`.
+    pub(crate) fn for_confirmation_target(&self, target_blocks: u32) -> u64 {
+        match target_blocks {
+            0..=1 => self.fastest_fee,
+            2..=3 => self.half_hour_fee,
+            4..=6 => self.hour_fee,
+            _ => self.economy_fee,
+        }
+    }
+}
+
 #[derive(Default, Deserialize, Serialize, Clone, Debug)]
 pub struct OnchainTx {
     pub txid: String,
@@ -222,6 +306,15 @@ pub struct TxStatus {
     pub block_time: Option<u64>,
 }
 
+#[derive(Default, Deserialize, Serialize, Clone, Debug)]
+pub struct Outspend {
+    pub spent: bool,
+    /// The transaction spending the output, if it is spent.
+    pub txid: Option<String>,
+    pub vin: Option<u32>,
+    pub status: Option<TxStatus>,
+}
+
 #[derive(Default, Deserialize, Serialize, Clone, Debug)]
 pub struct Vout {
     pub scriptpubkey: String,
@@ -289,6 +382,44 @@ impl ChainService for MempoolSpace {
         Ok(parse_json(&response)?)
     }
 
+    async fn block_hash(&self, height: u32) -> SdkResult<String> {
+        let (response, _) = get_and_check_success(
+            self.rest_client.as_ref(),
+            &format!("{}/block-height/{height}", self.base_url),
+        )
+        .await?;
+        Ok(response.trim().to_string())
+    }
+
+    async fn transaction_status(&self, txid: String) -> SdkResult<TxStatus> {
+        let (response, _) = get_and_check_success(
+            self.rest_client.as_ref(),
+            &format!("{}/tx/{txid}/status", self.base_url),
+        )
+        .await?;
+        Ok(parse_json(&response)?)
+    }
+
+    async fn transaction_outspends(&self, txid: String) -> SdkResult<Vec<Outspend>> {
+        let (response, _) = get_and_check_success(
+            self.rest_client.as_ref(),
+            &format!("{}/tx/{txid}/outspends", self.base_url),
+        )
+        .await?;
+        Ok(parse_json(&response)?)
+    }
+
+    async fn transaction_hex(&self, txid: String) -> SdkResult<Vec<u8>> {
+        let (response, _) = get_and_check_success(
+            self.rest_client.as_ref(),
+            &format!("{}/tx/{txid}/hex", self.base_url),
+        )
+        .await?;
+        hex::decode(response.trim()).map_err(|e| SdkError::Generic {
+            err: format!("Invalid transaction hex for {txid}: {e}"),
+        })
+    }
+
     async fn broadcast_transaction(&self, tx: Vec<u8>) -> SdkResult<String> {
         let (txid_or_error, _) = self
             .rest_client
@@ -311,7 +442,7 @@ mod tests {
     use std::sync::Arc;
 
     use crate::chain::{
-        MempoolSpace, OnchainTx, RedundantChainService, RedundantChainServiceTrait,
+        MempoolSpace, OnchainTx, RecommendedFees, RedundantChainService, RedundantChainServiceTrait,
     };
     use anyhow::Result;
     use sdk_common::prelude::{MockResponse, MockRestClient, RestClient};
@@ -347,6 +478,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    async fn test_recommended_fees_for_confirmation_target() -> Result<()> {
+        let fees = RecommendedFees {
+            fastest_fee: 20,
+            half_hour_fee: 10,
+            hour_fee: 5,
+            economy_fee: 2,
+            minimum_fee: 1,
+        };
+        assert_eq!(fees.for_confirmation_target(1), 20);
+        assert_eq!(fees.for_confirmation_target(3), 10);
+        assert_eq!(fees.for_confirmation_target(6), 5);
+        assert_eq!(fees.for_confirmation_target(144), 2);
+
+        Ok(())
+    }
+
     #[test]
     async fn test_recommended_fees_with_fallback() -> Result<()> {
         let mock_rest_client = MockRestClient::new();
@@ -459,6 +607,51 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    async fn test_transaction_outspends() -> Result<()> {
+        let mock_rest_client = MockRestClient::new();
+        let transaction_outspends_response_body = r#"[{"spent":true,"txid":"4da22eff957b855c8bde2d8b61bdb9e10add799a04c709dd7142cc796cee0b65","vin":1,"status":{"confirmed":true,"block_height":674365,"block_hash":"000000000000000000038f780364221846a3c11e2a5b33eee69029afe5775a0f","block_time":1615598852}},{"spent":false}]"#;
+        mock_rest_client.add_response(MockResponse::new(
+            200,
+            transaction_outspends_response_body.to_string(),
+        ));
+
+        let ms = MempoolSpace::new(Arc::new(mock_rest_client));
+        let outspends = ms
+            .transaction_outspends(
+                "5e0668bf1cd24f2f8656ee82d4886f5303a06b26838e24b7db73afc59e228985".to_string(),
+            )
+            .await?;
+        assert_eq!(outspends.len(), 2);
+        assert!(outspends[0].spent);
+        assert_eq!(
+            outspends[0].txid.as_deref(),
+            Some("4da22eff957b855c8bde2d8b61bdb9e10add799a04c709dd7142cc796cee0b65")
+        );
+        assert_eq!(outspends[0].vin, Some(1));
+        assert!(!outspends[1].spent);
+        assert_eq!(outspends[1].txid, None);
+
+        Ok(())
+    }
+
+    #[test]
+    async fn test_transaction_hex() -> Result<()> {
+        let mock_rest_client = MockRestClient::new();
+        mock_rest_client.add_response(MockResponse::new(200, "0200000000\n".to_string()));
+        mock_rest_client.add_response(MockResponse::new(200, "not hex".to_string()));
+
+        let ms = MempoolSpace::new(Arc::new(mock_rest_client));
+        let txid = "5e0668bf1cd24f2f8656ee82d4886f5303a06b26838e24b7db73afc59e228985";
+        assert_eq!(
+            ms.transaction_hex(txid.to_string()).await?,
+            vec![2, 0, 0, 0, 0]
+        );
+        assert!(ms.transaction_hex(txid.to_string()).await.is_err());
+
+        Ok(())
+    }
+
     // #[test]
     // async fn test_address_transactions_mempool() {
     //     let mock_rest_client = MockRestClient::new();
@@ -25,6 +25,22 @@ pub enum ConnectError {
     #[error("Restore only: {err}")]
     RestoreOnly { err: String },
 
+    /// This error is raised when the node is in use by another running instance, for example
+    /// on another device.
+    #[error("Node locked: {err}")]
+    NodeLocked { err: String },
+
+    /// This error is raised when the node state on the remote storage is older than the one
+    /// previously synced by this device and [crate::models::Config::allow_state_regression]
+    /// is not set.
+    #[error("State regression: {err}")]
+    StateRegression { err: String },
+
+    /// This error is raised when [crate::models::ConnectRequest::seed] is not the seed the
+    /// node state kept in the working directory or on the remote storage was created with.
+    #[error("Seed mismatch: {err}")]
+    SeedMismatch { err: String },
+
     /// This error is raised when a connection to an external service fails.
     #[error("Service connectivity: {err}")]
     ServiceConnectivity { err: String },
@@ -42,6 +58,9 @@ impl From<NodeError> for ConnectError {
     fn from(value: NodeError) -> Self {
         match value {
             NodeError::RestoreOnly(err) => Self::RestoreOnly { err },
+            NodeError::NodeLocked(err) => Self::NodeLocked { err },
+            NodeError::StateRegression(err) => Self::StateRegression { err },
+            NodeError::SeedMismatch(err) => Self::SeedMismatch { err },
             NodeError::ServiceConnectivity(err) => Self::ServiceConnectivity { err },
             _ => Self::Generic {
                 err: value.to_string(),
@@ -114,6 +133,7 @@ impl From<SendPaymentError> for LnUrlPayError {
             SendPaymentError::RouteTooExpensive { err } => Self::RouteTooExpensive { err },
             SendPaymentError::ServiceConnectivity { err } => Self::ServiceConnectivity { err },
             SendPaymentError::InsufficientBalance { err } => Self::InsufficientBalance { err },
+            SendPaymentError::Cancelled { err } => Self::Generic { err },
         }
     }
 }
@@ -130,12 +150,19 @@ impl From<ReceivePaymentError> for LnUrlWithdrawError {
     fn from(value: ReceivePaymentError) -> Self {
         match value {
             ReceivePaymentError::Generic { err }
+            | ReceivePaymentError::InvalidDescription { err }
             | ReceivePaymentError::InvoiceExpired { err }
             | ReceivePaymentError::InvoiceNoDescription { err }
-            | ReceivePaymentError::InvoicePreimageAlreadyExists { err } => Self::Generic { err },
+            | ReceivePaymentError::InvoicePreimageAlreadyExists { err }
+            | ReceivePaymentError::JitChannelLimitReached { err }
+            | ReceivePaymentError::LspRequestRejected { err }
+            | ReceivePaymentError::LspFeeTooHigh { err }
+            | ReceivePaymentError::OpenChannelNeeded { err, .. }
+            | ReceivePaymentError::Cancelled { err } => Self::Generic { err },
             ReceivePaymentError::InvalidAmount { err } => Self::InvalidAmount { err },
             ReceivePaymentError::InvalidInvoice { err } => Self::InvalidInvoice { err },
-            ReceivePaymentError::ServiceConnectivity { err } => Self::ServiceConnectivity { err },
+            ReceivePaymentError::ServiceConnectivity { err }
+            | ReceivePaymentError::LspUnavailable { err } => Self::ServiceConnectivity { err },
             ReceivePaymentError::InvoiceNoRoutingHints { err } => {
                 Self::InvoiceNoRoutingHints { err }
             }
@@ -225,6 +252,10 @@ pub enum RedeemOnchainError {
     /// This error is raised when the node does not have enough funds to redeem the onchain balance.
     #[error("{err}")]
     InsufficientFunds { err: String },
+
+    /// This error is raised when the fee rate is outside of the bounds set in the [crate::Config].
+    #[error("Invalid fee rate: {err}")]
+    InvalidFeeRate { err: String },
 }
 
 impl From<NodeError> for RedeemOnchainError {
@@ -269,6 +300,10 @@ pub enum ReceivePaymentError {
     #[error("Invalid amount: {err}")]
     InvalidAmount { err: String },
 
+    /// This error is raised when the description is too long to fit in the invoice.
+    #[error("Invalid description: {err}")]
+    InvalidDescription { err: String },
+
     /// This error is raised when the lightning invoice cannot be parsed.
     #[error("Invalid invoice: {err}")]
     InvalidInvoice { err: String },
@@ -294,6 +329,37 @@ pub enum ReceivePaymentError {
     /// This error is raised when a connection to an external service fails.
     #[error("Service connectivity: {err}")]
     ServiceConnectivity { err: String },
+
+    /// This error is raised when receiving the payment needs a new channel, but the
+    /// configured maximum number of channels opened by the LSP is reached.
+    #[error("JIT channel limit reached: {err}")]
+    JitChannelLimitReached { err: String },
+
+    /// This error is raised when receiving the payment needs a new channel, but the LSP
+    /// could not be reached.
+    #[error("LSP unavailable: {err}")]
+    LspUnavailable { err: String },
+
+    /// This error is raised when the LSP refused to open a channel for the payment, for
+    /// example because the amount is outside of the payment size limits it accepts.
+    #[error("LSP request rejected: {err}")]
+    LspRequestRejected { err: String },
+
+    /// This error is raised when the channel opening fee offered by the LSP is higher than
+    /// the maximum fee accepted for the payment.
+    #[error("LSP fee too high: {err}")]
+    LspFeeTooHigh { err: String },
+
+    /// This error is raised when receiving the payment needs a new channel, but opening
+    /// channels was not allowed by [crate::models::ReceivePaymentRequest::no_open_channel].
+    /// `opening_fee_msat` is the fee the channel opening would have cost.
+    #[error("Open channel needed: {err}")]
+    OpenChannelNeeded { err: String, opening_fee_msat: u64 },
+
+    /// This error is raised when the receive was cancelled by the caller, see
+    /// [crate::BreezServices::receive_payment_with_cancellation].
+    #[error("Cancelled: {err}")]
+    Cancelled { err: String },
 }
 
 impl From<anyhow::Error> for ReceivePaymentError {
@@ -324,6 +390,11 @@ impl From<NodeError> for ReceivePaymentError {
                 Self::InvoicePreimageAlreadyExists { err }
             }
             NodeError::ServiceConnectivity(err) => Self::ServiceConnectivity { err },
+            NodeError::InvalidAmount(err) => Self::InvalidAmount { err },
+            NodeError::LspUnavailable(err) => Self::LspUnavailable { err },
+            NodeError::LspRequestRejected(err) => Self::LspRequestRejected { err },
+            NodeError::LspFeeTooHigh(err) => Self::LspFeeTooHigh { err },
+            NodeError::Cancelled(err) => Self::Cancelled { err },
             _ => Self::Generic {
                 err: value.to_string(),
             },
@@ -416,6 +487,7 @@ impl From<LnUrlError> for SdkError {
 impl From<NodeError> for SdkError {
     fn from(value: NodeError) -> Self {
         match value {
+            NodeError::ConnectTimeout(err) => Self::ServiceConnectivity { err },
             NodeError::ServiceConnectivity(err) => Self::ServiceConnectivity { err },
             _ => Self::Generic {
                 err: value.to_string(),
@@ -482,7 +554,8 @@ impl From<SendPaymentError> for SdkError {
             | SendPaymentError::PaymentTimeout { err }
             | SendPaymentError::RouteNotFound { err }
             | SendPaymentError::RouteTooExpensive { err }
-            | SendPaymentError::InsufficientBalance { err } => Self::Generic { err },
+            | SendPaymentError::InsufficientBalance { err }
+            | SendPaymentError::Cancelled { err } => Self::Generic { err },
             SendPaymentError::ServiceConnectivity { err } => Self::ServiceConnectivity { err },
         }
     }
@@ -644,6 +717,12 @@ pub enum SendPaymentError {
     /// This error is raised when the node does not have enough funds to make the payment.
     #[error("Insufficient balance: {err}")]
     InsufficientBalance { err: String },
+
+    /// This error is raised when waiting for the payment was cancelled by the caller, see
+    /// [crate::BreezServices::send_payment_with_cancellation]. The payment may still complete
+    /// if it was already dispatched.
+    #[error("Cancelled: {err}")]
+    Cancelled { err: String },
 }
 
 impl From<anyhow::Error> for SendPaymentError {
@@ -673,6 +752,7 @@ impl From<NodeError> for SendPaymentError {
             NodeError::RouteNotFound(err) => Self::RouteNotFound { err },
             NodeError::RouteTooExpensive(err) => Self::RouteTooExpensive { err },
             NodeError::ServiceConnectivity(err) => Self::ServiceConnectivity { err },
+            NodeError::Cancelled(err) => Self::Cancelled { err },
             _ => Self::Generic {
                 err: value.to_string(),
             },
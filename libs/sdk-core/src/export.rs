@@ -0,0 +1,146 @@
+use chrono::{DateTime, SecondsFormat};
+
+use crate::error::{SdkError, SdkResult};
+use crate::models::{Payment, PaymentDetails, PaymentExportFormat, PaymentType};
+
+const CSV_HEADER: &str =
+    "id,payment_type,status,payment_time_utc,amount_msat,fee_msat,description,counterparty,payment_hash";
+
+/// Serializes the payments in the given format.
+pub(crate) fn export_payments(
+    payments: &[Payment],
+    format: PaymentExportFormat,
+) -> SdkResult<String> {
+    match format {
+        PaymentExportFormat::Csv => payments_to_csv(payments),
+        PaymentExportFormat::Json => Ok(serde_json::to_string(payments)?),
+    }
+}
+
+fn payments_to_csv(payments: &[Payment]) -> SdkResult<String> {
+    let mut csv = String::from(CSV_HEADER);
+    for payment in payments {
+        // RFC 3339 in UTC, e.g. `2023-11-14T22:13:20Z`.
+        let payment_time = DateTime::from_timestamp(payment.payment_time, 0)
+            .ok_or_else(|| {
+                SdkError::generic(&format!("Invalid payment time {}", payment.payment_time))
+            })?
+            .to_rfc3339_opts(SecondsFormat::Secs, true);
+        let (counterparty, payment_hash) = match &payment.details {
+            PaymentDetails::Ln { data } => {
+                // The destination of a received payment is the node itself.
+                let counterparty = match payment.payment_type {
+                    PaymentType::Sent => data.destination_pubkey.as_str(),
+                    _ => "",
+                };
+                (counterparty, data.payment_hash.as_str())
+            }
+            PaymentDetails::ClosedChannel { data } => (data.counterparty_node_id.as_str(), ""),
+        };
+        let row = [
+            payment.id.as_str(),
+            &format!("{:?}", payment.payment_type),
+            &format!("{:?}", payment.status),
+            &payment_time,
+            &payment.amount_msat.to_string(),
+            &payment.fee_msat.to_string(),
+            payment.description.as_deref().unwrap_or_default(),
+            counterparty,
+            payment_hash,
+        ];
+        csv.push('\n');
+        csv.push_str(&row.map(escape_csv_field).join(","));
+    }
+    Ok(csv)
+}
+
+/// Quotes the field if it contains a separator, a quote or a line break, as per RFC 4180.
+fn escape_csv_field(field: &str) -> String {
+    match field.contains([',', '"', '\n', '\r']) {
+        true => format!("\"{}\"", field.replace('"', "\"\"")),
+        false => field.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{
+        ChannelState, ClosedChannelPaymentDetails, LnPaymentDetails, PaymentStatus,
+    };
+
+    fn payments() -> Vec<Payment> {
+        vec![
+            Payment {
+                id: "1".to_string(),
+                payment_type: PaymentType::Sent,
+                payment_time: 1_700_000_000,
+                amount_msat: 10_000,
+                fee_msat: 1_000,
+                status: PaymentStatus::Complete,
+                description: Some("Coffee, \"large\"".to_string()),
+                details: PaymentDetails::Ln {
+                    data: LnPaymentDetails {
+                        payment_hash: "aa".repeat(32),
+                        destination_pubkey: "02".repeat(33),
+                        ..Default::default()
+                    },
+                },
+                ..Default::default()
+            },
+            Payment {
+                id: "2".to_string(),
+                payment_type: PaymentType::ClosedChannel,
+                payment_time: 1_700_000_100,
+                amount_msat: 50_000_000,
+                status: PaymentStatus::Pending,
+                details: PaymentDetails::ClosedChannel {
+                    data: ClosedChannelPaymentDetails {
+                        state: ChannelState::PendingClose,
+                        funding_txid: "bb".repeat(32),
+                        short_channel_id: None,
+                        closing_txid: None,
+                        counterparty_node_id: "03".repeat(33),
+                        capacity_sat: 100_000,
+                        is_force_close: false,
+                    },
+                },
+                ..Default::default()
+            },
+        ]
+    }
+
+    #[test]
+    fn test_export_payments_csv() {
+        let csv = export_payments(&payments(), PaymentExportFormat::Csv).unwrap();
+        let lines: Vec<_> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert_eq!(
+            lines[1],
+            format!(
+                "1,Sent,Complete,2023-11-14T22:13:20Z,10000,1000,\"Coffee, \"\"large\"\"\",{},{}",
+                "02".repeat(33),
+                "aa".repeat(32)
+            )
+        );
+        assert_eq!(
+            lines[2],
+            format!(
+                "2,ClosedChannel,Pending,2023-11-14T22:15:00Z,50000000,0,,{},",
+                "03".repeat(33)
+            )
+        );
+
+        let csv = export_payments(&[], PaymentExportFormat::Csv).unwrap();
+        assert_eq!(csv, CSV_HEADER);
+    }
+
+    #[test]
+    fn test_export_payments_json() {
+        let payments = payments();
+        let json = export_payments(&payments, PaymentExportFormat::Json).unwrap();
+        let decoded: Vec<Payment> = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, payments);
+    }
+}
@@ -139,6 +139,7 @@ fn wire__crate__binding__breez_events_stream_impl(
 fn wire__crate__binding__breez_log_stream_impl(
     port_: flutter_rust_bridge::for_generated::MessagePort,
     s: impl CstDecode<StreamSink<crate::models::LogEntry, flutter_rust_bridge::for_generated::DcoCodec>>,
+    filter: impl CstDecode<Option<String>>,
 ) {
     FLUTTER_RUST_BRIDGE_HANDLER.wrap_normal::<flutter_rust_bridge::for_generated::DcoCodec, _, _>(
         flutter_rust_bridge::for_generated::TaskInfo {
@@ -148,10 +149,11 @@ fn wire__crate__binding__breez_log_stream_impl(
         },
         move || {
             let api_s = s.cst_decode();
+            let api_filter = filter.cst_decode();
             move |context| {
                 transform_result_dco::<_, _, flutter_rust_bridge::for_generated::anyhow::Error>(
                     (move || {
-                        let output_ok = crate::binding::breez_log_stream(api_s)?;
+                        let output_ok = crate::binding::breez_log_stream(api_s, api_filter)?;
                         Ok(output_ok)
                     })(),
                 )
@@ -1990,6 +1992,67 @@ impl SseDecode for crate::breez_services::BreezEvent {
                     details: var_details,
                 };
             }
+            10 => {
+                return crate::breez_services::BreezEvent::RemoteLockAcquired;
+            }
+            11 => {
+                return crate::breez_services::BreezEvent::RemoteLockRefreshFailed;
+            }
+            12 => {
+                return crate::breez_services::BreezEvent::RemoteLockLost;
+            }
+            13 => {
+                let mut var_channelId = <String>::sse_decode(deserializer);
+                let mut var_amountSat = <i64>::sse_decode(deserializer);
+                let mut var_fundingTxid = <String>::sse_decode(deserializer);
+                return crate::breez_services::BreezEvent::SplicePending {
+                    channel_id: var_channelId,
+                    amount_sat: var_amountSat,
+                    funding_txid: var_fundingTxid,
+                };
+            }
+            14 => {
+                let mut var_channelId = <String>::sse_decode(deserializer);
+                let mut var_amountSat = <i64>::sse_decode(deserializer);
+                return crate::breez_services::BreezEvent::SpliceCompleted {
+                    channel_id: var_channelId,
+                    amount_sat: var_amountSat,
+                };
+            }
+            15 => {
+                let mut var_channelId = <String>::sse_decode(deserializer);
+                let mut var_amountSat = <i64>::sse_decode(deserializer);
+                let mut var_reason = <String>::sse_decode(deserializer);
+                return crate::breez_services::BreezEvent::SpliceFailed {
+                    channel_id: var_channelId,
+                    amount_sat: var_amountSat,
+                    reason: var_reason,
+                };
+            }
+            16 => {
+                return crate::breez_services::BreezEvent::RestoreDownloadStarted;
+            }
+            17 => {
+                return crate::breez_services::BreezEvent::RestoreDownloadCompleted;
+            }
+            18 => {
+                return crate::breez_services::BreezEvent::RestoreChainSyncStarted;
+            }
+            19 => {
+                return crate::breez_services::BreezEvent::RestoreChainSyncCompleted;
+            }
+            20 => {
+                let mut var_oldTipHeight = <u32>::sse_decode(deserializer);
+                let mut var_oldTipHash = <String>::sse_decode(deserializer);
+                let mut var_newTipHeight = <u32>::sse_decode(deserializer);
+                let mut var_newTipHash = <String>::sse_decode(deserializer);
+                return crate::breez_services::BreezEvent::ChainReorg {
+                    old_tip_height: var_oldTipHeight,
+                    old_tip_hash: var_oldTipHash,
+                    new_tip_height: var_newTipHeight,
+                    new_tip_hash: var_newTipHash,
+                };
+            }
             _ => {
                 unimplemented!("");
             }
@@ -2081,11 +2144,17 @@ impl SseDecode for crate::models::ClosedChannelPaymentDetails {
         let mut var_fundingTxid = <String>::sse_decode(deserializer);
         let mut var_shortChannelId = <Option<String>>::sse_decode(deserializer);
         let mut var_closingTxid = <Option<String>>::sse_decode(deserializer);
+        let mut var_counterpartyNodeId = <String>::sse_decode(deserializer);
+        let mut var_capacitySat = <u64>::sse_decode(deserializer);
+        let mut var_isForceClose = <bool>::sse_decode(deserializer);
         return crate::models::ClosedChannelPaymentDetails {
             state: var_state,
             funding_txid: var_fundingTxid,
             short_channel_id: var_shortChannelId,
             closing_txid: var_closingTxid,
+            counterparty_node_id: var_counterpartyNodeId,
+            capacity_sat: var_capacitySat,
+            is_force_close: var_isForceClose,
         };
     }
 }
@@ -2106,6 +2175,20 @@ impl SseDecode for crate::models::Config {
         let mut var_apiKey = <Option<String>>::sse_decode(deserializer);
         let mut var_maxfeePercent = <f64>::sse_decode(deserializer);
         let mut var_exemptfeeMsat = <u64>::sse_decode(deserializer);
+        let mut var_paymentRetries = <u32>::sse_decode(deserializer);
+        let mut var_remoteLockLeaseSecs = <u32>::sse_decode(deserializer);
+        let mut var_remoteLockRetrySecs = <u32>::sse_decode(deserializer);
+        let mut var_connectTimeoutSecs = <u32>::sse_decode(deserializer);
+        let mut var_anchorChannelsEnabled = <bool>::sse_decode(deserializer);
+        let mut var_allowStateRegression = <bool>::sse_decode(deserializer);
+        let mut var_minSatPerVbyte = <u32>::sse_decode(deserializer);
+        let mut var_maxSatPerVbyte = <u32>::sse_decode(deserializer);
+        let mut var_syncRetries = <u32>::sse_decode(deserializer);
+        let mut var_maxJitChannels = <u32>::sse_decode(deserializer);
+        let mut var_sweepConfirmationTarget = <u32>::sse_decode(deserializer);
+        let mut var_maxStoreValueBytes = <u32>::sse_decode(deserializer);
+        let mut var_vssClientId = <Option<String>>::sse_decode(deserializer);
+        let mut var_restrictFilePermissions = <bool>::sse_decode(deserializer);
         return crate::models::Config {
             breezserver: var_breezserver,
             chainnotifier_url: var_chainnotifierUrl,
@@ -2120,6 +2203,20 @@ impl SseDecode for crate::models::Config {
             api_key: var_apiKey,
             maxfee_percent: var_maxfeePercent,
             exemptfee_msat: var_exemptfeeMsat,
+            payment_retries: var_paymentRetries,
+            remote_lock_lease_secs: var_remoteLockLeaseSecs,
+            remote_lock_retry_secs: var_remoteLockRetrySecs,
+            connect_timeout_secs: var_connectTimeoutSecs,
+            anchor_channels_enabled: var_anchorChannelsEnabled,
+            allow_state_regression: var_allowStateRegression,
+            min_sat_per_vbyte: var_minSatPerVbyte,
+            max_sat_per_vbyte: var_maxSatPerVbyte,
+            sync_retries: var_syncRetries,
+            max_jit_channels: var_maxJitChannels,
+            sweep_confirmation_target: var_sweepConfirmationTarget,
+            max_store_value_bytes: var_maxStoreValueBytes,
+            vss_client_id: var_vssClientId,
+            restrict_file_permissions: var_restrictFilePermissions,
         };
     }
 }
@@ -2666,6 +2763,8 @@ impl SseDecode for crate::models::LnPaymentDetails {
         let mut var_swapInfo = <Option<crate::models::SwapInfo>>::sse_decode(deserializer);
         let mut var_reverseSwapInfo =
             <Option<crate::models::ReverseSwapInfo>>::sse_decode(deserializer);
+        let mut var_customTlvs = <Option<Vec<crate::models::TlvEntry>>>::sse_decode(deserializer);
+        let mut var_openChannelBolt11 = <Option<String>>::sse_decode(deserializer);
         return crate::models::LnPaymentDetails {
             payment_hash: var_paymentHash,
             destination_pubkey: var_destinationPubkey,
@@ -2680,6 +2779,8 @@ impl SseDecode for crate::models::LnPaymentDetails {
             lnurl_withdraw_endpoint: var_lnurlWithdrawEndpoint,
             swap_info: var_swapInfo,
             reverse_swap_info: var_reverseSwapInfo,
+            custom_tlvs: var_customTlvs,
+            open_channel_bolt11: var_openChannelBolt11,
         };
     }
 }
@@ -2942,6 +3043,9 @@ impl SseDecode for crate::lsp::LspInformation {
         let mut var_lspPubkey = <Vec<u8>>::sse_decode(deserializer);
         let mut var_openingFeeParamsList =
             <crate::models::OpeningFeeParamsMenu>::sse_decode(deserializer);
+        let mut var_channelOverProvisioningPpm = <u32>::sse_decode(deserializer);
+        let mut var_minChannelLifetime = <u32>::sse_decode(deserializer);
+        let mut var_maxClientToSelfDelay = <u32>::sse_decode(deserializer);
         return crate::lsp::LspInformation {
             id: var_id,
             name: var_name,
@@ -2954,6 +3058,9 @@ impl SseDecode for crate::lsp::LspInformation {
             min_htlc_msat: var_minHtlcMsat,
             lsp_pubkey: var_lspPubkey,
             opening_fee_params_list: var_openingFeeParamsList,
+            channel_over_provisioning_ppm: var_channelOverProvisioningPpm,
+            min_channel_lifetime: var_minChannelLifetime,
+            max_client_to_self_delay: var_maxClientToSelfDelay,
         };
     }
 }
@@ -3011,6 +3118,7 @@ impl SseDecode for crate::models::NodeState {
         let mut var_connectedPeers = <Vec<String>>::sse_decode(deserializer);
         let mut var_maxReceivableSinglePaymentAmountMsat = <u64>::sse_decode(deserializer);
         let mut var_totalInboundLiquidityMsats = <u64>::sse_decode(deserializer);
+        let mut var_syncWarning = <Option<String>>::sse_decode(deserializer);
         return crate::models::NodeState {
             id: var_id,
             block_height: var_blockHeight,
@@ -3025,6 +3133,7 @@ impl SseDecode for crate::models::NodeState {
             connected_peers: var_connectedPeers,
             max_receivable_single_payment_amount_msat: var_maxReceivableSinglePaymentAmountMsat,
             total_inbound_liquidity_msats: var_totalInboundLiquidityMsats,
+            sync_warning: var_syncWarning,
         };
     }
 }
@@ -3568,6 +3677,9 @@ impl SseDecode for crate::models::ReceivePaymentRequest {
         let mut var_useDescriptionHash = <Option<bool>>::sse_decode(deserializer);
         let mut var_expiry = <Option<u32>>::sse_decode(deserializer);
         let mut var_cltv = <Option<u32>>::sse_decode(deserializer);
+        let mut var_registerNotifications = <Option<bool>>::sse_decode(deserializer);
+        let mut var_truncateDescription = <Option<bool>>::sse_decode(deserializer);
+        let mut var_noOpenChannel = <Option<bool>>::sse_decode(deserializer);
         return crate::models::ReceivePaymentRequest {
             amount_msat: var_amountMsat,
             description: var_description,
@@ -3576,6 +3688,9 @@ impl SseDecode for crate::models::ReceivePaymentRequest {
             use_description_hash: var_useDescriptionHash,
             expiry: var_expiry,
             cltv: var_cltv,
+            register_notifications: var_registerNotifications,
+            truncate_description: var_truncateDescription,
+            no_open_channel: var_noOpenChannel,
         };
     }
 }
@@ -3792,9 +3907,11 @@ impl SseDecode for crate::models::SendPaymentRequest {
     fn sse_decode(deserializer: &mut flutter_rust_bridge::for_generated::SseDeserializer) -> Self {
         let mut var_bolt11 = <String>::sse_decode(deserializer);
         let mut var_amountMsat = <Option<u64>>::sse_decode(deserializer);
+        let mut var_maxFeeMsat = <Option<u64>>::sse_decode(deserializer);
         return crate::models::SendPaymentRequest {
             bolt11: var_bolt11,
             amount_msat: var_amountMsat,
+            max_fee_msat: var_maxFeeMsat,
         };
     }
 }
@@ -3815,10 +3932,12 @@ impl SseDecode for crate::models::SendSpontaneousPaymentRequest {
         let mut var_nodeId = <String>::sse_decode(deserializer);
         let mut var_amountMsat = <u64>::sse_decode(deserializer);
         let mut var_extraTlvs = <Option<Vec<crate::models::TlvEntry>>>::sse_decode(deserializer);
+        let mut var_comment = <Option<String>>::sse_decode(deserializer);
         return crate::models::SendSpontaneousPaymentRequest {
             node_id: var_nodeId,
             amount_msat: var_amountMsat,
             extra_tlvs: var_extraTlvs,
+            comment: var_comment,
         };
     }
 }
@@ -4285,6 +4404,67 @@ impl flutter_rust_bridge::IntoDart for crate::breez_services::BreezEvent {
             crate::breez_services::BreezEvent::SwapUpdated { details } => {
                 [9.into_dart(), details.into_into_dart().into_dart()].into_dart()
             }
+            crate::breez_services::BreezEvent::RemoteLockAcquired => [10.into_dart()].into_dart(),
+            crate::breez_services::BreezEvent::RemoteLockRefreshFailed => {
+                [11.into_dart()].into_dart()
+            }
+            crate::breez_services::BreezEvent::RemoteLockLost => [12.into_dart()].into_dart(),
+            crate::breez_services::BreezEvent::SplicePending {
+                channel_id,
+                amount_sat,
+                funding_txid,
+            } => [
+                13.into_dart(),
+                channel_id.into_into_dart().into_dart(),
+                amount_sat.into_into_dart().into_dart(),
+                funding_txid.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::breez_services::BreezEvent::SpliceCompleted {
+                channel_id,
+                amount_sat,
+            } => [
+                14.into_dart(),
+                channel_id.into_into_dart().into_dart(),
+                amount_sat.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::breez_services::BreezEvent::SpliceFailed {
+                channel_id,
+                amount_sat,
+                reason,
+            } => [
+                15.into_dart(),
+                channel_id.into_into_dart().into_dart(),
+                amount_sat.into_into_dart().into_dart(),
+                reason.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
+            crate::breez_services::BreezEvent::RestoreDownloadStarted => {
+                [16.into_dart()].into_dart()
+            }
+            crate::breez_services::BreezEvent::RestoreDownloadCompleted => {
+                [17.into_dart()].into_dart()
+            }
+            crate::breez_services::BreezEvent::RestoreChainSyncStarted => {
+                [18.into_dart()].into_dart()
+            }
+            crate::breez_services::BreezEvent::RestoreChainSyncCompleted => {
+                [19.into_dart()].into_dart()
+            }
+            crate::breez_services::BreezEvent::ChainReorg {
+                old_tip_height,
+                old_tip_hash,
+                new_tip_height,
+                new_tip_hash,
+            } => [
+                20.into_dart(),
+                old_tip_height.into_into_dart().into_dart(),
+                old_tip_hash.into_into_dart().into_dart(),
+                new_tip_height.into_into_dart().into_dart(),
+                new_tip_hash.into_into_dart().into_dart(),
+            ]
+            .into_dart(),
             _ => {
                 unimplemented!("");
             }
@@ -4432,6 +4612,9 @@ impl flutter_rust_bridge::IntoDart for crate::models::ClosedChannelPaymentDetail
             self.funding_txid.into_into_dart().into_dart(),
             self.short_channel_id.into_into_dart().into_dart(),
             self.closing_txid.into_into_dart().into_dart(),
+            self.counterparty_node_id.into_into_dart().into_dart(),
+            self.capacity_sat.into_into_dart().into_dart(),
+            self.is_force_close.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -4464,6 +4647,20 @@ impl flutter_rust_bridge::IntoDart for crate::models::Config {
             self.api_key.into_into_dart().into_dart(),
             self.maxfee_percent.into_into_dart().into_dart(),
             self.exemptfee_msat.into_into_dart().into_dart(),
+            self.payment_retries.into_into_dart().into_dart(),
+            self.remote_lock_lease_secs.into_into_dart().into_dart(),
+            self.remote_lock_retry_secs.into_into_dart().into_dart(),
+            self.connect_timeout_secs.into_into_dart().into_dart(),
+            self.anchor_channels_enabled.into_into_dart().into_dart(),
+            self.allow_state_regression.into_into_dart().into_dart(),
+            self.min_sat_per_vbyte.into_into_dart().into_dart(),
+            self.max_sat_per_vbyte.into_into_dart().into_dart(),
+            self.sync_retries.into_into_dart().into_dart(),
+            self.max_jit_channels.into_into_dart().into_dart(),
+            self.sweep_confirmation_target.into_into_dart().into_dart(),
+            self.max_store_value_bytes.into_into_dart().into_dart(),
+            self.vss_client_id.into_into_dart().into_dart(),
+            self.restrict_file_permissions.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -4828,6 +5025,8 @@ impl flutter_rust_bridge::IntoDart for crate::models::LnPaymentDetails {
             self.lnurl_withdraw_endpoint.into_into_dart().into_dart(),
             self.swap_info.into_into_dart().into_dart(),
             self.reverse_swap_info.into_into_dart().into_dart(),
+            self.custom_tlvs.into_into_dart().into_dart(),
+            self.open_channel_bolt11.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -5202,6 +5401,11 @@ impl flutter_rust_bridge::IntoDart for crate::lsp::LspInformation {
             self.min_htlc_msat.into_into_dart().into_dart(),
             self.lsp_pubkey.into_into_dart().into_dart(),
             self.opening_fee_params_list.into_into_dart().into_dart(),
+            self.channel_over_provisioning_ppm
+                .into_into_dart()
+                .into_dart(),
+            self.min_channel_lifetime.into_into_dart().into_dart(),
+            self.max_client_to_self_delay.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -5295,6 +5499,7 @@ impl flutter_rust_bridge::IntoDart for crate::models::NodeState {
             self.total_inbound_liquidity_msats
                 .into_into_dart()
                 .into_dart(),
+            self.sync_warning.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -5758,6 +5963,9 @@ impl flutter_rust_bridge::IntoDart for crate::models::ReceivePaymentRequest {
             self.use_description_hash.into_into_dart().into_dart(),
             self.expiry.into_into_dart().into_dart(),
             self.cltv.into_into_dart().into_dart(),
+            self.register_notifications.into_into_dart().into_dart(),
+            self.truncate_description.into_into_dart().into_dart(),
+            self.no_open_channel.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -6081,6 +6289,7 @@ impl flutter_rust_bridge::IntoDart for crate::models::SendPaymentRequest {
         [
             self.bolt11.into_into_dart().into_dart(),
             self.amount_msat.into_into_dart().into_dart(),
+            self.max_fee_msat.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -6120,6 +6329,7 @@ impl flutter_rust_bridge::IntoDart for crate::models::SendSpontaneousPaymentRequ
             self.node_id.into_into_dart().into_dart(),
             self.amount_msat.into_into_dart().into_dart(),
             self.extra_tlvs.into_into_dart().into_dart(),
+            self.comment.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
@@ -6573,6 +6783,67 @@ impl SseEncode for crate::breez_services::BreezEvent {
                 <i32>::sse_encode(9, serializer);
                 <crate::models::SwapInfo>::sse_encode(details, serializer);
             }
+            crate::breez_services::BreezEvent::RemoteLockAcquired => {
+                <i32>::sse_encode(10, serializer);
+            }
+            crate::breez_services::BreezEvent::RemoteLockRefreshFailed => {
+                <i32>::sse_encode(11, serializer);
+            }
+            crate::breez_services::BreezEvent::RemoteLockLost => {
+                <i32>::sse_encode(12, serializer);
+            }
+            crate::breez_services::BreezEvent::SplicePending {
+                channel_id,
+                amount_sat,
+                funding_txid,
+            } => {
+                <i32>::sse_encode(13, serializer);
+                <String>::sse_encode(channel_id, serializer);
+                <i64>::sse_encode(amount_sat, serializer);
+                <String>::sse_encode(funding_txid, serializer);
+            }
+            crate::breez_services::BreezEvent::SpliceCompleted {
+                channel_id,
+                amount_sat,
+            } => {
+                <i32>::sse_encode(14, serializer);
+                <String>::sse_encode(channel_id, serializer);
+                <i64>::sse_encode(amount_sat, serializer);
+            }
+            crate::breez_services::BreezEvent::SpliceFailed {
+                channel_id,
+                amount_sat,
+                reason,
+            } => {
+                <i32>::sse_encode(15, serializer);
+                <String>::sse_encode(channel_id, serializer);
+                <i64>::sse_encode(amount_sat, serializer);
+                <String>::sse_encode(reason, serializer);
+            }
+            crate::breez_services::BreezEvent::RestoreDownloadStarted => {
+                <i32>::sse_encode(16, serializer);
+            }
+            crate::breez_services::BreezEvent::RestoreDownloadCompleted => {
+                <i32>::sse_encode(17, serializer);
+            }
+            crate::breez_services::BreezEvent::RestoreChainSyncStarted => {
+                <i32>::sse_encode(18, serializer);
+            }
+            crate::breez_services::BreezEvent::RestoreChainSyncCompleted => {
+                <i32>::sse_encode(19, serializer);
+            }
+            crate::breez_services::BreezEvent::ChainReorg {
+                old_tip_height,
+                old_tip_hash,
+                new_tip_height,
+                new_tip_hash,
+            } => {
+                <i32>::sse_encode(20, serializer);
+                <u32>::sse_encode(old_tip_height, serializer);
+                <String>::sse_encode(old_tip_hash, serializer);
+                <u32>::sse_encode(new_tip_height, serializer);
+                <String>::sse_encode(new_tip_hash, serializer);
+            }
             _ => {
                 unimplemented!("");
             }
@@ -6653,6 +6924,9 @@ impl SseEncode for crate::models::ClosedChannelPaymentDetails {
         <String>::sse_encode(self.funding_txid, serializer);
         <Option<String>>::sse_encode(self.short_channel_id, serializer);
         <Option<String>>::sse_encode(self.closing_txid, serializer);
+        <String>::sse_encode(self.counterparty_node_id, serializer);
+        <u64>::sse_encode(self.capacity_sat, serializer);
+        <bool>::sse_encode(self.is_force_close, serializer);
     }
 }
 
@@ -6672,6 +6946,20 @@ impl SseEncode for crate::models::Config {
         <Option<String>>::sse_encode(self.api_key, serializer);
         <f64>::sse_encode(self.maxfee_percent, serializer);
         <u64>::sse_encode(self.exemptfee_msat, serializer);
+        <u32>::sse_encode(self.payment_retries, serializer);
+        <u32>::sse_encode(self.remote_lock_lease_secs, serializer);
+        <u32>::sse_encode(self.remote_lock_retry_secs, serializer);
+        <u32>::sse_encode(self.connect_timeout_secs, serializer);
+        <bool>::sse_encode(self.anchor_channels_enabled, serializer);
+        <bool>::sse_encode(self.allow_state_regression, serializer);
+        <u32>::sse_encode(self.min_sat_per_vbyte, serializer);
+        <u32>::sse_encode(self.max_sat_per_vbyte, serializer);
+        <u32>::sse_encode(self.sync_retries, serializer);
+        <u32>::sse_encode(self.max_jit_channels, serializer);
+        <u32>::sse_encode(self.sweep_confirmation_target, serializer);
+        <u32>::sse_encode(self.max_store_value_bytes, serializer);
+        <Option<String>>::sse_encode(self.vss_client_id, serializer);
+        <bool>::sse_encode(self.restrict_file_permissions, serializer);
     }
 }
 
@@ -7103,6 +7391,8 @@ impl SseEncode for crate::models::LnPaymentDetails {
         <Option<String>>::sse_encode(self.lnurl_withdraw_endpoint, serializer);
         <Option<crate::models::SwapInfo>>::sse_encode(self.swap_info, serializer);
         <Option<crate::models::ReverseSwapInfo>>::sse_encode(self.reverse_swap_info, serializer);
+        <Option<Vec<crate::models::TlvEntry>>>::sse_encode(self.custom_tlvs, serializer);
+        <Option<String>>::sse_encode(self.open_channel_bolt11, serializer);
     }
 }
 
@@ -7298,6 +7588,9 @@ impl SseEncode for crate::lsp::LspInformation {
         <i64>::sse_encode(self.min_htlc_msat, serializer);
         <Vec<u8>>::sse_encode(self.lsp_pubkey, serializer);
         <crate::models::OpeningFeeParamsMenu>::sse_encode(self.opening_fee_params_list, serializer);
+        <u32>::sse_encode(self.channel_over_provisioning_ppm, serializer);
+        <u32>::sse_encode(self.min_channel_lifetime, serializer);
+        <u32>::sse_encode(self.max_client_to_self_delay, serializer);
     }
 }
 
@@ -7350,6 +7643,7 @@ impl SseEncode for crate::models::NodeState {
         <Vec<String>>::sse_encode(self.connected_peers, serializer);
         <u64>::sse_encode(self.max_receivable_single_payment_amount_msat, serializer);
         <u64>::sse_encode(self.total_inbound_liquidity_msats, serializer);
+        <Option<String>>::sse_encode(self.sync_warning, serializer);
     }
 }
 
@@ -7788,6 +8082,9 @@ impl SseEncode for crate::models::ReceivePaymentRequest {
         <Option<bool>>::sse_encode(self.use_description_hash, serializer);
         <Option<u32>>::sse_encode(self.expiry, serializer);
         <Option<u32>>::sse_encode(self.cltv, serializer);
+        <Option<bool>>::sse_encode(self.register_notifications, serializer);
+        <Option<bool>>::sse_encode(self.truncate_description, serializer);
+        <Option<bool>>::sse_encode(self.no_open_channel, serializer);
     }
 }
 
@@ -7943,6 +8240,7 @@ impl SseEncode for crate::models::SendPaymentRequest {
     fn sse_encode(self, serializer: &mut flutter_rust_bridge::for_generated::SseSerializer) {
         <String>::sse_encode(self.bolt11, serializer);
         <Option<u64>>::sse_encode(self.amount_msat, serializer);
+        <Option<u64>>::sse_encode(self.max_fee_msat, serializer);
     }
 }
 
@@ -7959,6 +8257,7 @@ impl SseEncode for crate::models::SendSpontaneousPaymentRequest {
         <String>::sse_encode(self.node_id, serializer);
         <u64>::sse_encode(self.amount_msat, serializer);
         <Option<Vec<crate::models::TlvEntry>>>::sse_encode(self.extra_tlvs, serializer);
+        <Option<String>>::sse_encode(self.comment, serializer);
     }
 }
 
@@ -8783,6 +9082,45 @@ mod io {
                         details: ans.details.cst_decode(),
                     }
                 }
+                10 => crate::breez_services::BreezEvent::RemoteLockAcquired,
+                11 => crate::breez_services::BreezEvent::RemoteLockRefreshFailed,
+                12 => crate::breez_services::BreezEvent::RemoteLockLost,
+                13 => {
+                    let ans = unsafe { self.kind.SplicePending };
+                    crate::breez_services::BreezEvent::SplicePending {
+                        channel_id: ans.channel_id.cst_decode(),
+                        amount_sat: ans.amount_sat.cst_decode(),
+                        funding_txid: ans.funding_txid.cst_decode(),
+                    }
+                }
+                14 => {
+                    let ans = unsafe { self.kind.SpliceCompleted };
+                    crate::breez_services::BreezEvent::SpliceCompleted {
+                        channel_id: ans.channel_id.cst_decode(),
+                        amount_sat: ans.amount_sat.cst_decode(),
+                    }
+                }
+                15 => {
+                    let ans = unsafe { self.kind.SpliceFailed };
+                    crate::breez_services::BreezEvent::SpliceFailed {
+                        channel_id: ans.channel_id.cst_decode(),
+                        amount_sat: ans.amount_sat.cst_decode(),
+                        reason: ans.reason.cst_decode(),
+                    }
+                }
+                16 => crate::breez_services::BreezEvent::RestoreDownloadStarted,
+                17 => crate::breez_services::BreezEvent::RestoreDownloadCompleted,
+                18 => crate::breez_services::BreezEvent::RestoreChainSyncStarted,
+                19 => crate::breez_services::BreezEvent::RestoreChainSyncCompleted,
+                20 => {
+                    let ans = unsafe { self.kind.ChainReorg };
+                    crate::breez_services::BreezEvent::ChainReorg {
+                        old_tip_height: ans.old_tip_height.cst_decode(),
+                        old_tip_hash: ans.old_tip_hash.cst_decode(),
+                        new_tip_height: ans.new_tip_height.cst_decode(),
+                        new_tip_hash: ans.new_tip_hash.cst_decode(),
+                    }
+                }
                 _ => unreachable!(),
             }
         }
@@ -8834,6 +9172,9 @@ mod io {
                 funding_txid: self.funding_txid.cst_decode(),
                 short_channel_id: self.short_channel_id.cst_decode(),
                 closing_txid: self.closing_txid.cst_decode(),
+                counterparty_node_id: self.counterparty_node_id.cst_decode(),
+                capacity_sat: self.capacity_sat.cst_decode(),
+                is_force_close: self.is_force_close.cst_decode(),
             }
         }
     }
@@ -8854,6 +9195,20 @@ mod io {
                 api_key: self.api_key.cst_decode(),
                 maxfee_percent: self.maxfee_percent.cst_decode(),
                 exemptfee_msat: self.exemptfee_msat.cst_decode(),
+                payment_retries: self.payment_retries.cst_decode(),
+                remote_lock_lease_secs: self.remote_lock_lease_secs.cst_decode(),
+                remote_lock_retry_secs: self.remote_lock_retry_secs.cst_decode(),
+                connect_timeout_secs: self.connect_timeout_secs.cst_decode(),
+                anchor_channels_enabled: self.anchor_channels_enabled.cst_decode(),
+                allow_state_regression: self.allow_state_regression.cst_decode(),
+                min_sat_per_vbyte: self.min_sat_per_vbyte.cst_decode(),
+                max_sat_per_vbyte: self.max_sat_per_vbyte.cst_decode(),
+                sync_retries: self.sync_retries.cst_decode(),
+                max_jit_channels: self.max_jit_channels.cst_decode(),
+                sweep_confirmation_target: self.sweep_confirmation_target.cst_decode(),
+                max_store_value_bytes: self.max_store_value_bytes.cst_decode(),
+                vss_client_id: self.vss_client_id.cst_decode(),
+                restrict_file_permissions: self.restrict_file_permissions.cst_decode(),
             }
         }
     }
@@ -9250,6 +9605,8 @@ mod io {
                 lnurl_withdraw_endpoint: self.lnurl_withdraw_endpoint.cst_decode(),
                 swap_info: self.swap_info.cst_decode(),
                 reverse_swap_info: self.reverse_swap_info.cst_decode(),
+                custom_tlvs: self.custom_tlvs.cst_decode(),
+                open_channel_bolt11: self.open_channel_bolt11.cst_decode(),
             }
         }
     }
@@ -9458,6 +9815,9 @@ mod io {
                 min_htlc_msat: self.min_htlc_msat.cst_decode(),
                 lsp_pubkey: self.lsp_pubkey.cst_decode(),
                 opening_fee_params_list: self.opening_fee_params_list.cst_decode(),
+                channel_over_provisioning_ppm: self.channel_over_provisioning_ppm.cst_decode(),
+                min_channel_lifetime: self.min_channel_lifetime.cst_decode(),
+                max_client_to_self_delay: self.max_client_to_self_delay.cst_decode(),
             }
         }
     }
@@ -9497,6 +9857,7 @@ mod io {
                     .max_receivable_single_payment_amount_msat
                     .cst_decode(),
                 total_inbound_liquidity_msats: self.total_inbound_liquidity_msats.cst_decode(),
+                sync_warning: self.sync_warning.cst_decode(),
             }
         }
     }
@@ -9713,6 +10074,9 @@ mod io {
                 use_description_hash: self.use_description_hash.cst_decode(),
                 expiry: self.expiry.cst_decode(),
                 cltv: self.cltv.cst_decode(),
+                register_notifications: self.register_notifications.cst_decode(),
+                truncate_description: self.truncate_description.cst_decode(),
+                no_open_channel: self.no_open_channel.cst_decode(),
             }
         }
     }
@@ -9865,6 +10229,7 @@ mod io {
             crate::models::SendPaymentRequest {
                 bolt11: self.bolt11.cst_decode(),
                 amount_msat: self.amount_msat.cst_decode(),
+                max_fee_msat: self.max_fee_msat.cst_decode(),
             }
         }
     }
@@ -9885,6 +10250,7 @@ mod io {
                 node_id: self.node_id.cst_decode(),
                 amount_msat: self.amount_msat.cst_decode(),
                 extra_tlvs: self.extra_tlvs.cst_decode(),
+                comment: self.comment.cst_decode(),
             }
         }
     }
@@ -10192,6 +10558,9 @@ mod io {
                 funding_txid: core::ptr::null_mut(),
                 short_channel_id: core::ptr::null_mut(),
                 closing_txid: core::ptr::null_mut(),
+                counterparty_node_id: core::ptr::null_mut(),
+                capacity_sat: Default::default(),
+                is_force_close: Default::default(),
             }
         }
     }
@@ -10216,6 +10585,20 @@ mod io {
                 api_key: core::ptr::null_mut(),
                 maxfee_percent: Default::default(),
                 exemptfee_msat: Default::default(),
+                payment_retries: Default::default(),
+                remote_lock_lease_secs: Default::default(),
+                remote_lock_retry_secs: Default::default(),
+                connect_timeout_secs: Default::default(),
+                anchor_channels_enabled: Default::default(),
+                allow_state_regression: Default::default(),
+                min_sat_per_vbyte: Default::default(),
+                max_sat_per_vbyte: Default::default(),
+                sync_retries: Default::default(),
+                max_jit_channels: Default::default(),
+                sweep_confirmation_target: Default::default(),
+                max_store_value_bytes: Default::default(),
+                vss_client_id: core::ptr::null_mut(),
+                restrict_file_permissions: Default::default(),
             }
         }
     }
@@ -10412,6 +10795,8 @@ mod io {
                 lnurl_withdraw_endpoint: core::ptr::null_mut(),
                 swap_info: core::ptr::null_mut(),
                 reverse_swap_info: core::ptr::null_mut(),
+                custom_tlvs: core::ptr::null_mut(),
+                open_channel_bolt11: core::ptr::null_mut(),
             }
         }
     }
@@ -10644,6 +11029,9 @@ mod io {
                 min_htlc_msat: Default::default(),
                 lsp_pubkey: core::ptr::null_mut(),
                 opening_fee_params_list: Default::default(),
+                channel_over_provisioning_ppm: Default::default(),
+                min_channel_lifetime: Default::default(),
+                max_client_to_self_delay: Default::default(),
             }
         }
     }
@@ -10693,6 +11081,7 @@ mod io {
                 connected_peers: core::ptr::null_mut(),
                 max_receivable_single_payment_amount_msat: Default::default(),
                 total_inbound_liquidity_msats: Default::default(),
+                sync_warning: core::ptr::null_mut(),
             }
         }
     }
@@ -10964,6 +11353,9 @@ mod io {
                 use_description_hash: core::ptr::null_mut(),
                 expiry: core::ptr::null_mut(),
                 cltv: core::ptr::null_mut(),
+                register_notifications: core::ptr::null_mut(),
+                truncate_description: core::ptr::null_mut(),
+                no_open_channel: core::ptr::null_mut(),
             }
         }
     }
@@ -11163,6 +11555,7 @@ mod io {
             Self {
                 bolt11: core::ptr::null_mut(),
                 amount_msat: core::ptr::null_mut(),
+                max_fee_msat: core::ptr::null_mut(),
             }
         }
     }
@@ -11189,6 +11582,7 @@ mod io {
                 node_id: core::ptr::null_mut(),
                 amount_msat: Default::default(),
                 extra_tlvs: core::ptr::null_mut(),
+                comment: core::ptr::null_mut(),
             }
         }
     }
@@ -11395,8 +11789,9 @@ mod io {
     pub extern "C" fn frbgen_breez_sdk_wire__crate__binding__breez_log_stream(
         port_: i64,
         s: *mut wire_cst_list_prim_u_8_strict,
+        filter: *mut wire_cst_list_prim_u_8_strict,
     ) {
-        wire__crate__binding__breez_log_stream_impl(port_, s)
+        wire__crate__binding__breez_log_stream_impl(port_, s, filter)
     }
 
     #[unsafe(no_mangle)]
@@ -12558,6 +12953,10 @@ mod io {
         BackupFailed: wire_cst_BreezEvent_BackupFailed,
         ReverseSwapUpdated: wire_cst_BreezEvent_ReverseSwapUpdated,
         SwapUpdated: wire_cst_BreezEvent_SwapUpdated,
+        SplicePending: wire_cst_BreezEvent_SplicePending,
+        SpliceCompleted: wire_cst_BreezEvent_SpliceCompleted,
+        SpliceFailed: wire_cst_BreezEvent_SpliceFailed,
+        ChainReorg: wire_cst_BreezEvent_ChainReorg,
         nil__: (),
     }
     #[repr(C)]
@@ -12597,6 +12996,34 @@ mod io {
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
+    pub struct wire_cst_BreezEvent_SplicePending {
+        channel_id: *mut wire_cst_list_prim_u_8_strict,
+        amount_sat: i64,
+        funding_txid: *mut wire_cst_list_prim_u_8_strict,
+    }
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct wire_cst_BreezEvent_SpliceCompleted {
+        channel_id: *mut wire_cst_list_prim_u_8_strict,
+        amount_sat: i64,
+    }
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct wire_cst_BreezEvent_SpliceFailed {
+        channel_id: *mut wire_cst_list_prim_u_8_strict,
+        amount_sat: i64,
+        reason: *mut wire_cst_list_prim_u_8_strict,
+    }
+    #[repr(C)]
+    #[derive(Clone, Copy)]
+    pub struct wire_cst_BreezEvent_ChainReorg {
+        old_tip_height: u32,
+        old_tip_hash: *mut wire_cst_list_prim_u_8_strict,
+        new_tip_height: u32,
+        new_tip_hash: *mut wire_cst_list_prim_u_8_strict,
+    }
+    #[repr(C)]
+    #[derive(Clone, Copy)]
     pub struct wire_cst_buy_bitcoin_request {
         provider: i32,
         opening_fee_params: *mut wire_cst_opening_fee_params,
@@ -12627,6 +13054,9 @@ mod io {
         funding_txid: *mut wire_cst_list_prim_u_8_strict,
         short_channel_id: *mut wire_cst_list_prim_u_8_strict,
         closing_txid: *mut wire_cst_list_prim_u_8_strict,
+        counterparty_node_id: *mut wire_cst_list_prim_u_8_strict,
+        capacity_sat: u64,
+        is_force_close: bool,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -12644,6 +13074,20 @@ mod io {
         api_key: *mut wire_cst_list_prim_u_8_strict,
         maxfee_percent: f64,
         exemptfee_msat: u64,
+        payment_retries: u32,
+        remote_lock_lease_secs: u32,
+        remote_lock_retry_secs: u32,
+        connect_timeout_secs: u32,
+        anchor_channels_enabled: bool,
+        allow_state_regression: bool,
+        min_sat_per_vbyte: u32,
+        max_sat_per_vbyte: u32,
+        sync_retries: u32,
+        max_jit_channels: u32,
+        sweep_confirmation_target: u32,
+        max_store_value_bytes: u32,
+        vss_client_id: *mut wire_cst_list_prim_u_8_strict,
+        restrict_file_permissions: bool,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -12931,6 +13375,8 @@ mod io {
         lnurl_withdraw_endpoint: *mut wire_cst_list_prim_u_8_strict,
         swap_info: *mut wire_cst_swap_info,
         reverse_swap_info: *mut wire_cst_reverse_swap_info,
+        custom_tlvs: *mut wire_cst_list_tlv_entry,
+        open_channel_bolt11: *mut wire_cst_list_prim_u_8_strict,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -13108,6 +13554,9 @@ mod io {
         min_htlc_msat: i64,
         lsp_pubkey: *mut wire_cst_list_prim_u_8_strict,
         opening_fee_params_list: wire_cst_opening_fee_params_menu,
+        channel_over_provisioning_ppm: u32,
+        min_channel_lifetime: u32,
+        max_client_to_self_delay: u32,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -13136,6 +13585,7 @@ mod io {
         connected_peers: *mut wire_cst_list_String,
         max_receivable_single_payment_amount_msat: u64,
         total_inbound_liquidity_msats: u64,
+        sync_warning: *mut wire_cst_list_prim_u_8_strict,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -13291,6 +13741,9 @@ mod io {
         use_description_hash: *mut bool,
         expiry: *mut u32,
         cltv: *mut u32,
+        register_notifications: *mut bool,
+        truncate_description: *mut bool,
+        no_open_channel: *mut bool,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -13403,6 +13856,7 @@ mod io {
     pub struct wire_cst_send_payment_request {
         bolt11: *mut wire_cst_list_prim_u_8_strict,
         amount_msat: *mut u64,
+        max_fee_msat: *mut u64,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -13415,6 +13869,7 @@ mod io {
         node_id: *mut wire_cst_list_prim_u_8_strict,
         amount_msat: u64,
         extra_tlvs: *mut wire_cst_list_tlv_entry,
+        comment: *mut wire_cst_list_prim_u_8_strict,
     }
     #[repr(C)]
     #[derive(Clone, Copy)]
@@ -1,7 +1,7 @@
 use crate::backup::{BackupState, BackupTransport};
 use crate::error::{SdkError, SdkResult};
 use crate::ldk::store::{VersionedStore, VssStore};
-use crate::ldk::store_builder::{build_vss_store, CustomRetryPolicy};
+use crate::ldk::store_builder::{build_vss_store, CustomRetryPolicy, BACKUP_STORE_ID};
 use crate::node_api::NodeResult;
 use crate::Config;
 
@@ -13,7 +13,7 @@ impl LdkBackupTransport {
     const KEY: &str = "backup";
 
     pub fn new(config: &Config, seed: &[u8]) -> NodeResult<Self> {
-        let store = build_vss_store(config, seed, "backups")?;
+        let store = build_vss_store(config, seed, BACKUP_STORE_ID)?;
         Ok(Self { store })
     }
 }
@@ -50,12 +50,12 @@ impl From<ldk_node::NodeError> for NodeError {
             ldk_node::NodeError::GossipUpdateTimeout => NodeError::ServiceConnectivity(msg(err)),
             ldk_node::NodeError::GossipUpdateFailed => NodeError::ServiceConnectivity(msg(err)),
 
-            ldk_node::NodeError::LiquidityRequestFailed => generic(err),
+            ldk_node::NodeError::LiquidityRequestFailed => NodeError::LspRequestRejected(msg(err)),
 
             ldk_node::NodeError::UriParameterParsingFailed => generic(err),
 
             ldk_node::NodeError::InvalidAddress => generic(err),
-            ldk_node::NodeError::InvalidAmount => generic(err),
+            ldk_node::NodeError::InvalidAmount => NodeError::InvalidAmount(msg(err)),
             ldk_node::NodeError::InvalidChannelId => generic(err),
             ldk_node::NodeError::InvalidDateTime => generic(err),
             ldk_node::NodeError::InvalidFeeRate => generic(err),
@@ -82,8 +82,8 @@ impl From<ldk_node::NodeError> for NodeError {
 
             ldk_node::NodeError::InsufficientFunds => NodeError::InsufficientFunds(msg(err)),
 
-            ldk_node::NodeError::LiquiditySourceUnavailable => generic(err),
-            ldk_node::NodeError::LiquidityFeeTooHigh => generic(err),
+            ldk_node::NodeError::LiquiditySourceUnavailable => NodeError::LspUnavailable(msg(err)),
+            ldk_node::NodeError::LiquidityFeeTooHigh => NodeError::LspFeeTooHigh(msg(err)),
 
             ldk_node::NodeError::ChannelSplicingFailed => generic(err),
             ldk_node::NodeError::InvalidBlindedPaths => generic(err),
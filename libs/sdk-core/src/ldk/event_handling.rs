@@ -1,23 +1,42 @@
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 
-use ldk_node::lightning::events::PaymentFailureReason;
+use ldk_node::lightning::events::{ClosureReason, PaymentFailureReason};
 use ldk_node::lightning::ln::channelmanager::PaymentId;
-use ldk_node::payment::PaymentDetails;
+use ldk_node::lightning::ln::types::ChannelId;
+use ldk_node::lightning::util::scid_utils::{block_from_scid, tx_index_from_scid, vout_from_scid};
+use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage};
+use ldk_node::payment::{PaymentDetails, PaymentKind};
+use ldk_node::LightningBalance::{ClaimableAwaitingConfirmations, ClaimableOnChannelClose};
 use ldk_node::{Event, Node};
 use tokio::sync::{broadcast, mpsc};
 use tokio::time::error::Elapsed;
 use tokio::time::{timeout, Duration};
 
-use crate::ldk::store::Store;
+use crate::ldk::node_state::NodeStateCache;
+use crate::ldk::splice_tracker::SpliceTracker;
+use crate::ldk::store::{ChannelCloseRecord, ChannelRecord, PaymentOutcome, Store};
+use crate::ldk::utils::Hex;
 use crate::node_api::{IncomingPayment, NodeError, NodeResult};
+use crate::TlvEntry;
 
 pub async fn start_event_handling(
     node: Arc<Node>,
     events_tx: broadcast::Sender<Event>,
     store: Store,
     incoming_payments_tx: broadcast::Sender<IncomingPayment>,
+    node_state_cache: NodeStateCache,
+    splice_tracker: SpliceTracker,
     mut shutdown: mpsc::Receiver<()>,
 ) {
+    match reload_outstanding_invoices(&store, now_ts()) {
+        Ok(count) => info!("Awaiting payment of {count} outstanding invoices"),
+        Err(err) => error!("Failed to reload outstanding invoices: {err}"),
+    }
+    if let Err(err) = record_channels(&node, &store) {
+        error!("Failed to record the channels of the node: {err}");
+    }
+
     loop {
         let event = tokio::select! {
             event = node.next_event_async() => event,
@@ -27,16 +46,36 @@ pub async fn start_event_handling(
             },
         };
         debug!("Event: {event:?}");
+        node_state_cache.on_event(&event);
+        splice_tracker.on_event(&event);
         let _ = events_tx.send(event.clone()); // Error here will mean that there are no subscribers.
+        if let Err(err) = persist_payment_outcome(&store, &event) {
+            error!("Failed to persist payment outcome: {err}");
+        }
 
         match event {
             Event::PaymentReceived {
                 payment_id,
                 payment_hash,
                 amount_msat,
-                ..
+                custom_records,
             } => {
-                match store.load_preimage(&payment_hash) {
+                let custom_tlvs: Vec<TlvEntry> = custom_records
+                    .into_iter()
+                    .map(|record| TlvEntry {
+                        field_number: record.type_num,
+                        value: record.value,
+                    })
+                    .collect();
+                if !custom_tlvs.is_empty() {
+                    if let Err(err) = store.store_custom_tlvs(&payment_hash, &custom_tlvs) {
+                        error!(
+                            "Failed to store custom TLVs for payment with id={payment_id:?}: {err}"
+                        );
+                    }
+                }
+
+                match load_received_preimage(&node, &store, payment_id, &payment_hash) {
                     Ok(preimage) => {
                         let bolt11 = match store.load_bolt11(&payment_hash) {
                             Ok(bolt11) => bolt11,
@@ -45,11 +84,20 @@ pub async fn start_event_handling(
                                 None
                             }
                         };
+                        if bolt11.is_some() {
+                            if let Err(err) = store.remove_outstanding_invoice(&payment_hash) {
+                                error!("Failed to remove outstanding invoice for payment with id={payment_id:?}: {err}");
+                            }
+                        }
+                        let open_channel_bolt11 =
+                            bolt11.clone().filter(|_| is_jit_payment(&node, payment_id));
                         let payment = IncomingPayment {
                             payment_hash: payment_hash.0.to_vec(),
                             preimage: preimage.0.to_vec(),
                             amount_msat,
                             bolt11: bolt11.unwrap_or_default(),
+                            custom_tlvs: (!custom_tlvs.is_empty()).then_some(custom_tlvs),
+                            open_channel_bolt11,
                         };
                         if let Err(e) = incoming_payments_tx.send(payment) {
                             warn!("Failed to send payment to incoming_payments_tx: {e}");
@@ -70,18 +118,24 @@ pub async fn start_event_handling(
                 claimable_amount_msat,
                 ..
             } => {
-                match store.load_preimage(&payment_hash) {
-                    Ok(preimage) => {
+                let record = store
+                    .load_preimage_record(&payment_hash)
+                    .map_err(|err| format!("Failed to read preimage: {err}"));
+                match record.and_then(|record| {
+                    record.check_claimable(claimable_amount_msat, now_ts())?;
+                    Ok(record)
+                }) {
+                    Ok(record) => {
                         if let Err(e) = node.bolt11_payment().claim_for_hash(
                             payment_hash,
                             claimable_amount_msat,
-                            preimage,
+                            record.preimage(),
                         ) {
                             error!("Failed to claim payment: {e}");
                         }
                     }
                     Err(err) => {
-                        error!("Failed to read preimage when payment claimable for payment with id={payment_id:?}: {err}");
+                        error!("Not claiming payment with id={payment_id:?}: {err}");
                         if let Err(e) = node.bolt11_payment().fail_for_hash(payment_hash) {
                             error!("Failed to fail payment: {e}");
                         }
@@ -89,9 +143,23 @@ pub async fn start_event_handling(
                 };
             }
             Event::PaymentForwarded { .. } => (),
-            Event::ChannelPending { .. } => (),
-            Event::ChannelReady { .. } => (),
-            Event::ChannelClosed { .. } => (),
+            // Channel ready is also emitted once a splice is locked, which changes the funding
+            // transaction of the channel.
+            Event::ChannelPending { .. } | Event::ChannelReady { .. } => {
+                if let Err(err) = record_channels(&node, &store) {
+                    error!("Failed to record the channels of the node: {err}");
+                }
+            }
+            Event::ChannelClosed {
+                channel_id, reason, ..
+            } => {
+                let amount_sat = closed_channel_balance_sat(&node, &channel_id);
+                if let Err(err) =
+                    record_channel_close(&store, &channel_id, reason, amount_sat, now_ts())
+                {
+                    error!("Failed to record the close of channel {channel_id}: {err}");
+                }
+            }
 
             Event::SplicePending { .. } => (),
             Event::SpliceFailed { .. } => (),
@@ -103,12 +171,200 @@ pub async fn start_event_handling(
     }
 }
 
+/// Persists the final state of sent payments, so that it is known even when no caller was
+/// waiting for the outcome, e.g. for payments sent before a restart.
+fn persist_payment_outcome(store: &Store, event: &Event) -> Result<(), bitcoin::io::Error> {
+    let (payment_id, outcome) = match event {
+        Event::PaymentSuccessful {
+            payment_id,
+            fee_paid_msat,
+            ..
+        } => (
+            payment_id,
+            PaymentOutcome {
+                fee_paid_msat: *fee_paid_msat,
+                failure_reason: None,
+            },
+        ),
+        Event::PaymentFailed {
+            payment_id, reason, ..
+        } => (
+            payment_id,
+            PaymentOutcome {
+                fee_paid_msat: None,
+                failure_reason: Some(format!(
+                    "{:?}",
+                    reason.unwrap_or(PaymentFailureReason::UnexpectedError)
+                )),
+            },
+        ),
+        _ => return Ok(()),
+    };
+    match payment_id {
+        Some(payment_id) => store.store_payment_outcome(&payment_id.to_hex(), &outcome),
+        None => Ok(()),
+    }
+}
+
+/// Keeps the details of the funded channels of the node, which are needed to report them
+/// once they are closed.
+fn record_channels(node: &Node, store: &Store) -> Result<(), bitcoin::io::Error> {
+    for channel in node.list_channels() {
+        let Some(funding_txo) = channel.funding_txo else {
+            continue;
+        };
+        let record = ChannelRecord {
+            counterparty_node_id: channel.counterparty_node_id.to_string(),
+            funding_txid: funding_txo.txid.to_string(),
+            funding_output_index: funding_txo.vout,
+            short_channel_id: channel.short_channel_id.map(format_short_channel_id),
+            capacity_sat: channel.channel_value_sats,
+            close: None,
+        };
+        store.store_channel(&channel.channel_id.0.to_hex(), &record)?;
+    }
+    Ok(())
+}
+
+fn format_short_channel_id(scid: u64) -> String {
+    format!(
+        "{}x{}x{}",
+        block_from_scid(scid),
+        tx_index_from_scid(scid),
+        vout_from_scid(scid)
+    )
+}
+
+/// The funds on the user side of a channel which was just closed.
+fn closed_channel_balance_sat(node: &Node, channel_id: &ChannelId) -> u64 {
+    node.list_balances()
+        .lightning_balances
+        .iter()
+        .filter_map(|b| match b {
+            ClaimableOnChannelClose {
+                channel_id: id,
+                amount_satoshis,
+                ..
+            }
+            | ClaimableAwaitingConfirmations {
+                channel_id: id,
+                amount_satoshis,
+                ..
+            } if id == channel_id => Some(amount_satoshis),
+            _ => None,
+        })
+        .sum()
+}
+
+fn record_channel_close(
+    store: &Store,
+    channel_id: &ChannelId,
+    reason: Option<ClosureReason>,
+    amount_sat: u64,
+    now_ts: u64,
+) -> Result<(), bitcoin::io::Error> {
+    let channel_id = channel_id.0.to_hex();
+    // Channels closed before their funding transaction was negotiated were never recorded.
+    let Some(mut record) = store.load_channel(&channel_id)? else {
+        debug!("Not recording the close of unfunded channel {channel_id}");
+        return Ok(());
+    };
+    let is_force_close = !matches!(
+        reason,
+        Some(
+            ClosureReason::LegacyCooperativeClosure
+                | ClosureReason::CounterpartyInitiatedCooperativeClosure
+                | ClosureReason::LocallyInitiatedCooperativeClosure
+        )
+    );
+    record.close = Some(ChannelCloseRecord {
+        closed_at: now_ts,
+        amount_sat,
+        is_force_close,
+        closing_txid: None,
+    });
+    store.store_channel(&channel_id, &record)
+}
+
+fn now_ts() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Reloads the index of invoices awaiting payment, dropping the expired ones along with
+/// their preimages and invoices, since payments for them would be failed anyway.
+///
+/// Invoices which were paid are no longer awaiting payment and are kept until their payment
+/// is pruned.
+///
+/// Returns the number of invoices still awaiting payment.
+fn reload_outstanding_invoices(store: &Store, now_ts: u64) -> Result<usize, bitcoin::io::Error> {
+    let mut outstanding = 0;
+    for hash in store.list_outstanding_invoices()? {
+        let expired = match store.load_preimage_record(&hash) {
+            Ok(record) => record.expiry_ts.is_some_and(|expiry_ts| now_ts > expiry_ts),
+            Err(err) if err.kind() == bitcoin::io::ErrorKind::NotFound => true,
+            Err(err) => return Err(err),
+        };
+        if !expired {
+            outstanding += 1;
+            continue;
+        }
+        debug!("Removing expired invoice with hash {}", hash.to_hex());
+        store.remove_preimage(&hash)?;
+        store.remove_bolt11(&hash)?;
+        store.remove_outstanding_invoice(&hash)?;
+    }
+    Ok(outstanding)
+}
+
+/// Preimages of invoices are kept in the store, while the preimage of a keysend payment is
+/// only known to the node.
+fn load_received_preimage(
+    node: &Node,
+    store: &Store,
+    payment_id: Option<PaymentId>,
+    payment_hash: &PaymentHash,
+) -> Result<PaymentPreimage, bitcoin::io::Error> {
+    let spontaneous_preimage = payment_id
+        .and_then(|id| node.payment(&id))
+        .and_then(|payment| match payment.kind {
+            PaymentKind::Spontaneous { preimage, .. } => preimage,
+            _ => None,
+        });
+    match spontaneous_preimage {
+        Some(preimage) => Ok(preimage),
+        None => store.load_preimage(payment_hash),
+    }
+}
+
+/// Whether the payment was received over a JIT channel opened by the LSP.
+fn is_jit_payment(node: &Node, payment_id: Option<PaymentId>) -> bool {
+    payment_id
+        .and_then(|id| node.payment(&id))
+        .is_some_and(|payment| matches!(payment.kind, PaymentKind::Bolt11Jit { .. }))
+}
+
 pub async fn wait_for_payment_success(
     node: &Node,
-    mut events_rx: broadcast::Receiver<Event>,
+    events_rx: broadcast::Receiver<Event>,
     p_id: PaymentId,
 ) -> NodeResult<PaymentDetails> {
-    debug!("Waiting for payment success id:{p_id}");
+    wait_for_payment_outcome(node, events_rx, p_id)
+        .await?
+        .map_err(|reason| NodeError::PaymentFailed(format!("{reason:?}")))
+}
+
+/// Waits for the payment to either succeed or fail, returning the failure reason
+/// to the caller so that it can decide whether the payment is worth retrying.
+pub async fn wait_for_payment_outcome(
+    node: &Node,
+    mut events_rx: broadcast::Receiver<Event>,
+    p_id: PaymentId,
+) -> NodeResult<Result<PaymentDetails, PaymentFailureReason>> {
+    debug!("Waiting for payment outcome id:{p_id}");
     timeout(Duration::from_secs(30), async {
         while let Ok(event) = events_rx.recv().await {
             match event {
@@ -117,13 +373,13 @@ pub async fn wait_for_payment_success(
                         .list_payments_with_filter(|p| p.id == p_id)
                         .into_iter()
                         .next()
+                        .map(Ok)
                         .ok_or(NodeError::generic("Failed to find payment we just sent"));
                 }
                 Event::PaymentFailed {
                     payment_id, reason, ..
                 } if payment_id == Some(p_id) => {
-                    let reason = reason.unwrap_or(PaymentFailureReason::UnexpectedError);
-                    return Err(NodeError::PaymentFailed(format!("{reason:?}")));
+                    return Ok(Err(reason.unwrap_or(PaymentFailureReason::UnexpectedError)));
                 }
                 _ => continue,
             }
@@ -135,3 +391,146 @@ pub async fn wait_for_payment_success(
         NodeError::PaymentFailed("Timeout waiting for payment success".to_string())
     })?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldk::store::{mock_mirroring_store, PreimageRecord};
+
+    async fn create_store() -> Store {
+        Store::new(Arc::new(mock_mirroring_store().await))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_persist_payment_outcome() {
+        let store = create_store().await;
+        let payment_id = PaymentId([1; 32]);
+        persist_payment_outcome(
+            &store,
+            &Event::PaymentFailed {
+                payment_id: Some(payment_id),
+                payment_hash: Some(PaymentHash([1; 32])),
+                reason: Some(PaymentFailureReason::RouteNotFound),
+            },
+        )
+        .unwrap();
+        let outcome = store.load_payment_outcome(&payment_id.to_hex()).unwrap();
+        assert_eq!(
+            outcome,
+            Some(PaymentOutcome {
+                fee_paid_msat: None,
+                failure_reason: Some("RouteNotFound".to_string()),
+            })
+        );
+
+        // A successful retry overrides the failure.
+        persist_payment_outcome(
+            &store,
+            &Event::PaymentSuccessful {
+                payment_id: Some(payment_id),
+                payment_hash: PaymentHash([1; 32]),
+                payment_preimage: Some(PaymentPreimage([2; 32])),
+                fee_paid_msat: Some(1_000),
+            },
+        )
+        .unwrap();
+        let outcome = store.load_payment_outcome(&payment_id.to_hex()).unwrap();
+        assert_eq!(
+            outcome,
+            Some(PaymentOutcome {
+                fee_paid_msat: Some(1_000),
+                failure_reason: None,
+            })
+        );
+
+        // Events without a payment id are ignored.
+        persist_payment_outcome(
+            &store,
+            &Event::PaymentFailed {
+                payment_id: None,
+                payment_hash: None,
+                reason: None,
+            },
+        )
+        .unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reload_outstanding_invoices() {
+        let store = create_store().await;
+        let invoices = [
+            (PaymentHash([1; 32]), Some(100), true),
+            (PaymentHash([2; 32]), Some(300), true),
+            (PaymentHash([3; 32]), Some(100), false),
+        ];
+        for (hash, expiry_ts, outstanding) in invoices {
+            let record = PreimageRecord {
+                expiry_ts,
+                ..PreimageRecord::new(PaymentPreimage(hash.0))
+            };
+            store.store_preimage(&hash, &record).unwrap();
+            store
+                .store_bolt11(&hash.to_hex(), "lnbc".to_string())
+                .unwrap();
+            if outstanding {
+                store.store_outstanding_invoice(&hash).unwrap();
+            }
+        }
+
+        assert_eq!(reload_outstanding_invoices(&store, 200).unwrap(), 1);
+        // The expired unpaid invoice is evicted.
+        assert_eq!(store.load_bolt11(&PaymentHash([1; 32])).unwrap(), None);
+        assert!(store.load_preimage(&PaymentHash([1; 32])).is_err());
+        // The unexpired and the paid invoices are kept.
+        assert!(store.load_bolt11(&PaymentHash([2; 32])).unwrap().is_some());
+        assert!(store.load_bolt11(&PaymentHash([3; 32])).unwrap().is_some());
+        assert_eq!(
+            store.list_outstanding_invoices().unwrap(),
+            vec![PaymentHash([2; 32])]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_record_channel_close() {
+        let store = create_store().await;
+        let channel_id = ChannelId([1; 32]);
+        let record = ChannelRecord {
+            counterparty_node_id: "02".repeat(33),
+            funding_txid: "aa".repeat(32),
+            funding_output_index: 1,
+            short_channel_id: None,
+            capacity_sat: 100_000,
+            close: None,
+        };
+        store
+            .store_channel(&channel_id.0.to_hex(), &record)
+            .unwrap();
+        assert!(store.list_closed_channels().unwrap().is_empty());
+
+        let reason = Some(ClosureReason::LocallyInitiatedCooperativeClosure);
+        record_channel_close(&store, &channel_id, reason, 40_000, 100).unwrap();
+        let closed_channels = store.list_closed_channels().unwrap();
+        assert_eq!(closed_channels.len(), 1);
+        let (id, closed) = &closed_channels[0];
+        assert_eq!(id, &channel_id.0.to_hex());
+        assert_eq!(
+            closed.close,
+            Some(ChannelCloseRecord {
+                closed_at: 100,
+                amount_sat: 40_000,
+                is_force_close: false,
+                closing_txid: None,
+            })
+        );
+
+        let reason = Some(ClosureReason::CommitmentTxConfirmed);
+        record_channel_close(&store, &channel_id, reason, 40_000, 200).unwrap();
+        let closed = store.load_channel(&channel_id.0.to_hex()).unwrap().unwrap();
+        assert!(closed.close.is_some_and(|close| close.is_force_close));
+
+        // The close of a channel which was never funded is not recorded.
+        let unfunded_channel_id = ChannelId([2; 32]);
+        record_channel_close(&store, &unfunded_channel_id, None, 0, 100).unwrap();
+        assert_eq!(store.list_closed_channels().unwrap().len(), 1);
+    }
+}
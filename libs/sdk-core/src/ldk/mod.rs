@@ -3,10 +3,14 @@ mod error;
 mod event_handling;
 mod node_api;
 mod node_state;
+mod reorg_tracker;
 mod restore_state;
+mod splice_tracker;
 mod store;
 mod store_builder;
 mod utils;
 
 pub(crate) use backup_transport::LdkBackupTransport;
 pub(crate) use node_api::Ldk;
+pub(crate) use store_builder::{check_vss, migrate_vss, recovery_fingerprint, reset_local_cache};
+pub(crate) use utils::sat_to_msat;
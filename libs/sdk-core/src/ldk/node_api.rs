@@ -1,45 +1,80 @@
 use core::str::FromStr;
 use std::collections::HashSet;
+use std::fmt::Display;
+use std::fs::File;
+use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 
 use chrono::{DateTime, Utc};
 use ldk_node::bitcoin::hashes::sha256::Hash as Sha256;
 use ldk_node::bitcoin::hashes::Hash;
+use ldk_node::bitcoin::io::ErrorKind;
 use ldk_node::bitcoin::secp256k1::PublicKey;
+use ldk_node::bitcoin::{Address, FeeRate};
+use ldk_node::lightning::events::PaymentFailureReason;
+use ldk_node::lightning::ln::channelmanager::PaymentId;
 use ldk_node::lightning::ln::msgs::SocketAddress;
 use ldk_node::lightning::routing::router::{
     RouteParametersConfig, DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA,
 };
-use ldk_node::lightning_invoice::{Bolt11InvoiceDescription, Description};
+use ldk_node::lightning::util::persist::{
+    KVSTORE_NAMESPACE_KEY_ALPHABET, KVSTORE_NAMESPACE_KEY_MAX_LEN,
+};
+use ldk_node::lightning_invoice::{Bolt11Invoice, Bolt11InvoiceDescription, Description};
 use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage};
-use ldk_node::{Builder, CustomTlvRecord, Event, Node};
-use rand::Rng;
+use ldk_node::payment::{ConfirmationStatus, PaymentKind};
+use ldk_node::{Builder, ChannelDetails, CustomTlvRecord, Event, Node};
+use prost::Message;
 use sdk_common::ensure_sdk;
-use sdk_common::prelude::Network;
+use sdk_common::prelude::{Network, ReqwestRestClient, RestClient};
 use serde_json::{json, Value};
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, watch};
 use tokio_stream::wrappers::errors::BroadcastStreamRecvError::Lagged;
-use tokio_stream::wrappers::BroadcastStream;
+use tokio_stream::wrappers::{BroadcastStream, WatchStream};
 use tokio_stream::{Stream, StreamExt};
+use tokio_util::sync::CancellationToken;
 
 use crate::bitcoin::bip32::{ChildNumber, Xpriv};
 use crate::bitcoin::secp256k1::Secp256k1;
+use crate::chain::{
+    ChainService, RecommendedFees, RedundantChainService, RedundantChainServiceTrait,
+};
 use crate::error::{ReceivePaymentError, SdkError, SdkResult};
 use crate::grpc;
-use crate::ldk::event_handling::{start_event_handling, wait_for_payment_success};
-use crate::ldk::node_state::convert_payment;
+use crate::ldk::event_handling::{
+    start_event_handling, wait_for_payment_outcome, wait_for_payment_success,
+};
+use crate::ldk::node_state::{
+    convert_closed_channel, convert_payment, convert_pending_sweep, fee_summary, get_payment_hash,
+    is_close_resolved, pending_close_status, NodeStateCache,
+};
+use crate::ldk::reorg_tracker::ReorgTracker;
 use crate::ldk::restore_state::RestoreStateTracker;
-use crate::ldk::store::{KVStore, Store};
-use crate::ldk::store_builder::{build_mirroring_store, build_vss_store};
-use crate::ldk::utils::Hex;
-use crate::models::{Config, LspAPI, NodeState, OpeningFeeParams, OpeningFeeParamsMenu};
+use crate::ldk::splice_tracker::SpliceTracker;
+use crate::ldk::store::{ChannelRecord, KVStore, PreimageRecord, Store};
+use crate::ldk::store_builder::{
+    build_mirroring_store, build_vss_store, check_vss, lock_working_dir, MirroringStore,
+    NODE_STORE_ID,
+};
+use crate::ldk::utils::{
+    check_rgs, esplora_recommended_fees, fee_estimates, msat_to_sat, probe, sweep_sat_per_vbyte,
+    wait_until_synced, EntropySource, Hex, ThreadRngEntropySource,
+};
+use crate::models::{
+    ChannelInfo, Config, ConnectivityCheckResult, FeeEstimates, FeeSummary, GraphStatus,
+    LiquidityEstimate, LspAPI, NodeState, OpeningFeeParams, OpeningFeeParamsMenu, PeerInfo,
+    PendingCloseStatus, PendingSweep, PendingSweepStage, ResyncResult, VssCheckResult,
+    INVOICE_PAYMENT_FEE_EXPIRY_SECONDS,
+};
 use crate::node_api::{
-    CreateInvoiceRequest, FetchBolt11Result, IncomingPayment, NodeAPI, NodeError, NodeResult,
+    split_node_address, CreateInvoiceRequest, FetchBolt11Result, IncomingPayment, NodeAPI,
+    NodeError, NodeResult, RemoteLockEvent, ReorgEvent, RestoreEvent, RestoreEventListener,
+    SpliceEvent,
 };
 use crate::{
-    CustomMessage, LspInformation, MaxChannelAmount, Payment, PaymentResponse,
+    CustomMessage, LspInformation, MaxChannelAmount, Payment, PaymentResponse, PaymentStatus,
     PrepareRedeemOnchainFundsRequest, PrepareRedeemOnchainFundsResponse, RouteHintHop, TlvEntry,
 };
 
@@ -50,7 +85,22 @@ pub(crate) struct Ldk {
     incoming_payments_tx: broadcast::Sender<IncomingPayment>,
     events_tx: broadcast::Sender<Event>,
     store: Store,
+    mirroring_store: Arc<MirroringStore>,
+    chain_service: Arc<dyn ChainService>,
+    /// The mempool.space instance serving the recommended fees, if configured.
+    fee_service: Option<Arc<dyn ChainService>>,
+    rest_client: Arc<dyn RestClient>,
+    /// The randomness of new invoices, fixed in tests to make them reproducible.
+    entropy_source: Arc<dyn EntropySource>,
+    node_state_cache: NodeStateCache,
+    splice_tracker: SpliceTracker,
+    reorg_tracker: ReorgTracker,
+    remote_lock_events_rx: watch::Receiver<RemoteLockEvent>,
     remote_lock_shutdown_tx: mpsc::Sender<()>,
+    /// Set after the node state was downloaded, to report the first sync with the chain.
+    restore_event_listener: Mutex<Option<RestoreEventListener>>,
+    /// Held for the lifetime of the node to keep other instances out of the working directory.
+    _working_dir_lock: File,
 }
 
 impl Ldk {
@@ -58,6 +108,7 @@ impl Ldk {
         config: Config,
         seed: &[u8],
         restore_only: Option<bool>,
+        restore_event_listener: Option<RestoreEventListener>,
     ) -> NodeResult<Self> {
         debug!("Building LDK Node");
         ensure_sdk!(
@@ -65,17 +116,16 @@ impl Ldk {
             NodeError::generic("Only Regtest mode is supported for now")
         );
 
-        let (lsp_id, lsp_address) = get_lsp(&config)?;
+        let working_dir_lock = lock_working_dir(&config.working_dir)?;
 
-        // Allow anchor channels from the LSP without having on-chain funds available.
-        let ldk_node_config = ldk_node::config::Config {
-            anchor_channels_config: Some(ldk_node::config::AnchorChannelsConfig {
-                trusted_peers_no_reserve: vec![lsp_id],
-                ..Default::default()
-            }),
-            ..Default::default()
-        };
-        let mut builder = Builder::from_config(ldk_node_config);
+        let lsp = get_lsp(&config)?;
+        ensure_sdk!(
+            lsp.is_some() || !lsp_required(&config.network),
+            NodeError::generic(LSP_REQUIRED_ERROR)
+        );
+
+        let lsp_id = lsp.as_ref().map(|(id, _)| *id);
+        let mut builder = Builder::from_config(build_ldk_node_config(&config, lsp_id));
 
         let mut bytes = [0u8; 64];
         bytes.copy_from_slice(seed);
@@ -87,16 +137,28 @@ impl Ldk {
         builder.set_chain_source_esplora(config.esplora_url.clone(), None);
         builder.set_gossip_source_rgs(config.rgs_url.clone());
 
-        builder.set_liquidity_source_lsps2(lsp_id, lsp_address, None);
+        if let Some((lsp_id, lsp_address)) = lsp {
+            builder.set_liquidity_source_lsps2(lsp_id, lsp_address, None);
+        }
 
-        let vss_store = build_vss_store(&config, &seed, "ldk_node")?;
+        let vss_store = build_vss_store(&config, &seed, NODE_STORE_ID)?;
 
         // It is not possible to use oneshot here, because `oneshot::Sender::send()`
         // consumes itself, not allowing to call `closed()` method after.
         let (remote_lock_shutdown_tx, remote_lock_shutdown_rx) = mpsc::channel(1);
-        let mirroring_store =
-            build_mirroring_store(&config.working_dir, vss_store, remote_lock_shutdown_rx).await?;
-        let kv_store: KVStore = Arc::new(mirroring_store);
+        let (remote_lock_events_tx, remote_lock_events_rx) =
+            watch::channel(RemoteLockEvent::Acquired);
+        let (mirroring_store, is_restore) = build_mirroring_store(
+            &config,
+            &seed,
+            vss_store,
+            remote_lock_events_tx,
+            remote_lock_shutdown_rx,
+            restore_event_listener.as_ref(),
+        )
+        .await?;
+        let mirroring_store = Arc::new(mirroring_store);
+        let kv_store: KVStore = mirroring_store.clone();
         let store = Store::new(Arc::clone(&kv_store));
 
         let restore_state_tracker = RestoreStateTracker::new(Arc::clone(&kv_store));
@@ -106,6 +168,7 @@ impl Ldk {
                 "restore_only requested but no persisted node state was found".to_string(),
             ));
         }
+        restore_state_tracker.migrate(was_initialized)?;
 
         let node = builder
             .build_with_store(kv_store)
@@ -119,6 +182,20 @@ impl Ldk {
         let (incoming_payments_tx, _) = broadcast::channel(10);
         let (events_tx, _) = broadcast::channel(10);
 
+        let rest_client: Arc<dyn RestClient> = Arc::new(
+            ReqwestRestClient::new().map_err(|e| NodeError::ServiceConnectivity(e.to_string()))?,
+        );
+        let chain_service = Arc::new(RedundantChainService::from_base_urls(
+            Arc::clone(&rest_client),
+            vec![config.esplora_url.clone()],
+        ));
+        let fee_service = config.mempoolspace_url.clone().map(|url| {
+            Arc::new(RedundantChainService::from_base_urls(
+                Arc::clone(&rest_client),
+                vec![url],
+            )) as Arc<dyn ChainService>
+        });
+
         Ok(Self {
             config,
             seed,
@@ -126,28 +203,402 @@ impl Ldk {
             incoming_payments_tx,
             events_tx,
             store,
+            mirroring_store,
+            chain_service,
+            fee_service,
+            rest_client,
+            entropy_source: Arc::new(ThreadRngEntropySource),
+            node_state_cache: NodeStateCache::default(),
+            splice_tracker: SpliceTracker::default(),
+            reorg_tracker: ReorgTracker::default(),
+            remote_lock_events_rx,
             remote_lock_shutdown_tx,
+            restore_event_listener: Mutex::new(restore_event_listener.filter(|_| is_restore)),
+            _working_dir_lock: working_dir_lock,
         })
     }
 }
 
+impl Ldk {
+    fn inbound_capacity_msat(&self) -> u64 {
+        self.node
+            .list_channels()
+            .iter()
+            .map(|c| c.inbound_capacity_msat)
+            .sum()
+    }
+
+    fn find_channel(&self, channel_id: &str) -> NodeResult<ChannelDetails> {
+        self.node
+            .list_channels()
+            .into_iter()
+            .find(|c| c.channel_id.0.to_hex() == channel_id)
+            .ok_or_else(|| NodeError::Generic(format!("Channel {channel_id} not found")))
+    }
+
+    /// Returns an opening fee menu built from the static terms of the LSP of the network, not
+    /// one fetched from the LSP. LDK Node requests the actual fee params from the LSP itself
+    /// when it buys a JIT channel, only accepting a fee up to the one computed from this menu.
+    pub fn lsp_fee_menu(&self) -> NodeResult<OpeningFeeParamsMenu> {
+        Ok(fee_menu(lsp_terms(self.config.network)?))
+    }
+
+    fn largest_inbound_capacity_msat(&self) -> u64 {
+        self.node
+            .list_channels()
+            .iter()
+            .map(|c| c.inbound_capacity_msat)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the node state, recomputing it only if it was invalidated by an event of the
+    /// node or a new block since it was last computed.
+    async fn node_info(&self) -> NodeState {
+        let block_height = self.node.status().current_best_block.height;
+        match self.node_state_cache.get(block_height) {
+            Some(state) => state,
+            None => self.node_info_fresh().await,
+        }
+    }
+
+    /// Recomputes the node state, bypassing the cache.
+    async fn node_info_fresh(&self) -> NodeState {
+        let generation = self.node_state_cache.generation();
+        let restore_event_listener = self.restore_event_listener.lock().unwrap().take();
+        if let Some(listener) = &restore_event_listener {
+            listener(RestoreEvent::ChainSyncStarted);
+        }
+        let node = Arc::clone(&self.node);
+        let max_retries = self.config.sync_retries.min(MAX_SYNC_RETRIES);
+        // Both the sync and the delays between its attempts block the thread.
+        let sync_result = tokio::task::spawn_blocking(move || {
+            retry_sync(max_retries, SYNC_RETRY_DELAY, || node.sync_wallets())
+        })
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|res| res.map_err(|e| e.to_string()));
+        if let Some(listener) = restore_event_listener {
+            match &sync_result {
+                Ok(_) => listener(RestoreEvent::ChainSyncCompleted),
+                // Reported again along with the next sync.
+                Err(_) => *self.restore_event_listener.lock().unwrap() = Some(listener),
+            }
+        }
+        // The state known locally is still returned, with a warning that it may be stale.
+        let sync_warning = match sync_result {
+            Ok(0) => None,
+            Ok(retries) => {
+                info!("LDK wallets were synced after {retries} retries");
+                None
+            }
+            Err(e) => {
+                warn!("Failed to sync LDK wallets: {e}");
+                Some(format!("Failed to sync with the chain: {e}"))
+            }
+        };
+        if let Err(e) = self.sweep_to_channel_close_to_addresses().await {
+            warn!("Failed to sweep funds to the close-to addresses of channels: {e}");
+        }
+        let state = NodeState {
+            sync_warning,
+            ..NodeState::from(&*self.node)
+        };
+        self.node_state_cache.set(generation, state.clone());
+        state
+    }
+
+    /// Sends the funds of each channel closed by [NodeAPI::close_channel] or
+    /// [NodeAPI::close_all_channels] with a close-to address to that address, once they are
+    /// claimed to the on-chain wallet. The fee is paid from the rest of the on-chain funds, if
+    /// there are any.
+    async fn sweep_to_channel_close_to_addresses(&self) -> NodeResult<()> {
+        let block_height = self.node.status().current_best_block.height;
+        for (channel_id, address) in self.store.list_channel_close_to_addresses()? {
+            let Some(mut record) = self.store.load_channel(&channel_id)? else {
+                continue;
+            };
+            self.fill_closing_txid(&channel_id, &mut record).await?;
+            let Some(close) = record.close else {
+                continue;
+            };
+            let balances = self.node.list_balances();
+            if close.closing_txid.is_none()
+                || !is_close_resolved(&balances, &channel_id, block_height)
+            {
+                continue;
+            }
+            if close.amount_sat > 0 {
+                let address = parse_address(&address, &self.config.network)?;
+                let fee_rate = self.sweep_fee_rate().await;
+                let onchain_payment = self.node.onchain_payment();
+                let txid = if balances.spendable_onchain_balance_sats > close.amount_sat {
+                    onchain_payment.send_to_address(&address, close.amount_sat, fee_rate)?
+                } else {
+                    // The fee of the closing transaction may leave less than the funds on the
+                    // user side at the time of the close.
+                    let retain_reserves = !self.node.list_channels().is_empty();
+                    onchain_payment.send_all_to_address(&address, retain_reserves, fee_rate)?
+                };
+                info!("Swept funds of channel {channel_id} to {address} in transaction {txid}");
+            }
+            self.store.remove_channel_close_to_address(&channel_id)?;
+        }
+        Ok(())
+    }
+
+    /// Returns the recommended fees of mempool.space, if configured, or else derives them
+    /// from the fee estimates of Esplora, which does not serve the recommended fees.
+    async fn recommended_fees(&self) -> SdkResult<RecommendedFees> {
+        if let Some(fee_service) = &self.fee_service {
+            match fee_service.recommended_fees().await {
+                Ok(fees) => return Ok(fees),
+                Err(e) => warn!("Failed to fetch the recommended fees from mempool.space: {e}"),
+            }
+        }
+        esplora_recommended_fees(self.rest_client.as_ref(), &self.config.esplora_url).await
+    }
+
+    /// Returns the fee rate for sweeps to confirm within the configured target, or `None` to
+    /// let LDK Node estimate it if the recommended fees are not available.
+    async fn sweep_fee_rate(&self) -> Option<FeeRate> {
+        match self.recommended_fees().await {
+            Ok(fees) => FeeRate::from_sat_per_vb(sweep_sat_per_vbyte(&fees, &self.config)),
+            Err(e) => {
+                warn!("Failed to fetch the recommended fees for the sweep: {e}");
+                None
+            }
+        }
+    }
+
+    /// Reports the closed channels as payments of the funds that were on the user side,
+    /// looking up the closing transactions which are not known yet.
+    async fn closed_channel_payments(&self) -> NodeResult<Vec<Payment>> {
+        let balances = self.node.list_balances();
+        let block_height = self.node.status().current_best_block.height;
+        let mut payments = Vec::new();
+        for (channel_id, mut record) in self.store.list_closed_channels()? {
+            self.fill_closing_txid(&channel_id, &mut record).await?;
+            let Some(close) = &record.close else {
+                continue;
+            };
+            let is_pending = close.closing_txid.is_none()
+                || pending_close_status(&balances, &channel_id, block_height).is_some();
+            payments.push(convert_closed_channel(
+                channel_id, &record, close, is_pending,
+            ));
+        }
+        Ok(payments)
+    }
+
+    /// Looks up the closing transaction of a closed channel if it is not known yet, storing
+    /// it once found.
+    async fn fill_closing_txid(
+        &self,
+        channel_id: &str,
+        record: &mut ChannelRecord,
+    ) -> NodeResult<()> {
+        let Some(close) = record.close.as_mut() else {
+            return Ok(());
+        };
+        if close.closing_txid.is_none() {
+            close.closing_txid = self
+                .find_closing_txid(&record.funding_txid, record.funding_output_index)
+                .await;
+            if close.closing_txid.is_some() {
+                self.store.store_channel(channel_id, record)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Looks up the transaction spending the funding output of the channel.
+    async fn find_closing_txid(
+        &self,
+        funding_txid: &str,
+        funding_output_index: u32,
+    ) -> Option<String> {
+        match self
+            .chain_service
+            .transaction_outspends(funding_txid.to_string())
+            .await
+        {
+            Ok(outspends) => outspends
+                .into_iter()
+                .nth(funding_output_index as usize)
+                .and_then(|outspend| outspend.txid),
+            Err(e) => {
+                warn!("Failed to look up the spending transaction of {funding_txid}: {e}");
+                None
+            }
+        }
+    }
+
+    /// Creates an invoice for a payment hash whose preimage is already stored.
+    fn receive_for_hash(
+        &self,
+        req: &CreateInvoiceRequest,
+        description: &Bolt11InvoiceDescription,
+        payment_hash: PaymentHash,
+    ) -> NodeResult<Bolt11Invoice> {
+        let payments = self.node.bolt11_payment();
+        let invoice = match req.opening_fee_msat {
+            Some(opening_fee_msat) => {
+                ensure_sdk!(
+                    get_lsp(&self.config)?.is_some(),
+                    NodeError::generic(LSP_REQUIRED_ERROR)
+                );
+                payments.receive_via_jit_channel_for_hash(
+                    req.amount_msat,
+                    description,
+                    req.expiry,
+                    Some(opening_fee_msat),
+                    payment_hash,
+                )
+            }
+            None => {
+                payments.receive_for_hash(req.amount_msat, description, req.expiry, payment_hash)
+            }
+        }?;
+        ensure_min_final_cltv(invoice.min_final_cltv_expiry_delta(), req.min_accepted_cltv)?;
+        Ok(invoice)
+    }
+
+    /// Removes an invoice awaiting payment together with its preimage, so that payments for
+    /// it are failed.
+    fn remove_outstanding_invoice(&self, payment_hash: &PaymentHash) -> NodeResult<()> {
+        self.store.remove_preimage(payment_hash)?;
+        self.store.remove_bolt11(payment_hash)?;
+        self.store.remove_outstanding_invoice(payment_hash)?;
+        Ok(())
+    }
+
+    /// Connects to the LSP if it is not connected, as a JIT receive would otherwise fail in
+    /// LDK Node with a less helpful error.
+    ///
+    /// With `persist` the LSP is also added to the peers LDK Node reconnects to, which is done
+    /// even if it is connected already.
+    async fn connect_lsp(&self, persist: bool) -> NodeResult<PublicKey> {
+        let Some((lsp_id, lsp_address)) = get_lsp(&self.config)? else {
+            return Err(NodeError::generic(LSP_REQUIRED_ERROR));
+        };
+        let is_connected = self
+            .node
+            .list_peers()
+            .iter()
+            .any(|p| p.node_id == lsp_id && p.is_connected);
+        if is_connected && !persist {
+            return Ok(lsp_id);
+        }
+
+        if !is_connected {
+            info!("LSP {lsp_id} is not connected, connecting");
+        }
+        let node = Arc::clone(&self.node);
+        let connect_timeout = Duration::from_secs(self.config.connect_timeout_secs.into());
+        connect_with_timeout(connect_timeout, move || {
+            Ok(node.connect(lsp_id, lsp_address, persist)?)
+        })
+        .await
+        .map_err(|e| NodeError::LspUnavailable(format!("Failed to connect to the LSP: {e}")))?;
+        Ok(lsp_id)
+    }
+
+    /// Checks whether the invoice can be routed within the routing fee limit, if any, by
+    /// sending probes along the route found.
+    fn has_route(
+        &self,
+        invoice: &Bolt11Invoice,
+        amount_msat: Option<u64>,
+        max_fee_msat: Option<u64>,
+    ) -> bool {
+        let params = Some(route_parameters(max_fee_msat));
+        let payments = self.node.bolt11_payment();
+        let res = match amount_msat {
+            Some(amount_msat) => payments.send_probes_using_amount(invoice, amount_msat, params),
+            None => payments.send_probes(invoice, params),
+        };
+        if let Err(e) = &res {
+            debug!("No route found with a routing fee limit of {max_fee_msat:?} msat: {e}");
+        }
+        res.is_ok()
+    }
+}
+
 #[tonic::async_trait]
 impl NodeAPI for Ldk {
     async fn configure_node(&self, _close_to_address: Option<String>) -> NodeResult<()> {
         Err(NodeError::generic("LDK implementation not yet available"))
     }
 
-    async fn delete_invoice(&self, _bolt11: String) -> NodeResult<()> {
-        Err(NodeError::generic("LDK implementation not yet available"))
+    async fn delete_invoice(&self, bolt11: String) -> NodeResult<()> {
+        let invoice = ldk_node::lightning_invoice::Bolt11Invoice::from_str(&bolt11)?;
+        let payment_hash = PaymentHash(invoice.payment_hash().to_byte_array());
+        ensure_sdk!(
+            self.store
+                .list_outstanding_invoices()?
+                .contains(&payment_hash),
+            NodeError::generic("Invoice is not awaiting payment")
+        );
+        self.remove_outstanding_invoice(&payment_hash)
     }
 
-    fn max_receivable_single_payment_msat(&self) -> Result<u64, ReceivePaymentError> {
-        Ok(self
-            .node
-            .list_channels()
-            .iter()
-            .map(|c| c.inbound_capacity_msat)
-            .sum())
+    async fn cancel_jit_invoice(&self, payment_hash: String) -> NodeResult<()> {
+        let payment_hash = parse_payment_hash(&payment_hash)?;
+        if !self
+            .store
+            .list_outstanding_invoices()?
+            .contains(&payment_hash)
+        {
+            debug!(
+                "No invoice awaiting payment for {}, nothing to cancel",
+                payment_hash.to_hex()
+            );
+            return Ok(());
+        }
+        // LSPS2 has no message to cancel a JIT channel request, the LSP releases the
+        // intercept SCID once the opening fee params expire. Until then intercepted HTLCs
+        // are failed back, as the preimage is gone.
+        self.remove_outstanding_invoice(&payment_hash)?;
+        self.store
+            .remove_payment_registration(&payment_hash.to_hex())?;
+        Ok(())
+    }
+
+    fn max_receivable_single_payment_msat(&self, mpp: bool) -> Result<u64, ReceivePaymentError> {
+        match mpp {
+            true => Ok(self.inbound_capacity_msat()),
+            false => Ok(self.largest_inbound_capacity_msat()),
+        }
+    }
+
+    async fn estimate_liquidity_for(
+        &self,
+        target_receivable_msat: u64,
+    ) -> NodeResult<LiquidityEstimate> {
+        let current_inbound_msat = self.inbound_capacity_msat();
+        if target_receivable_msat <= current_inbound_msat {
+            return Ok(estimate_liquidity(
+                current_inbound_msat,
+                target_receivable_msat,
+                None,
+            ));
+        }
+
+        let opening_fee_params = self
+            .list_lsps(self.node.node_id().to_string())
+            .await
+            .map_err(|e| NodeError::Generic(format!("Failed to list LSPs: {e}")))?
+            .first()
+            .ok_or(NodeError::generic("Empty LSP list"))?
+            .cheapest_open_channel_fee(INVOICE_PAYMENT_FEE_EXPIRY_SECONDS)
+            .map_err(|e| NodeError::Generic(e.to_string()))?
+            .clone();
+        Ok(estimate_liquidity(
+            current_inbound_msat,
+            target_receivable_msat,
+            Some(opening_fee_params),
+        ))
     }
 
     async fn create_invoice(&self, req: CreateInvoiceRequest) -> NodeResult<String> {
@@ -161,6 +612,10 @@ impl NodeAPI for Ldk {
             Bolt11InvoiceDescription::Direct(description)
         };
 
+        if req.opening_fee_msat.is_some() {
+            self.connect_lsp(false).await?;
+        }
+
         let preimage = match req.preimage.map(|p| p.as_slice().try_into()) {
             Some(Ok(preimage)) => Some(PaymentPreimage(preimage)),
             Some(Err(e)) => {
@@ -168,27 +623,39 @@ impl NodeAPI for Ldk {
             }
             None => None,
         };
-        let preimage =
-            preimage.unwrap_or_else(|| PaymentPreimage(rand::thread_rng().gen::<[u8; 32]>()));
+        let preimage = self
+            .store
+            .reserve_new_preimage(preimage, self.entropy_source.as_ref())
+            .map_err(|e| match e.kind() {
+                ErrorKind::AlreadyExists => NodeError::InvoicePreimageAlreadyExists(
+                    "An invoice was already created with this preimage".to_string(),
+                ),
+                _ => e.into(),
+            })?;
         let payment_hash: PaymentHash = preimage.into();
-        self.store.store_preimage(&payment_hash, &preimage)?;
 
-        let payments = self.node.bolt11_payment();
-        let invoice = match req.opening_fee_msat {
-            Some(opening_fee_msat) => payments.receive_via_jit_channel_for_hash(
-                req.amount_msat,
-                &description,
-                req.expiry,
-                Some(opening_fee_msat),
-                payment_hash,
-            ),
-            None => {
-                payments.receive_for_hash(req.amount_msat, &description, req.expiry, payment_hash)
+        let invoice = match self.receive_for_hash(&req, &description, payment_hash) {
+            Ok(invoice) => invoice,
+            Err(e) => {
+                // Release the reserved preimage, no invoice was handed out for it.
+                self.store.remove_preimage(&payment_hash)?;
+                return Err(e);
             }
-        }?;
+        };
         let bolt11 = invoice.to_string();
-        self.store
-            .store_bolt11(&invoice.payment_hash().to_hex(), bolt11.clone())?;
+        let hash = invoice.payment_hash().to_hex();
+        let record = PreimageRecord {
+            amount_msat: Some(
+                req.amount_msat
+                    .saturating_sub(req.opening_fee_msat.unwrap_or_default()),
+            ),
+            expiry_ts: invoice.expires_at().map(|expires_at| expires_at.as_secs()),
+            bolt11_ref: Some(hash.clone()),
+            ..PreimageRecord::new(preimage)
+        };
+        self.store.store_preimage(&payment_hash, &record)?;
+        self.store.store_bolt11(&hash, bolt11.clone())?;
+        self.store.store_outstanding_invoice(&payment_hash)?;
         Ok(bolt11)
     }
 
@@ -197,45 +664,138 @@ impl NodeAPI for Ldk {
     }
 
     async fn get_node_state(&self) -> NodeState {
-        if let Err(e) = self.node.sync_wallets() {
-            warn!("Failed to sync LDK wallets: {e}");
+        self.node_info().await
+    }
+
+    async fn payment_status(&self, payment_hash: String) -> NodeResult<Option<PaymentStatus>> {
+        let payment_hash = parse_payment_hash(&payment_hash)?;
+        // LDK Node identifies BOLT-11 payments by their payment hash.
+        if let Some(payment) = self.node.payment(&PaymentId(payment_hash.0)) {
+            return Ok(Some(payment.status.into()));
         }
-        let node = &*self.node;
-        node.into()
+        let is_outstanding = self
+            .store
+            .list_outstanding_invoices()?
+            .contains(&payment_hash);
+        Ok(is_outstanding.then_some(PaymentStatus::Pending))
     }
 
     async fn list_payments(&self) -> NodeResult<Vec<Payment>> {
         let local_node_id = self.node.node_id();
-        self.node
+        let pruned_payments = self.store.list_pruned_payments()?;
+        let mut payments = self
+            .node
             .list_payments()
             .into_iter()
+            .filter(|p| !pruned_payments.contains(&p.id.to_hex()))
+            // The transactions of the on-chain wallet are not reported as payments, the funds
+            // of closed channels are reported as closed channel payments instead.
+            .filter(|p| !matches!(p.kind, PaymentKind::Onchain { .. }))
             .map(|p| convert_payment(p, &local_node_id, &self.store))
-            .collect::<Result<Vec<_>, _>>()
+            .collect::<Result<Vec<_>, _>>()?;
+        payments.extend(self.closed_channel_payments().await?);
+        Ok(payments)
+    }
+
+    async fn prune_payments(
+        &self,
+        older_than_ts: u64,
+        statuses: Vec<PaymentStatus>,
+    ) -> NodeResult<Vec<String>> {
+        ensure_sdk!(
+            !statuses.contains(&PaymentStatus::Pending),
+            NodeError::generic("Pending payments cannot be pruned")
+        );
+        let pruned_payments = self.store.list_pruned_payments()?;
+        let mut payment_ids = Vec::new();
+        for payment in self.node.list_payments() {
+            let payment_id = payment.id.to_hex();
+            if payment.latest_update_timestamp >= older_than_ts
+                || !statuses.contains(&payment.status.into())
+                || pruned_payments.contains(&payment_id)
+            {
+                continue;
+            }
+            // The payment records of LDK Node are kept, they are needed to detect
+            // duplicate payments.
+            if let Some(hash) = get_payment_hash(&payment) {
+                self.store.remove_bolt11(hash)?;
+                self.store.remove_custom_tlvs(hash)?;
+            }
+            self.store.remove_payment_outcome(&payment_id)?;
+            self.store.store_pruned_payment(&payment_id)?;
+            payment_ids.push(payment_id);
+        }
+        info!("Pruned {} payments", payment_ids.len());
+        Ok(payment_ids)
     }
 
-    async fn send_payment(&self, bolt11: String, amount_msat: Option<u64>) -> NodeResult<Payment> {
+    async fn send_payment(
+        &self,
+        bolt11: String,
+        amount_msat: Option<u64>,
+        max_fee_msat: Option<u64>,
+        cancel: CancellationToken,
+    ) -> NodeResult<Payment> {
         let invoice = ldk_node::lightning_invoice::Bolt11Invoice::from_str(&bolt11)?;
-        let payments = self.node.bolt11_payment();
-        let events = self.events_tx.subscribe(); // Subscribe before we try to send.
-        let params = Some(RouteParametersConfig {
-            max_total_routing_fee_msat: None,
-            max_total_cltv_expiry_delta: DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA,
-            max_path_count: 3,
-            max_channel_saturation_power_of_half: 2,
+        let max_total_routing_fee_msat = max_fee_msat.or_else(|| {
+            amount_msat
+                .or(invoice.amount_milli_satoshis())
+                .map(|amount_msat| self.config.max_routing_fee_msat(amount_msat))
         });
+        let params = Some(route_parameters(max_total_routing_fee_msat));
 
         self.store
             .store_bolt11(&invoice.payment_hash().to_hex(), bolt11)?;
 
-        let payment_id = match amount_msat {
-            Some(amount_msat) => payments.send_using_amount(&invoice, amount_msat, params),
-            None => payments.send(&invoice, params),
-        }?;
-
-        let payment = wait_for_payment_success(&self.node, events, payment_id).await?;
+        let invoice = &invoice;
+        let attempts = retry_payment(self.config.payment_retries, move || async move {
+            let payments = self.node.bolt11_payment();
+            let events = self.events_tx.subscribe(); // Subscribe before we try to send.
+            let payment_id = match amount_msat {
+                Some(amount_msat) => payments.send_using_amount(invoice, amount_msat, params),
+                None => payments.send(invoice, params),
+            }?;
+            wait_for_payment_outcome(&self.node, events, payment_id).await
+        });
+        // Sending is synchronous, so cancelling only drops the wait for the outcome. LDK Node
+        // keeps handling a dispatched payment, which is picked up by the next sync.
+        let outcome = tokio::select! {
+            biased; // Do not send an already cancelled payment.
+            _ = cancel.cancelled() => {
+                return Err(NodeError::Cancelled(format!(
+                    "Stopped waiting for payment {}",
+                    invoice.payment_hash()
+                )));
+            }
+            outcome = attempts => outcome?,
+        };
+        let payment = match (outcome, max_total_routing_fee_msat) {
+            (Ok(payment), _) => payment,
+            // LDK reports a route exceeding the fee limit as not found, so check whether a
+            // route exists when the limit is lifted.
+            (Err(PaymentFailureReason::RouteNotFound), Some(max_fee_msat))
+                if self.has_route(invoice, amount_msat, None) =>
+            {
+                return Err(NodeError::RouteTooExpensive(format!(
+                    "The routing fee would exceed the limit of {max_fee_msat} msat, retry with a higher max_fee_msat"
+                )));
+            }
+            (Err(reason), _) => return Err(NodeError::PaymentFailed(format!("{reason:?}"))),
+        };
         convert_payment(payment, &self.node.node_id(), &self.store)
     }
 
+    async fn probe_payment(
+        &self,
+        bolt11: String,
+        amount_msat: Option<u64>,
+        max_fee_msat: u64,
+    ) -> NodeResult<bool> {
+        let invoice = Bolt11Invoice::from_str(&bolt11)?;
+        Ok(self.has_route(&invoice, amount_msat, Some(max_fee_msat)))
+    }
+
     async fn send_spontaneous_payment(
         &self,
         node_id: String,
@@ -305,12 +865,19 @@ impl NodeAPI for Ldk {
         }
         debug!("LDK Node started");
 
+        let reorg_tracker = self.reorg_tracker.clone();
+        let node = Arc::clone(&self.node);
+        let chain_service = Arc::clone(&self.chain_service);
+        tokio::spawn(async move { reorg_tracker.track(node, chain_service).await });
+
         debug!("Starting event handling");
         start_event_handling(
             Arc::clone(&self.node),
             self.events_tx.clone(),
             self.store.clone(),
             self.incoming_payments_tx.clone(),
+            self.node_state_cache.clone(),
+            self.splice_tracker.clone(),
             shutdown,
         )
         .await;
@@ -322,6 +889,17 @@ impl NodeAPI for Ldk {
         }
         debug!("LDK Node stopped");
 
+        // The remote lock is still held, so no one else modified the remote store meanwhile.
+        debug!("Flushing dirty keys of the store");
+        match self.mirroring_store.flush(SHUTDOWN_FLUSH_TIMEOUT).await {
+            Ok(dirty_keys) if dirty_keys.is_empty() => debug!("Store was flushed"),
+            Ok(dirty_keys) => {
+                let keys: Vec<_> = dirty_keys.iter().map(|k| k.full_key.as_str()).collect();
+                warn!("Keys left dirty on shutdown: {keys:?}");
+            }
+            Err(e) => error!("Failed to flush the store: {e}"),
+        }
+
         debug!("Stopping remote lock refreshing");
         let _ = self.remote_lock_shutdown_tx.send(()).await;
         debug!("Waiting for remote lock refreshing stopped");
@@ -330,24 +908,375 @@ impl NodeAPI for Ldk {
         debug!("Exiting Ldk::start()");
     }
 
+    async fn wait_until_synced(&self, timeout: Duration) -> NodeResult<()> {
+        wait_until_synced(
+            self.chain_service.as_ref(),
+            || self.node.status().current_best_block.height,
+            timeout,
+        )
+        .await
+    }
+
     async fn connect_peer(&self, node_id: String, addr: String) -> NodeResult<()> {
-        let node_id = PublicKey::from_str(&node_id)
-            .map_err(|e| NodeError::Generic(format!("Invalid LSP public key: {e}")))?;
-        let address = SocketAddress::from_str(&addr)
-            .map_err(|e| NodeError::Generic(format!("Invalid LSP address: {e}")))?;
+        let (node_id, address) = parse_peer(&node_id, &addr)?;
         let persist = false;
-        self.node.connect(node_id, address, persist)?;
+        let node = Arc::clone(&self.node);
+        let connect_timeout = Duration::from_secs(self.config.connect_timeout_secs.into());
+        connect_with_timeout(connect_timeout, move || {
+            Ok(node.connect(node_id, address, persist)?)
+        })
+        .await?;
+        self.node_state_cache.invalidate();
+        Ok(())
+    }
+
+    async fn open_channel(
+        &self,
+        node_id: String,
+        addr: String,
+        amount_sat: u64,
+        push_msat: u64,
+    ) -> NodeResult<()> {
+        let (node_id, address) = parse_peer(&node_id, &addr)?;
+        let spendable_sat = self.node.list_balances().spendable_onchain_balance_sats;
+        ensure_sdk!(
+            amount_sat <= spendable_sat,
+            NodeError::InsufficientFunds(format!(
+                "Channel amount {amount_sat} sat exceeds the spendable on-chain balance {spendable_sat} sat"
+            ))
+        );
+        self.node
+            .open_channel(node_id, address, amount_sat, Some(push_msat), None)?;
+        self.node_state_cache.invalidate();
+        info!("Opening a channel of {amount_sat} sat to {node_id}");
+        Ok(())
+    }
+
+    async fn ensure_lsp_connected(&self) -> NodeResult<String> {
+        Ok(self.connect_lsp(true).await?.to_string())
+    }
+
+    async fn app_kv_get(&self, key: String) -> NodeResult<Option<Vec<u8>>> {
+        validate_app_data_key(&key)?;
+        Ok(self.store.load_app_data(&key)?)
+    }
+
+    async fn app_kv_set(&self, key: String, value: Vec<u8>) -> NodeResult<()> {
+        validate_app_data_key(&key)?;
+        ensure_sdk!(
+            value.len() <= MAX_APP_DATA_SIZE,
+            NodeError::Generic(format!(
+                "App data value is {} bytes, above the maximum of {MAX_APP_DATA_SIZE} bytes",
+                value.len()
+            ))
+        );
+        Ok(self.store.store_app_data(&key, value)?)
+    }
+
+    async fn app_kv_delete(&self, key: String) -> NodeResult<()> {
+        validate_app_data_key(&key)?;
+        Ok(self.store.remove_app_data(&key)?)
+    }
+
+    async fn disconnect_peer(&self, node_id: String) -> NodeResult<()> {
+        let node_id = PublicKey::from_str(&node_id)
+            .map_err(|e| NodeError::Generic(format!("Invalid peer public key: {e}")))?;
+        let is_connected = self
+            .node
+            .list_peers()
+            .iter()
+            .any(|p| p.node_id == node_id && p.is_connected);
+        ensure_sdk!(
+            is_connected,
+            NodeError::Generic(format!("Not connected to peer {node_id}"))
+        );
+        self.node.disconnect(node_id)?;
+        self.node_state_cache.invalidate();
+        Ok(())
+    }
+
+    async fn list_peers(&self) -> NodeResult<Vec<PeerInfo>> {
+        Ok(self
+            .node
+            .list_peers()
+            .into_iter()
+            .map(|p| PeerInfo {
+                node_id: p.node_id.to_string(),
+                address: p.address.to_string(),
+                is_connected: p.is_connected,
+                is_persisted: p.is_persisted,
+            })
+            .collect())
+    }
+
+    async fn list_channels(&self) -> NodeResult<Vec<ChannelInfo>> {
+        Ok(self
+            .node
+            .list_channels()
+            .into_iter()
+            .map(|c| ChannelInfo {
+                channel_id: c.channel_id.0.to_hex(),
+                counterparty_node_id: c.counterparty_node_id.to_string(),
+                funding_txid: c.funding_txo.map(|txo| txo.txid.to_string()),
+                capacity_sat: c.channel_value_sats,
+                outbound_capacity_msat: c.outbound_capacity_msat,
+                inbound_capacity_msat: c.inbound_capacity_msat,
+                is_channel_ready: c.is_channel_ready,
+                is_usable: c.is_usable,
+                confirmations: c.confirmations,
+                confirmations_required: c.confirmations_required,
+            })
+            .collect())
+    }
+
+    async fn splice_in(&self, channel_id: String, amount_sat: u64) -> NodeResult<()> {
+        let channel = self.find_channel(&channel_id)?;
+        let spendable_sat = self.node.list_balances().spendable_onchain_balance_sats;
+        ensure_sdk!(
+            amount_sat <= spendable_sat,
+            NodeError::InsufficientFunds(format!(
+                "Splice-in amount {amount_sat} sat exceeds the spendable on-chain balance {spendable_sat} sat"
+            ))
+        );
+        self.node.splice_in(
+            &channel.user_channel_id,
+            channel.counterparty_node_id,
+            amount_sat,
+        )?;
+        self.splice_tracker
+            .on_splice_initiated(&channel_id, amount_sat as i64);
+        info!("Initiated splice-in of {amount_sat} sat into channel {channel_id}");
+        Ok(())
+    }
+
+    async fn splice_out(
+        &self,
+        channel_id: String,
+        amount_sat: u64,
+        to_address: String,
+    ) -> NodeResult<()> {
+        let channel = self.find_channel(&channel_id)?;
+        let address = parse_address(&to_address, &self.config.network)?;
+        let outbound_capacity_sat = msat_to_sat(channel.outbound_capacity_msat);
+        ensure_sdk!(
+            amount_sat <= outbound_capacity_sat,
+            NodeError::InsufficientFunds(format!(
+                "Splice-out amount {amount_sat} sat exceeds the channel balance {outbound_capacity_sat} sat"
+            ))
+        );
+        self.node.splice_out(
+            &channel.user_channel_id,
+            channel.counterparty_node_id,
+            &address,
+            amount_sat,
+        )?;
+        self.splice_tracker
+            .on_splice_initiated(&channel_id, -(amount_sat as i64));
+        info!("Initiated splice-out of {amount_sat} sat from channel {channel_id} to {address}");
+        Ok(())
+    }
+
+    async fn new_onchain_address(&self) -> NodeResult<String> {
+        Ok(self.node.onchain_payment().new_address()?.to_string())
+    }
+
+    async fn close_all_channels(
+        &self,
+        close_to_address: Option<String>,
+        force: bool,
+    ) -> NodeResult<()> {
+        if let Some(address) = &close_to_address {
+            parse_address(address, &self.config.network)?;
+        }
+        for channel in self.node.list_channels() {
+            // Only the funds of the closed channels are swept, once each of them is resolved.
+            if let Some(address) = &close_to_address {
+                self.store
+                    .store_channel_close_to_address(&channel.channel_id.0.to_hex(), address)?;
+            }
+            if force {
+                self.node.force_close_channel(
+                    &channel.user_channel_id,
+                    channel.counterparty_node_id,
+                    None,
+                )?;
+            } else {
+                self.node
+                    .close_channel(&channel.user_channel_id, channel.counterparty_node_id)?;
+            }
+        }
+        self.node_state_cache.invalidate();
         Ok(())
     }
 
-    async fn close_all_channels(&self) -> NodeResult<()> {
-        for channel_id in self.node.list_channels() {
+    async fn close_channel(
+        &self,
+        channel_id: String,
+        close_to_address: Option<String>,
+        force: bool,
+    ) -> NodeResult<()> {
+        let channel = self.find_channel(&channel_id)?;
+        if let Some(address) = close_to_address {
+            parse_address(&address, &self.config.network)?;
+            self.store
+                .store_channel_close_to_address(&channel_id, &address)?;
+        }
+        if force {
+            self.node.force_close_channel(
+                &channel.user_channel_id,
+                channel.counterparty_node_id,
+                None,
+            )?;
+        } else {
             self.node
-                .close_channel(&channel_id.user_channel_id, channel_id.counterparty_node_id)?;
+                .close_channel(&channel.user_channel_id, channel.counterparty_node_id)?;
         }
+        self.node_state_cache.invalidate();
+        info!("Closing channel {channel_id}");
         Ok(())
     }
 
+    async fn fee_summary(&self, from_ts: i64, to_ts: i64) -> NodeResult<FeeSummary> {
+        let payments = self.list_payments().await?;
+        Ok(fee_summary(&payments, from_ts, to_ts))
+    }
+
+    async fn abort_channel_open(&self, channel_id: String) -> NodeResult<()> {
+        let channel = self.find_channel(&channel_id)?;
+        ensure_sdk!(
+            !channel.is_channel_ready,
+            NodeError::Generic(format!(
+                "Channel {channel_id} is already open, it can only be closed"
+            ))
+        );
+        // The funding transaction cannot be cancelled, so the funds return to the on-chain
+        // wallet once it confirms and the commitment transaction is resolved.
+        self.node.force_close_channel(
+            &channel.user_channel_id,
+            channel.counterparty_node_id,
+            Some("Channel open aborted".to_string()),
+        )?;
+        self.node_state_cache.invalidate();
+        info!("Aborted the opening of channel {channel_id}");
+        Ok(())
+    }
+
+    async fn pending_close_status(
+        &self,
+        channel_id: String,
+    ) -> NodeResult<Option<PendingCloseStatus>> {
+        let balances = self.node.list_balances();
+        let block_height = self.node.status().current_best_block.height;
+        Ok(pending_close_status(&balances, &channel_id, block_height))
+    }
+
+    async fn list_pending_sweeps(&self) -> NodeResult<Vec<PendingSweep>> {
+        Ok(self
+            .node
+            .list_balances()
+            .pending_balances_from_channel_closures
+            .iter()
+            .map(convert_pending_sweep)
+            .collect())
+    }
+
+    async fn rebroadcast_pending(&self) -> NodeResult<Vec<String>> {
+        let mut txids: Vec<String> = self
+            .node
+            .list_payments()
+            .into_iter()
+            .filter_map(|p| match p.kind {
+                PaymentKind::Onchain {
+                    txid,
+                    status: ConfirmationStatus::Unconfirmed,
+                } => Some(txid.to_string()),
+                _ => None,
+            })
+            .collect();
+        txids.extend(
+            self.list_pending_sweeps()
+                .await?
+                .into_iter()
+                .filter(|s| s.stage == PendingSweepStage::BroadcastAwaitingConfirmation)
+                .filter_map(|s| s.txid),
+        );
+        txids.sort();
+        txids.dedup();
+
+        // LDK Node does not expose the transactions, so they are fetched from the chain service.
+        let mut rebroadcast = Vec::new();
+        for txid in txids {
+            let res = async {
+                let tx = self.chain_service.transaction_hex(txid.clone()).await?;
+                self.chain_service.broadcast_transaction(tx).await
+            }
+            .await;
+            match res {
+                Ok(_) => rebroadcast.push(txid),
+                Err(e) => warn!("Failed to rebroadcast transaction {txid}: {e}"),
+            }
+        }
+        info!("Rebroadcast {} transactions", rebroadcast.len());
+        Ok(rebroadcast)
+    }
+
+    async fn graph_status(&self) -> NodeResult<GraphStatus> {
+        let graph = self.node.network_graph();
+        Ok(GraphStatus {
+            node_count: graph.list_nodes().len() as u64,
+            channel_count: graph.list_channels().len() as u64,
+            latest_rgs_snapshot_timestamp: self.node.status().latest_rgs_snapshot_timestamp,
+        })
+    }
+
+    async fn fee_estimates(&self) -> NodeResult<FeeEstimates> {
+        // LDK Node does not expose its fee estimator, so the estimates come from the same
+        // source as the fee rates picked for sweeps.
+        let fees = self
+            .recommended_fees()
+            .await
+            .map_err(|e| NodeError::ServiceConnectivity(e.to_string()))?;
+        Ok(fee_estimates(&fees))
+    }
+
+    async fn connectivity_check(&self) -> NodeResult<ConnectivityCheckResult> {
+        let vss = probe(async {
+            match check_vss(&self.config, &self.seed).await? {
+                VssCheckResult::Ok => Ok(()),
+                VssCheckResult::ConnectivityFailure { err }
+                | VssCheckResult::AuthFailure { err }
+                | VssCheckResult::UnexpectedFailure { err } => Err(NodeError::Generic(err)),
+            }
+        });
+        let chain_source = probe(async { self.chain_service.current_tip().await.map(|_| ()) });
+        let timestamp = self
+            .node
+            .status()
+            .latest_rgs_snapshot_timestamp
+            .unwrap_or_default();
+        let timeout = Duration::from_secs(self.config.connect_timeout_secs.into());
+        let rgs = probe(check_rgs(&self.config.rgs_url, timestamp, timeout));
+        let lsp = probe(async { self.connect_lsp(false).await.map(|_| ()) });
+        let (vss, chain_source, rgs, lsp) = tokio::join!(vss, chain_source, rgs, lsp);
+        Ok(ConnectivityCheckResult {
+            vss,
+            chain_source,
+            rgs,
+            lsp,
+        })
+    }
+
+    async fn resync_from_remote(&self) -> NodeResult<ResyncResult> {
+        // The node keeps using the state it loaded on startup, its next writes are applied on
+        // top of the downloaded one.
+        let result = self.mirroring_store.resync_from_remote().await?;
+        info!(
+            "Resynced the local cache: {} keys discarded, {} keys downloaded",
+            result.discarded_keys, result.downloaded_keys
+        );
+        Ok(result)
+    }
+
     async fn stream_incoming_payments(
         &self,
     ) -> NodeResult<Pin<Box<dyn Stream<Item = IncomingPayment> + Send>>> {
@@ -358,8 +1287,59 @@ impl NodeAPI for Ldk {
         Ok(Box::pin(stream))
     }
 
+    async fn stream_remote_lock_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = RemoteLockEvent> + Send>>> {
+        Ok(Box::pin(WatchStream::new(
+            self.remote_lock_events_rx.clone(),
+        )))
+    }
+
+    async fn stream_splice_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = SpliceEvent> + Send>>> {
+        let stream = BroadcastStream::new(self.splice_tracker.subscribe()).filter_map(|r| {
+            r.map_err(|Lagged(n)| warn!("Splice events stream missed {n} events"))
+                .ok()
+        });
+        Ok(Box::pin(stream))
+    }
+
+    async fn stream_reorg_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = ReorgEvent> + Send>>> {
+        let stream = BroadcastStream::new(self.reorg_tracker.subscribe()).filter_map(|r| {
+            r.map_err(|Lagged(n)| warn!("Reorg events stream missed {n} events"))
+                .ok()
+        });
+        Ok(Box::pin(stream))
+    }
+
     async fn generate_diagnostic_data(&self) -> NodeResult<Value> {
-        Ok(json!({}))
+        let dirty_keys: Vec<Value> = self
+            .mirroring_store
+            .list_dirty_keys()?
+            .into_iter()
+            .map(|k| json!({"key": k.full_key, "removed": k.removed}))
+            .collect();
+        // Only the lengths of the values are included, as the values may be secret.
+        let store_index: Vec<Value> = self
+            .mirroring_store
+            .dump_store_index()?
+            .into_iter()
+            .map(|e| {
+                json!({
+                    "primary_ns": e.primary_ns,
+                    "secondary_ns": e.secondary_ns,
+                    "key": e.key,
+                    "value_len": e.value_len,
+                    "local_version": e.local_version,
+                    "remote_version": e.remote_version,
+                    "removed": e.removed,
+                })
+            })
+            .collect();
+        Ok(json!({ "dirty_keys": dirty_keys, "store_index": store_index }))
     }
 
     async fn sign_message(&self, _message: &str) -> NodeResult<String> {
@@ -386,11 +1366,15 @@ impl NodeAPI for Ldk {
     }
 
     async fn derive_bip32_key(&self, path: Vec<ChildNumber>) -> NodeResult<Xpriv> {
-        let bitcoin_network: crate::bitcoin::Network = self.config.network.into();
-        Ok(
-            Xpriv::new_master(bitcoin_network, &self.seed)?
-                .derive_priv(&Secp256k1::new(), &path)?,
-        )
+        derive_bip32_key(&self.seed, self.config.network, &path)
+    }
+
+    async fn derive_bip32_key_for_network(
+        &self,
+        path: Vec<ChildNumber>,
+        network: Network,
+    ) -> NodeResult<Xpriv> {
+        derive_bip32_key(&self.seed, network, &path)
     }
 
     async fn get_open_peers(&self) -> NodeResult<HashSet<Vec<u8>>> {
@@ -401,36 +1385,36 @@ impl NodeAPI for Ldk {
 #[tonic::async_trait]
 impl LspAPI for Ldk {
     async fn list_lsps(&self, _node_pubkey: String) -> SdkResult<Vec<LspInformation>> {
-        // TODO: Load data dynamically from LSP.
-        // TODO: For now hard-code values for other environments.
-        // Regtest
-        let year = Duration::from_secs(60 * 60 * 24 * 365);
-        let in_one_year = SystemTime::now() + year;
-        let in_one_year: DateTime<Utc> = in_one_year.into();
-        let opening_fee_params = OpeningFeeParams {
-            min_msat: 1_000_000,
-            proportional: 40_000,
-            valid_until: in_one_year.to_rfc3339(),
-            max_idle_time: 0,
-            max_client_to_self_delay: 10_000,
-            promise: "I promise".to_string(),
+        let Some((pubkey, address)) = get_lsp(&self.config)? else {
+            return Ok(Vec::new());
         };
-        let (pubkey, address) = get_lsp(&self.config)?;
-        let lsp = LspInformation {
+        let terms = lsp_terms(self.config.network)?;
+        let mut lsp = LspInformation {
             id: pubkey.to_string(),
-            name: "Breez SDK Regtest LSPS2".to_string(),
-            widget_url: "http://widget.example.com".to_string(),
+            name: terms.name.to_string(),
+            widget_url: terms.widget_url.to_string(),
             pubkey: pubkey.to_string(),
             host: address.to_string(),
-            base_fee_msat: 1_000,
-            fee_rate: 0.0,
-            time_lock_delta: 72,
-            min_htlc_msat: 1,
+            base_fee_msat: terms.base_fee_msat,
+            fee_rate: terms.fee_rate,
+            time_lock_delta: terms.time_lock_delta,
+            min_htlc_msat: terms.min_htlc_msat,
             lsp_pubkey: pubkey.serialize().to_vec(),
-            opening_fee_params_list: OpeningFeeParamsMenu {
-                values: vec![opening_fee_params],
-            },
+            opening_fee_params_list: self.lsp_fee_menu()?,
+            channel_over_provisioning_ppm: terms.channel_over_provisioning_ppm,
+            min_channel_lifetime: terms.min_channel_lifetime,
+            max_client_to_self_delay: terms.max_client_to_self_delay,
         };
+        // The invoices route through the LSP with the parameters it announced for its
+        // channels, so the live values take precedence over the hard-coded ones above.
+        // The fee terms are static, see [Ldk::lsp_fee_menu].
+        let channel = self.node.list_channels().into_iter().find(|c| {
+            c.counterparty_node_id == pubkey
+                && c.counterparty_forwarding_info_cltv_expiry_delta.is_some()
+        });
+        if let Some(channel) = channel {
+            apply_forwarding_info(&mut lsp, &channel);
+        }
         Ok(vec![lsp])
     }
 
@@ -462,10 +1446,101 @@ impl LspAPI for Ldk {
         &self,
         _lsp_id: String,
         _lsp_pubkey: Vec<u8>,
-        _payment_info: grpc::PaymentInformation,
+        payment_info: grpc::PaymentInformation,
     ) -> SdkResult<grpc::RegisterPaymentReply> {
-        Ok(Default::default())
+        // With LSPS2 the LSP learns about the payment when the JIT channel is requested, so
+        // there is nothing to send to it.
+        let register = async { Ok(grpc::RegisterPaymentReply {}) };
+        register_payment_once(&self.store, &payment_info.payment_hash, register).await
+    }
+}
+
+/// Registers the payment with `register`, unless it was already registered, in which case
+/// the reply of the first registration is returned.
+async fn register_payment_once(
+    store: &Store,
+    payment_hash: &[u8],
+    register: impl Future<Output = SdkResult<grpc::RegisterPaymentReply>>,
+) -> SdkResult<grpc::RegisterPaymentReply> {
+    let payment_hash = payment_hash.to_hex();
+    if let Some(reply) = store
+        .load_payment_registration(&payment_hash)
+        .map_err(NodeError::from)?
+    {
+        debug!("Payment {payment_hash} is already registered");
+        return grpc::RegisterPaymentReply::decode(&*reply).map_err(|e| SdkError::Generic {
+            err: format!("Invalid payment registration: {e}"),
+        });
+    }
+    let reply = register.await?;
+    store
+        .store_payment_registration(&payment_hash, reply.encode_to_vec())
+        .map_err(NodeError::from)?;
+    Ok(reply)
+}
+
+/// Sets the routing parameters of the LSP to those it announced for the channel.
+fn apply_forwarding_info(lsp: &mut LspInformation, channel: &ChannelDetails) {
+    if let Some(fee_base_msat) = channel.counterparty_forwarding_info_fee_base_msat {
+        lsp.base_fee_msat = fee_base_msat.into();
+    }
+    if let Some(fee_ppm) = channel.counterparty_forwarding_info_fee_proportional_millionths {
+        lsp.fee_rate = f64::from(fee_ppm) / 1_000_000.0;
+    }
+    if let Some(cltv_expiry_delta) = channel.counterparty_forwarding_info_cltv_expiry_delta {
+        lsp.time_lock_delta = cltv_expiry_delta.into();
     }
+    // The smallest HTLC we accept from the LSP, below which payments cannot be received.
+    lsp.min_htlc_msat = i64::try_from(channel.inbound_htlc_minimum_msat).unwrap_or(i64::MAX);
+}
+
+/// Checks that the delta of an invoice is at least the accepted minimum. LDK Node does not
+/// allow to set the delta, it can only be checked to be large enough.
+fn ensure_min_final_cltv(invoice_delta: u64, min_accepted_cltv: Option<u32>) -> NodeResult<()> {
+    if let Some(cltv) = min_accepted_cltv {
+        ensure_sdk!(
+            invoice_delta >= u64::from(cltv),
+            NodeError::Generic(format!(
+                "Unsupported min_final_cltv_expiry_delta {cltv}, the node uses {invoice_delta}"
+            ))
+        );
+    }
+    Ok(())
+}
+
+fn derive_bip32_key(seed: &[u8], network: Network, path: &[ChildNumber]) -> NodeResult<Xpriv> {
+    let bitcoin_network: crate::bitcoin::Network = network.into();
+    Ok(Xpriv::new_master(bitcoin_network, seed)?.derive_priv(&Secp256k1::new(), path)?)
+}
+
+/// Checks that the key of app data is a valid key of the node store.
+fn validate_app_data_key(key: &str) -> NodeResult<()> {
+    ensure_sdk!(
+        !key.is_empty()
+            && key.len() <= KVSTORE_NAMESPACE_KEY_MAX_LEN
+            && key
+                .chars()
+                .all(|c| KVSTORE_NAMESPACE_KEY_ALPHABET.contains(c)),
+        NodeError::Generic(format!(
+            "Invalid app data key {key:?}, it must be 1 to {KVSTORE_NAMESPACE_KEY_MAX_LEN} characters of {KVSTORE_NAMESPACE_KEY_ALPHABET}"
+        ))
+    );
+    Ok(())
+}
+
+fn parse_payment_hash(payment_hash: &str) -> NodeResult<PaymentHash> {
+    hex::decode(payment_hash)
+        .ok()
+        .and_then(|hash| hash.try_into().ok())
+        .map(PaymentHash)
+        .ok_or(NodeError::generic("Invalid payment hash"))
+}
+
+fn parse_address(address: &str, network: &Network) -> NodeResult<Address> {
+    Address::from_str(address)
+        .map_err(|e| NodeError::Generic(format!("Invalid address: {e}")))?
+        .require_network(to_ldk_network(network))
+        .map_err(|e| NodeError::Generic(format!("Invalid address: {e}")))
 }
 
 fn to_ldk_network(network: &Network) -> ldk_node::bitcoin::network::Network {
@@ -477,18 +1552,585 @@ fn to_ldk_network(network: &Network) -> ldk_node::bitcoin::network::Network {
     }
 }
 
-fn get_lsp(config: &Config) -> NodeResult<(PublicKey, SocketAddress)> {
-    match config.lsps2_address.split_once('@') {
-        None => Err(NodeError::generic(
-            "Invalid lsps2_address, does not containt @",
-        )),
-        Some((id, address)) => {
-            let id = id
-                .parse()
-                .map_err(|e| NodeError::Generic(format!("Invalid LSP public key: {e}")))?;
-            let address = SocketAddress::from_str(address)
-                .map_err(|e| NodeError::Generic(format!("Invalid LSP address: {e}")))?;
-            Ok((id, address))
+fn build_ldk_node_config(config: &Config, lsp_id: Option<PublicKey>) -> ldk_node::config::Config {
+    // Without the anchor channels config LDK Node neither opens nor accepts anchor channels,
+    // so trusting the LSP only makes sense when anchors are enabled.
+    let anchor_channels_config = config.anchor_channels_enabled.then(|| {
+        // Allow anchor channels from the LSP without having on-chain funds available.
+        ldk_node::config::AnchorChannelsConfig {
+            trusted_peers_no_reserve: lsp_id.into_iter().collect(),
+            ..Default::default()
         }
+    });
+    ldk_node::config::Config {
+        anchor_channels_config,
+        ..Default::default()
+    }
+}
+
+fn fee_menu(terms: &LspTerms) -> OpeningFeeParamsMenu {
+    let year = Duration::from_secs(60 * 60 * 24 * 365);
+    let in_one_year = SystemTime::now() + year;
+    let in_one_year: DateTime<Utc> = in_one_year.into();
+    let opening_fee_params = OpeningFeeParams {
+        min_msat: terms.min_fee_msat,
+        proportional: terms.proportional_fee,
+        valid_until: in_one_year.to_rfc3339(),
+        max_idle_time: 0,
+        max_client_to_self_delay: terms.max_client_to_self_delay,
+        // Not issued by the LSP, so there is no promise to hold it to.
+        promise: String::new(),
+    };
+    OpeningFeeParamsMenu {
+        values: vec![opening_fee_params],
+    }
+}
+
+/// The hard-coded terms of the LSP of a network, used until they are fetched from the LSP.
+/// LSPS2 does not advertise the over-provisioning, so it is only ever known from here.
+struct LspTerms {
+    name: &'static str,
+    widget_url: &'static str,
+    base_fee_msat: i64,
+    fee_rate: f64,
+    time_lock_delta: u32,
+    min_htlc_msat: i64,
+    min_fee_msat: u64,
+    proportional_fee: u32,
+    channel_over_provisioning_ppm: u32,
+    min_channel_lifetime: u32,
+    max_client_to_self_delay: u32,
+}
+
+// Kept in sync with the config of lsps2-server of the integration tests by hand.
+const REGTEST_LSP_TERMS: LspTerms = LspTerms {
+    name: "Breez SDK Regtest LSPS2",
+    widget_url: "http://widget.example.com",
+    base_fee_msat: 1_000,
+    fee_rate: 0.0,
+    time_lock_delta: 72,
+    min_htlc_msat: 1,
+    min_fee_msat: 1_000_000,
+    proportional_fee: 40_000,
+    channel_over_provisioning_ppm: 1_000_000,
+    min_channel_lifetime: 100_000,
+    max_client_to_self_delay: 10_000,
+};
+
+/// Returns the hard-coded terms of the LSP of the network. Only the regtest LSP has known
+/// terms, the LSPs of the other networks are unsupported until theirs are fetched.
+fn lsp_terms(network: Network) -> NodeResult<&'static LspTerms> {
+    match network {
+        Network::Regtest => Ok(&REGTEST_LSP_TERMS),
+        _ => Err(NodeError::Generic(format!(
+            "The terms of the LSP are unknown on {network:?}"
+        ))),
+    }
+}
+
+const LSP_REQUIRED_ERROR: &str = "LSP address required for JIT receives on this network";
+/// Delay before the first re-attempt of a failed sync with the chain.
+const SYNC_RETRY_DELAY: Duration = Duration::from_millis(500);
+/// The longest delay between the re-attempts of a failed sync with the chain.
+const MAX_SYNC_RETRY_DELAY: Duration = Duration::from_secs(30);
+/// The most re-attempts of a failed sync with the chain, whatever the configured number.
+const MAX_SYNC_RETRIES: u32 = 10;
+const SHUTDOWN_FLUSH_TIMEOUT: Duration = Duration::from_secs(10);
+/// The largest value of app data, which is meant for small bits like settings.
+const MAX_APP_DATA_SIZE: usize = 8 * 1024;
+
+/// Whether a node on the network needs an LSP to receive its first payments. On Regtest
+/// channels can be opened manually, so nodes may run without an LSP.
+fn lsp_required(network: &Network) -> bool {
+    *network != Network::Regtest
+}
+
+/// Parses `lsps2_address` of the config. Returns `None` if no LSP is configured.
+fn get_lsp(config: &Config) -> NodeResult<Option<(PublicKey, SocketAddress)>> {
+    if config.lsps2_address.is_empty() {
+        return Ok(None);
+    }
+    let (id, address) = split_node_address(&config.lsps2_address)?;
+    let id = id
+        .parse()
+        .map_err(|e| NodeError::Generic(format!("Invalid LSP public key: {e}")))?;
+    let address = SocketAddress::from_str(address)
+        .map_err(|e| NodeError::Generic(format!("Invalid LSP address: {e}")))?;
+    Ok(Some((id, address)))
+}
+
+fn parse_peer(node_id: &str, addr: &str) -> NodeResult<(PublicKey, SocketAddress)> {
+    let node_id = PublicKey::from_str(node_id)
+        .map_err(|e| NodeError::Generic(format!("Invalid peer public key: {e}")))?;
+    let address = SocketAddress::from_str(addr)
+        .map_err(|e| NodeError::Generic(format!("Invalid peer address: {e}")))?;
+    Ok((node_id, address))
+}
+
+/// Failures caused by the state of the network rather than by the payment itself,
+/// which may succeed when re-attempted with a freshly computed route.
+fn is_retryable(reason: PaymentFailureReason) -> bool {
+    matches!(
+        reason,
+        PaymentFailureReason::RouteNotFound | PaymentFailureReason::RetriesExhausted
+    )
+}
+
+/// Runs `attempt` until it succeeds, fails with a non-retryable reason, or
+/// `max_retries` re-attempts have been made.
+///
+/// Returns the reason of the last failure, if the payment did not succeed.
+async fn retry_payment<T, F, Fut>(
+    max_retries: u32,
+    mut attempt: F,
+) -> NodeResult<Result<T, PaymentFailureReason>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = NodeResult<Result<T, PaymentFailureReason>>>,
+{
+    let mut retries = 0;
+    loop {
+        match attempt().await? {
+            Err(reason) if is_retryable(reason) && retries < max_retries => {
+                retries += 1;
+                warn!("Payment failed with {reason:?}, retrying ({retries}/{max_retries})");
+            }
+            outcome => return Ok(outcome),
+        }
+    }
+}
+
+fn route_parameters(max_total_routing_fee_msat: Option<u64>) -> RouteParametersConfig {
+    RouteParametersConfig {
+        max_total_routing_fee_msat,
+        max_total_cltv_expiry_delta: DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA,
+        max_path_count: 3,
+        max_channel_saturation_power_of_half: 2,
+    }
+}
+
+/// Runs the blocking `sync` until it succeeds or `max_retries` re-attempts have been made,
+/// doubling the delay between the attempts starting from `initial_delay`, up to
+/// [MAX_SYNC_RETRY_DELAY]. The thread sleeps meanwhile, so it must not be called from an
+/// async context.
+///
+/// Returns the number of re-attempts which were needed.
+fn retry_sync<E: Display>(
+    max_retries: u32,
+    initial_delay: Duration,
+    mut sync: impl FnMut() -> Result<(), E>,
+) -> Result<u32, E> {
+    let mut retries = 0;
+    let mut delay = initial_delay;
+    loop {
+        match sync() {
+            Ok(()) => return Ok(retries),
+            Err(e) if retries < max_retries => {
+                retries += 1;
+                warn!("Sync failed with {e}, retrying in {delay:?} ({retries}/{max_retries})");
+                std::thread::sleep(delay);
+                delay = delay.saturating_mul(2).min(MAX_SYNC_RETRY_DELAY);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Runs the blocking `connect` on a separate thread, giving up after `connect_timeout`.
+///
+/// The connection attempt itself cannot be cancelled, it keeps running in the
+/// background until it completes or fails.
+async fn connect_with_timeout<F>(connect_timeout: Duration, connect: F) -> NodeResult<()>
+where
+    F: FnOnce() -> NodeResult<()> + Send + 'static,
+{
+    match tokio::time::timeout(connect_timeout, tokio::task::spawn_blocking(connect)).await {
+        Ok(Ok(res)) => res,
+        Ok(Err(e)) => Err(NodeError::Generic(format!("Failed to connect: {e}"))),
+        Err(_) => Err(NodeError::ConnectTimeout(format!(
+            "Failed to connect within {}s",
+            connect_timeout.as_secs()
+        ))),
+    }
+}
+
+/// Estimates the liquidity needed to receive `target_receivable_msat` in a single payment.
+///
+/// The fee math mirrors the one used on receive: if a new channel is needed, the LSP
+/// fee is charged for the whole amount.
+fn estimate_liquidity(
+    current_inbound_msat: u64,
+    target_receivable_msat: u64,
+    opening_fee_params: Option<OpeningFeeParams>,
+) -> LiquidityEstimate {
+    match opening_fee_params {
+        Some(params) if target_receivable_msat > current_inbound_msat => LiquidityEstimate {
+            current_inbound_msat,
+            required_new_inbound_msat: target_receivable_msat,
+            opening_fee_msat: Some(params.get_channel_fees_msat_for(target_receivable_msat)),
+            opening_fee_params: Some(params),
+        },
+        _ => LiquidityEstimate {
+            current_inbound_msat,
+            required_new_inbound_msat: 0,
+            opening_fee_msat: None,
+            opening_fee_params: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use ldk_node::config::ChannelConfig;
+    use ldk_node::lightning::ln::types::ChannelId;
+    use ldk_node::UserChannelId;
+
+    use super::*;
+    use crate::ldk::store::mock_mirroring_store;
+
+    fn opening_fee_params() -> OpeningFeeParams {
+        OpeningFeeParams {
+            min_msat: 1_000_000,
+            proportional: 40_000,
+            valid_until: "2030-01-01T00:00:00Z".to_string(),
+            max_idle_time: 0,
+            max_client_to_self_delay: 10_000,
+            promise: "promise".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_estimate_liquidity_within_inbound() {
+        let estimate = estimate_liquidity(50_000_000, 10_000_000, Some(opening_fee_params()));
+        assert_eq!(estimate.current_inbound_msat, 50_000_000);
+        assert_eq!(estimate.required_new_inbound_msat, 0);
+        assert_eq!(estimate.opening_fee_msat, None);
+        assert_eq!(estimate.opening_fee_params, None);
+    }
+
+    #[test]
+    fn test_estimate_liquidity_above_inbound() {
+        let estimate = estimate_liquidity(10_000_000, 100_000_000, Some(opening_fee_params()));
+        assert_eq!(estimate.current_inbound_msat, 10_000_000);
+        assert_eq!(estimate.required_new_inbound_msat, 100_000_000);
+        // 4% of 100k sat is above the minimum fee of 1k sat.
+        assert_eq!(estimate.opening_fee_msat, Some(4_000_000));
+        assert_eq!(estimate.opening_fee_params, Some(opening_fee_params()));
+
+        // Small amounts are charged the minimum fee.
+        let estimate = estimate_liquidity(0, 2_000_000, Some(opening_fee_params()));
+        assert_eq!(estimate.opening_fee_msat, Some(1_000_000));
+    }
+
+    #[test]
+    fn test_retry_sync() {
+        let mut attempts = 0;
+        let result = retry_sync(2, Duration::ZERO, || {
+            attempts += 1;
+            match attempts {
+                1 => Err("Esplora is unreachable"),
+                _ => Ok(()),
+            }
+        });
+        assert_eq!(result, Ok(1));
+        assert_eq!(attempts, 2);
+
+        let mut attempts = 0;
+        let result = retry_sync(2, Duration::ZERO, || {
+            attempts += 1;
+            Err("Esplora is unreachable")
+        });
+        assert_eq!(result, Err("Esplora is unreachable"));
+        assert_eq!(attempts, 3);
+
+        let mut attempts = 0;
+        let result = retry_sync(0, Duration::ZERO, || {
+            attempts += 1;
+            Ok::<_, &str>(())
+        });
+        assert_eq!(result, Ok(0));
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_payment_after_transient_failure() {
+        let mut attempts = 0;
+        let result = retry_payment(2, || {
+            attempts += 1;
+            let outcome = match attempts {
+                1 => Err(PaymentFailureReason::RouteNotFound),
+                _ => Ok("paid"),
+            };
+            async move { Ok(outcome) }
+        })
+        .await;
+        assert_eq!(result.unwrap(), Ok("paid"));
+        assert_eq!(attempts, 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_payment_non_retryable_failure() {
+        let mut attempts = 0;
+        let result: NodeResult<Result<(), _>> = retry_payment(2, || {
+            attempts += 1;
+            async { Ok(Err(PaymentFailureReason::RecipientRejected)) }
+        })
+        .await;
+        assert_eq!(
+            result.unwrap(),
+            Err(PaymentFailureReason::RecipientRejected)
+        );
+        assert_eq!(attempts, 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_payment_gives_up_after_max_retries() {
+        let mut attempts = 0;
+        let result: NodeResult<Result<(), _>> = retry_payment(2, || {
+            attempts += 1;
+            async { Ok(Err(PaymentFailureReason::RetriesExhausted)) }
+        })
+        .await;
+        assert_eq!(result.unwrap(), Err(PaymentFailureReason::RetriesExhausted));
+        assert_eq!(attempts, 3);
+    }
+
+    #[test]
+    fn test_ensure_min_final_cltv() {
+        assert!(ensure_min_final_cltv(80, None).is_ok());
+        assert!(ensure_min_final_cltv(80, Some(18)).is_ok());
+        assert!(ensure_min_final_cltv(80, Some(80)).is_ok());
+        assert!(ensure_min_final_cltv(80, Some(81)).is_err());
+    }
+
+    #[test]
+    fn test_validate_app_data_key() {
+        assert!(validate_app_data_key("last_notification_id").is_ok());
+        assert!(validate_app_data_key(&"a".repeat(KVSTORE_NAMESPACE_KEY_MAX_LEN)).is_ok());
+        assert!(validate_app_data_key("").is_err());
+        assert!(validate_app_data_key(&"a".repeat(KVSTORE_NAMESPACE_KEY_MAX_LEN + 1)).is_err());
+        assert!(validate_app_data_key("app/key").is_err());
+    }
+
+    #[test]
+    fn test_parse_payment_hash() {
+        assert_eq!(
+            parse_payment_hash(&"ab".repeat(32)).unwrap(),
+            PaymentHash([0xab; 32])
+        );
+        assert!(parse_payment_hash("ab").is_err());
+        assert!(parse_payment_hash("not a hash").is_err());
+    }
+
+    #[test]
+    fn test_parse_address() {
+        let address = "bcrt1qs758ursh4q9z627kt3pp5yysm78ddny6txaqgw";
+        assert_eq!(
+            parse_address(address, &Network::Regtest)
+                .unwrap()
+                .to_string(),
+            address
+        );
+        assert!(parse_address(address, &Network::Bitcoin).is_err());
+        assert!(parse_address("not an address", &Network::Regtest).is_err());
+    }
+
+    #[test]
+    fn test_derive_bip32_key() {
+        let seed = [7; 64];
+        let path = [
+            ChildNumber::from_hardened_idx(139).unwrap(),
+            ChildNumber::from(0),
+        ];
+
+        let mainnet = derive_bip32_key(&seed, Network::Bitcoin, &path).unwrap();
+        let signet = derive_bip32_key(&seed, Network::Signet, &path).unwrap();
+        assert!(mainnet.to_string().starts_with("xprv"));
+        assert!(signet.to_string().starts_with("tprv"));
+        // The network only changes the encoding, not the key.
+        assert_eq!(mainnet.private_key, signet.private_key);
+        assert_eq!(mainnet.chain_code, signet.chain_code);
+        assert_eq!(
+            derive_bip32_key(&seed, Network::Testnet, &path).unwrap(),
+            signet
+        );
+    }
+
+    #[test]
+    fn test_build_ldk_node_config() {
+        let mut config = Config::regtest(String::new());
+        let lsp_id = PublicKey::from_str(
+            "03e7156ae33b0a208d0744199163177e909e80176e55d97a2f221ede0f934dd9ad",
+        )
+        .unwrap();
+
+        let ldk_node_config = build_ldk_node_config(&config, Some(lsp_id));
+        let anchor_channels_config = ldk_node_config.anchor_channels_config.unwrap();
+        assert_eq!(
+            anchor_channels_config.trusted_peers_no_reserve,
+            vec![lsp_id]
+        );
+
+        config.anchor_channels_enabled = false;
+        let ldk_node_config = build_ldk_node_config(&config, Some(lsp_id));
+        assert!(ldk_node_config.anchor_channels_config.is_none());
+    }
+
+    #[test]
+    fn test_get_lsp() {
+        let lsp_id = "03e7156ae33b0a208d0744199163177e909e80176e55d97a2f221ede0f934dd9ad";
+        let mut config = Config::regtest(String::new());
+        assert!(get_lsp(&config).unwrap().is_none());
+
+        config.lsps2_address = format!("{lsp_id}@127.0.0.1:9735");
+        let (id, address) = get_lsp(&config).unwrap().unwrap();
+        assert_eq!(id.to_string(), lsp_id);
+        assert_eq!(address.to_string(), "127.0.0.1:9735");
+
+        for malformed in [
+            lsp_id.to_string(),
+            "not a key@127.0.0.1:9735".to_string(),
+            format!("{lsp_id}@not an address"),
+        ] {
+            config.lsps2_address = malformed;
+            assert!(get_lsp(&config).is_err());
+        }
+    }
+
+    #[test]
+    fn test_fee_menu() {
+        let menu = fee_menu(&REGTEST_LSP_TERMS);
+        assert!(!menu.values.is_empty());
+        let now = Utc::now();
+        assert!(menu
+            .values
+            .iter()
+            .all(|ofp| ofp.valid_until_date().unwrap() > now));
+        assert!(menu.get_cheapest_opening_fee_params().is_ok());
+    }
+
+    #[test]
+    fn test_lsp_terms() {
+        let terms = lsp_terms(Network::Regtest).unwrap();
+        // Channels can only be pre-opened if the LSP over-provisions them.
+        assert!(terms.channel_over_provisioning_ppm > 0);
+        let params = fee_menu(terms).get_cheapest_opening_fee_params().unwrap();
+        // The fee leaves something of the payment to forward.
+        assert!(params.proportional < 1_000_000);
+
+        for network in [Network::Bitcoin, Network::Testnet, Network::Signet] {
+            assert!(lsp_terms(network).is_err(), "{network:?}");
+        }
+    }
+
+    #[test]
+    fn test_apply_forwarding_info() {
+        let counterparty_node_id = PublicKey::from_str(
+            "03e7156ae33b0a208d0744199163177e909e80176e55d97a2f221ede0f934dd9ad",
+        )
+        .unwrap();
+        let channel = ChannelDetails {
+            channel_id: ChannelId([1; 32]),
+            counterparty_node_id,
+            funding_txo: None,
+            short_channel_id: None,
+            outbound_scid_alias: None,
+            inbound_scid_alias: None,
+            channel_value_sats: 100_000,
+            unspendable_punishment_reserve: None,
+            user_channel_id: UserChannelId(1),
+            feerate_sat_per_1000_weight: 253,
+            outbound_capacity_msat: 0,
+            inbound_capacity_msat: 90_000_000,
+            confirmations_required: None,
+            confirmations: None,
+            is_outbound: false,
+            is_channel_ready: true,
+            is_usable: true,
+            is_announced: false,
+            cltv_expiry_delta: None,
+            counterparty_unspendable_punishment_reserve: 1_000,
+            counterparty_outbound_htlc_minimum_msat: Some(1),
+            counterparty_outbound_htlc_maximum_msat: None,
+            counterparty_forwarding_info_fee_base_msat: Some(2_000),
+            counterparty_forwarding_info_fee_proportional_millionths: Some(100),
+            counterparty_forwarding_info_cltv_expiry_delta: Some(144),
+            next_outbound_htlc_limit_msat: 0,
+            next_outbound_htlc_minimum_msat: 0,
+            force_close_spend_delay: None,
+            inbound_htlc_minimum_msat: 5_000,
+            inbound_htlc_maximum_msat: None,
+            config: ChannelConfig::default(),
+            channel_shutdown_state: None,
+        };
+        let terms = &REGTEST_LSP_TERMS;
+        let mut lsp = LspInformation {
+            id: counterparty_node_id.to_string(),
+            name: terms.name.to_string(),
+            widget_url: terms.widget_url.to_string(),
+            pubkey: counterparty_node_id.to_string(),
+            host: "127.0.0.1:9735".to_string(),
+            base_fee_msat: terms.base_fee_msat,
+            fee_rate: terms.fee_rate,
+            time_lock_delta: terms.time_lock_delta,
+            min_htlc_msat: terms.min_htlc_msat,
+            lsp_pubkey: counterparty_node_id.serialize().to_vec(),
+            opening_fee_params_list: fee_menu(terms),
+            channel_over_provisioning_ppm: terms.channel_over_provisioning_ppm,
+            min_channel_lifetime: terms.min_channel_lifetime,
+            max_client_to_self_delay: terms.max_client_to_self_delay,
+        };
+        apply_forwarding_info(&mut lsp, &channel);
+        assert_eq!(lsp.base_fee_msat, 2_000);
+        assert_eq!(lsp.fee_rate, 0.0001);
+        assert_eq!(lsp.time_lock_delta, 144);
+        // The minimum of the HTLCs we accept, not of the ones the LSP accepts from us.
+        assert_eq!(lsp.min_htlc_msat, 5_000);
+    }
+
+    #[test]
+    fn test_lsp_required() {
+        assert!(!lsp_required(&Network::Regtest));
+        assert!(lsp_required(&Network::Bitcoin));
+        assert!(lsp_required(&Network::Testnet));
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_timeout() {
+        // A connection attempt which hangs past the timeout.
+        let res = connect_with_timeout(Duration::from_millis(10), || {
+            std::thread::sleep(Duration::from_millis(200));
+            Ok(())
+        })
+        .await;
+        assert!(matches!(res, Err(NodeError::ConnectTimeout(_))));
+
+        let res = connect_with_timeout(Duration::from_secs(1), || Ok(())).await;
+        assert!(res.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_register_payment_once() {
+        let store = Store::new(Arc::new(mock_mirroring_store().await));
+        let registrations = AtomicU32::new(0);
+        let registrations_ref = &registrations;
+        let register = move || async move {
+            registrations_ref.fetch_add(1, Ordering::SeqCst);
+            Ok(grpc::RegisterPaymentReply {})
+        };
+
+        for _ in 0..2 {
+            register_payment_once(&store, &[1; 32], register())
+                .await
+                .unwrap();
+        }
+        assert_eq!(registrations.load(Ordering::SeqCst), 1);
+
+        register_payment_once(&store, &[2; 32], register())
+            .await
+            .unwrap();
+        assert_eq!(registrations.load(Ordering::SeqCst), 2);
     }
 }
@@ -1,15 +1,22 @@
 use core::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use ldk_node::bitcoin::secp256k1::PublicKey;
+use ldk_node::lightning::chain::channelmonitor::ANTI_REORG_DELAY;
 use ldk_node::lightning_invoice::Bolt11Invoice;
 use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage};
-use ldk_node::LightningBalance::ClaimableOnChannelClose;
-use ldk_node::{Node, PendingSweepBalance};
+use ldk_node::LightningBalance::{ClaimableAwaitingConfirmations, ClaimableOnChannelClose};
+use ldk_node::{BalanceDetails, Event, Node, PendingSweepBalance};
 
-use crate::ldk::store::Store;
-use crate::ldk::utils::Hex;
+use crate::ldk::store::{ChannelCloseRecord, ChannelRecord, Store};
+use crate::ldk::utils::{sat_to_msat, Hex};
 use crate::node_api::NodeError;
-use crate::{LnPaymentDetails, NodeState, Payment, PaymentDetails, PaymentStatus, PaymentType};
+use crate::{
+    ChannelState, ClosedChannelPaymentDetails, FeeSummary, LnPaymentDetails, NodeState, Payment,
+    PaymentDetails, PaymentStatus, PaymentType, PendingCloseStatus, PendingSweep,
+    PendingSweepStage, TlvEntry,
+};
 
 impl From<&Node> for NodeState {
     fn from(node: &Node) -> Self {
@@ -54,19 +61,104 @@ impl From<&Node> for NodeState {
         Self {
             id: node.node_id().to_string(),
             block_height: node.status().current_best_block.height,
-            channels_balance_msat: balances.total_lightning_balance_sats * 1000
-                + inbound_htlcs_msat,
-            onchain_balance_msat: balances.total_onchain_balance_sats * 1000,
-            pending_onchain_balance_msat: pending_onchain_balance_sats * 1000,
+            channels_balance_msat: sat_to_msat(balances.total_lightning_balance_sats)
+                .saturating_add(inbound_htlcs_msat),
+            onchain_balance_msat: sat_to_msat(balances.total_onchain_balance_sats),
+            pending_onchain_balance_msat: sat_to_msat(pending_onchain_balance_sats),
             utxos: Vec::new(), // Not available in LDK Node.
             max_payable_msat,
             max_receivable_msat: MAX_PAYMENT_AMOUNT_MSAT,
             max_single_payment_amount_msat: MAX_PAYMENT_AMOUNT_MSAT,
-            max_chan_reserve_msats: max_chan_reserve_sats * 1000,
+            max_chan_reserve_msats: sat_to_msat(max_chan_reserve_sats),
             connected_peers,
-            // TODO: Calculate a better approximation.
+            // Invoices advertise MPP, so a payment can use the inbound liquidity of all channels.
             max_receivable_single_payment_amount_msat: inbound_capacity_msats,
             total_inbound_liquidity_msats: inbound_capacity_msats,
+            sync_warning: None,
+        }
+    }
+}
+
+/// How long a computed [NodeState] is kept at most. On-chain deposits raise no event of the
+/// node, so they only show up once the state is recomputed with a sync of the wallets.
+const NODE_STATE_CACHE_TTL: Duration = Duration::from_secs(30);
+
+/// Keeps the last computed [NodeState] until it is invalidated by an event of the node,
+/// a new block or its expiry.
+#[derive(Clone)]
+pub(crate) struct NodeStateCache {
+    inner: Arc<Mutex<CachedNodeState>>,
+    ttl: Duration,
+}
+
+impl Default for NodeStateCache {
+    fn default() -> Self {
+        Self::new(NODE_STATE_CACHE_TTL)
+    }
+}
+
+#[derive(Default)]
+struct CachedNodeState {
+    /// The state along with when it was cached.
+    state: Option<(NodeState, Instant)>,
+    /// Incremented on every invalidation, so that a state computed concurrently with an
+    /// invalidation is not cached.
+    generation: u64,
+}
+
+impl NodeStateCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            inner: Default::default(),
+            ttl,
+        }
+    }
+
+    /// Returns the cached state if it is still valid at the given block height.
+    pub fn get(&self, block_height: u32) -> Option<NodeState> {
+        let inner = self.inner.lock().unwrap();
+        inner
+            .state
+            .as_ref()
+            .filter(|(state, cached_at)| {
+                state.block_height == block_height && cached_at.elapsed() < self.ttl
+            })
+            .map(|(state, _)| state.clone())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.inner.lock().unwrap().generation
+    }
+
+    /// Caches the state computed at the given generation, unless the cache was invalidated
+    /// in the meantime. A state with a sync warning is not cached, so that the next request
+    /// retries the sync.
+    pub fn set(&self, generation: u64, state: NodeState) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.generation == generation && state.sync_warning.is_none() {
+            inner.state = Some((state, Instant::now()));
+        }
+    }
+
+    pub fn invalidate(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.generation += 1;
+        inner.state = None;
+    }
+
+    pub fn on_event(&self, event: &Event) {
+        match event {
+            // Every event of the node changes balances or channels.
+            Event::PaymentReceived { .. }
+            | Event::PaymentSuccessful { .. }
+            | Event::PaymentFailed { .. }
+            | Event::PaymentClaimable { .. }
+            | Event::PaymentForwarded { .. }
+            | Event::ChannelPending { .. }
+            | Event::ChannelReady { .. }
+            | Event::ChannelClosed { .. }
+            | Event::SplicePending { .. }
+            | Event::SpliceFailed { .. } => self.invalidate(),
         }
     }
 }
@@ -83,40 +175,126 @@ pub fn convert_payment(
         } => lsp_fee_msat,
         _ => 0,
     };
-    let bolt11 = match get_payment_hash(&payment) {
-        Some(payment_hash) => store.load_bolt11(payment_hash)?,
-        None => None,
+    let (bolt11, custom_tlvs) = match get_payment_hash(&payment) {
+        Some(payment_hash) => (
+            store.load_bolt11(payment_hash)?,
+            store.load_custom_tlvs(payment_hash)?,
+        ),
+        None => (None, None),
     };
-    let (description, destination_pubkey) = match &bolt11 {
+    let outcome = store
+        .load_payment_outcome(&payment.id.to_hex())?
+        .unwrap_or_default();
+    let (description, destination_pubkey, invoice_amount_msat) = match &bolt11 {
         Some(bolt11) => {
             let invoice = Bolt11Invoice::from_str(bolt11)?;
             let description = invoice.description().to_string();
             let destination_pubkey = invoice.get_payee_pub_key().to_string();
-            (Some(description), destination_pubkey)
+            (
+                Some(description),
+                destination_pubkey,
+                invoice.amount_milli_satoshis(),
+            )
         }
-        None => (None, String::new()),
+        None => (None, String::new(), None),
     };
+    let (amount_msat, fee_msat) = payment_amounts(
+        payment.direction,
+        payment.amount_msat,
+        payment.fee_paid_msat.or(outcome.fee_paid_msat),
+        invoice_amount_msat,
+        lsp_fee_msat,
+    );
 
-    let details = to_payment_details(&payment, local_node_id, bolt11, destination_pubkey)?;
+    let details = to_payment_details(
+        &payment,
+        local_node_id,
+        bolt11,
+        destination_pubkey,
+        custom_tlvs,
+    )?;
     Ok(Payment {
         id: payment.id.to_hex(),
         payment_type: payment.direction.into(),
         payment_time: payment.latest_update_timestamp as i64,
-        amount_msat: payment.amount_msat.unwrap_or_default(),
-        fee_msat: payment.fee_paid_msat.unwrap_or(lsp_fee_msat),
+        amount_msat,
+        fee_msat,
         status: payment.status.into(),
-        error: None,
+        error: outcome
+            .failure_reason
+            .filter(|_| payment.status == ldk_node::payment::PaymentStatus::Failed),
         description,
         details,
         metadata: None,
     })
 }
 
+/// Returns the amount and the fee of a payment, keeping them apart.
+///
+/// For a sent payment the amount is the one received by the payee, i.e. the amount given for
+/// an open amount invoice or the invoice amount, and the fee is the routing fee paid on top.
+/// For a received payment the fee is the one skimmed by the LSP, unless reported otherwise.
+fn payment_amounts(
+    direction: ldk_node::payment::PaymentDirection,
+    amount_msat: Option<u64>,
+    fee_paid_msat: Option<u64>,
+    invoice_amount_msat: Option<u64>,
+    lsp_fee_msat: u64,
+) -> (u64, u64) {
+    match direction {
+        ldk_node::payment::PaymentDirection::Outbound => (
+            amount_msat.or(invoice_amount_msat).unwrap_or_default(),
+            fee_paid_msat.unwrap_or_default(),
+        ),
+        ldk_node::payment::PaymentDirection::Inbound => (
+            amount_msat.unwrap_or_default(),
+            fee_paid_msat.unwrap_or(lsp_fee_msat),
+        ),
+    }
+}
+
+/// Represents the funds that were on the user side of a closed channel as a payment,
+/// which is pending until the funds are spendable on chain.
+pub fn convert_closed_channel(
+    channel_id: String,
+    record: &ChannelRecord,
+    close: &ChannelCloseRecord,
+    is_pending: bool,
+) -> Payment {
+    let (status, state) = match is_pending {
+        true => (PaymentStatus::Pending, ChannelState::PendingClose),
+        false => (PaymentStatus::Complete, ChannelState::Closed),
+    };
+    Payment {
+        id: channel_id,
+        payment_type: PaymentType::ClosedChannel,
+        payment_time: close.closed_at as i64,
+        amount_msat: sat_to_msat(close.amount_sat),
+        fee_msat: 0,
+        status,
+        error: None,
+        description: Some("Closed Channel".to_string()),
+        details: PaymentDetails::ClosedChannel {
+            data: ClosedChannelPaymentDetails {
+                state,
+                funding_txid: record.funding_txid.clone(),
+                short_channel_id: record.short_channel_id.clone(),
+                closing_txid: close.closing_txid.clone(),
+                counterparty_node_id: record.counterparty_node_id.clone(),
+                capacity_sat: record.capacity_sat,
+                is_force_close: close.is_force_close,
+            },
+        },
+        metadata: None,
+    }
+}
+
 fn to_payment_details(
     payment: &ldk_node::payment::PaymentDetails,
     local_node_id: &PublicKey,
     bolt11: Option<String>,
     destination_pubkey: String,
+    custom_tlvs: Option<Vec<TlvEntry>>,
 ) -> Result<PaymentDetails, NodeError> {
     let destination_pubkey = match payment.direction {
         ldk_node::payment::PaymentDirection::Inbound => local_node_id.to_string(),
@@ -124,15 +302,37 @@ fn to_payment_details(
     };
     match &payment.kind {
         ldk_node::payment::PaymentKind::Bolt11 { hash, preimage, .. } => Ok(PaymentDetails::Ln {
-            data: ln_payment_details(hash, preimage, destination_pubkey, false, bolt11),
+            data: ln_payment_details(
+                hash,
+                preimage,
+                destination_pubkey,
+                false,
+                bolt11,
+                custom_tlvs,
+            ),
         }),
         ldk_node::payment::PaymentKind::Bolt11Jit { hash, preimage, .. } => {
-            Ok(PaymentDetails::Ln {
-                data: ln_payment_details(hash, preimage, destination_pubkey, false, bolt11),
-            })
+            let mut data = ln_payment_details(
+                hash,
+                preimage,
+                destination_pubkey,
+                false,
+                bolt11.clone(),
+                custom_tlvs,
+            );
+            // The invoice of a JIT receive is the one that triggered the channel open.
+            data.open_channel_bolt11 = bolt11;
+            Ok(PaymentDetails::Ln { data })
         }
         ldk_node::payment::PaymentKind::Spontaneous { hash, preimage } => Ok(PaymentDetails::Ln {
-            data: ln_payment_details(hash, preimage, destination_pubkey, true, bolt11),
+            data: ln_payment_details(
+                hash,
+                preimage,
+                destination_pubkey,
+                true,
+                bolt11,
+                custom_tlvs,
+            ),
         }),
         other => Err(NodeError::Generic(format!(
             "Unsupported payment kind: {other:?}"
@@ -146,6 +346,7 @@ fn ln_payment_details(
     destination_pubkey: String,
     keysend: bool,
     bolt11: Option<String>,
+    custom_tlvs: Option<Vec<TlvEntry>>,
 ) -> LnPaymentDetails {
     LnPaymentDetails {
         payment_hash: hash.to_hex(),
@@ -153,6 +354,7 @@ fn ln_payment_details(
         payment_preimage: preimage.as_ref().map(Hex::to_hex).unwrap_or_default(),
         keysend,
         bolt11: bolt11.unwrap_or_default(),
+        custom_tlvs,
         ..Default::default()
     }
 }
@@ -190,7 +392,118 @@ fn get_balance(balance: &PendingSweepBalance) -> u64 {
     }
 }
 
-fn get_payment_hash(payment: &ldk_node::payment::PaymentDetails) -> Option<&PaymentHash> {
+pub fn convert_pending_sweep(balance: &PendingSweepBalance) -> PendingSweep {
+    let (channel_id, stage, txid) = match balance {
+        PendingSweepBalance::PendingBroadcast { channel_id, .. } => {
+            (channel_id, PendingSweepStage::PendingBroadcast, None)
+        }
+        PendingSweepBalance::BroadcastAwaitingConfirmation {
+            channel_id,
+            latest_spending_txid,
+            ..
+        } => (
+            channel_id,
+            PendingSweepStage::BroadcastAwaitingConfirmation,
+            Some(latest_spending_txid),
+        ),
+        PendingSweepBalance::AwaitingThresholdConfirmations {
+            channel_id,
+            latest_spending_txid,
+            ..
+        } => (
+            channel_id,
+            PendingSweepStage::AwaitingThresholdConfirmations,
+            Some(latest_spending_txid),
+        ),
+    };
+    PendingSweep {
+        channel_id: channel_id.map(|id| id.0.to_hex()),
+        amount_sat: get_balance(balance),
+        stage,
+        txid: txid.map(|txid| txid.to_string()),
+    }
+}
+
+/// Aggregates the balances of the closed channel with the given id which are not spendable
+/// yet, returning `None` if there are none.
+pub fn pending_close_status(
+    balances: &BalanceDetails,
+    channel_id: &str,
+    block_height: u32,
+) -> Option<PendingCloseStatus> {
+    let claimable = balances.lightning_balances.iter().filter_map(|b| match b {
+        ClaimableAwaitingConfirmations {
+            channel_id: id,
+            amount_satoshis,
+            confirmation_height,
+            ..
+        } if id.0.to_hex() == channel_id => Some((*amount_satoshis, Some(*confirmation_height))),
+        _ => None,
+    });
+    let sweeps = balances
+        .pending_balances_from_channel_closures
+        .iter()
+        .filter_map(|b| {
+            let (id, spendable_height) = match b {
+                PendingSweepBalance::PendingBroadcast { channel_id, .. }
+                | PendingSweepBalance::BroadcastAwaitingConfirmation { channel_id, .. } => {
+                    (channel_id, None)
+                }
+                PendingSweepBalance::AwaitingThresholdConfirmations {
+                    channel_id,
+                    confirmation_height,
+                    ..
+                } => (channel_id, Some(confirmation_height + ANTI_REORG_DELAY - 1)),
+            };
+            id.as_ref()
+                .filter(|id| id.0.to_hex() == channel_id)
+                .map(|_| (get_balance(b), spendable_height))
+        });
+    let balances: Vec<_> = claimable.chain(sweeps).collect();
+    if balances.is_empty() {
+        return None;
+    }
+    let spendable_height = balances
+        .iter()
+        .map(|(_, height)| *height)
+        .collect::<Option<Vec<_>>>()
+        .and_then(|heights| heights.into_iter().max());
+    Some(PendingCloseStatus {
+        channel_id: channel_id.to_string(),
+        amount_sat: balances.iter().map(|(amount, _)| amount).sum(),
+        block_height,
+        spendable_height,
+    })
+}
+
+/// Whether all the funds of the closed channel with the given id were claimed to the on-chain
+/// wallet.
+pub fn is_close_resolved(balances: &BalanceDetails, channel_id: &str, block_height: u32) -> bool {
+    let is_closing = balances.lightning_balances.iter().any(|b| {
+        matches!(b, ClaimableOnChannelClose { channel_id: id, .. } if id.0.to_hex() == channel_id)
+    });
+    !is_closing && pending_close_status(balances, channel_id, block_height).is_none()
+}
+
+/// Sums the fees of the completed payments made from `from_ts` (inclusive) to `to_ts`
+/// (exclusive). The fee of a received payment is the fee of the LSP for opening a channel.
+pub fn fee_summary(payments: &[Payment], from_ts: i64, to_ts: i64) -> FeeSummary {
+    payments
+        .iter()
+        .filter(|p| p.status == PaymentStatus::Complete)
+        .filter(|p| (from_ts..to_ts).contains(&p.payment_time))
+        .fold(FeeSummary::default(), |mut summary, p| {
+            match p.payment_type {
+                PaymentType::Sent => summary.routing_fee_msat += p.fee_msat,
+                PaymentType::Received => summary.lsp_fee_msat += p.fee_msat,
+                PaymentType::ClosedChannel => (),
+            }
+            summary.total_fee_msat += p.fee_msat;
+            summary
+        })
+}
+
+pub fn get_payment_hash(payment: &ldk_node::payment::PaymentDetails) -> Option<&PaymentHash> {
     match &payment.kind {
         ldk_node::payment::PaymentKind::Bolt11 { hash, .. }
         | ldk_node::payment::PaymentKind::Bolt11Jit { hash, .. }
@@ -198,3 +511,207 @@ fn get_payment_hash(payment: &ldk_node::payment::PaymentDetails) -> Option<&Paym
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ldk_node::bitcoin::hashes::Hash;
+    use ldk_node::bitcoin::{BlockHash, Txid};
+    use ldk_node::lightning::ln::types::ChannelId;
+
+    use super::*;
+
+    fn node_state(block_height: u32, channels_balance_msat: u64) -> NodeState {
+        NodeState {
+            block_height,
+            channels_balance_msat,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_node_state_cache() {
+        let cache = NodeStateCache::default();
+        assert_eq!(cache.get(100), None);
+
+        cache.set(cache.generation(), node_state(100, 1_000));
+        assert_eq!(cache.get(100), Some(node_state(100, 1_000)));
+        // A new block makes the cached state stale.
+        assert_eq!(cache.get(101), None);
+
+        cache.on_event(&Event::PaymentReceived {
+            payment_id: None,
+            payment_hash: PaymentHash([0; 32]),
+            amount_msat: 2_000,
+            custom_records: Vec::new(),
+        });
+        assert_eq!(cache.get(100), None);
+        cache.set(cache.generation(), node_state(100, 3_000));
+        assert_eq!(cache.get(100), Some(node_state(100, 3_000)));
+    }
+
+    #[test]
+    fn test_payment_amounts() {
+        use ldk_node::payment::PaymentDirection::{Inbound, Outbound};
+
+        // Open amount invoice, the given amount is paid and the routing fee comes on top.
+        assert_eq!(
+            payment_amounts(Outbound, Some(1_100_000), Some(1_000), None, 0),
+            (1_100_000, 1_000)
+        );
+        assert_eq!(
+            payment_amounts(Outbound, Some(1_100_000), Some(3_500), None, 0),
+            (1_100_000, 3_500)
+        );
+        // The fee is not known until the payment succeeds.
+        assert_eq!(
+            payment_amounts(Outbound, None, None, Some(1_000_000), 0),
+            (1_000_000, 0)
+        );
+        assert_eq!(
+            payment_amounts(Inbound, Some(9_000_000), None, Some(10_000_000), 1_000_000),
+            (9_000_000, 1_000_000)
+        );
+    }
+
+    #[test]
+    fn test_convert_closed_channel() {
+        let record = ChannelRecord {
+            counterparty_node_id: "02".repeat(33),
+            funding_txid: "aa".repeat(32),
+            funding_output_index: 0,
+            short_channel_id: Some("101x1x0".to_string()),
+            capacity_sat: 100_000,
+            close: None,
+        };
+        let close = ChannelCloseRecord {
+            closed_at: 1_700_000_000,
+            amount_sat: 40_000,
+            is_force_close: false,
+            closing_txid: Some("bb".repeat(32)),
+        };
+        let payment = convert_closed_channel("cc".repeat(32), &record, &close, true);
+        assert_eq!(payment.payment_type, PaymentType::ClosedChannel);
+        assert_eq!(payment.status, PaymentStatus::Pending);
+        assert_eq!(payment.amount_msat, 40_000_000);
+        assert_eq!(payment.payment_time, 1_700_000_000);
+        assert_eq!(
+            payment.details,
+            PaymentDetails::ClosedChannel {
+                data: ClosedChannelPaymentDetails {
+                    state: ChannelState::PendingClose,
+                    funding_txid: "aa".repeat(32),
+                    short_channel_id: Some("101x1x0".to_string()),
+                    closing_txid: Some("bb".repeat(32)),
+                    counterparty_node_id: "02".repeat(33),
+                    capacity_sat: 100_000,
+                    is_force_close: false,
+                },
+            }
+        );
+
+        let payment = convert_closed_channel("cc".repeat(32), &record, &close, false);
+        assert_eq!(payment.status, PaymentStatus::Complete);
+        assert!(matches!(
+            payment.details,
+            PaymentDetails::ClosedChannel { data } if data.state == ChannelState::Closed
+        ));
+    }
+
+    #[test]
+    fn test_convert_pending_sweep() {
+        let channel_id = ChannelId([1; 32]);
+        let txid = Txid::from_str(&"aa".repeat(32)).unwrap();
+
+        let sweep = convert_pending_sweep(&PendingSweepBalance::PendingBroadcast {
+            channel_id: None,
+            amount_satoshis: 1_000,
+        });
+        assert_eq!(
+            sweep,
+            PendingSweep {
+                channel_id: None,
+                amount_sat: 1_000,
+                stage: PendingSweepStage::PendingBroadcast,
+                txid: None,
+            }
+        );
+
+        let sweep = convert_pending_sweep(&PendingSweepBalance::AwaitingThresholdConfirmations {
+            channel_id: Some(channel_id),
+            latest_spending_txid: txid,
+            confirmation_hash: BlockHash::all_zeros(),
+            confirmation_height: 100,
+            amount_satoshis: 2_000,
+        });
+        assert_eq!(
+            sweep,
+            PendingSweep {
+                channel_id: Some("01".repeat(32)),
+                amount_sat: 2_000,
+                stage: PendingSweepStage::AwaitingThresholdConfirmations,
+                txid: Some("aa".repeat(32)),
+            }
+        );
+    }
+
+    #[test]
+    fn test_fee_summary() {
+        let payment = |payment_type, status, payment_time, fee_msat| Payment {
+            payment_type,
+            status,
+            payment_time,
+            fee_msat,
+            ..Default::default()
+        };
+        let payments = vec![
+            payment(PaymentType::Sent, PaymentStatus::Complete, 100, 1_000),
+            payment(PaymentType::Sent, PaymentStatus::Complete, 150, 2_000),
+            payment(PaymentType::Received, PaymentStatus::Complete, 199, 30_000),
+            payment(PaymentType::ClosedChannel, PaymentStatus::Complete, 120, 0),
+            // Out of the range.
+            payment(PaymentType::Sent, PaymentStatus::Complete, 99, 4_000),
+            payment(PaymentType::Received, PaymentStatus::Complete, 200, 50_000),
+            // Not completed.
+            payment(PaymentType::Sent, PaymentStatus::Failed, 110, 8_000),
+            payment(PaymentType::Sent, PaymentStatus::Pending, 110, 16_000),
+        ];
+        assert_eq!(
+            fee_summary(&payments, 100, 200),
+            FeeSummary {
+                total_fee_msat: 33_000,
+                routing_fee_msat: 3_000,
+                lsp_fee_msat: 30_000,
+            }
+        );
+        assert_eq!(fee_summary(&payments, 300, 400), FeeSummary::default());
+    }
+
+    #[test]
+    fn test_node_state_cache_concurrent_invalidation() {
+        let cache = NodeStateCache::default();
+        let generation = cache.generation();
+        cache.invalidate();
+        cache.set(generation, node_state(100, 1_000));
+        assert_eq!(cache.get(100), None);
+    }
+
+    #[test]
+    fn test_node_state_cache_sync_warning() {
+        let cache = NodeStateCache::default();
+        let state = NodeState {
+            sync_warning: Some("Failed to sync with the chain".to_string()),
+            ..node_state(100, 1_000)
+        };
+        cache.set(cache.generation(), state);
+        assert_eq!(cache.get(100), None);
+    }
+
+    #[test]
+    fn test_node_state_cache_expiry() {
+        let cache = NodeStateCache::new(Duration::from_millis(10));
+        cache.set(cache.generation(), node_state(100, 1_000));
+        assert_eq!(cache.get(100), Some(node_state(100, 1_000)));
+        std::thread::sleep(Duration::from_millis(20));
+        assert_eq!(cache.get(100), None);
+    }
+}
@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use ldk_node::Node;
+use tokio::sync::broadcast;
+use tokio::time::{interval, Duration};
+
+use crate::chain::ChainService;
+use crate::node_api::ReorgEvent;
+
+const REORG_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Tracks the tip of the chain synced by the node and reports the reorganizations of the
+/// chain as [ReorgEvent]s.
+#[derive(Clone)]
+pub(crate) struct ReorgTracker {
+    events_tx: broadcast::Sender<ReorgEvent>,
+}
+
+impl Default for ReorgTracker {
+    fn default() -> Self {
+        let (events_tx, _) = broadcast::channel(16);
+        Self { events_tx }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Tip {
+    height: u32,
+    hash: String,
+}
+
+impl ReorgTracker {
+    pub fn subscribe(&self) -> broadcast::Receiver<ReorgEvent> {
+        self.events_tx.subscribe()
+    }
+
+    /// Polls the best block of the node until the node stops running.
+    ///
+    /// LDK Node does not report reorganizations, so a tip which is not an ancestor of the
+    /// new one is detected by asking the chain service for the block at the height of the
+    /// previous tip.
+    pub async fn track(&self, node: Arc<Node>, chain_service: Arc<dyn ChainService>) {
+        let mut tip = current_tip(&node);
+        let mut interval = interval(REORG_POLL_INTERVAL);
+        loop {
+            interval.tick().await;
+            if !node.status().is_running {
+                debug!("Node stopped, stopping reorg tracking");
+                return;
+            }
+            let new_tip = current_tip(&node);
+            if new_tip == tip {
+                continue;
+            }
+            let hash_at_old_height = match new_tip.height > tip.height {
+                true => match chain_service.block_hash(tip.height).await {
+                    Ok(hash) => Some(hash),
+                    Err(e) => {
+                        warn!("Failed to get block hash at height {}: {e}", tip.height);
+                        None
+                    }
+                },
+                false => None,
+            };
+            if let Some(event) = detect_reorg(&tip, &new_tip, hash_at_old_height.as_deref()) {
+                info!("Chain reorg detected: {event:?}");
+                // Error here will mean that there are no subscribers.
+                let _ = self.events_tx.send(event);
+            }
+            tip = new_tip;
+        }
+    }
+}
+
+fn current_tip(node: &Node) -> Tip {
+    let best_block = node.status().current_best_block;
+    Tip {
+        height: best_block.height,
+        hash: best_block.block_hash.to_string(),
+    }
+}
+
+/// A new tip at the same or a lower height always replaces the old one. Otherwise the block
+/// now at the height of the old tip, if known, tells whether the old tip was replaced.
+fn detect_reorg(
+    old_tip: &Tip,
+    new_tip: &Tip,
+    hash_at_old_height: Option<&str>,
+) -> Option<ReorgEvent> {
+    let replaced = match new_tip.height > old_tip.height {
+        true => hash_at_old_height.is_some_and(|hash| hash != old_tip.hash),
+        false => new_tip != old_tip,
+    };
+    replaced.then(|| ReorgEvent {
+        old_tip_height: old_tip.height,
+        old_tip_hash: old_tip.hash.clone(),
+        new_tip_height: new_tip.height,
+        new_tip_hash: new_tip.hash.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tip(height: u32, hash: &str) -> Tip {
+        Tip {
+            height,
+            hash: hash.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_detect_reorg() {
+        let old_tip = tip(100, "a");
+
+        // The chain grew on top of the old tip.
+        assert_eq!(detect_reorg(&old_tip, &tip(101, "b"), Some("a")), None);
+        // The block at the old height is unknown.
+        assert_eq!(detect_reorg(&old_tip, &tip(101, "b"), None), None);
+        // Unchanged tip.
+        assert_eq!(detect_reorg(&old_tip, &tip(100, "a"), None), None);
+
+        let expected = |height, hash: &str| ReorgEvent {
+            old_tip_height: 100,
+            old_tip_hash: "a".to_string(),
+            new_tip_height: height,
+            new_tip_hash: hash.to_string(),
+        };
+        // The old tip was replaced by a longer chain.
+        assert_eq!(
+            detect_reorg(&old_tip, &tip(102, "c"), Some("b")),
+            Some(expected(102, "c"))
+        );
+        // The old tip was replaced at the same height.
+        assert_eq!(
+            detect_reorg(&old_tip, &tip(100, "b"), None),
+            Some(expected(100, "b"))
+        );
+        // The chain went back.
+        assert_eq!(
+            detect_reorg(&old_tip, &tip(99, "z"), None),
+            Some(expected(99, "z"))
+        );
+    }
+}
@@ -1,15 +1,26 @@
+use std::str::FromStr;
 use std::sync::Arc;
 
-use ldk_node::lightning::io::ErrorKind;
+use ldk_node::lightning::io::{Error, ErrorKind};
 use ldk_node::lightning::util::persist::KVStoreSync;
+use ldk_node::lightning_invoice::Bolt11Invoice;
+use ldk_node::lightning_types::payment::PaymentPreimage;
 use ldk_node::DynStore;
 
+use crate::ldk::store::{PreimageRecord, BOLT11_NS, BREEZ_NS, PREIMAGES_NS};
 use crate::node_api::NodeError;
 
 const PRIMARY_NS: &str = "breez";
 const SECONDARY_NS: &str = "restore_state";
 const KEY_INITIALIZED: &str = "initialized";
 const VALUE_INITIALIZED: &[u8] = b"1";
+const KEY_VERSION: &str = "version";
+
+/// The version of the persisted layout, bump it when adding a migration.
+const CURRENT_VERSION: u32 = 2;
+
+const LEGACY_PREIMAGES_PRIMARY_NS: &str = "preimages";
+const LEGACY_PREIMAGES_SECONDARY_NS: &str = "";
 
 /// Tracks whether an LDK node instance has persisted state using the configured KV store.
 pub(crate) struct RestoreStateTracker {
@@ -46,4 +57,220 @@ impl RestoreStateTracker {
         )
         .map_err(|err| NodeError::Generic(format!("Failed to write restore state marker: {err}")))
     }
+
+    /// Brings the persisted layout to the current version.
+    ///
+    /// A freshly initialized store is marked as being at the current version,
+    /// since there is no data to migrate.
+    pub(crate) fn migrate(&self, was_initialized: bool) -> Result<(), NodeError> {
+        let version = match was_initialized {
+            true => self.version()?,
+            false => CURRENT_VERSION,
+        };
+        if version < 1 {
+            info!("Migrating preimages from the legacy layout");
+            migrate_legacy_preimages(self.kv_store.as_ref()).map_err(|err| {
+                NodeError::Generic(format!("Failed to migrate legacy preimages: {err}"))
+            })?;
+        }
+        if version < 2 {
+            info!("Migrating raw preimages to preimage records");
+            migrate_raw_preimages(self.kv_store.as_ref()).map_err(|err| {
+                NodeError::Generic(format!("Failed to migrate raw preimages: {err}"))
+            })?;
+        }
+        if version != CURRENT_VERSION {
+            self.set_version(CURRENT_VERSION)?;
+        }
+        Ok(())
+    }
+
+    fn version(&self) -> Result<u32, NodeError> {
+        match KVStoreSync::read(
+            self.kv_store.as_ref(),
+            PRIMARY_NS,
+            SECONDARY_NS,
+            KEY_VERSION,
+        ) {
+            Ok(value) => String::from_utf8_lossy(&value).parse().map_err(|err| {
+                NodeError::Generic(format!("Failed to parse restore state version: {err}"))
+            }),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(0),
+            Err(err) => Err(NodeError::Generic(format!(
+                "Failed to read restore state version: {err}"
+            ))),
+        }
+    }
+
+    fn set_version(&self, version: u32) -> Result<(), NodeError> {
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            PRIMARY_NS,
+            SECONDARY_NS,
+            KEY_VERSION,
+            version.to_string().into_bytes(),
+        )
+        .map_err(|err| NodeError::Generic(format!("Failed to write restore state version: {err}")))
+    }
+}
+
+fn migrate_legacy_preimages(kv_store: &DynStore) -> Result<(), Error> {
+    let keys = KVStoreSync::list(
+        kv_store,
+        LEGACY_PREIMAGES_PRIMARY_NS,
+        LEGACY_PREIMAGES_SECONDARY_NS,
+    )?;
+    for key in keys {
+        let preimage = KVStoreSync::read(
+            kv_store,
+            LEGACY_PREIMAGES_PRIMARY_NS,
+            LEGACY_PREIMAGES_SECONDARY_NS,
+            &key,
+        )?;
+        KVStoreSync::write(kv_store, BREEZ_NS, PREIMAGES_NS, &key, preimage)?;
+        KVStoreSync::remove(
+            kv_store,
+            LEGACY_PREIMAGES_PRIMARY_NS,
+            LEGACY_PREIMAGES_SECONDARY_NS,
+            &key,
+            false,
+        )?;
+    }
+    Ok(())
+}
+
+/// Rewrites preimages stored as raw bytes as [PreimageRecord]s, filling in the invoice
+/// details from the stored invoice when there is one.
+///
+/// The amount is left unset, since the opening fee of JIT invoices is not known.
+fn migrate_raw_preimages(kv_store: &DynStore) -> Result<(), Error> {
+    for key in KVStoreSync::list(kv_store, BREEZ_NS, PREIMAGES_NS)? {
+        let value = KVStoreSync::read(kv_store, BREEZ_NS, PREIMAGES_NS, &key)?;
+        let Ok(preimage) = value.as_slice().try_into() else {
+            continue;
+        };
+        let mut record = PreimageRecord::new(PaymentPreimage(preimage));
+        match KVStoreSync::read(kv_store, BREEZ_NS, BOLT11_NS, &key) {
+            Ok(bolt11) => match Bolt11Invoice::from_str(&String::from_utf8_lossy(&bolt11)) {
+                Ok(invoice) => {
+                    record.expiry_ts = invoice.expires_at().map(|expires_at| expires_at.as_secs());
+                    record.bolt11_ref = Some(key.clone());
+                }
+                // The preimage is still needed to claim the payment, so it is migrated anyway.
+                Err(e) => warn!("Migrating preimage {key} without its unparsable invoice: {e}"),
+            },
+            Err(err) if err.kind() == ErrorKind::NotFound => (),
+            Err(err) => return Err(err),
+        }
+        KVStoreSync::write(kv_store, BREEZ_NS, PREIMAGES_NS, &key, record.encode()?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::ldk::store::{mock_mirroring_store, Store};
+    use crate::ldk::utils::Hex;
+    use crate::test_utils::create_invoice;
+    use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage};
+
+    async fn create_kv_store() -> Arc<DynStore> {
+        Arc::new(mock_mirroring_store().await)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_migrate_legacy_preimages() {
+        let kv_store = create_kv_store().await;
+        let preimage = PaymentPreimage([1; 32]);
+        let hash: PaymentHash = preimage.into();
+        KVStoreSync::write(
+            kv_store.as_ref(),
+            LEGACY_PREIMAGES_PRIMARY_NS,
+            LEGACY_PREIMAGES_SECONDARY_NS,
+            &hash.to_hex(),
+            preimage.0.to_vec(),
+        )
+        .unwrap();
+
+        let tracker = RestoreStateTracker::new(Arc::clone(&kv_store));
+        tracker.mark_initialized().unwrap();
+        assert_eq!(tracker.version().unwrap(), 0);
+        tracker.migrate(true).unwrap();
+        assert_eq!(tracker.version().unwrap(), CURRENT_VERSION);
+
+        let store = Store::new(Arc::clone(&kv_store));
+        assert_eq!(store.load_preimage(&hash).unwrap(), preimage);
+        let legacy_keys = KVStoreSync::list(
+            kv_store.as_ref(),
+            LEGACY_PREIMAGES_PRIMARY_NS,
+            LEGACY_PREIMAGES_SECONDARY_NS,
+        )
+        .unwrap();
+        assert!(legacy_keys.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_migrate_raw_preimages() {
+        let kv_store = create_kv_store().await;
+        let store = Store::new(Arc::clone(&kv_store));
+        let preimage = PaymentPreimage([1; 32]);
+        let hash: PaymentHash = preimage.into();
+        let invoice = create_invoice("test".to_string(), 1_000, vec![], Some(preimage.0.to_vec()));
+        store.store_bolt11(&hash.to_hex(), invoice.bolt11).unwrap();
+        let unpaid_preimage = PaymentPreimage([2; 32]);
+        let unpaid_hash: PaymentHash = unpaid_preimage.into();
+        let unparsable_preimage = PaymentPreimage([3; 32]);
+        let unparsable_hash: PaymentHash = unparsable_preimage.into();
+        store
+            .store_bolt11(&unparsable_hash.to_hex(), "lnbc1invalid".to_string())
+            .unwrap();
+        let preimages = [
+            (hash, preimage),
+            (unpaid_hash, unpaid_preimage),
+            (unparsable_hash, unparsable_preimage),
+        ];
+        for (hash, preimage) in preimages {
+            KVStoreSync::write(
+                kv_store.as_ref(),
+                BREEZ_NS,
+                PREIMAGES_NS,
+                &hash.to_hex(),
+                preimage.0.to_vec(),
+            )
+            .unwrap();
+        }
+
+        let tracker = RestoreStateTracker::new(Arc::clone(&kv_store));
+        tracker.mark_initialized().unwrap();
+        tracker.set_version(1).unwrap();
+        tracker.migrate(true).unwrap();
+        assert_eq!(tracker.version().unwrap(), CURRENT_VERSION);
+
+        let record = store.load_preimage_record(&hash).unwrap();
+        assert_eq!(record.preimage(), preimage);
+        assert_eq!(record.amount_msat, None);
+        assert_eq!(record.expiry_ts, Some(invoice.timestamp + 3600));
+        assert_eq!(record.bolt11_ref, Some(hash.to_hex()));
+        let value =
+            KVStoreSync::read(kv_store.as_ref(), BREEZ_NS, PREIMAGES_NS, &hash.to_hex()).unwrap();
+        assert_eq!(PreimageRecord::decode(&value).unwrap(), record);
+        assert_ne!(value.len(), 32);
+
+        let record = store.load_preimage_record(&unpaid_hash).unwrap();
+        assert_eq!(record, PreimageRecord::new(unpaid_preimage));
+        // An invoice which cannot be parsed does not hold back the migration of its preimage.
+        let record = store.load_preimage_record(&unparsable_hash).unwrap();
+        assert_eq!(record, PreimageRecord::new(unparsable_preimage));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_migrate_fresh_store() {
+        let kv_store = create_kv_store().await;
+        let tracker = RestoreStateTracker::new(kv_store);
+        tracker.migrate(false).unwrap();
+        assert_eq!(tracker.version().unwrap(), CURRENT_VERSION);
+    }
 }
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use ldk_node::Event;
+use tokio::sync::broadcast;
+
+use crate::ldk::utils::Hex;
+use crate::node_api::SpliceEvent;
+
+/// Tracks the splices of the channels of the node and reports their progress
+/// as [SpliceEvent]s.
+#[derive(Clone)]
+pub(crate) struct SpliceTracker {
+    /// Amounts of the splices in progress by channel id, positive for splice-ins and
+    /// negative for splice-outs. Splices initiated by the counterparty have a zero amount.
+    amounts: Arc<Mutex<HashMap<String, i64>>>,
+    events_tx: broadcast::Sender<SpliceEvent>,
+}
+
+impl Default for SpliceTracker {
+    fn default() -> Self {
+        let (events_tx, _) = broadcast::channel(16);
+        Self {
+            amounts: Default::default(),
+            events_tx,
+        }
+    }
+}
+
+impl SpliceTracker {
+    pub fn subscribe(&self) -> broadcast::Receiver<SpliceEvent> {
+        self.events_tx.subscribe()
+    }
+
+    pub fn on_splice_initiated(&self, channel_id: &str, amount_sat: i64) {
+        self.amounts
+            .lock()
+            .unwrap()
+            .insert(channel_id.to_string(), amount_sat);
+    }
+
+    pub fn on_event(&self, event: &Event) {
+        if let Some(splice_event) = self.to_splice_event(event) {
+            // Error here will mean that there are no subscribers.
+            let _ = self.events_tx.send(splice_event);
+        }
+    }
+
+    fn to_splice_event(&self, event: &Event) -> Option<SpliceEvent> {
+        let mut amounts = self.amounts.lock().unwrap();
+        match event {
+            Event::SplicePending {
+                channel_id,
+                new_funding_txo,
+                ..
+            } => {
+                let channel_id = channel_id.0.to_hex();
+                let amount_sat = *amounts.entry(channel_id.clone()).or_default();
+                Some(SpliceEvent::Pending {
+                    channel_id,
+                    amount_sat,
+                    funding_txid: new_funding_txo.txid.to_string(),
+                })
+            }
+            Event::SpliceFailed { channel_id, .. } => {
+                let channel_id = channel_id.0.to_hex();
+                let amount_sat = amounts.remove(&channel_id).unwrap_or_default();
+                Some(SpliceEvent::Failed {
+                    channel_id,
+                    amount_sat,
+                    reason: "Splice negotiation with the counterparty failed".to_string(),
+                })
+            }
+            // The channel becomes ready again once the splice transaction is locked.
+            Event::ChannelReady { channel_id, .. } => {
+                let channel_id = channel_id.0.to_hex();
+                let amount_sat = amounts.remove(&channel_id)?;
+                Some(SpliceEvent::Completed {
+                    channel_id,
+                    amount_sat,
+                })
+            }
+            _ => None,
+        }
+    }
+}
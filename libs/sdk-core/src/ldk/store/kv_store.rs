@@ -1,54 +1,263 @@
 use core::convert::TryInto;
-use std::sync::Arc;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 
 use bitcoin::io::{Error, ErrorKind};
 use ldk_node::lightning::util::persist::KVStoreSync;
 use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage};
 use ldk_node::DynStore;
+use serde::{Deserialize, Serialize};
 
-use crate::ldk::utils::Hex;
+use crate::ldk::utils::{EntropySource, Hex};
+use crate::TlvEntry;
 
 pub(crate) const BREEZ_NS: &str = "breez";
+pub(crate) const APP_DATA_NS: &str = "app_data";
 pub(crate) const BOLT11_NS: &str = "bolt11";
+pub(crate) const CHANNELS_NS: &str = "channels";
+pub(crate) const CHANNEL_CLOSE_TO_ADDRESSES_NS: &str = "channel_close_to_addresses";
+pub(crate) const CUSTOM_TLVS_NS: &str = "custom_tlvs";
+pub(crate) const OUTSTANDING_INVOICES_NS: &str = "outstanding_invoices";
+pub(crate) const PAYMENT_OUTCOMES_NS: &str = "payment_outcomes";
 pub(crate) const PREIMAGES_NS: &str = "preimages";
+pub(crate) const PRUNED_PAYMENTS_NS: &str = "pruned_payments";
+pub(crate) const REGISTERED_PAYMENTS_NS: &str = "registered_payments";
+
+/// How many random preimages are tried for a new invoice before giving up.
+const NEW_PREIMAGE_ATTEMPTS: usize = 3;
 
 pub(crate) type KVStore = Arc<DynStore>;
 
+/// The preimage of an invoice created by the node, along with the details needed to
+/// validate a payment before claiming it.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PreimageRecord {
+    pub preimage: [u8; 32],
+    /// The minimum amount to be received, net of the LSP fee for JIT invoices.
+    pub amount_msat: Option<u64>,
+    /// Seconds since the epoch after which the invoice is expired.
+    pub expiry_ts: Option<u64>,
+    /// The key of the invoice in the [BOLT11_NS] namespace.
+    pub bolt11_ref: Option<String>,
+}
+
+impl PreimageRecord {
+    pub(crate) fn new(preimage: PaymentPreimage) -> Self {
+        Self {
+            preimage: preimage.0,
+            amount_msat: None,
+            expiry_ts: None,
+            bolt11_ref: None,
+        }
+    }
+
+    pub(crate) fn preimage(&self) -> PaymentPreimage {
+        PaymentPreimage(self.preimage)
+    }
+
+    pub(crate) fn encode(&self) -> Result<Vec<u8>, Error> {
+        serde_json::to_vec(self).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+    }
+
+    /// Decodes a record, accepting the legacy format of a raw preimage as well.
+    pub(crate) fn decode(value: &[u8]) -> Result<Self, Error> {
+        match value.try_into() {
+            Ok(preimage) => Ok(Self::new(PaymentPreimage(preimage))),
+            Err(_) => {
+                serde_json::from_slice(value).map_err(|e| Error::new(ErrorKind::InvalidData, e))
+            }
+        }
+    }
+
+    /// Checks that a payment of `amount_msat` arriving at `now_ts` can be claimed.
+    pub(crate) fn check_claimable(&self, amount_msat: u64, now_ts: u64) -> Result<(), String> {
+        if let Some(expected_amount_msat) = self.amount_msat {
+            if amount_msat < expected_amount_msat {
+                return Err(format!(
+                    "Claimable amount {amount_msat} msat is less than the expected {expected_amount_msat} msat"
+                ));
+            }
+        }
+        if let Some(expiry_ts) = self.expiry_ts {
+            if now_ts > expiry_ts {
+                return Err(format!("Invoice expired at {expiry_ts}"));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The final state of a sent payment, as reported by the events of the node.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct PaymentOutcome {
+    pub fee_paid_msat: Option<u64>,
+    pub failure_reason: Option<String>,
+}
+
+/// The details of a channel which are no longer known to the node once it is closed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ChannelRecord {
+    pub counterparty_node_id: String,
+    pub funding_txid: String,
+    pub funding_output_index: u32,
+    pub short_channel_id: Option<String>,
+    pub capacity_sat: u64,
+    pub close: Option<ChannelCloseRecord>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub(crate) struct ChannelCloseRecord {
+    /// Seconds since the epoch at which the channel was closed.
+    pub closed_at: u64,
+    /// The funds on the user side of the channel at the time it was closed.
+    pub amount_sat: u64,
+    pub is_force_close: bool,
+    /// Only known once the closing transaction is found on chain.
+    pub closing_txid: Option<String>,
+}
+
 #[derive(Clone)]
 pub(crate) struct Store {
     kv_store: KVStore,
+    /// Serializes the check and write of new preimages.
+    preimages_lock: Arc<Mutex<()>>,
 }
 
 impl Store {
     pub(crate) fn new(kv_store: KVStore) -> Self {
-        Self { kv_store }
+        Self {
+            kv_store,
+            preimages_lock: Default::default(),
+        }
+    }
+
+    /// Stores a record for a new invoice, unless a preimage is already stored for its hash.
+    ///
+    /// Returns whether the record was stored.
+    pub(crate) fn insert_preimage(
+        &self,
+        hash: &PaymentHash,
+        record: &PreimageRecord,
+    ) -> Result<bool, Error> {
+        let _guard = self.preimages_lock.lock().unwrap();
+        let key = hash.to_hex();
+        match KVStoreSync::read(self.kv_store.as_ref(), BREEZ_NS, PREIMAGES_NS, &key) {
+            Ok(_) => return Ok(false),
+            Err(e) if e.kind() == ErrorKind::NotFound => (),
+            Err(e) => return Err(e),
+        }
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            PREIMAGES_NS,
+            &key,
+            record.encode()?,
+        )?;
+        Ok(true)
+    }
+
+    /// Reserves the preimage of a new invoice, either the given one or one drawn from
+    /// `entropy_source`.
+    ///
+    /// A random preimage whose hash is already taken is regenerated a few times before
+    /// failing, a given one fails right away.
+    pub(crate) fn reserve_new_preimage(
+        &self,
+        preimage: Option<PaymentPreimage>,
+        entropy_source: &dyn EntropySource,
+    ) -> Result<PaymentPreimage, Error> {
+        let attempts = match preimage {
+            Some(_) => 1,
+            None => NEW_PREIMAGE_ATTEMPTS,
+        };
+        for _ in 0..attempts {
+            let preimage =
+                preimage.unwrap_or_else(|| PaymentPreimage(entropy_source.random_bytes()));
+            if self.insert_preimage(&preimage.into(), &PreimageRecord::new(preimage))? {
+                return Ok(preimage);
+            }
+        }
+        Err(Error::new(
+            ErrorKind::AlreadyExists,
+            "A preimage is already stored for the payment hash",
+        ))
     }
 
     pub(crate) fn store_preimage(
         &self,
         hash: &PaymentHash,
-        preimage: &PaymentPreimage,
+        record: &PreimageRecord,
     ) -> Result<(), Error> {
         KVStoreSync::write(
             self.kv_store.as_ref(),
             BREEZ_NS,
             PREIMAGES_NS,
             &hash.to_hex(),
-            preimage.0.to_vec(),
+            record.encode()?,
         )
     }
 
-    pub(crate) fn load_preimage(&self, hash: &PaymentHash) -> Result<PaymentPreimage, Error> {
-        let preimage = KVStoreSync::read(
+    pub(crate) fn load_preimage_record(&self, hash: &PaymentHash) -> Result<PreimageRecord, Error> {
+        let value = KVStoreSync::read(
             self.kv_store.as_ref(),
             BREEZ_NS,
             PREIMAGES_NS,
             &hash.to_hex(),
         )?;
-        match preimage.as_slice().try_into() {
-            Ok(preimage) => Ok(PaymentPreimage(preimage)),
-            Err(err) => Err(Error::new(ErrorKind::InvalidData, err)),
-        }
+        PreimageRecord::decode(&value)
+    }
+
+    pub(crate) fn load_preimage(&self, hash: &PaymentHash) -> Result<PaymentPreimage, Error> {
+        Ok(self.load_preimage_record(hash)?.preimage())
+    }
+
+    pub(crate) fn remove_preimage(&self, hash: &PaymentHash) -> Result<(), Error> {
+        KVStoreSync::remove(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            PREIMAGES_NS,
+            &hash.to_hex(),
+            false,
+        )
+    }
+
+    /// Adds an invoice created by the node to the index of invoices awaiting payment.
+    pub(crate) fn store_outstanding_invoice(&self, hash: &PaymentHash) -> Result<(), Error> {
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            OUTSTANDING_INVOICES_NS,
+            &hash.to_hex(),
+            Vec::new(),
+        )
+    }
+
+    pub(crate) fn list_outstanding_invoices(&self) -> Result<Vec<PaymentHash>, Error> {
+        let keys = KVStoreSync::list(self.kv_store.as_ref(), BREEZ_NS, OUTSTANDING_INVOICES_NS)?;
+        keys.into_iter()
+            .map(|key| {
+                let hash: [u8; 32] = hex::decode(&key)
+                    .ok()
+                    .and_then(|hash| hash.try_into().ok())
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidData,
+                            format!("Invalid outstanding invoice key {key}"),
+                        )
+                    })?;
+                Ok(PaymentHash(hash))
+            })
+            .collect()
+    }
+
+    pub(crate) fn remove_outstanding_invoice(&self, hash: &PaymentHash) -> Result<(), Error> {
+        KVStoreSync::remove(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            OUTSTANDING_INVOICES_NS,
+            &hash.to_hex(),
+            false,
+        )
     }
 
     pub(crate) fn store_bolt11(&self, hash: &str, bolt11: String) -> Result<(), Error> {
@@ -68,4 +277,379 @@ impl Store {
             Err(e) => Err(e),
         }
     }
+
+    pub(crate) fn remove_bolt11(&self, hash: &PaymentHash) -> Result<(), Error> {
+        KVStoreSync::remove(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            BOLT11_NS,
+            &hash.to_hex(),
+            false,
+        )
+    }
+
+    pub(crate) fn store_custom_tlvs(
+        &self,
+        hash: &PaymentHash,
+        custom_tlvs: &[TlvEntry],
+    ) -> Result<(), Error> {
+        let value =
+            serde_json::to_vec(custom_tlvs).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            CUSTOM_TLVS_NS,
+            &hash.to_hex(),
+            value,
+        )
+    }
+
+    pub(crate) fn load_custom_tlvs(
+        &self,
+        hash: &PaymentHash,
+    ) -> Result<Option<Vec<TlvEntry>>, Error> {
+        match KVStoreSync::read(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            CUSTOM_TLVS_NS,
+            &hash.to_hex(),
+        ) {
+            Ok(value) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) fn remove_custom_tlvs(&self, hash: &PaymentHash) -> Result<(), Error> {
+        KVStoreSync::remove(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            CUSTOM_TLVS_NS,
+            &hash.to_hex(),
+            false,
+        )
+    }
+
+    pub(crate) fn store_payment_outcome(
+        &self,
+        payment_id: &str,
+        outcome: &PaymentOutcome,
+    ) -> Result<(), Error> {
+        let value =
+            serde_json::to_vec(outcome).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            PAYMENT_OUTCOMES_NS,
+            payment_id,
+            value,
+        )
+    }
+
+    pub(crate) fn load_payment_outcome(
+        &self,
+        payment_id: &str,
+    ) -> Result<Option<PaymentOutcome>, Error> {
+        match KVStoreSync::read(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            PAYMENT_OUTCOMES_NS,
+            payment_id,
+        ) {
+            Ok(value) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) fn remove_payment_outcome(&self, payment_id: &str) -> Result<(), Error> {
+        KVStoreSync::remove(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            PAYMENT_OUTCOMES_NS,
+            payment_id,
+            false,
+        )
+    }
+
+    pub(crate) fn store_pruned_payment(&self, payment_id: &str) -> Result<(), Error> {
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            PRUNED_PAYMENTS_NS,
+            payment_id,
+            Vec::new(),
+        )
+    }
+
+    pub(crate) fn list_pruned_payments(&self) -> Result<HashSet<String>, Error> {
+        let payment_ids = KVStoreSync::list(self.kv_store.as_ref(), BREEZ_NS, PRUNED_PAYMENTS_NS)?;
+        Ok(payment_ids.into_iter().collect())
+    }
+
+    /// Stores the encoded reply of the LSP to the registration of the payment with the given
+    /// hash.
+    pub(crate) fn store_payment_registration(
+        &self,
+        hash: &str,
+        reply: Vec<u8>,
+    ) -> Result<(), Error> {
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            REGISTERED_PAYMENTS_NS,
+            hash,
+            reply,
+        )
+    }
+
+    pub(crate) fn load_payment_registration(&self, hash: &str) -> Result<Option<Vec<u8>>, Error> {
+        match KVStoreSync::read(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            REGISTERED_PAYMENTS_NS,
+            hash,
+        ) {
+            Ok(reply) => Ok(Some(reply)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) fn remove_payment_registration(&self, hash: &str) -> Result<(), Error> {
+        KVStoreSync::remove(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            REGISTERED_PAYMENTS_NS,
+            hash,
+            false,
+        )
+    }
+
+    /// Stores data of the app, which is synced to remote like the node state.
+    pub(crate) fn store_app_data(&self, key: &str, value: Vec<u8>) -> Result<(), Error> {
+        KVStoreSync::write(self.kv_store.as_ref(), BREEZ_NS, APP_DATA_NS, key, value)
+    }
+
+    pub(crate) fn load_app_data(&self, key: &str) -> Result<Option<Vec<u8>>, Error> {
+        match KVStoreSync::read(self.kv_store.as_ref(), BREEZ_NS, APP_DATA_NS, key) {
+            Ok(value) => Ok(Some(value)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub(crate) fn remove_app_data(&self, key: &str) -> Result<(), Error> {
+        KVStoreSync::remove(self.kv_store.as_ref(), BREEZ_NS, APP_DATA_NS, key, false)
+    }
+
+    pub(crate) fn store_channel(
+        &self,
+        channel_id: &str,
+        record: &ChannelRecord,
+    ) -> Result<(), Error> {
+        let value =
+            serde_json::to_vec(record).map_err(|e| Error::new(ErrorKind::InvalidData, e))?;
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            CHANNELS_NS,
+            channel_id,
+            value,
+        )
+    }
+
+    pub(crate) fn load_channel(&self, channel_id: &str) -> Result<Option<ChannelRecord>, Error> {
+        match KVStoreSync::read(self.kv_store.as_ref(), BREEZ_NS, CHANNELS_NS, channel_id) {
+            Ok(value) => serde_json::from_slice(&value)
+                .map(Some)
+                .map_err(|e| Error::new(ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Returns the records of the closed channels by channel id.
+    pub(crate) fn list_closed_channels(&self) -> Result<Vec<(String, ChannelRecord)>, Error> {
+        let mut channels = Vec::new();
+        for channel_id in KVStoreSync::list(self.kv_store.as_ref(), BREEZ_NS, CHANNELS_NS)? {
+            if let Some(record) = self.load_channel(&channel_id)? {
+                if record.close.is_some() {
+                    channels.push((channel_id, record));
+                }
+            }
+        }
+        Ok(channels)
+    }
+
+    pub(crate) fn store_channel_close_to_address(
+        &self,
+        channel_id: &str,
+        address: &str,
+    ) -> Result<(), Error> {
+        KVStoreSync::write(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            CHANNEL_CLOSE_TO_ADDRESSES_NS,
+            channel_id,
+            address.as_bytes().to_vec(),
+        )
+    }
+
+    /// Returns the close-to addresses of the channels by channel id.
+    pub(crate) fn list_channel_close_to_addresses(&self) -> Result<Vec<(String, String)>, Error> {
+        let mut addresses = Vec::new();
+        for channel_id in KVStoreSync::list(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            CHANNEL_CLOSE_TO_ADDRESSES_NS,
+        )? {
+            let address = KVStoreSync::read(
+                self.kv_store.as_ref(),
+                BREEZ_NS,
+                CHANNEL_CLOSE_TO_ADDRESSES_NS,
+                &channel_id,
+            )?;
+            addresses.push((channel_id, String::from_utf8_lossy(&address).into_owned()));
+        }
+        Ok(addresses)
+    }
+
+    pub(crate) fn remove_channel_close_to_address(&self, channel_id: &str) -> Result<(), Error> {
+        KVStoreSync::remove(
+            self.kv_store.as_ref(),
+            BREEZ_NS,
+            CHANNEL_CLOSE_TO_ADDRESSES_NS,
+            channel_id,
+            false,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ldk::store::mock_mirroring_store;
+    use crate::ldk::utils::ThreadRngEntropySource;
+
+    struct FixedEntropySource([u8; 32]);
+
+    impl EntropySource for FixedEntropySource {
+        fn random_bytes(&self) -> [u8; 32] {
+            self.0
+        }
+    }
+
+    async fn create_store() -> Store {
+        Store::new(Arc::new(mock_mirroring_store().await))
+    }
+
+    #[test]
+    fn test_preimage_record_roundtrip() {
+        let record = PreimageRecord {
+            amount_msat: Some(1_000),
+            expiry_ts: Some(1_700_000_000),
+            bolt11_ref: Some("hash".to_string()),
+            ..PreimageRecord::new(PaymentPreimage([1; 32]))
+        };
+        let decoded = PreimageRecord::decode(&record.encode().unwrap()).unwrap();
+        assert_eq!(decoded, record);
+
+        // Legacy raw preimages are decoded as records without details.
+        let decoded = PreimageRecord::decode(&[2; 32]).unwrap();
+        assert_eq!(decoded, PreimageRecord::new(PaymentPreimage([2; 32])));
+
+        assert!(PreimageRecord::decode(&[3; 16]).is_err());
+    }
+
+    #[test]
+    fn test_preimage_record_check_claimable() {
+        let record = PreimageRecord {
+            amount_msat: Some(1_000),
+            expiry_ts: Some(100),
+            ..PreimageRecord::new(PaymentPreimage([1; 32]))
+        };
+        assert!(record.check_claimable(1_000, 100).is_ok());
+        assert!(record.check_claimable(2_000, 50).is_ok());
+        assert!(record.check_claimable(999, 50).is_err());
+        assert!(record.check_claimable(1_000, 101).is_err());
+
+        let record = PreimageRecord::new(PaymentPreimage([1; 32]));
+        assert!(record.check_claimable(1, u64::MAX).is_ok());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reserve_new_preimage_concurrently() {
+        let store = create_store().await;
+        let tasks: Vec<_> = (0..100)
+            .map(|_| {
+                let store = store.clone();
+                tokio::task::spawn_blocking(move || {
+                    store.reserve_new_preimage(None, &ThreadRngEntropySource)
+                })
+            })
+            .collect();
+
+        let mut preimages = HashSet::new();
+        for task in tasks {
+            let preimage = task.await.unwrap().unwrap();
+            assert!(preimages.insert(preimage.0));
+        }
+        for preimage in preimages {
+            let preimage = PaymentPreimage(preimage);
+            assert_eq!(store.load_preimage(&preimage.into()).unwrap(), preimage);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reserve_new_preimage_deterministic() {
+        let store = create_store().await;
+        let entropy_source = FixedEntropySource([7; 32]);
+        let preimage = store.reserve_new_preimage(None, &entropy_source).unwrap();
+        assert_eq!(preimage, PaymentPreimage([7; 32]));
+        let hash: PaymentHash = preimage.into();
+        assert_eq!(
+            hash.to_hex(),
+            "4bb06f8e4e3a7715d201d573d0aa423762e55dabd61a2c02278fa56cc6d294e0"
+        );
+
+        // Every attempt draws the same preimage, whose hash is taken.
+        let err = store
+            .reserve_new_preimage(None, &entropy_source)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reserve_new_preimage_collision() {
+        let store = create_store().await;
+        let preimage = PaymentPreimage([1; 32]);
+        let hash: PaymentHash = preimage.into();
+        assert_eq!(
+            store
+                .reserve_new_preimage(Some(preimage), &ThreadRngEntropySource)
+                .unwrap(),
+            preimage
+        );
+
+        let err = store
+            .reserve_new_preimage(Some(preimage), &ThreadRngEntropySource)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+
+        // The existing record is left untouched.
+        let record = PreimageRecord {
+            amount_msat: Some(1_000),
+            ..PreimageRecord::new(preimage)
+        };
+        assert!(!store.insert_preimage(&hash, &record).unwrap());
+        assert_eq!(
+            store.load_preimage_record(&hash).unwrap(),
+            PreimageRecord::new(preimage)
+        );
+    }
 }
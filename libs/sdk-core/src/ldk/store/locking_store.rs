@@ -21,20 +21,20 @@ struct VersionedTimeLock {
 ///
 /// The locking mechanism works by:
 /// 1. Acquiring a lock when the store is created
-/// 2. Refreshing the lock periodically to maintain ownership
+/// 2. Refreshing the lock halfway through its duration to maintain ownership
 /// 3. Releasing the lock when explicitly unlocked
 ///
 /// If another instance already holds the lock, attempting to create a new `LockingStore`
-/// will result in a `Conflict` error.
+/// will result in a `Locked` error. A lock left behind by a crashed instance is detected
+/// by its missed refresh and taken over once it expires.
 pub struct LockingStore<S: VersionedStore + Send + Sync> {
     inner: S,
     versioned_tl: Mutex<VersionedTimeLock>,
+    lock_duration: Duration,
 }
 
 impl<S: VersionedStore + Send + Sync> LockingStore<S> {
-    const KEY: &str = "lock";
-    const LOCK_DURATION: Duration = Duration::from_secs(60);
-    const REFRESH_WINDOW: Duration = Duration::from_secs(30);
+    pub(crate) const KEY: &str = "lock";
 
     /// Creates a new `LockingStore` and attempts to acquire the distributed lock.
     ///
@@ -42,26 +42,58 @@ impl<S: VersionedStore + Send + Sync> LockingStore<S> {
     /// 1. Check if a lock already exists in the underlying store
     /// 2. If no lock exists, create one for this instance
     /// 3. If a lock exists, verify it's not held by another instance
-    /// 4. If the lock is held by another instance, return a `Conflict` error
-    pub async fn new(instance_id: String, store: S) -> Result<(Self, PreviousHolder), Error> {
-        let (lock_data, version) = store.get(Self::KEY.to_string()).await?.unwrap_or_default();
-        let lock_data = LockData::decode(&lock_data)
-            .map_err(|e| Error::Internal(format!("Failed to decode lock_data: {e:?}")))?;
-        let (tl, previous_holder) = TimeLock::new(Self::LOCK_DURATION, instance_id, lock_data)
-            .map_err(|LockedBy(instance_id)| {
-                Error::Conflict(format!("Remote lock aquired by `{instance_id}`"))
-            })?;
+    /// 4. If the lock is held by another instance which missed its refresh, wait for the
+    ///    lock to expire and take it over, unless the lock was refreshed in the meantime
+    /// 5. If the lock is held by another live instance, return a `Locked` error
+    pub async fn new(
+        instance_id: String,
+        lock_duration: Duration,
+        store: S,
+    ) -> Result<(Self, PreviousHolder), Error> {
+        let (lock_data, version) = Self::get_lock_data(&store).await?;
+        let is_stale = lock_data.is_stale(lock_duration / 2);
+        let expires_in = lock_data.expires_in();
+        let (tl, previous_holder) = match TimeLock::new(
+            lock_duration,
+            instance_id.clone(),
+            lock_data,
+        ) {
+            Ok(result) => result,
+            Err(LockedBy(holder)) if is_stale => {
+                warn!("Remote lock of `{holder}` is stale, waiting {expires_in:?} to take it over");
+                tokio::time::sleep(expires_in).await;
+                // Any write of the holder bumps the version, which proves it is alive.
+                let (_, latest_version) = Self::get_lock_data(&store).await?;
+                if latest_version != version {
+                    return Err(Error::Locked(format!(
+                        "Remote lock was refreshed by `{holder}`"
+                    )));
+                }
+                TimeLock::take_over(lock_duration, instance_id)
+            }
+            Err(LockedBy(holder)) => {
+                return Err(Error::Locked(format!("Remote lock aquired by `{holder}`")));
+            }
+        };
         let versioned_tl = Mutex::new(VersionedTimeLock { tl, version });
 
         let locking_store = Self {
             inner: store,
             versioned_tl,
+            lock_duration,
         };
         locking_store.lock().await?;
 
         Ok((locking_store, previous_holder))
     }
 
+    async fn get_lock_data(store: &S) -> Result<(LockData, i64), Error> {
+        let (lock_data, version) = store.get(Self::KEY.to_string()).await?.unwrap_or_default();
+        let lock_data = LockData::decode(&lock_data)
+            .map_err(|e| Error::Internal(format!("Failed to decode lock_data: {e:?}")))?;
+        Ok((lock_data, version))
+    }
+
     /// Refreshes the distributed lock to extend its duration.
     ///
     /// This method should be called periodically to maintain ownership of the lock.
@@ -76,7 +108,7 @@ impl<S: VersionedStore + Send + Sync> LockingStore<S> {
         // asserting that the previous lock was acquired by us
         // (i.e., no one acquired/released the lock in between).
         let locked_until = self.lock().await?;
-        Ok(locked_until - Self::REFRESH_WINDOW)
+        Ok(locked_until - self.lock_duration / 2)
     }
 
     async fn lock(&self) -> Result<SystemTime, Error> {
@@ -118,8 +150,13 @@ impl<S: VersionedStore + Send + Sync> LockingStore<S> {
         Ok(())
     }
 
+    /// Checks if this instance still holds the distributed lock.
+    pub async fn is_locked(&self) -> bool {
+        self.versioned_tl.lock().await.tl.is_locked()
+    }
+
     async fn ensure_locked(&self) -> Result<(), Error> {
-        if !self.versioned_tl.lock().await.tl.is_locked() {
+        if !self.is_locked().await {
             return Err(Error::Conflict("Remote lock was not aquired".to_string()));
         }
         Ok(())
@@ -158,13 +195,16 @@ mod tests {
     use super::*;
     use crate::ldk::store::mock_versioned_store::MockVersionedStore;
 
+    const LOCK_DURATION: Duration = Duration::from_secs(60);
+
     #[tokio::test]
     async fn test_locking_store() {
         let store = MockVersionedStore::default();
         let instance_id_1 = "instance_1".to_string();
-        let (locking_store, previous_holder) = LockingStore::new(instance_id_1, store.clone())
-            .await
-            .unwrap();
+        let (locking_store, previous_holder) =
+            LockingStore::new(instance_id_1, LOCK_DURATION, store.clone())
+                .await
+                .unwrap();
         assert_eq!(previous_holder, PreviousHolder::RemoteInstance);
 
         // Lock was aquired, store can be accessed.
@@ -180,8 +220,8 @@ mod tests {
 
         // Another instance tries to acquire the lock.
         let instance_id_2 = "instance_2".to_string();
-        let result = LockingStore::new(instance_id_2.clone(), store.clone()).await;
-        assert!(matches!(result, Err(Error::Conflict(_))));
+        let result = LockingStore::new(instance_id_2.clone(), LOCK_DURATION, store.clone()).await;
+        assert!(matches!(result, Err(Error::Locked(_))));
 
         // The first instance releases the lock.
         locking_store.unlock().await.unwrap();
@@ -192,7 +232,7 @@ mod tests {
 
         // Another instance tries to acquire the lock again.
         let (locking_store2, previous_holder) =
-            LockingStore::new(instance_id_2.clone(), store.clone())
+            LockingStore::new(instance_id_2.clone(), LOCK_DURATION, store.clone())
                 .await
                 .unwrap();
         assert_eq!(previous_holder, PreviousHolder::RemoteInstance);
@@ -207,6 +247,67 @@ mod tests {
         // The instance crashed before releasing the lock.
         drop(locking_store2);
         // but it can instantly reaquire the lock.
-        let _locking_store2 = LockingStore::new(instance_id_2, store).await.unwrap();
+        let _locking_store2 = LockingStore::new(instance_id_2, LOCK_DURATION, store)
+            .await
+            .unwrap();
+    }
+
+    fn put_lock_data(store: &MockVersionedStore, instance_id: &str, locked_until: SystemTime) {
+        let lock_data = LockData::new(instance_id.to_string(), locked_until);
+        let value = lock_data.encode().unwrap();
+        store.data.lock().unwrap().insert(
+            LockingStore::<MockVersionedStore>::KEY.to_string(),
+            (value, 1),
+        );
+    }
+
+    #[tokio::test]
+    async fn test_locking_store_takes_over_stale_lock() {
+        let store = MockVersionedStore::default();
+        // The instance crashed and missed its refresh, the lock expires in a moment.
+        put_lock_data(
+            &store,
+            "crashed_instance",
+            SystemTime::now() - Duration::from_secs(2),
+        );
+
+        let (locking_store, previous_holder) =
+            LockingStore::new("instance".to_string(), LOCK_DURATION, store.clone())
+                .await
+                .unwrap();
+        assert_eq!(previous_holder, PreviousHolder::RemoteInstance);
+        locking_store
+            .put("key".to_string(), "value".as_bytes().to_vec(), 0)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_locking_store_respects_live_lock() {
+        let store = MockVersionedStore::default();
+        // The instance has just refreshed the lock.
+        put_lock_data(&store, "live_instance", SystemTime::now() + LOCK_DURATION);
+
+        let result = LockingStore::new("instance".to_string(), LOCK_DURATION, store.clone()).await;
+        assert!(matches!(result, Err(Error::Locked(_))));
+        let (_, version) = store.data.lock().unwrap().get("lock").unwrap().clone();
+        assert_eq!(version, 1);
+    }
+
+    #[tokio::test]
+    async fn test_locking_store_refreshes_before_expiry() {
+        let store = MockVersionedStore::default();
+        let lock_duration = Duration::from_secs(10);
+        let (locking_store, _) =
+            LockingStore::new("instance".to_string(), lock_duration, store.clone())
+                .await
+                .unwrap();
+
+        let before_refresh = SystemTime::now();
+        let next_refresh = locking_store.refresh_lock().await.unwrap();
+        let (value, _) = store.data.lock().unwrap().get("lock").unwrap().clone();
+        let locked_until = LockData::decode(&value).unwrap().locked_until();
+        assert!(locked_until >= before_refresh + lock_duration);
+        assert_eq!(next_refresh, locked_until - lock_duration / 2);
     }
 }
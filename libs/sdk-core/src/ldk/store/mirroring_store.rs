@@ -1,18 +1,25 @@
 use std::collections::HashMap;
 use std::ops::Deref;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
 use ldk_node::bitcoin::io::ErrorKind;
 use ldk_node::lightning::io;
 use ldk_node::lightning::util::async_poll::AsyncResult;
-use ldk_node::lightning::util::persist::{KVStore, KVStoreSync};
+use ldk_node::lightning::util::persist::{
+    KVStore, KVStoreSync, CHANNEL_MANAGER_PERSISTENCE_KEY,
+    CHANNEL_MANAGER_PERSISTENCE_PRIMARY_NAMESPACE, CHANNEL_MANAGER_PERSISTENCE_SECONDARY_NAMESPACE,
+};
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, Connection, Error as SqlError, OptionalExtension};
 use tokio::runtime::Handle;
 
 use crate::ldk::store::time_lock::PreviousHolder;
-use crate::ldk::store::versioned_store::{Error as RemoteError, VersionedStore};
+use crate::ldk::store::versioned_store::{
+    Error as RemoteError, VersionedStore, DEFAULT_MAX_VALUE_SIZE,
+};
+use crate::models::ResyncResult;
 use crate::node_api::NodeError;
 use crate::persist::error::PersistError;
 
@@ -24,6 +31,8 @@ pub enum Error {
     LocalSql(#[from] SqlError),
     #[error("Remote error: {0}")]
     Remote(#[from] RemoteError),
+    #[error("State regression: {0}")]
+    StateRegression(String),
 }
 
 impl From<Error> for NodeError {
@@ -38,15 +47,51 @@ impl From<Error> for NodeError {
             Error::Remote(e) => {
                 NodeError::ServiceConnectivity(format!("Mirroring store remote error: {e}"))
             }
+            Error::StateRegression(e) => NodeError::StateRegression(e),
         }
     }
 }
 
+/// Keys which versions must never go backwards, since adopting an older value could
+/// lead to broadcasting a revoked channel state.
+const CRITICAL_KEYS: [(&str, &str, &str); 1] = [(
+    CHANNEL_MANAGER_PERSISTENCE_PRIMARY_NAMESPACE,
+    CHANNEL_MANAGER_PERSISTENCE_SECONDARY_NAMESPACE,
+    CHANNEL_MANAGER_PERSISTENCE_KEY,
+)];
+
+/// A key which is modified or removed locally, but not yet synced to remote.
+#[derive(Debug, Eq, PartialEq)]
+pub struct DirtyKey {
+    /// The key in the `primary_ns/secondary_ns/key` format.
+    pub full_key: String,
+    /// Whether the key was removed locally.
+    pub removed: bool,
+}
+
+/// A row of the local store without its value, describing the structure of the store.
+#[derive(Debug, Eq, PartialEq)]
+pub struct StoreIndexEntry {
+    pub primary_ns: String,
+    pub secondary_ns: String,
+    pub key: String,
+    /// The length of the value in bytes.
+    pub value_len: u64,
+    pub local_version: i64,
+    /// The local version last synced to remote, or -1 if the key was never synced.
+    pub remote_version: i64,
+    /// Whether the key was removed locally.
+    pub removed: bool,
+}
+
 pub struct MirroringStore<S: Deref<Target = T>, T: VersionedStore + Send + Sync> {
     handle: Handle,
     remote_client: S,
     pool: Pool<SqliteConnectionManager>,
     key_locks: Mutex<HashMap<String, Arc<Mutex<()>>>>,
+    /// Shared by writes and removals, taken exclusively to replace the whole local store.
+    resync_lock: RwLock<()>,
+    max_value_size: usize,
 }
 
 impl<S: Deref<Target = T>, T: VersionedStore + Send + Sync> MirroringStore<S, T> {
@@ -55,6 +100,7 @@ impl<S: Deref<Target = T>, T: VersionedStore + Send + Sync> MirroringStore<S, T>
         pool: Pool<SqliteConnectionManager>,
         remote: S,
         previous_holder: PreviousHolder,
+        allow_state_regression: bool,
     ) -> Result<Self, Error> {
         let conn = &*pool.get()?;
         conn.execute(
@@ -82,11 +128,19 @@ impl<S: Deref<Target = T>, T: VersionedStore + Send + Sync> MirroringStore<S, T>
             }
             (PreviousHolder::RemoteInstance, false) => {
                 info!("Local store is clean, something new on remote possible. Downloading from remote...");
-                download(conn, &*remote).await?;
+                download(conn, &*remote, allow_state_regression).await?;
             }
             (PreviousHolder::RemoteInstance, true) => {
-                info!("Local store is *dirty*, something new on remote possible. Downloading from remote...");
-                download(conn, &*remote).await?;
+                let remote_keys = remote.list().await?;
+                if is_remote_unchanged(conn, &remote_keys)? {
+                    // No other instance wrote since our last upload, so the local changes
+                    // are the latest state and downloading would drop them.
+                    info!("Local store is *dirty*, nothing new on remote. Uploading to remote...");
+                    upload(conn, &*remote).await?;
+                } else {
+                    info!("Local store is *dirty*, something new on remote. Downloading from remote...");
+                    download(conn, &*remote, allow_state_regression).await?;
+                }
             }
         };
 
@@ -95,11 +149,144 @@ impl<S: Deref<Target = T>, T: VersionedStore + Send + Sync> MirroringStore<S, T>
             pool,
             remote_client: remote,
             key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        })
+    }
+
+    /// Sets the largest value, in bytes, accepted by [KVStoreSync::write].
+    pub fn with_max_value_size(mut self, max_value_size: usize) -> Self {
+        self.max_value_size = max_value_size;
+        self
+    }
+
+    /// Lists keys which are modified or removed locally, but not yet synced to remote.
+    pub fn list_dirty_keys(&self) -> Result<Vec<DirtyKey>, Error> {
+        let conn = self.pool.get()?;
+        let mut statement = conn.prepare(
+            "SELECT primary_ns, secondary_ns, key, removed FROM store WHERE local_version != remote_version OR removed = 1 ORDER BY primary_ns, secondary_ns, key",
+        )?;
+        let dirty_keys = statement
+            .query_map([], |row| {
+                let primary_ns: String = row.get(0)?;
+                let secondary_ns: String = row.get(1)?;
+                let key: String = row.get(2)?;
+                Ok(DirtyKey {
+                    full_key: format!("{primary_ns}/{secondary_ns}/{key}"),
+                    removed: row.get(3)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(dirty_keys)
+    }
+
+    /// Lists all rows of the local store, including the removed ones, without their values,
+    /// so that the structure of the store can be inspected without exposing its secrets.
+    pub fn dump_store_index(&self) -> Result<Vec<StoreIndexEntry>, Error> {
+        let conn = self.pool.get()?;
+        let mut statement = conn.prepare(
+            "SELECT primary_ns, secondary_ns, key, length(value), local_version, remote_version, removed FROM store ORDER BY primary_ns, secondary_ns, key",
+        )?;
+        let entries = statement
+            .query_map([], |row| {
+                Ok(StoreIndexEntry {
+                    primary_ns: row.get(0)?,
+                    secondary_ns: row.get(1)?,
+                    key: row.get(2)?,
+                    value_len: row.get(3)?,
+                    local_version: row.get(4)?,
+                    remote_version: row.get(5)?,
+                    removed: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }
+
+    /// Uploads all dirty keys to remote.
+    ///
+    /// Fails with a `Conflict` error without touching any data if remote
+    /// contains newer versions of the dirty keys, since uploading local
+    /// changes on top of them would lose remote updates.
+    ///
+    /// Writes and removals wait until the upload is done.
+    pub async fn force_resync(&self) -> Result<(), Error> {
+        tokio::task::block_in_place(|| {
+            let _resync_lock = self.resync_lock.write().unwrap();
+            self.handle.block_on(self.upload_dirty_keys())
+        })
+    }
+
+    /// Uploads all dirty keys to remote as [Self::force_resync] does, giving up after
+    /// `timeout`. Returns the keys left dirty.
+    pub async fn flush(&self, timeout: Duration) -> Result<Vec<DirtyKey>, Error> {
+        if self.list_dirty_keys()?.is_empty() {
+            return Ok(Vec::new());
+        }
+        let result = tokio::task::block_in_place(|| {
+            let _resync_lock = self.resync_lock.write().unwrap();
+            self.handle
+                .block_on(tokio::time::timeout(timeout, self.upload_dirty_keys()))
+        });
+        match result {
+            Ok(Ok(())) => (),
+            Ok(Err(e)) => warn!("Failed to flush dirty keys: {e}"),
+            Err(_) => warn!("Flushing dirty keys timed out after {timeout:?}"),
+        }
+        self.list_dirty_keys()
+    }
+
+    /// Discards the local store and downloads it again from remote, for when the local
+    /// store is suspected to be corrupted.
+    ///
+    /// Local changes not yet synced to remote are uploaded first, as they may be channel
+    /// state that must not be lost, and the resync fails without touching the local store if
+    /// they cannot be. The local store is replaced in a single transaction, so it is left
+    /// as it was if the download fails. Fails with a `StateRegression` error if remote holds
+    /// older versions of the [CRITICAL_KEYS] than the ones last synced.
+    ///
+    /// Writes and removals wait until the resync is done, so none of them is lost.
+    pub async fn resync_from_remote(&self) -> Result<ResyncResult, Error> {
+        tokio::task::block_in_place(|| {
+            let _resync_lock = self.resync_lock.write().unwrap();
+            let conn = &*self.pool.get()?;
+            if is_dirty(conn)? {
+                info!("Local store is *dirty*, uploading to remote before resyncing...");
+                self.handle.block_on(async {
+                    ensure_remote_not_newer(conn, &*self.remote_client).await?;
+                    upload(conn, &*self.remote_client).await
+                })?;
+            }
+
+            let tx = conn.unchecked_transaction()?;
+            let discarded_keys: i64 =
+                tx.query_row("SELECT COUNT(*) FROM store", [], |row| row.get(0))?;
+            info!("Discarding {discarded_keys} local keys, downloading from remote...");
+            // The local rows are only deleted by the download, once checked for a regression.
+            let downloaded_keys =
+                self.handle
+                    .block_on(download(&tx, &*self.remote_client, false))?;
+            tx.commit()?;
+            Ok(ResyncResult {
+                discarded_keys: discarded_keys as u32,
+                downloaded_keys: downloaded_keys as u32,
+            })
         })
     }
 
+    /// Uploads all dirty keys to remote, unless it holds newer versions of them. The resync
+    /// lock must be held, so that the local store does not change meanwhile.
+    async fn upload_dirty_keys(&self) -> Result<(), Error> {
+        let conn = &*self.pool.get()?;
+        ensure_remote_not_newer(conn, &*self.remote_client).await?;
+        upload(conn, &*self.remote_client).await
+    }
+
     fn key_lock(&self, full_key: String) -> Arc<Mutex<()>> {
         let mut locks = self.key_locks.lock().unwrap();
+        // A lock referenced only by the map is not used by anyone, and cannot be taken
+        // meanwhile as locks are only handed out while holding the map, so it can be dropped.
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
         Arc::clone(locks.entry(full_key).or_default())
     }
 }
@@ -125,6 +312,17 @@ impl<S: Deref<Target = T>, T: VersionedStore + Send + Sync> KVStoreSync for Mirr
         value: Vec<u8>,
     ) -> io::Result<()> {
         let full_key = format!("{primary_ns}/{secondary_ns}/{key}");
+        if value.len() > self.max_value_size {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "Value of {full_key} is {} bytes, above the maximum of {} bytes",
+                    value.len(),
+                    self.max_value_size
+                ),
+            ));
+        }
+        let _resync_lock = self.resync_lock.read().unwrap();
         let mutex = self.key_lock(full_key.clone());
         let _lock = mutex.lock().unwrap();
 
@@ -189,6 +387,7 @@ impl<S: Deref<Target = T>, T: VersionedStore + Send + Sync> KVStoreSync for Mirr
         _lazy: bool,
     ) -> io::Result<()> {
         let full_key = format!("{primary_ns}/{secondary_ns}/{key}");
+        let _resync_lock = self.resync_lock.read().unwrap();
         let mutex = self.key_lock(full_key.clone());
         let _lock = mutex.lock().unwrap();
         debug!("Removing {full_key}");
@@ -239,10 +438,22 @@ fn is_dirty(conn: &Connection) -> rusqlite::Result<bool> {
     Ok(dirty_rows > 0)
 }
 
-async fn download<S: VersionedStore>(conn: &Connection, remote: &S) -> Result<(), Error> {
+async fn download<S: VersionedStore>(
+    conn: &Connection,
+    remote: &S,
+    allow_state_regression: bool,
+) -> Result<usize, Error> {
+    let remote_keys = remote.list().await?;
+    match ensure_no_regression(conn, &remote_keys) {
+        Err(Error::StateRegression(e)) if allow_state_regression => {
+            warn!("Adopting remote state despite a regression: {e}");
+        }
+        res => res?,
+    }
     conn.execute("DELETE FROM store", [])?;
 
-    for (full_key, version) in remote.list().await? {
+    let mut downloaded_keys = 0;
+    for (full_key, version) in remote_keys {
         trace!("Downloading {full_key} @ {version} ...");
         let parts: Vec<&str> = full_key.splitn(3, '/').collect();
         let (primary, secondary, key) = match &parts[..] {
@@ -256,6 +467,104 @@ async fn download<S: VersionedStore>(conn: &Connection, remote: &S) -> Result<()
                 "INSERT INTO store (primary_ns, secondary_ns, key, value, local_version, remote_version, removed) VALUES (?1, ?2, ?3, ?4, ?5, ?5, 0)",
                 params![primary, secondary, key, value, version - 1],
             )?;
+            downloaded_keys += 1;
+        }
+    }
+    Ok(downloaded_keys)
+}
+
+/// Whether remote contains exactly the versions of the keys last synced by the local
+/// instance, i.e. no other instance modified or removed any key since.
+fn is_remote_unchanged(conn: &Connection, remote_keys: &[(String, i64)]) -> Result<bool, Error> {
+    let mut statement = conn.prepare(
+        "SELECT primary_ns, secondary_ns, key, remote_version FROM store WHERE remote_version >= 0",
+    )?;
+    let synced_versions = statement
+        .query_map([], |row| {
+            let primary_ns: String = row.get(0)?;
+            let secondary_ns: String = row.get(1)?;
+            let key: String = row.get(2)?;
+            Ok((format!("{primary_ns}/{secondary_ns}/{key}"), row.get(3)?))
+        })?
+        .collect::<Result<HashMap<String, i64>, _>>()?;
+
+    // Remote keeps the version incremented by one compared to the last synced one.
+    Ok(remote_keys.len() == synced_versions.len()
+        && remote_keys
+            .iter()
+            .all(|(full_key, version)| synced_versions.get(full_key) == Some(&(version - 1))))
+}
+
+/// Fails with a `StateRegression` error if remote contains an older version of any of
+/// the [CRITICAL_KEYS] than the one last synced by the local instance.
+fn ensure_no_regression(conn: &Connection, remote_keys: &[(String, i64)]) -> Result<(), Error> {
+    for (primary_ns, secondary_ns, key) in CRITICAL_KEYS {
+        let synced_version: Option<i64> = conn
+            .query_row(
+                "SELECT remote_version FROM store WHERE primary_ns = ?1 AND secondary_ns = ?2 AND key = ?3",
+                params![primary_ns, secondary_ns, key],
+                |row| row.get(0),
+            )
+            .optional()?;
+        // The key was never synced, so there is nothing to regress from.
+        let Some(synced_version) = synced_version.filter(|v| *v >= 0) else {
+            continue;
+        };
+
+        let full_key = format!("{primary_ns}/{secondary_ns}/{key}");
+        // Remote keeps the version incremented by one compared to the last synced one.
+        let remote_version = remote_keys
+            .iter()
+            .find(|(k, _)| *k == full_key)
+            .map(|(_, version)| version - 1);
+        match remote_version {
+            Some(version) if version >= synced_version => (),
+            Some(version) => {
+                return Err(Error::StateRegression(format!(
+                    "Remote has an older version of {full_key}: {version} < {synced_version}"
+                )))
+            }
+            None => {
+                return Err(Error::StateRegression(format!(
+                    "Remote is missing {full_key} synced at version {synced_version}"
+                )))
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn ensure_remote_not_newer<S: VersionedStore>(
+    conn: &Connection,
+    remote: &S,
+) -> Result<(), Error> {
+    let remote_versions: HashMap<String, i64> = remote.list().await?.into_iter().collect();
+
+    let mut statement = conn.prepare(
+        "SELECT primary_ns, secondary_ns, key, remote_version FROM store WHERE local_version != remote_version OR removed = 1",
+    )?;
+    let dirty_rows = statement.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, String>(2)?,
+            row.get::<_, i64>(3)?,
+        ))
+    })?;
+
+    for row in dirty_rows {
+        let (primary_ns, secondary_ns, key, remote_version) = row?;
+        let full_key = format!("{primary_ns}/{secondary_ns}/{key}");
+        // Remote keeps the version incremented by one compared to the last synced one.
+        let last_synced_version = remote_version + 1;
+        match remote_versions.get(&full_key) {
+            Some(version) if *version > last_synced_version => {
+                return Err(RemoteError::Conflict(format!(
+                    "Remote has a newer version of {full_key}: {version} > {last_synced_version}"
+                ))
+                .into());
+            }
+            _ => (),
         }
     }
     Ok(())
@@ -372,7 +681,6 @@ mod tests {
     use r2d2_sqlite::SqliteConnectionManager;
     use rusqlite::backup::Backup;
     use rusqlite::Connection;
-    use std::time::Duration;
     use tokio::runtime::Handle;
 
     fn create_in_memory_db() -> Pool<SqliteConnectionManager> {
@@ -388,6 +696,7 @@ mod tests {
             create_in_memory_db(),
             &mock_store,
             PreviousHolder::RemoteInstance,
+            false,
         )
         .await
         .unwrap();
@@ -419,6 +728,7 @@ mod tests {
             create_in_memory_db(),
             &mock_store,
             PreviousHolder::RemoteInstance,
+            false,
         )
         .await
         .unwrap();
@@ -443,6 +753,56 @@ mod tests {
         assert_eq!(err.kind(), ErrorKind::NotFound);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_key_locks_cleanup() {
+        let mock_store = MockVersionedStore::default();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            create_in_memory_db(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+
+        let held = store.key_lock("ns/sub/held".to_string());
+        for i in 0..1000 {
+            KVStoreSync::write(&store, "ns", "sub", &format!("key{i}"), b"value".to_vec()).unwrap();
+        }
+        // Only the lock still in use and the one of the last write are left.
+        assert_eq!(store.key_locks.lock().unwrap().len(), 2);
+        // The lock in use is kept, so it is shared with whoever asks for it next.
+        assert!(Arc::ptr_eq(
+            &held,
+            &store.key_lock("ns/sub/held".to_string())
+        ));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_max_value_size() {
+        let mock_store = MockVersionedStore::default();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            create_in_memory_db(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap()
+        .with_max_value_size(16);
+
+        let err = KVStoreSync::write(&store, "ns", "sub", "key", vec![1; 17]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        let err = KVStoreSync::read(&store, "ns", "sub", "key").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+
+        KVStoreSync::write(&store, "ns", "sub", "key", vec![1; 16]).unwrap();
+        let value = KVStoreSync::read(&store, "ns", "sub", "key").unwrap();
+        assert_eq!(value, vec![1; 16]);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_mirroring_store_remote_failure_handling() {
         // Simulate remote failure.
@@ -456,6 +816,7 @@ mod tests {
             create_in_memory_db(),
             &mock_store,
             PreviousHolder::LocalInstance,
+            false,
         )
         .await
         .unwrap();
@@ -475,15 +836,22 @@ mod tests {
                 create_in_memory_db(),
                 &mock_store,
                 PreviousHolder::RemoteInstance,
+                false,
             )
             .await
             .unwrap();
             let err = KVStoreSync::read(&store, "ns", "sub", "key_dirty").unwrap_err();
             assert_eq!(err.kind(), ErrorKind::NotFound);
+            // The other instance writes to remote.
+            mock_store
+                .data
+                .lock()
+                .unwrap()
+                .insert("ns/sub/key_other".to_string(), (b"value_other".to_vec(), 1));
         }
 
         {
-            // Recovery of a dirty instance with another instance accessing the
+            // Recovery of a dirty instance with another instance writing to the
             // store in between.
             let dirty_local_db = create_in_memory_db();
             clone_data(
@@ -496,6 +864,7 @@ mod tests {
                 dirty_local_db,
                 &mock_store,
                 PreviousHolder::RemoteInstance,
+                false,
             )
             .await
             .unwrap();
@@ -518,6 +887,7 @@ mod tests {
                 dirty_local_db,
                 &mock_store,
                 PreviousHolder::LocalInstance,
+                false,
             )
             .await
             .unwrap();
@@ -530,6 +900,67 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_dirty_local_remote_unchanged() {
+        let failing_mock_store = MockVersionedStore {
+            should_fail_put: true,
+            ..Default::default()
+        };
+        let mock_store = MockVersionedStore {
+            data: Arc::clone(&failing_mock_store.data),
+            ..Default::default()
+        };
+
+        let pool = create_in_memory_db();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "key", b"synced".to_vec()).unwrap();
+        let failing_store = MirroringStore {
+            handle: Handle::current().clone(),
+            remote_client: &failing_mock_store,
+            pool: pool.clone(),
+            key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        };
+        KVStoreSync::write(&failing_store, "ns", "sub", "key", b"dirty".to_vec()).unwrap_err();
+        KVStoreSync::write(&failing_store, "ns", "sub", "new_key", b"dirty".to_vec()).unwrap_err();
+
+        // Another instance held the lock in between, but did not write anything.
+        let local_db = create_in_memory_db();
+        clone_data(&pool.get().unwrap(), &mut local_db.get().unwrap());
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            local_db,
+            &mock_store,
+            PreviousHolder::RemoteInstance,
+            false,
+        )
+        .await
+        .unwrap();
+
+        // The local changes are kept and uploaded.
+        assert_eq!(
+            KVStoreSync::read(&store, "ns", "sub", "key").unwrap(),
+            b"dirty"
+        );
+        assert_eq!(
+            KVStoreSync::read(&store, "ns", "sub", "new_key").unwrap(),
+            b"dirty"
+        );
+        assert!(store.list_dirty_keys().unwrap().is_empty());
+        let data = mock_store.data.lock().unwrap();
+        assert_eq!(data.get("ns/sub/key").unwrap().0, b"dirty");
+        assert_eq!(data.get("ns/sub/new_key").unwrap().0, b"dirty");
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_mirroring_store_remote_failure_handling_remove() {
         // Simulate remote failure.
@@ -544,6 +975,7 @@ mod tests {
                 create_in_memory_db(),
                 &mock_store,
                 PreviousHolder::LocalInstance,
+                false,
             )
             .await
             .unwrap();
@@ -577,6 +1009,7 @@ mod tests {
                 create_in_memory_db(),
                 &mock_store,
                 PreviousHolder::RemoteInstance,
+                false,
             )
             .await
             .unwrap();
@@ -596,6 +1029,7 @@ mod tests {
                 dirty_local_db,
                 &mock_store,
                 PreviousHolder::LocalInstance,
+                false,
             )
             .await
             .unwrap();
@@ -612,6 +1046,462 @@ mod tests {
         }
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_force_resync() {
+        // Simulate remote failure.
+        let failing_mock_store = MockVersionedStore {
+            should_fail_put: true,
+            should_fail_delete: true,
+            ..Default::default()
+        };
+        let mock_store = MockVersionedStore {
+            data: Arc::clone(&failing_mock_store.data),
+            ..Default::default()
+        };
+
+        let pool = create_in_memory_db();
+        {
+            let store = MirroringStore::new(
+                Handle::current().clone(),
+                pool.clone(),
+                &mock_store,
+                PreviousHolder::LocalInstance,
+                false,
+            )
+            .await
+            .unwrap();
+            KVStoreSync::write(&store, "ns", "sub", "key_to_remove", b"remove_me".to_vec())
+                .unwrap();
+            assert!(store.list_dirty_keys().unwrap().is_empty());
+        }
+
+        let store = MirroringStore {
+            handle: Handle::current().clone(),
+            remote_client: &failing_mock_store,
+            pool: pool.clone(),
+            key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        };
+        KVStoreSync::write(&store, "ns", "sub", "key_dirty", b"value_dirty".to_vec()).unwrap_err();
+        KVStoreSync::remove(&store, "ns", "sub", "key_to_remove", false).unwrap_err();
+        let dirty_keys = store.list_dirty_keys().unwrap();
+        assert_eq!(
+            dirty_keys,
+            vec![
+                DirtyKey {
+                    full_key: "ns/sub/key_dirty".to_string(),
+                    removed: false,
+                },
+                DirtyKey {
+                    full_key: "ns/sub/key_to_remove".to_string(),
+                    removed: true,
+                },
+            ]
+        );
+        store.force_resync().await.unwrap_err();
+
+        // Remote recovered.
+        let store = MirroringStore {
+            handle: Handle::current().clone(),
+            remote_client: &mock_store,
+            pool,
+            key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        };
+        store.force_resync().await.unwrap();
+        assert!(store.list_dirty_keys().unwrap().is_empty());
+        let data = mock_store.data.lock().unwrap();
+        assert_eq!(data.get("ns/sub/key_dirty").unwrap().0, b"value_dirty");
+        assert!(!data.contains_key("ns/sub/key_to_remove"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_flush() {
+        let failing_mock_store = MockVersionedStore {
+            should_fail_put: true,
+            ..Default::default()
+        };
+        let mock_store = MockVersionedStore {
+            data: Arc::clone(&failing_mock_store.data),
+            ..Default::default()
+        };
+
+        let pool = create_in_memory_db();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        // Nothing to flush.
+        assert!(store
+            .flush(Duration::from_secs(5))
+            .await
+            .unwrap()
+            .is_empty());
+
+        let failing_store = MirroringStore {
+            handle: Handle::current().clone(),
+            remote_client: &failing_mock_store,
+            pool: pool.clone(),
+            key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        };
+        KVStoreSync::write(&failing_store, "ns", "sub", "key", b"value".to_vec()).unwrap_err();
+
+        // Remote is still failing, the key is left dirty.
+        let dirty_keys = failing_store.flush(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(
+            dirty_keys,
+            vec![DirtyKey {
+                full_key: "ns/sub/key".to_string(),
+                removed: false,
+            }]
+        );
+
+        // Remote recovered.
+        assert!(store
+            .flush(Duration::from_secs(5))
+            .await
+            .unwrap()
+            .is_empty());
+        let data = mock_store.data.lock().unwrap();
+        assert_eq!(data.get("ns/sub/key").unwrap().0, b"value");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_force_resync_remote_newer() {
+        let failing_mock_store = MockVersionedStore {
+            should_fail_put: true,
+            ..Default::default()
+        };
+        let mock_store = MockVersionedStore {
+            data: Arc::clone(&failing_mock_store.data),
+            ..Default::default()
+        };
+
+        let pool = create_in_memory_db();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "key", b"value".to_vec()).unwrap();
+
+        let store = MirroringStore {
+            handle: Handle::current().clone(),
+            remote_client: &failing_mock_store,
+            pool: pool.clone(),
+            key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        };
+        KVStoreSync::write(&store, "ns", "sub", "key", b"local".to_vec()).unwrap_err();
+
+        // Another instance updated the key in the meantime.
+        mock_store
+            .put("ns/sub/key".to_string(), b"remote".to_vec(), 1)
+            .await
+            .unwrap();
+
+        let store = MirroringStore {
+            handle: Handle::current().clone(),
+            remote_client: &mock_store,
+            pool,
+            key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        };
+        let err = store.force_resync().await.unwrap_err();
+        assert!(matches!(err, Error::Remote(RemoteError::Conflict(_))));
+        // Neither local nor remote state was touched.
+        assert_eq!(store.list_dirty_keys().unwrap().len(), 1);
+        let value = KVStoreSync::read(&store, "ns", "sub", "key").unwrap();
+        assert_eq!(value, b"local");
+        let data = mock_store.data.lock().unwrap();
+        assert_eq!(data.get("ns/sub/key").unwrap().0, b"remote");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_resync_from_remote() {
+        let mock_store = MockVersionedStore::default();
+        let pool = create_in_memory_db();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "key", b"value".to_vec()).unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "other_key", b"other_value".to_vec()).unwrap();
+
+        // Corrupt the local rows.
+        let conn = pool.get().unwrap();
+        conn.execute("UPDATE store SET value = x'00' WHERE key = 'key'", [])
+            .unwrap();
+        conn.execute("DELETE FROM store WHERE key = 'other_key'", [])
+            .unwrap();
+        conn.execute(
+            "INSERT INTO store (primary_ns, secondary_ns, key, value, local_version, remote_version) VALUES ('ns', 'sub', 'unknown_key', x'00', 0, 0)",
+            [],
+        )
+        .unwrap();
+        drop(conn);
+
+        let result = store.resync_from_remote().await.unwrap();
+        assert_eq!(
+            result,
+            ResyncResult {
+                discarded_keys: 2,
+                downloaded_keys: 2,
+            }
+        );
+        assert!(store.list_dirty_keys().unwrap().is_empty());
+        let list = KVStoreSync::list(&store, "ns", "sub").unwrap();
+        assert_eq!(list, vec!["key".to_string(), "other_key".to_string()]);
+        let data = mock_store.data.lock().unwrap().clone();
+        for (full_key, (value, _)) in data {
+            let key = full_key.strip_prefix("ns/sub/").unwrap();
+            assert_eq!(KVStoreSync::read(&store, "ns", "sub", key).unwrap(), value);
+        }
+
+        // Writes continue on top of the downloaded versions.
+        KVStoreSync::write(&store, "ns", "sub", "key", b"new_value".to_vec()).unwrap();
+        assert!(store.list_dirty_keys().unwrap().is_empty());
+        let data = mock_store.data.lock().unwrap();
+        assert_eq!(data.get("ns/sub/key").unwrap().0, b"new_value");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_resync_from_remote_keeps_local_changes() {
+        let mock_store = MockVersionedStore::default();
+        let pool = create_in_memory_db();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "key", b"value".to_vec()).unwrap();
+
+        // The remote fails in the middle of the resync, after the local rows are deleted.
+        let failing_get_store = MockVersionedStore {
+            should_fail_get: true,
+            ..mock_store.clone()
+        };
+        let failing_get_mirror = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &failing_get_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        assert!(failing_get_mirror.resync_from_remote().await.is_err());
+        assert_eq!(
+            KVStoreSync::read(&store, "ns", "sub", "key").unwrap(),
+            b"value"
+        );
+
+        // A local change which cannot be uploaded is not discarded.
+        let failing_put_store = MockVersionedStore {
+            should_fail_put: true,
+            ..mock_store.clone()
+        };
+        let failing_put_mirror = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &failing_put_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(
+            &failing_put_mirror,
+            "ns",
+            "sub",
+            "key",
+            b"new_value".to_vec(),
+        )
+        .unwrap_err();
+        assert!(failing_put_mirror.resync_from_remote().await.is_err());
+        assert_eq!(
+            KVStoreSync::read(&store, "ns", "sub", "key").unwrap(),
+            b"new_value"
+        );
+        assert_eq!(store.list_dirty_keys().unwrap().len(), 1);
+
+        // Once the remote is back, the local change is uploaded before the resync.
+        let result = store.resync_from_remote().await.unwrap();
+        assert_eq!(result.downloaded_keys, 1);
+        assert_eq!(
+            KVStoreSync::read(&store, "ns", "sub", "key").unwrap(),
+            b"new_value"
+        );
+        assert!(store.list_dirty_keys().unwrap().is_empty());
+        let data = mock_store.data.lock().unwrap();
+        assert_eq!(data.get("ns/sub/key").unwrap().0, b"new_value");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_dump_store_index() {
+        let mock_store = MockVersionedStore {
+            should_fail_delete: true,
+            ..Default::default()
+        };
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            create_in_memory_db(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "key", b"value".to_vec()).unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "key", b"new_value".to_vec()).unwrap();
+        KVStoreSync::write(&store, "ns", "", "other_key", b"other".to_vec()).unwrap();
+        KVStoreSync::write(&store, "ns", "sub", "removed_key", b"x".to_vec()).unwrap();
+        KVStoreSync::remove(&store, "ns", "sub", "removed_key", false).unwrap_err();
+
+        let entry = |secondary_ns: &str, key: &str, value_len, version, removed| StoreIndexEntry {
+            primary_ns: "ns".to_string(),
+            secondary_ns: secondary_ns.to_string(),
+            key: key.to_string(),
+            value_len,
+            local_version: version,
+            remote_version: version,
+            removed,
+        };
+        assert_eq!(
+            store.dump_store_index().unwrap(),
+            vec![
+                entry("", "other_key", 5, 0, false),
+                entry("sub", "key", 9, 1, false),
+                entry("sub", "removed_key", 1, 0, true),
+            ]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_channel_manager_regression() {
+        let mock_store = MockVersionedStore::default();
+        let pool = create_in_memory_db();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&store, "", "", "manager", b"old".to_vec()).unwrap();
+        let old_remote = mock_store.data.lock().unwrap().clone();
+        KVStoreSync::write(&store, "", "", "manager", b"new".to_vec()).unwrap();
+
+        // Remote rolled back to the older channel manager.
+        *mock_store.data.lock().unwrap() = old_remote;
+
+        let local_db = create_in_memory_db();
+        clone_data(&pool.get().unwrap(), &mut local_db.get().unwrap());
+        let err = MirroringStore::new(
+            Handle::current().clone(),
+            local_db.clone(),
+            &mock_store,
+            PreviousHolder::RemoteInstance,
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(matches!(err, Error::StateRegression(_)));
+        // Local state was not touched.
+        let store = MirroringStore {
+            handle: Handle::current().clone(),
+            remote_client: &mock_store,
+            pool: local_db.clone(),
+            key_locks: Default::default(),
+            resync_lock: Default::default(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        };
+        let value = KVStoreSync::read(&store, "", "", "manager").unwrap();
+        assert_eq!(value, b"new");
+
+        // Explicitly allowing the regression adopts the remote state.
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            local_db,
+            &mock_store,
+            PreviousHolder::RemoteInstance,
+            true,
+        )
+        .await
+        .unwrap();
+        let value = KVStoreSync::read(&store, "", "", "manager").unwrap();
+        assert_eq!(value, b"old");
+
+        // A remote without the channel manager is a regression as well.
+        mock_store.data.lock().unwrap().clear();
+        let err = MirroringStore::new(
+            Handle::current().clone(),
+            pool,
+            &mock_store,
+            PreviousHolder::RemoteInstance,
+            false,
+        )
+        .await
+        .err()
+        .unwrap();
+        assert!(matches!(err, Error::StateRegression(_)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirroring_store_resync_from_remote_regression() {
+        let mock_store = MockVersionedStore::default();
+        let pool = create_in_memory_db();
+        let store = MirroringStore::new(
+            Handle::current().clone(),
+            pool.clone(),
+            &mock_store,
+            PreviousHolder::LocalInstance,
+            false,
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&store, "", "", "manager", b"old".to_vec()).unwrap();
+        let old_remote = mock_store.data.lock().unwrap().clone();
+        KVStoreSync::write(&store, "", "", "manager", b"new".to_vec()).unwrap();
+
+        // Remote rolled back to the older channel manager.
+        *mock_store.data.lock().unwrap() = old_remote;
+
+        let err = store.resync_from_remote().await.unwrap_err();
+        assert!(matches!(err, Error::StateRegression(_)));
+        // Local state was not touched.
+        let value = KVStoreSync::read(&store, "", "", "manager").unwrap();
+        assert_eq!(value, b"new");
+        assert!(store.list_dirty_keys().unwrap().is_empty());
+    }
+
     fn clone_data(src: &Connection, dst: &mut Connection) {
         Backup::new(src, dst)
             .unwrap()
@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use tokio::runtime::Handle;
 use tonic::async_trait;
 
 use crate::ldk::store::versioned_store::{Error, VersionedStore};
+use crate::ldk::store::{MirroringStore, PreviousHolder};
 
 pub type Map = HashMap<String, (Vec<u8>, i64)>;
 
@@ -80,3 +84,17 @@ impl VersionedStore for MockVersionedStore {
         Ok(result)
     }
 }
+
+/// Creates a [MirroringStore] of an empty [MockVersionedStore], backed by an in-memory
+/// SQLite database.
+pub async fn mock_mirroring_store() -> MirroringStore<Arc<MockVersionedStore>, MockVersionedStore> {
+    MirroringStore::new(
+        Handle::current(),
+        Pool::new(SqliteConnectionManager::memory()).unwrap(),
+        Arc::new(MockVersionedStore::default()),
+        PreviousHolder::LocalInstance,
+        false,
+    )
+    .await
+    .unwrap()
+}
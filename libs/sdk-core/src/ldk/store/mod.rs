@@ -7,9 +7,14 @@ mod time_lock;
 mod versioned_store;
 mod vss_store;
 
-pub(crate) use kv_store::{KVStore, Store};
+pub(crate) use kv_store::{
+    ChannelCloseRecord, ChannelRecord, KVStore, PaymentOutcome, PreimageRecord, Store, BOLT11_NS,
+    BREEZ_NS, PREIMAGES_NS,
+};
 pub(crate) use locking_store::LockingStore;
 pub(crate) use mirroring_store::MirroringStore;
+#[cfg(test)]
+pub(crate) use mock_versioned_store::{mock_mirroring_store, MockVersionedStore};
 pub(crate) use time_lock::PreviousHolder;
-pub(crate) use versioned_store::VersionedStore;
+pub(crate) use versioned_store::{Error as RemoteError, VersionedStore};
 pub(crate) use vss_store::VssStore;
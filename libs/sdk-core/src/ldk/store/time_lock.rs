@@ -66,15 +66,20 @@ impl TimeLock {
         } else if SystemTime::now() < latest_lock_data.locked_until + Self::CLOCK_SKEW_LEEWAY {
             Err(LockedBy(latest_lock_data.instance_id))
         } else {
-            let tl = Self {
-                lock_duration,
-                instance_id,
-                locked_until: UNIX_EPOCH,
-            };
-            Ok((tl, PreviousHolder::RemoteInstance))
+            Ok(Self::take_over(lock_duration, instance_id))
         }
     }
 
+    /// Creates a new `TimeLock` instance taking over the expired lock of another instance.
+    pub fn take_over(lock_duration: Duration, instance_id: String) -> (Self, PreviousHolder) {
+        let tl = Self {
+            lock_duration,
+            instance_id,
+            locked_until: UNIX_EPOCH,
+        };
+        (tl, PreviousHolder::RemoteInstance)
+    }
+
     /// Checks if the current instance holds an active lock.
     pub fn is_locked(&self) -> bool {
         SystemTime::now() < self.locked_until - Self::CLOCK_SKEW_LEEWAY
@@ -141,6 +146,19 @@ pub struct LockData {
 }
 
 impl LockData {
+    #[cfg(test)]
+    pub fn new(instance_id: String, locked_until: SystemTime) -> Self {
+        Self {
+            locked_until,
+            instance_id,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn locked_until(&self) -> SystemTime {
+        self.locked_until
+    }
+
     pub fn decode(data: &[u8]) -> Result<Self, serde_json::Error> {
         if data.is_empty() {
             return Ok(LockData {
@@ -154,6 +172,19 @@ impl LockData {
     pub fn encode(&self) -> Result<Vec<u8>, serde_json::Error> {
         serde_json::to_vec(&self)
     }
+
+    /// Checks if the holder of the lock missed its refresh, which a live instance does
+    /// `refresh_window` before the lock expires.
+    pub fn is_stale(&self, refresh_window: Duration) -> bool {
+        SystemTime::now() + refresh_window > self.locked_until + TimeLock::CLOCK_SKEW_LEEWAY
+    }
+
+    /// Returns how long to wait until the lock can be acquired by another instance.
+    pub fn expires_in(&self) -> Duration {
+        (self.locked_until + TimeLock::CLOCK_SKEW_LEEWAY)
+            .duration_since(SystemTime::now())
+            .unwrap_or_default()
+    }
 }
 
 #[cfg(test)]
@@ -208,6 +239,30 @@ mod tests {
         assert_eq!(decoded.instance_id, instance_id);
     }
 
+    #[test]
+    fn test_lock_data_is_stale() {
+        let refresh_window = Duration::from_secs(30);
+        let lock_data = |locked_until| LockData {
+            locked_until,
+            instance_id: "other-instance".to_string(),
+        };
+
+        // Just refreshed.
+        let live = lock_data(SystemTime::now() + Duration::from_secs(60));
+        assert!(!live.is_stale(refresh_window));
+        assert!(live.expires_in() > Duration::from_secs(60));
+
+        // The refresh is overdue.
+        let stale = lock_data(SystemTime::now() + Duration::from_secs(10));
+        assert!(stale.is_stale(refresh_window));
+        assert!(stale.expires_in() <= Duration::from_secs(13));
+
+        // Already expired.
+        let expired = lock_data(UNIX_EPOCH);
+        assert!(expired.is_stale(refresh_window));
+        assert_eq!(expired.expires_in(), Duration::ZERO);
+    }
+
     // TimeLock tests
     #[test]
     fn test_time_lock_not_locked() {
@@ -8,11 +8,26 @@ pub enum Error {
     /// A conflict occurred, typically when trying to update a key with an outdated version.
     /// The string contains details about the conflict.
     Conflict(String),
+    /// The lock is held by another instance that is still refreshing it.
+    /// The string contains details about the holder of the lock.
+    Locked(String),
+    /// The request was rejected because of invalid credentials.
+    /// The string contains details about the rejection.
+    Unauthorized(String),
+    /// The value is larger than the store accepts.
+    /// The string contains details about the size of the value.
+    TooLarge(String),
+    /// The store could not be reached.
+    /// The string contains details about the connection failure.
+    Unreachable(String),
     /// An internal error occurred during the operation.
     /// The string contains details about the internal error.
     Internal(String),
 }
 
+/// The largest value accepted by the stores unless configured otherwise.
+pub(crate) const DEFAULT_MAX_VALUE_SIZE: usize = 64 * 1024 * 1024;
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{self:?}")
@@ -54,6 +69,7 @@ pub trait VersionedStore {
     /// # Returns
     /// * `Ok(())` - If the value was stored successfully
     /// * `Err(Error::Conflict)` - If the version doesn't match (concurrent modification)
+    /// * `Err(Error::TooLarge)` - If the value exceeds the size accepted by the store
     /// * `Err(Error::Internal)` - If an internal error occurred
     async fn put(&self, key: String, value: Vec<u8>, version: i64) -> Result<(), Error>;
 
@@ -1,3 +1,7 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
 use bitcoin::hashes::{sha256, Hash, HashEngine, Hmac, HmacEngine};
 use rand::RngCore;
 use sdk_common::ensure_sdk;
@@ -13,7 +17,7 @@ use vss_client_ng::util::key_obfuscator::KeyObfuscator;
 use vss_client_ng::util::retry::RetryPolicy;
 use vss_client_ng::util::storable_builder::{EntropySource, StorableBuilder};
 
-use crate::ldk::store::versioned_store::{Error, VersionedStore};
+use crate::ldk::store::versioned_store::{Error, VersionedStore, DEFAULT_MAX_VALUE_SIZE};
 
 pub struct VssStore<P: RetryPolicy<E = VssError> + Send + Sync> {
     client: VssClient<P>,
@@ -21,6 +25,9 @@ pub struct VssStore<P: RetryPolicy<E = VssError> + Send + Sync> {
     storable_builder: StorableBuilder<RandEntropySource>,
     key_obfuscator: KeyObfuscator,
     data_encryption_key: [u8; 32],
+    cache: Option<ValueCache>,
+    uncached_keys: Vec<String>,
+    max_value_size: usize,
 }
 
 impl<P: RetryPolicy<E = VssError> + Send + Sync> VssStore<P> {
@@ -36,6 +43,37 @@ impl<P: RetryPolicy<E = VssError> + Send + Sync> VssStore<P> {
             storable_builder,
             key_obfuscator,
             data_encryption_key,
+            cache: None,
+            uncached_keys: Vec::new(),
+            max_value_size: DEFAULT_MAX_VALUE_SIZE,
+        }
+    }
+
+    /// Sets the largest value, in bytes, accepted by [VersionedStore::put].
+    pub fn with_max_value_size(mut self, max_value_size: usize) -> Self {
+        self.max_value_size = max_value_size;
+        self
+    }
+
+    /// Enables an in-memory LRU cache of values read from the store.
+    ///
+    /// The cache is only invalidated by local writes, so it must be enabled only
+    /// when no one else can modify the store concurrently (e.g., under a [super::LockingStore]).
+    pub fn with_cache(mut self, capacity: usize, ttl: Duration) -> Self {
+        self.cache = Some(ValueCache::new(capacity, ttl));
+        self
+    }
+
+    /// Excludes `key` from the cache, for a value that others modify even while this instance
+    /// has exclusive access to the rest of the store, like the remote lock.
+    pub fn with_uncached_key(mut self, key: &str) -> Self {
+        self.uncached_keys.push(key.to_string());
+        self
+    }
+
+    fn invalidate_cached(&self, obfuscated_key: &str) {
+        if let Some(cache) = &self.cache {
+            cache.remove(obfuscated_key);
         }
     }
 
@@ -87,6 +125,15 @@ impl<P: RetryPolicy<E = VssError> + Send + Sync> VssStore<P> {
 impl<P: RetryPolicy<E = VssError> + Send + Sync> VersionedStore for VssStore<P> {
     async fn get(&self, key: String) -> Result<Option<(Vec<u8>, i64)>, Error> {
         let obfuscated_key = self.obfuscate_key(&key);
+        let cache = self
+            .cache
+            .as_ref()
+            .filter(|_| !self.uncached_keys.contains(&key));
+        if let Some(cached) = cache.and_then(|c| c.get(&obfuscated_key)) {
+            trace!("Serving `{key}` from cache");
+            return Ok(Some(cached));
+        }
+        let generation = cache.map(ValueCache::generation);
         let request = GetObjectRequest {
             store_id: self.store_id.clone(),
             key: obfuscated_key.clone(),
@@ -101,6 +148,9 @@ impl<P: RetryPolicy<E = VssError> + Send + Sync> VersionedStore for VssStore<P>
                         "Version mismatch for key `{key}`: decrypted version={stored_version} but metadata version={}",
                         kv.version
                     )));
+                if let (Some(cache), Some(generation)) = (cache, generation) {
+                    cache.insert(generation, obfuscated_key, value.clone(), kv.version);
+                }
                 Ok(Some((value, kv.version)))
             }
             Ok(GetObjectResponse { value: None }) => Ok(None),
@@ -110,10 +160,18 @@ impl<P: RetryPolicy<E = VssError> + Send + Sync> VersionedStore for VssStore<P>
     }
 
     async fn put(&self, key: String, value: Vec<u8>, version: i64) -> Result<(), Error> {
+        ensure_sdk!(
+            value.len() <= self.max_value_size,
+            Error::TooLarge(format!(
+                "Value of `{key}` is {} bytes, above the maximum of {} bytes",
+                value.len(),
+                self.max_value_size
+            ))
+        );
         let obfuscated_key = self.obfuscate_key(&key);
         let value = self.construct_storable(&obfuscated_key, value, version);
         let key_value = KeyValue {
-            key: obfuscated_key,
+            key: obfuscated_key.clone(),
             version,
             value,
         };
@@ -123,13 +181,17 @@ impl<P: RetryPolicy<E = VssError> + Send + Sync> VersionedStore for VssStore<P>
             ..Default::default()
         };
 
-        self.client.put_object(&request).await?;
+        // Invalidate after the request, even a failed one might have reached the server.
+        let result = self.client.put_object(&request).await;
+        self.invalidate_cached(&obfuscated_key);
+        result?;
         Ok(())
     }
 
     async fn delete(&self, key: String) -> Result<(), Error> {
+        let obfuscated_key = self.obfuscate_key(&key);
         let key_value = KeyValue {
-            key: self.obfuscate_key(&key),
+            key: obfuscated_key.clone(),
             version: -1,
             value: Vec::new(),
         };
@@ -139,7 +201,9 @@ impl<P: RetryPolicy<E = VssError> + Send + Sync> VersionedStore for VssStore<P>
             key_value: Some(key_value),
         };
 
-        self.client.delete_object(&request).await?;
+        let result = self.client.delete_object(&request).await;
+        self.invalidate_cached(&obfuscated_key);
+        result?;
         Ok(())
     }
 
@@ -188,6 +252,86 @@ fn derive_data_encryption_and_obfuscation_keys(vss_seed: &[u8; 32]) -> ([u8; 32]
     (k1, k2)
 }
 
+struct CachedValue {
+    value: Vec<u8>,
+    version: i64,
+    inserted_at: Instant,
+}
+
+/// A bounded cache of values and their versions, evicting the least recently used entries.
+struct ValueCache {
+    capacity: usize,
+    ttl: Duration,
+    entries: Mutex<CachedEntries>,
+}
+
+struct CachedEntries {
+    // Ordered from the least to the most recently used.
+    values: VecDeque<(String, CachedValue)>,
+    /// Incremented on every removal, so that a value read concurrently with a change of it
+    /// is not cached.
+    generation: u64,
+}
+
+impl ValueCache {
+    fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: Mutex::new(CachedEntries {
+                values: VecDeque::with_capacity(capacity),
+                generation: 0,
+            }),
+        }
+    }
+
+    fn generation(&self) -> u64 {
+        self.entries.lock().unwrap().generation
+    }
+
+    fn get(&self, key: &str) -> Option<(Vec<u8>, i64)> {
+        let mut entries = self.entries.lock().unwrap();
+        let entries = &mut entries.values;
+        let position = entries.iter().position(|(k, _)| k == key)?;
+        let entry = entries.remove(position)?;
+        if entry.1.inserted_at.elapsed() > self.ttl {
+            return None;
+        }
+        let result = (entry.1.value.clone(), entry.1.version);
+        entries.push_back(entry);
+        Some(result)
+    }
+
+    /// Caches the value read at the given generation, unless a value was removed in the
+    /// meantime.
+    fn insert(&self, generation: u64, key: String, value: Vec<u8>, version: i64) {
+        if self.capacity == 0 {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap();
+        if entries.generation != generation {
+            return;
+        }
+        let entries = &mut entries.values;
+        entries.retain(|(k, _)| *k != key);
+        while entries.len() >= self.capacity {
+            entries.pop_front();
+        }
+        let value = CachedValue {
+            value,
+            version,
+            inserted_at: Instant::now(),
+        };
+        entries.push_back((key, value));
+    }
+
+    fn remove(&self, key: &str) {
+        let mut entries = self.entries.lock().unwrap();
+        entries.generation += 1;
+        entries.values.retain(|(k, _)| k != key);
+    }
+}
+
 struct RandEntropySource;
 
 impl EntropySource for RandEntropySource {
@@ -203,6 +347,9 @@ impl From<VssError> for Error {
                 Error::Internal("Received VssError::NoSuchKeyError".to_string())
             }
             VssError::ConflictError(e) => Error::Conflict(e),
+            VssError::AuthError(e) => Error::Unauthorized(e),
+            // Transport failures are reported as internal errors by the client.
+            VssError::InternalError(e) => Error::Unreachable(e),
             _ => Error::Internal(format!("{err:?}")),
         }
     }
@@ -211,6 +358,10 @@ impl From<VssError> for Error {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
     use std::time::Duration;
     use vss_client_ng::util::retry::RetryContext;
 
@@ -244,4 +395,156 @@ mod tests {
         assert_eq!(value, b"payload");
         assert_eq!(version, 1);
     }
+
+    #[tokio::test]
+    async fn put_rejects_too_large_value() {
+        let store = VssStore::new(
+            VssClient::new("http://example.com".to_string(), NoRetry),
+            "store-id".to_string(),
+            [1u8; 32],
+        )
+        .with_max_value_size(16);
+
+        let err = store
+            .put("test-key".to_string(), vec![0; 17], 0)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::TooLarge(_)));
+    }
+
+    /// Reads an HTTP request from the stream, returning its body.
+    fn read_body(stream: &mut TcpStream) -> Vec<u8> {
+        let mut buf = Vec::new();
+        let mut chunk = [0; 1024];
+        loop {
+            let n = stream.read(&mut chunk).unwrap();
+            assert!(n > 0, "Connection closed before the end of the request");
+            buf.extend_from_slice(&chunk[..n]);
+            let Some(header_end) = buf.windows(4).position(|w| w == b"\r\n\r\n") else {
+                continue;
+            };
+            let headers = String::from_utf8_lossy(&buf[..header_end]).to_lowercase();
+            let len = headers
+                .lines()
+                .find_map(|l| l.strip_prefix("content-length:"))
+                .map_or(0, |l| l.trim().parse().unwrap());
+            let body = header_end + 4..header_end + 4 + len;
+            if buf.len() >= body.end {
+                return buf[body].to_vec();
+            }
+        }
+    }
+
+    /// Answers every get request with `value` at version 1, as a VSS server would, returning
+    /// the number of requests received.
+    fn serve_values(listener: TcpListener, store: Arc<VssStore<NoRetry>>) -> Arc<AtomicUsize> {
+        let requests = Arc::new(AtomicUsize::new(0));
+        let counter = requests.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let request = GetObjectRequest::decode(read_body(&mut stream).as_slice()).unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                let response = GetObjectResponse {
+                    value: Some(KeyValue {
+                        value: store.construct_storable(&request.key, b"value".to_vec(), 0),
+                        key: request.key,
+                        version: 1,
+                    }),
+                }
+                .encode_to_vec();
+                let head = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    response.len()
+                );
+                stream.write_all(head.as_bytes()).unwrap();
+                stream.write_all(&response).unwrap();
+            }
+        });
+        requests
+    }
+
+    #[tokio::test]
+    async fn get_is_served_from_cache() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let store = Arc::new(
+            VssStore::new(
+                VssClient::new(url, NoRetry),
+                "store-id".to_string(),
+                [1u8; 32],
+            )
+            .with_cache(10, Duration::from_secs(60))
+            .with_uncached_key("lock"),
+        );
+        let requests = serve_values(listener, store.clone());
+
+        for _ in 0..2 {
+            let value = store.get("key".to_string()).await.unwrap();
+            assert_eq!(value, Some((b"value".to_vec(), 1)));
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+        // The uncached key is read from the server every time.
+        for _ in 0..2 {
+            let value = store.get("lock".to_string()).await.unwrap();
+            assert_eq!(value, Some((b"value".to_vec(), 1)));
+        }
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn value_cache_hit_and_invalidation() {
+        let cache = ValueCache::new(10, Duration::from_secs(60));
+        assert_eq!(cache.get("key"), None);
+
+        cache.insert(0, "key".to_string(), b"value".to_vec(), 1);
+        assert_eq!(cache.get("key"), Some((b"value".to_vec(), 1)));
+        assert_eq!(cache.get("key"), Some((b"value".to_vec(), 1)));
+
+        cache.insert(0, "key".to_string(), b"value2".to_vec(), 2);
+        assert_eq!(cache.get("key"), Some((b"value2".to_vec(), 2)));
+
+        cache.remove("key");
+        assert_eq!(cache.get("key"), None);
+    }
+
+    #[test]
+    fn value_cache_skips_values_read_during_a_change() {
+        let cache = ValueCache::new(10, Duration::from_secs(60));
+        // A get misses the cache and requests the value from the server.
+        let generation = cache.generation();
+        // Meanwhile a put changes the value and invalidates it.
+        cache.remove("key");
+        // The value the get read may be the one from before the put.
+        cache.insert(generation, "key".to_string(), b"old".to_vec(), 1);
+        assert_eq!(cache.get("key"), None);
+
+        // A get after the put caches the value it reads.
+        let generation = cache.generation();
+        cache.insert(generation, "key".to_string(), b"new".to_vec(), 2);
+        assert_eq!(cache.get("key"), Some((b"new".to_vec(), 2)));
+    }
+
+    #[test]
+    fn value_cache_evicts_least_recently_used() {
+        let cache = ValueCache::new(2, Duration::from_secs(60));
+        cache.insert(0, "a".to_string(), b"a".to_vec(), 1);
+        cache.insert(0, "b".to_string(), b"b".to_vec(), 1);
+        // Touch `a` so that `b` becomes the least recently used.
+        assert!(cache.get("a").is_some());
+        cache.insert(0, "c".to_string(), b"c".to_vec(), 1);
+
+        assert!(cache.get("a").is_some());
+        assert_eq!(cache.get("b"), None);
+        assert!(cache.get("c").is_some());
+    }
+
+    #[test]
+    fn value_cache_expires_entries() {
+        let cache = ValueCache::new(10, Duration::ZERO);
+        cache.insert(0, "key".to_string(), b"value".to_vec(), 1);
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.get("key"), None);
+    }
 }
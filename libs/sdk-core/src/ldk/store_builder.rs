@@ -1,20 +1,24 @@
 use std::collections::HashMap;
-use std::fs;
+use std::fs::{self, File, TryLockError};
 use std::io::ErrorKind;
-use std::path::Path;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use bitcoin::bip32::{ChildNumber, Xpriv};
-use bitcoin::secp256k1::{PublicKey, Secp256k1};
+use bitcoin::hashes::sha256::Hash as Sha256;
+use bitcoin::hashes::Hash;
+use bitcoin::secp256k1::{PublicKey, Secp256k1, SecretKey};
 use hex::ToHex;
 use r2d2::Pool;
 use r2d2_sqlite::SqliteConnectionManager;
 use rand::distributions::Alphanumeric;
 use rand::Rng;
+use sdk_common::ensure_sdk;
 use sdk_common::prelude::Network;
 use tokio::runtime::Handle;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 use vss_client_ng::client::VssClient;
 use vss_client_ng::error::VssError;
 use vss_client_ng::headers::sigs_auth::SigsAuthProvider;
@@ -23,10 +27,10 @@ use vss_client_ng::util::retry::{
     MaxAttemptsRetryPolicy, MaxTotalDelayRetryPolicy, RetryPolicy,
 };
 
-use crate::ldk::store::{PreviousHolder, VssStore};
-use crate::node_api::NodeResult;
+use crate::ldk::store::{PreviousHolder, RemoteError, VersionedStore, VssStore};
+use crate::node_api::{NodeError, NodeResult, RemoteLockEvent, RestoreEvent, RestoreEventListener};
 use crate::persist::error::PersistError;
-use crate::Config;
+use crate::{Config, VssCheckResult};
 
 pub(crate) type CustomRetryPolicy = FilteredRetryPolicy<
     JitteredRetryPolicy<
@@ -39,39 +43,176 @@ pub(crate) type LockingStore = crate::ldk::store::LockingStore<VssStore<CustomRe
 pub(crate) type MirroringStore = crate::ldk::store::MirroringStore<Arc<LockingStore>, LockingStore>;
 
 const VSS_HARDENED_CHILD_INDEX: u32 = 877;
+const VSS_CHECK_STORE_ID: &str = "vss_check";
+pub(crate) const NODE_STORE_ID: &str = "ldk_node";
+pub(crate) const BACKUP_STORE_ID: &str = "backups";
+/// The subdirectory of the working directory holding the local copy of the node state along
+/// with the id of the instance it belongs to, which are only valid together.
+const LOCAL_CACHE_DIR: &str = "node_cache_v1";
+/// Where the files of the legacy layout are gathered before becoming the local cache.
+const LOCAL_CACHE_STAGING_DIR: &str = "node_cache_v1.tmp";
+const INSTANCE_ID_FILE: &str = "instance_id";
+/// The fingerprint of the seed the node state was created with, kept both in the local cache
+/// and on the remote storage.
+const SEED_FINGERPRINT_FILE: &str = "seed_fingerprint";
+const SEED_FINGERPRINT_KEY: &str = "seed_fingerprint";
+const WORKING_DIR_LOCK_FILE: &str = ".lock";
+const SQLITE_FILE: &str = "ldk_node_storage.sql";
+/// Pragmas set on every connection to the local copy of the node state. With `secure_delete`
+/// the content of removed values, like preimages, is overwritten instead of left in the file.
+const DEFAULT_SQLITE_PRAGMAS: &[(&str, &str)] = &[("secure_delete", "ON")];
 const API_KEY_HEADER: &str = "X-Api-Key";
 const USER_PUBKEY_HEADER: &str = "X-Pubkey";
+const USER_AGENT_HEADER: &str = "User-Agent";
+const MAX_VSS_CLIENT_ID_LEN: usize = 128;
+const VSS_CACHE_CAPACITY: usize = 100;
+const VSS_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
+const MIN_REMOTE_LOCK_LEASE: Duration = Duration::from_secs(10);
+/// A failed refresh of the remote lock is retried at least this many times before the
+/// refresh window runs out.
+const MIN_REMOTE_LOCK_REFRESH_ATTEMPTS: u32 = 3;
 
-pub(crate) fn build_vss_store(
-    config: &Config,
-    seed: &[u8],
-    store_id: &str,
-) -> NodeResult<VssStore<CustomRetryPolicy>> {
+/// Derives the key used to sign the requests to VSS and to identify the user.
+fn derive_vss_key(network: Network, seed: &[u8]) -> NodeResult<(SecretKey, PublicKey)> {
     let secp = Secp256k1::new();
-    let bitcoin_network: bitcoin::Network = config.network.into();
+    let bitcoin_network: bitcoin::Network = network.into();
     let xprv = Xpriv::new_master(bitcoin_network, seed)?.derive_priv(
         &secp,
         &[ChildNumber::Hardened {
             index: VSS_HARDENED_CHILD_INDEX,
         }],
     )?;
-    let private_key = xprv.private_key;
-    let pubkey = PublicKey::from_secret_key(&secp, &private_key);
-    let pubkey_hex = pubkey.serialize().encode_hex::<String>();
+    let pubkey = PublicKey::from_secret_key(&secp, &xprv.private_key);
+    Ok((xprv.private_key, pubkey))
+}
 
-    let vss_seed = xprv.private_key.secret_bytes();
-    let store_id = match config.network {
-        Network::Regtest => {
-            // Regtest instance of VSS does not implement authentication,
-            // that is why the pubkey is used to avoid collisions.
-            format!("{pubkey_hex}/{store_id}")
-        }
-        _ => store_id.to_string(),
-    };
+/// Returns a non-secret fingerprint of the seed: the hash of the VSS pubkey, which
+/// identifies the VSS store the node state is kept in.
+pub(crate) fn recovery_fingerprint(network: Network, seed: &[u8]) -> NodeResult<String> {
+    let (_, pubkey) = derive_vss_key(network, seed)?;
+    Ok(Sha256::hash(&pubkey.serialize()).to_string())
+}
+
+pub(crate) fn build_vss_store(
+    config: &Config,
+    seed: &[u8],
+    store_id: &str,
+) -> NodeResult<VssStore<CustomRetryPolicy>> {
+    let retry_policy = retry_policy(10, Duration::from_secs(40));
+    build_vss_store_with_retry_policy(config, seed, store_id, retry_policy)
+}
+
+/// Checks that VSS is reachable and accepts the credentials derived from the seed, by
+/// writing, reading back and deleting a throwaway key.
+pub(crate) async fn check_vss(config: &Config, seed: &[u8]) -> NodeResult<VssCheckResult> {
+    // A single attempt, to report a failure promptly.
+    let retry_policy = retry_policy(1, Duration::from_secs(10));
+    let store = build_vss_store_with_retry_policy(config, seed, VSS_CHECK_STORE_ID, retry_policy)?;
+    let key = format!("check_{}", generate_instance_id());
+    let value = key.clone().into_bytes();
+    let result = async {
+        store.put(key.clone(), value.clone(), 0).await?;
+        let stored = store.get(key.clone()).await?;
+        ensure_sdk!(
+            stored.is_some_and(|(stored, _)| stored == value),
+            RemoteError::Internal("The written value was not read back".to_string())
+        );
+        store.delete(key).await
+    }
+    .await;
+    Ok(match result {
+        Ok(()) => VssCheckResult::Ok,
+        Err(RemoteError::Unreachable(err)) => VssCheckResult::ConnectivityFailure { err },
+        Err(RemoteError::Unauthorized(err)) => VssCheckResult::AuthFailure { err },
+        Err(err) => VssCheckResult::UnexpectedFailure {
+            err: err.to_string(),
+        },
+    })
+}
 
-    let retry_policy = ExponentialBackoffRetryPolicy::new(Duration::from_secs(1))
-        .with_max_attempts(10)
-        .with_max_total_delay(Duration::from_secs(40))
+/// Copies the node state from the VSS server of `config` to the one of `new_config`.
+///
+/// The values stay encrypted with the keys derived from the seed, while the requests are
+/// authenticated for the new server. Keys already copied are skipped, so an interrupted
+/// migration can be run again.
+pub(crate) async fn migrate_vss(
+    config: &Config,
+    new_config: &Config,
+    seed: &[u8],
+) -> NodeResult<()> {
+    for store_id in [NODE_STORE_ID, BACKUP_STORE_ID] {
+        let source = build_vss_store(config, seed, store_id)?;
+        let destination = build_vss_store(new_config, seed, store_id)?;
+        let migrated = migrate_store(&source, &destination).await.map_err(|e| {
+            PersistError::Generic(format!("Failed to migrate store {store_id}: {e}"))
+        })?;
+        info!("Migrated {migrated} keys of store {store_id}");
+    }
+    Ok(())
+}
+
+/// Copies the keys of `source` which are missing or differ in `destination`, then checks that
+/// both hold the same number of keys. Returns the number of keys in `source`.
+async fn migrate_store<S: VersionedStore, D: VersionedStore>(
+    source: &S,
+    destination: &D,
+) -> Result<usize, RemoteError> {
+    let keys = source.list().await?;
+    for (key, _) in &keys {
+        let Some((value, _)) = source.get(key.clone()).await? else {
+            continue;
+        };
+        let version = match destination.get(key.clone()).await? {
+            Some((existing, _)) if existing == value => continue,
+            Some((_, version)) => version,
+            None => 0,
+        };
+        destination.put(key.clone(), value, version).await?;
+    }
+
+    let migrated = destination.list().await?.len();
+    ensure_sdk!(
+        migrated == keys.len(),
+        RemoteError::Internal(format!(
+            "The new store holds {migrated} keys instead of {}",
+            keys.len()
+        ))
+    );
+    Ok(keys.len())
+}
+
+/// Takes an exclusive lock on the working directory, held until the returned file is dropped.
+///
+/// Unlike the remote lock, it promptly detects another instance started on the same host
+/// with the same working directory, which would share the local cache and instance id.
+pub(crate) fn lock_working_dir(working_dir: &str) -> NodeResult<File> {
+    fs::create_dir_all(working_dir).map_err(|e| {
+        PersistError::Generic(format!("Failed to create directory {working_dir}: {e}"))
+    })?;
+    let filepath = Path::new(working_dir).join(WORKING_DIR_LOCK_FILE);
+    let file = File::create(&filepath).map_err(|e| {
+        PersistError::Generic(format!(
+            "Failed to create file {}: {e}",
+            filepath.to_string_lossy()
+        ))
+    })?;
+    match file.try_lock() {
+        Ok(()) => Ok(file),
+        Err(TryLockError::WouldBlock) => Err(NodeError::NodeLocked(format!(
+            "Working directory {working_dir} is already in use"
+        ))),
+        Err(TryLockError::Error(e)) => Err(PersistError::Generic(format!(
+            "Failed to lock file {}: {e}",
+            filepath.to_string_lossy()
+        ))
+        .into()),
+    }
+}
+
+fn retry_policy(max_attempts: u32, max_total_delay: Duration) -> CustomRetryPolicy {
+    ExponentialBackoffRetryPolicy::new(Duration::from_secs(1))
+        .with_max_attempts(max_attempts)
+        .with_max_total_delay(max_total_delay)
         .with_max_jitter(Duration::from_millis(10))
         .skip_retry_on_error(Box::new(|e: &VssError| {
             matches!(
@@ -80,57 +221,384 @@ pub(crate) fn build_vss_store(
                     | VssError::InvalidRequestError(..)
                     | VssError::ConflictError(..)
             )
-        }) as _);
+        }) as _)
+}
+
+fn vss_store_id(network: Network, pubkey_hex: &str, store_id: &str) -> String {
+    match network {
+        Network::Regtest => {
+            // Regtest instance of VSS does not implement authentication,
+            // that is why the pubkey is used to avoid collisions.
+            format!("{pubkey_hex}/{store_id}")
+        }
+        // The other instances authenticate the requests and scope the stores by the user.
+        _ => store_id.to_string(),
+    }
+}
 
+fn build_vss_store_with_retry_policy(
+    config: &Config,
+    seed: &[u8],
+    store_id: &str,
+    retry_policy: CustomRetryPolicy,
+) -> NodeResult<VssStore<CustomRetryPolicy>> {
+    let (private_key, pubkey) = derive_vss_key(config.network, seed)?;
+    let pubkey_hex = pubkey.serialize().encode_hex::<String>();
+
+    let vss_seed = private_key.secret_bytes();
+    let store_id = vss_store_id(config.network, &pubkey_hex, store_id);
+
+    let header_provider = build_header_provider(config, private_key, pubkey_hex)?;
+    let header_provider = Arc::new(header_provider);
+
+    let vss_client =
+        VssClient::new_with_headers(config.vss_url.clone(), retry_policy, header_provider);
+    Ok(VssStore::new(vss_client, store_id, vss_seed)
+        .with_max_value_size(config.max_store_value_bytes as usize))
+}
+
+/// Builds the provider of the headers sent on every request to VSS, authenticating the
+/// requests and identifying the client.
+fn build_header_provider(
+    config: &Config,
+    private_key: SecretKey,
+    pubkey_hex: String,
+) -> NodeResult<SigsAuthProvider> {
     let api_key = config.api_key.clone().unwrap_or_default();
     let headers = HashMap::from([
         (API_KEY_HEADER.to_string(), api_key),
         (USER_PUBKEY_HEADER.to_string(), pubkey_hex),
+        (USER_AGENT_HEADER.to_string(), vss_user_agent(config)?),
     ]);
-    let header_provider = SigsAuthProvider::new(private_key, headers);
-    let header_provider = Arc::new(header_provider);
+    Ok(SigsAuthProvider::new(private_key, headers))
+}
 
-    let vss_client =
-        VssClient::new_with_headers(config.vss_url.clone(), retry_policy, header_provider);
-    Ok(VssStore::new(vss_client, store_id, vss_seed))
+/// Returns the configured client id, if any, or the SDK name and version.
+fn vss_user_agent(config: &Config) -> NodeResult<String> {
+    let Some(client_id) = &config.vss_client_id else {
+        return Ok(format!("breez-sdk/{}", env!("CARGO_PKG_VERSION")));
+    };
+    ensure_sdk!(
+        !client_id.is_empty()
+            && client_id.len() <= MAX_VSS_CLIENT_ID_LEN
+            && client_id.bytes().all(|b| b.is_ascii_graphic() || b == b' '),
+        NodeError::Generic(format!(
+            "VSS client id must be printable ASCII of 1 to {MAX_VSS_CLIENT_ID_LEN} characters"
+        ))
+    );
+    Ok(client_id.clone())
 }
 
+/// Builds the store of the node, along with whether the node state was downloaded from the
+/// remote storage, as it was last modified by another instance.
 pub(crate) async fn build_mirroring_store(
-    working_dir: &str,
+    config: &Config,
+    seed: &[u8],
     vss_store: VssStore<CustomRetryPolicy>,
+    remote_lock_events_tx: watch::Sender<RemoteLockEvent>,
     remote_lock_shutdown_rx: mpsc::Receiver<()>,
-) -> NodeResult<MirroringStore> {
-    let (locking_store, previous_holder) =
-        build_locking_store(working_dir, vss_store, remote_lock_shutdown_rx).await?;
-
-    let sqlite_file_path = Path::new(working_dir).join("ldk_node_storage.sql");
-    let manager = SqliteConnectionManager::file(sqlite_file_path);
-    let pool = Pool::new(manager)
-        .map_err(|e| PersistError::Sql(format!("Failed to create sqlite connection pool: {e}")))?;
-    MirroringStore::new(Handle::current(), pool, locking_store, previous_holder)
+    restore_event_listener: Option<&RestoreEventListener>,
+) -> NodeResult<(MirroringStore, bool)> {
+    let (lock_lease, lock_retry_interval) = remote_lock_timings(config)?;
+    let cache_dir = prepare_local_cache_dir(&config.working_dir)?;
+    if config.restrict_file_permissions {
+        restrict_permissions(&cache_dir)?;
+    }
+    let fingerprint = recovery_fingerprint(config.network, seed)?;
+    check_seed_fingerprint(&cache_dir, &vss_store, &fingerprint).await?;
+    // The remote lock guarantees no one else modifies the store, so caching is safe, except
+    // for the lock itself, which other instances read and take over.
+    let vss_store = vss_store
+        .with_cache(VSS_CACHE_CAPACITY, VSS_CACHE_TTL)
+        .with_uncached_key(LockingStore::KEY);
+    let (locking_store, previous_holder) = build_locking_store(
+        &cache_dir,
+        vss_store,
+        lock_lease,
+        lock_retry_interval,
+        remote_lock_events_tx,
+        remote_lock_shutdown_rx,
+    )
+    .await?;
+
+    let pool = build_sqlite_pool(&cache_dir.join(SQLITE_FILE), DEFAULT_SQLITE_PRAGMAS)?;
+    if config.restrict_file_permissions {
+        // Covers the files created by SQLite as well.
+        restrict_permissions(&cache_dir)?;
+    }
+    let is_restore = previous_holder == PreviousHolder::RemoteInstance;
+    let mirroring_store = mirror_remote_store(
+        pool,
+        locking_store,
+        previous_holder,
+        config.allow_state_regression,
+        restore_event_listener,
+    )
+    .await?
+    .with_max_value_size(config.max_store_value_bytes as usize);
+    Ok((mirroring_store, is_restore))
+}
+
+/// Checks that the local cache and the remote storage belong to the seed with the given
+/// fingerprint, recording it where it is missing. Otherwise the state of another node would
+/// be mixed in, failing later with errors like undecryptable values.
+async fn check_seed_fingerprint<T: VersionedStore>(
+    cache_dir: &Path,
+    remote: &T,
+    fingerprint: &str,
+) -> NodeResult<()> {
+    let mismatch = |location: &str| {
+        NodeError::SeedMismatch(format!(
+            "The seed does not match the node state in the {location} store"
+        ))
+    };
+
+    let filepath = cache_dir.join(SEED_FINGERPRINT_FILE);
+    match fs::read_to_string(&filepath) {
+        Ok(stored) => ensure_sdk!(stored.trim() == fingerprint, mismatch("local")),
+        Err(e) if e.kind() == ErrorKind::NotFound => {
+            fs::write(&filepath, fingerprint).map_err(|e| {
+                PersistError::Generic(format!(
+                    "Failed to create file {}: {e}",
+                    filepath.to_string_lossy()
+                ))
+            })?
+        }
+        Err(e) => {
+            return Err(PersistError::Generic(format!(
+                "Failed to read file {}: {e}",
+                filepath.to_string_lossy()
+            ))
+            .into())
+        }
+    }
+
+    let remote_error = |e: RemoteError| {
+        PersistError::Generic(format!("Failed to check the seed fingerprint: {e}"))
+    };
+    match remote
+        .get(SEED_FINGERPRINT_KEY.to_string())
         .await
-        .map_err(Into::into)
+        .map_err(remote_error)?
+    {
+        Some((stored, _)) => ensure_sdk!(stored == fingerprint.as_bytes(), mismatch("remote")),
+        None => remote
+            .put(
+                SEED_FINGERPRINT_KEY.to_string(),
+                fingerprint.as_bytes().to_vec(),
+                0,
+            )
+            .await
+            .map_err(remote_error)?,
+    }
+    Ok(())
+}
+
+/// Creates a connection pool to the SQLite database at `path`, setting the given pragmas on
+/// every new connection.
+fn build_sqlite_pool(
+    path: &Path,
+    pragmas: &'static [(&'static str, &'static str)],
+) -> NodeResult<Pool<SqliteConnectionManager>> {
+    let manager = SqliteConnectionManager::file(path).with_init(move |conn| {
+        for (name, value) in pragmas {
+            // Some pragmas report their new value, so the result is stepped through.
+            let mut stmt = conn.prepare(&format!("PRAGMA {name} = {value}"))?;
+            stmt.query([])?.next()?;
+        }
+        Ok(())
+    });
+    Ok(Pool::new(manager)
+        .map_err(|e| PersistError::Sql(format!("Failed to create sqlite connection pool: {e}")))?)
+}
+
+/// Mirrors the remote store locally, reporting the download of the node state when it was
+/// last modified by another instance.
+async fn mirror_remote_store<S: Deref<Target = T>, T: VersionedStore + Send + Sync>(
+    pool: Pool<SqliteConnectionManager>,
+    remote: S,
+    previous_holder: PreviousHolder,
+    allow_state_regression: bool,
+    restore_event_listener: Option<&RestoreEventListener>,
+) -> NodeResult<crate::ldk::store::MirroringStore<S, T>> {
+    let is_restore = previous_holder == PreviousHolder::RemoteInstance;
+    let notify = |event: RestoreEvent| {
+        if let Some(listener) = restore_event_listener.filter(|_| is_restore) {
+            listener(event);
+        }
+    };
+    notify(RestoreEvent::DownloadStarted);
+    let mirroring_store = crate::ldk::store::MirroringStore::new(
+        Handle::current(),
+        pool,
+        remote,
+        previous_holder,
+        allow_state_regression,
+    )
+    .await?;
+    notify(RestoreEvent::DownloadCompleted);
+    Ok(mirroring_store)
+}
+
+/// Returns the lease and the retry interval of the remote lock, making sure that a failed
+/// refresh can be retried a few times before the lock expires.
+fn remote_lock_timings(config: &Config) -> NodeResult<(Duration, Duration)> {
+    let lease = Duration::from_secs(config.remote_lock_lease_secs.into());
+    let retry_interval = Duration::from_secs(config.remote_lock_retry_secs.into());
+    ensure_sdk!(
+        lease >= MIN_REMOTE_LOCK_LEASE,
+        NodeError::Generic(format!(
+            "Remote lock lease must be at least {MIN_REMOTE_LOCK_LEASE:?}, but is {lease:?}"
+        ))
+    );
+    ensure_sdk!(
+        !retry_interval.is_zero()
+            && retry_interval * MIN_REMOTE_LOCK_REFRESH_ATTEMPTS <= lease / 2,
+        NodeError::Generic(format!(
+            "Remote lock retry interval of {retry_interval:?} is not short enough for a lease of {lease:?}"
+        ))
+    );
+    Ok((lease, retry_interval))
 }
 
 async fn build_locking_store(
-    working_dir: &str,
+    cache_dir: &Path,
     vss_store: VssStore<CustomRetryPolicy>,
+    lock_lease: Duration,
+    lock_retry_interval: Duration,
+    remote_lock_events_tx: watch::Sender<RemoteLockEvent>,
     remote_lock_shutdown_rx: mpsc::Receiver<()>,
 ) -> NodeResult<(Arc<LockingStore>, PreviousHolder)> {
-    let instance_id = read_or_generate_instance_id(working_dir)?;
-    let (locking_store, previous_holder) = LockingStore::new(instance_id, vss_store)
+    let instance_id = read_or_generate_instance_id(cache_dir)?;
+    let (locking_store, previous_holder) = LockingStore::new(instance_id, lock_lease, vss_store)
         .await
-        .map_err(|e| PersistError::Generic(format!("Failed to build locking store: {e}")))?;
+        .map_err(|e| match e {
+            RemoteError::Locked(e) => NodeError::NodeLocked(e),
+            e => PersistError::Generic(format!("Failed to build locking store: {e}")).into(),
+        })?;
     let locking_store = Arc::new(locking_store);
     tokio::task::spawn(start_refreshing(
         Arc::clone(&locking_store),
+        lock_retry_interval,
+        remote_lock_events_tx,
         remote_lock_shutdown_rx,
     ));
     Ok((locking_store, previous_holder))
 }
 
-fn read_or_generate_instance_id(working_dir: &str) -> Result<String, PersistError> {
-    let filepath = Path::new(working_dir).join("instance_id");
+/// Creates the local cache directory, moving there the files of the legacy layout in which
+/// they were kept directly in the working directory.
+///
+/// The files are gathered in a staging directory which is renamed once all of them were
+/// moved, so a migration interrupted by a crash is resumed on the next start.
+fn prepare_local_cache_dir(working_dir: &str) -> Result<PathBuf, PersistError> {
+    let cache_dir = Path::new(working_dir).join(LOCAL_CACHE_DIR);
+    if cache_dir.exists() {
+        return Ok(cache_dir);
+    }
+    let staging_dir = Path::new(working_dir).join(LOCAL_CACHE_STAGING_DIR);
+    fs::create_dir_all(&staging_dir).map_err(|e| {
+        PersistError::Generic(format!(
+            "Failed to create directory {}: {e}",
+            staging_dir.to_string_lossy()
+        ))
+    })?;
+    for filename in legacy_cache_files() {
+        let legacy_path = Path::new(working_dir).join(&filename);
+        if legacy_path.exists() {
+            info!("Moving {filename} to {LOCAL_CACHE_DIR}");
+            fs::rename(&legacy_path, staging_dir.join(&filename)).map_err(|e| {
+                PersistError::Generic(format!(
+                    "Failed to move file {}: {e}",
+                    legacy_path.to_string_lossy()
+                ))
+            })?;
+        }
+    }
+    fs::rename(&staging_dir, &cache_dir).map_err(|e| {
+        PersistError::Generic(format!(
+            "Failed to move directory {}: {e}",
+            staging_dir.to_string_lossy()
+        ))
+    })?;
+    Ok(cache_dir)
+}
+
+/// Makes the local cache directory and the files in it only accessible by the user running
+/// the SDK, see [Config::restrict_file_permissions].
+#[cfg(unix)]
+fn restrict_permissions(cache_dir: &Path) -> Result<(), PersistError> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let set_mode = |path: &Path, mode: u32| {
+        fs::set_permissions(path, fs::Permissions::from_mode(mode)).map_err(|e| {
+            PersistError::Generic(format!(
+                "Failed to set permissions of {}: {e}",
+                path.to_string_lossy()
+            ))
+        })
+    };
+    let read_dir_error = |e: std::io::Error| {
+        PersistError::Generic(format!(
+            "Failed to read directory {}: {e}",
+            cache_dir.to_string_lossy()
+        ))
+    };
+
+    set_mode(cache_dir, 0o700)?;
+    for entry in fs::read_dir(cache_dir).map_err(read_dir_error)? {
+        let path = entry.map_err(read_dir_error)?.path();
+        if path.is_file() {
+            set_mode(&path, 0o600)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn restrict_permissions(_cache_dir: &Path) -> Result<(), PersistError> {
+    Ok(())
+}
+
+fn legacy_cache_files() -> Vec<String> {
+    vec![
+        INSTANCE_ID_FILE.to_string(),
+        SQLITE_FILE.to_string(),
+        format!("{SQLITE_FILE}-wal"),
+        format!("{SQLITE_FILE}-shm"),
+    ]
+}
+
+/// Removes the local copy of the node state along with the instance id, so that the next
+/// start is treated as a new instance and downloads the node state from VSS.
+///
+/// Must not be called while the node is running.
+pub(crate) fn reset_local_cache(working_dir: &str) -> NodeResult<()> {
+    let working_dir = Path::new(working_dir);
+    let cache_dir = working_dir.join(LOCAL_CACHE_DIR);
+    ignore_not_found(&cache_dir, fs::remove_dir_all(&cache_dir))?;
+    let staging_dir = working_dir.join(LOCAL_CACHE_STAGING_DIR);
+    ignore_not_found(&staging_dir, fs::remove_dir_all(&staging_dir))?;
+    for filename in legacy_cache_files() {
+        let path = working_dir.join(filename);
+        ignore_not_found(&path, fs::remove_file(&path))?;
+    }
+    info!("Local cache was reset");
+    Ok(())
+}
+
+fn ignore_not_found(path: &Path, result: std::io::Result<()>) -> Result<(), PersistError> {
+    match result {
+        Err(e) if e.kind() != ErrorKind::NotFound => Err(PersistError::Generic(format!(
+            "Failed to remove {}: {e}",
+            path.to_string_lossy()
+        ))),
+        _ => Ok(()),
+    }
+}
+
+fn read_or_generate_instance_id(cache_dir: &Path) -> Result<String, PersistError> {
+    let filepath = cache_dir.join(INSTANCE_ID_FILE);
     match fs::read_to_string(&filepath) {
         Ok(instance_id) => Ok(instance_id.trim().to_string()),
         Err(e) if e.kind() == ErrorKind::NotFound => {
@@ -158,18 +626,37 @@ fn generate_instance_id() -> String {
         .collect()
 }
 
-async fn start_refreshing(locking_store: Arc<LockingStore>, mut shutdown_rx: mpsc::Receiver<()>) {
+async fn start_refreshing<S: VersionedStore + Send + Sync>(
+    locking_store: Arc<crate::ldk::store::LockingStore<S>>,
+    retry_interval: Duration,
+    events_tx: watch::Sender<RemoteLockEvent>,
+    mut shutdown_rx: mpsc::Receiver<()>,
+) {
     loop {
-        let duration = match locking_store.refresh_lock().await {
+        let (duration, event) = match locking_store.refresh_lock().await {
             Ok(until) => {
                 trace!("Remote lock was refreshed");
-                until.duration_since(SystemTime::now()).unwrap_or_default()
+                let duration = until.duration_since(SystemTime::now()).unwrap_or_default();
+                (duration, RemoteLockEvent::Acquired)
+            }
+            Err(RemoteError::Conflict(e)) => {
+                error!("Remote lock was taken over by another instance: {e}");
+                (retry_interval, RemoteLockEvent::Lost)
+            }
+            Err(e) if !locking_store.is_locked().await => {
+                error!("Remote lock expired: {e:?}");
+                (retry_interval, RemoteLockEvent::Lost)
             }
             Err(e) => {
                 warn!("Failed to refresh remote lock: {e:?}");
-                Duration::from_secs(5)
+                (retry_interval, RemoteLockEvent::RefreshFailed)
             }
         };
+        events_tx.send_if_modified(|current| {
+            let modified = *current != event;
+            *current = event;
+            modified
+        });
         tokio::select! {
             biased; // Prioritise shutdown event.
             _ = shutdown_rx.recv() => break,
@@ -185,3 +672,357 @@ async fn start_refreshing(locking_store: Arc<LockingStore>, mut shutdown_rx: mps
     // Explicitly drop the receiver to let the sender know we are done with releasing the lock.
     drop(shutdown_rx);
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use ldk_node::lightning::util::persist::KVStoreSync;
+    use vss_client_ng::headers::VssHeaderProvider;
+
+    use super::*;
+    use crate::ldk::store::MockVersionedStore;
+
+    type LockingStore = crate::ldk::store::LockingStore<MockVersionedStore>;
+
+    #[test]
+    fn test_vss_store_id() {
+        assert_eq!(
+            vss_store_id(Network::Regtest, "02ab", NODE_STORE_ID),
+            format!("02ab/{NODE_STORE_ID}")
+        );
+        assert_eq!(
+            vss_store_id(Network::Signet, "02ab", NODE_STORE_ID),
+            NODE_STORE_ID
+        );
+        assert_eq!(
+            vss_store_id(Network::Bitcoin, "02ab", BACKUP_STORE_ID),
+            BACKUP_STORE_ID
+        );
+    }
+
+    #[test]
+    fn test_remote_lock_timings() {
+        let mut config = Config::regtest(String::new());
+        assert_eq!(
+            remote_lock_timings(&config).unwrap(),
+            (Duration::from_secs(60), Duration::from_secs(5))
+        );
+
+        config.remote_lock_lease_secs = 20;
+        config.remote_lock_retry_secs = 2;
+        assert_eq!(
+            remote_lock_timings(&config).unwrap(),
+            (Duration::from_secs(20), Duration::from_secs(2))
+        );
+
+        config.remote_lock_retry_secs = 4;
+        assert!(remote_lock_timings(&config).is_err());
+
+        config.remote_lock_retry_secs = 0;
+        assert!(remote_lock_timings(&config).is_err());
+
+        config.remote_lock_lease_secs = 5;
+        config.remote_lock_retry_secs = 1;
+        assert!(remote_lock_timings(&config).is_err());
+    }
+
+    #[test]
+    fn test_recovery_fingerprint() {
+        let seed = [1; 64];
+        let fingerprint = recovery_fingerprint(Network::Regtest, &seed).unwrap();
+        assert_eq!(fingerprint.len(), 64);
+        assert_eq!(
+            recovery_fingerprint(Network::Regtest, &seed).unwrap(),
+            fingerprint
+        );
+        assert_eq!(
+            recovery_fingerprint(Network::Bitcoin, &seed).unwrap(),
+            fingerprint
+        );
+        assert_ne!(
+            recovery_fingerprint(Network::Regtest, &[2; 64]).unwrap(),
+            fingerprint
+        );
+    }
+
+    #[test]
+    fn test_lock_working_dir() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let working_dir = temp_dir.path().join("working_dir");
+        let working_dir = working_dir.to_str().unwrap();
+
+        let lock = lock_working_dir(working_dir).unwrap();
+        assert!(matches!(
+            lock_working_dir(working_dir),
+            Err(NodeError::NodeLocked(_))
+        ));
+
+        drop(lock);
+        lock_working_dir(working_dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_restrict_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let working_dir = temp_dir.path().to_str().unwrap();
+        let cache_dir = prepare_local_cache_dir(working_dir).unwrap();
+        read_or_generate_instance_id(&cache_dir).unwrap();
+        let sqlite_file = cache_dir.join(SQLITE_FILE);
+        fs::write(&sqlite_file, "").unwrap();
+        fs::set_permissions(&sqlite_file, fs::Permissions::from_mode(0o644)).unwrap();
+
+        restrict_permissions(&cache_dir).unwrap();
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode(&cache_dir), 0o700);
+        assert_eq!(mode(&cache_dir.join(INSTANCE_ID_FILE)), 0o600);
+        assert_eq!(mode(&sqlite_file), 0o600);
+    }
+
+    #[test]
+    fn test_prepare_local_cache_dir_resumes_migration() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let working_dir = temp_dir.path();
+        // A crash left the instance id in the staging directory and the sqlite file behind.
+        let staging_dir = working_dir.join(LOCAL_CACHE_STAGING_DIR);
+        fs::create_dir(&staging_dir).unwrap();
+        fs::write(staging_dir.join(INSTANCE_ID_FILE), "legacy").unwrap();
+        fs::write(working_dir.join(SQLITE_FILE), "state").unwrap();
+
+        let cache_dir = prepare_local_cache_dir(working_dir.to_str().unwrap()).unwrap();
+        assert_eq!(read_or_generate_instance_id(&cache_dir).unwrap(), "legacy");
+        assert_eq!(
+            fs::read_to_string(cache_dir.join(SQLITE_FILE)).unwrap(),
+            "state"
+        );
+        assert!(!working_dir.join(SQLITE_FILE).exists());
+        assert!(!staging_dir.exists());
+    }
+
+    #[tokio::test]
+    async fn test_reset_local_cache() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let working_dir = temp_dir.path().to_str().unwrap();
+        let store = MockVersionedStore::default();
+        let start = |store: MockVersionedStore| async move {
+            let cache_dir = prepare_local_cache_dir(working_dir).unwrap();
+            let instance_id = read_or_generate_instance_id(&cache_dir).unwrap();
+            let (locking_store, previous_holder) =
+                LockingStore::new(instance_id, Duration::from_secs(60), store)
+                    .await
+                    .unwrap();
+            locking_store.unlock().await.unwrap();
+            previous_holder
+        };
+
+        // The instance id of the legacy layout is kept.
+        fs::write(Path::new(working_dir).join(INSTANCE_ID_FILE), "legacy").unwrap();
+        start(store.clone()).await;
+        let cache_dir = Path::new(working_dir).join(LOCAL_CACHE_DIR);
+        assert_eq!(read_or_generate_instance_id(&cache_dir).unwrap(), "legacy");
+        assert!(!Path::new(working_dir).join(INSTANCE_ID_FILE).exists());
+
+        assert_eq!(start(store.clone()).await, PreviousHolder::LocalInstance);
+
+        // After a reset, the node state is downloaded from remote.
+        reset_local_cache(working_dir).unwrap();
+        assert!(!cache_dir.exists());
+        assert_eq!(start(store.clone()).await, PreviousHolder::RemoteInstance);
+        assert_eq!(start(store.clone()).await, PreviousHolder::LocalInstance);
+
+        // Resetting a missing cache is a no-op.
+        fs::remove_dir_all(&cache_dir).unwrap();
+        reset_local_cache(working_dir).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mirror_remote_store_restore_events() {
+        let store = Arc::new(MockVersionedStore::default());
+        let pool = || Pool::new(SqliteConnectionManager::memory()).unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let listener: RestoreEventListener = {
+            let events = Arc::clone(&events);
+            Arc::new(move |event: RestoreEvent| events.lock().unwrap().push(event))
+        };
+
+        // Populating the remote store, nothing is downloaded for the local instance.
+        let mirroring_store = mirror_remote_store(
+            pool(),
+            Arc::clone(&store),
+            PreviousHolder::LocalInstance,
+            false,
+            Some(&listener),
+        )
+        .await
+        .unwrap();
+        KVStoreSync::write(&mirroring_store, "ns", "sub", "key", b"value".to_vec()).unwrap();
+        assert!(events.lock().unwrap().is_empty());
+
+        // Restoring on a new device.
+        let mirroring_store = mirror_remote_store(
+            pool(),
+            Arc::clone(&store),
+            PreviousHolder::RemoteInstance,
+            false,
+            Some(&listener),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                RestoreEvent::DownloadStarted,
+                RestoreEvent::DownloadCompleted
+            ]
+        );
+        let value = KVStoreSync::read(&mirroring_store, "ns", "sub", "key").unwrap();
+        assert_eq!(value, b"value");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_local_cache_secure_delete() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join(SQLITE_FILE);
+        let mirroring_store = mirror_remote_store(
+            build_sqlite_pool(&path, DEFAULT_SQLITE_PRAGMAS).unwrap(),
+            Arc::new(MockVersionedStore::default()),
+            PreviousHolder::LocalInstance,
+            false,
+            None,
+        )
+        .await
+        .unwrap();
+        let value = b"secret preimage bytes".repeat(4);
+        let file_contains_value = || {
+            fs::read(&path)
+                .unwrap()
+                .windows(value.len())
+                .any(|bytes| bytes == value.as_slice())
+        };
+
+        KVStoreSync::write(&mirroring_store, "ns", "sub", "key", value.clone()).unwrap();
+        assert!(file_contains_value());
+
+        KVStoreSync::remove(&mirroring_store, "ns", "sub", "key", false).unwrap();
+        assert!(!file_contains_value());
+    }
+
+    #[tokio::test]
+    async fn test_remote_lock_lost() {
+        let store = MockVersionedStore::default();
+        let (locking_store, _) = LockingStore::new(
+            "instance".to_string(),
+            Duration::from_secs(60),
+            store.clone(),
+        )
+        .await
+        .unwrap();
+        // Another instance takes over the lock.
+        store.data.lock().unwrap().get_mut("lock").unwrap().1 += 1;
+
+        let (events_tx, mut events_rx) = watch::channel(RemoteLockEvent::Acquired);
+        let (_shutdown_tx, shutdown_rx) = mpsc::channel(1);
+        tokio::spawn(start_refreshing(
+            Arc::new(locking_store),
+            Duration::from_secs(1),
+            events_tx,
+            shutdown_rx,
+        ));
+        events_rx.changed().await.unwrap();
+        assert_eq!(*events_rx.borrow(), RemoteLockEvent::Lost);
+    }
+
+    #[tokio::test]
+    async fn test_vss_user_agent_header() {
+        async fn user_agent(config: &Config) -> NodeResult<Option<String>> {
+            let (private_key, pubkey) = derive_vss_key(config.network, &[1; 64])?;
+            let pubkey_hex = pubkey.serialize().encode_hex::<String>();
+            let provider = build_header_provider(config, private_key, pubkey_hex)?;
+            let headers = provider.get_headers(b"request").await.unwrap();
+            Ok(headers.get(USER_AGENT_HEADER).cloned())
+        }
+
+        let mut config = Config::regtest(String::new());
+        let default = user_agent(&config).await.unwrap().unwrap();
+        assert!(default.starts_with("breez-sdk/"));
+
+        config.vss_client_id = Some("my-app/1.2.3 (android)".to_string());
+        assert_eq!(
+            user_agent(&config).await.unwrap().as_deref(),
+            Some("my-app/1.2.3 (android)")
+        );
+
+        for invalid in [
+            String::new(),
+            "my-app\n".to_string(),
+            "é".to_string(),
+            "a".repeat(129),
+        ] {
+            config.vss_client_id = Some(invalid);
+            assert!(user_agent(&config).await.is_err());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_store() {
+        let source = MockVersionedStore::default();
+        for i in 0..10 {
+            source
+                .put(format!("ns/sub/key{i}"), vec![i; 32], 0)
+                .await
+                .unwrap();
+        }
+        let destination = MockVersionedStore::default();
+        // A key copied by an interrupted migration, but modified since.
+        destination
+            .put("ns/sub/key0".to_string(), vec![0; 16], 0)
+            .await
+            .unwrap();
+
+        assert_eq!(migrate_store(&source, &destination).await.unwrap(), 10);
+        assert_eq!(migrate_store(&source, &destination).await.unwrap(), 10);
+
+        let destination_keys = destination.list().await.unwrap();
+        assert_eq!(destination_keys.len(), 10);
+        for (key, _) in destination_keys {
+            let (value, _) = destination.get(key.clone()).await.unwrap().unwrap();
+            let (expected, _) = source.get(key).await.unwrap().unwrap();
+            assert_eq!(value, expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_check_seed_fingerprint() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let working_dir = temp_dir.path().to_str().unwrap();
+        let cache_dir = prepare_local_cache_dir(working_dir).unwrap();
+        let fingerprint_a = recovery_fingerprint(Network::Regtest, &[1; 64]).unwrap();
+        let fingerprint_b = recovery_fingerprint(Network::Regtest, &[2; 64]).unwrap();
+        let store_a = MockVersionedStore::default();
+        let store_b = MockVersionedStore::default();
+
+        check_seed_fingerprint(&cache_dir, &store_a, &fingerprint_a)
+            .await
+            .unwrap();
+        check_seed_fingerprint(&cache_dir, &store_a, &fingerprint_a)
+            .await
+            .unwrap();
+
+        // The local store still holds the state of the first seed.
+        let err = check_seed_fingerprint(&cache_dir, &store_b, &fingerprint_b)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NodeError::SeedMismatch(_)));
+
+        // Without a local store, the remote one is checked.
+        reset_local_cache(working_dir).unwrap();
+        let cache_dir = prepare_local_cache_dir(working_dir).unwrap();
+        let err = check_seed_fingerprint(&cache_dir, &store_a, &fingerprint_b)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NodeError::SeedMismatch(_)));
+    }
+}
@@ -1,3 +1,21 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use rand::Rng;
+use sdk_common::ensure_sdk;
+use sdk_common::prelude::{get_and_check_success, parse_json, RestClient};
+use tokio::time::sleep;
+
+use crate::chain::{ChainService, RecommendedFees};
+use crate::error::{SdkError, SdkResult};
+use crate::models::{Config, DependencyStatus, FeeEstimates};
+use crate::node_api::{NodeError, NodeResult};
+
+const SYNC_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const MSAT_PER_SAT: u64 = 1_000;
+
 pub trait Hex {
     fn to_hex(&self) -> String;
 }
@@ -7,3 +25,323 @@ impl<T: core::borrow::Borrow<[u8]>> Hex for T {
         hex::encode(self.borrow())
     }
 }
+
+/// A source of the randomness of new invoices, replaceable to make them reproducible.
+pub(crate) trait EntropySource: Send + Sync {
+    fn random_bytes(&self) -> [u8; 32];
+}
+
+/// The default [EntropySource], backed by the thread-local random number generator.
+pub(crate) struct ThreadRngEntropySource;
+
+impl EntropySource for ThreadRngEntropySource {
+    fn random_bytes(&self) -> [u8; 32] {
+        rand::thread_rng().gen()
+    }
+}
+
+/// Converts an amount in sat to msat, saturating at `u64::MAX` instead of overflowing.
+pub(crate) fn sat_to_msat(amount_sat: u64) -> u64 {
+    amount_sat.saturating_mul(MSAT_PER_SAT)
+}
+
+/// Converts an amount in msat to sat, rounding down.
+pub(crate) fn msat_to_sat(amount_msat: u64) -> u64 {
+    amount_msat / MSAT_PER_SAT
+}
+
+/// Returns the fee rate, in sat/vbyte, for sweeps to confirm within the configured target,
+/// bounded by the accepted fee rates.
+pub(crate) fn sweep_sat_per_vbyte(fees: &RecommendedFees, config: &Config) -> u64 {
+    fees.for_confirmation_target(config.sweep_confirmation_target)
+        .max(fees.minimum_fee)
+        .max(u64::from(config.min_sat_per_vbyte))
+        .min(u64::from(config.max_sat_per_vbyte))
+}
+
+/// Fetches the fee estimates of Esplora and maps them to [RecommendedFees].
+///
+/// See <https://github.com/Blockstream/esplora/blob/master/API.md#get-fee-estimates>
+pub(crate) async fn esplora_recommended_fees(
+    rest_client: &dyn RestClient,
+    esplora_url: &str,
+) -> SdkResult<RecommendedFees> {
+    let url = format!("{}/fee-estimates", esplora_url.trim_end_matches('/'));
+    let (response, _) = get_and_check_success(rest_client, &url).await?;
+    recommended_fees_from_estimates(&parse_json(&response)?)
+}
+
+/// Maps the fee estimates, in sat/vbyte by confirmation target, to [RecommendedFees]. A target
+/// without an estimate takes the one of the next longer target, or of the longest one.
+fn recommended_fees_from_estimates(estimates: &BTreeMap<u32, f64>) -> SdkResult<RecommendedFees> {
+    let Some((_, longest)) = estimates.last_key_value() else {
+        return Err(SdkError::service_connectivity(
+            "Esplora returned no fee estimates",
+        ));
+    };
+    let for_target = |target| {
+        let sat_per_vbyte = estimates
+            .range(target..)
+            .next()
+            .map_or(*longest, |(_, estimate)| *estimate);
+        (sat_per_vbyte.ceil() as u64).max(1)
+    };
+    Ok(RecommendedFees {
+        fastest_fee: for_target(1),
+        half_hour_fee: for_target(3),
+        hour_fee: for_target(6),
+        economy_fee: for_target(144),
+        minimum_fee: for_target(1008),
+    })
+}
+
+/// Maps the recommended fees to the confirmation targets of [FeeEstimates], making sure that
+/// a longer target is never more expensive.
+pub(crate) fn fee_estimates(fees: &RecommendedFees) -> FeeEstimates {
+    let low = fees.for_confirmation_target(144).max(fees.minimum_fee);
+    let normal = fees.for_confirmation_target(6).max(low);
+    let high = fees.for_confirmation_target(1).max(normal);
+    FeeEstimates {
+        high_sat_per_vbyte: high,
+        normal_sat_per_vbyte: normal,
+        low_sat_per_vbyte: low,
+    }
+}
+
+/// Waits until `best_block_height` reaches the tip of the chain source, failing if it does
+/// not within `timeout`.
+pub(crate) async fn wait_until_synced(
+    chain_service: &dyn ChainService,
+    best_block_height: impl Fn() -> u32,
+    timeout: Duration,
+) -> NodeResult<()> {
+    tokio::time::timeout(timeout, async {
+        let mut tip = None;
+        loop {
+            if tip.is_none() {
+                tip = chain_service
+                    .current_tip()
+                    .await
+                    .inspect_err(|e| warn!("Failed to fetch the chain tip: {e}"))
+                    .ok();
+            }
+            if tip.is_some_and(|tip| best_block_height() >= tip) {
+                return;
+            }
+            sleep(SYNC_POLL_INTERVAL).await;
+        }
+    })
+    .await
+    .map_err(|_| {
+        NodeError::ServiceConnectivity(format!(
+            "Node did not sync to the chain tip within {timeout:?}"
+        ))
+    })
+}
+
+/// Awaits the probe of an external dependency, measuring how long it took.
+pub(crate) async fn probe<E: Display>(
+    probe: impl Future<Output = Result<(), E>>,
+) -> DependencyStatus {
+    let start = Instant::now();
+    let result = probe.await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+    DependencyStatus {
+        reachable: result.is_ok(),
+        latency_ms,
+        error: result.err().map(|e| e.to_string()),
+    }
+}
+
+/// Checks that the RGS server serves the snapshot since `timestamp`, without downloading it.
+pub(crate) async fn check_rgs(rgs_url: &str, timestamp: u64, timeout: Duration) -> NodeResult<()> {
+    let url = format!("{}/{timestamp}", rgs_url.trim_end_matches('/'));
+    let response = reqwest::Client::new()
+        .head(&url)
+        .timeout(timeout)
+        .send()
+        .await
+        .map_err(|e| NodeError::ServiceConnectivity(format!("RGS server is unreachable: {e}")))?;
+    ensure_sdk!(
+        response.status().is_success(),
+        NodeError::ServiceConnectivity(format!(
+            "RGS server responded with status {}",
+            response.status()
+        ))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::test_utils::MockChainService;
+
+    #[test]
+    fn test_sat_msat_conversions() {
+        assert_eq!(sat_to_msat(0), 0);
+        assert_eq!(sat_to_msat(21), 21_000);
+        let max_sat = u64::MAX / MSAT_PER_SAT;
+        assert_eq!(sat_to_msat(max_sat), max_sat * MSAT_PER_SAT);
+        assert_eq!(sat_to_msat(max_sat + 1), u64::MAX);
+        assert_eq!(sat_to_msat(u64::MAX), u64::MAX);
+
+        assert_eq!(msat_to_sat(999), 0);
+        assert_eq!(msat_to_sat(21_999), 21);
+        assert_eq!(msat_to_sat(u64::MAX), max_sat);
+        assert_eq!(msat_to_sat(sat_to_msat(max_sat)), max_sat);
+    }
+
+    #[test]
+    fn test_sweep_sat_per_vbyte() {
+        let fees = RecommendedFees {
+            fastest_fee: 20,
+            half_hour_fee: 10,
+            hour_fee: 5,
+            economy_fee: 2,
+            minimum_fee: 1,
+        };
+        let mut config = Config::regtest("api-key".to_string());
+
+        config.sweep_confirmation_target = 1;
+        assert_eq!(sweep_sat_per_vbyte(&fees, &config), 20);
+        config.sweep_confirmation_target = 6;
+        assert_eq!(sweep_sat_per_vbyte(&fees, &config), 5);
+        config.sweep_confirmation_target = 144;
+        assert_eq!(sweep_sat_per_vbyte(&fees, &config), 2);
+
+        config.min_sat_per_vbyte = 3;
+        assert_eq!(sweep_sat_per_vbyte(&fees, &config), 3);
+        config.sweep_confirmation_target = 1;
+        config.max_sat_per_vbyte = 15;
+        assert_eq!(sweep_sat_per_vbyte(&fees, &config), 15);
+    }
+
+    #[test]
+    fn test_recommended_fees_from_estimates() {
+        let estimates = BTreeMap::from([(1, 20.5), (3, 10.0), (6, 5.2), (144, 2.0), (1008, 1.0)]);
+        let fees = recommended_fees_from_estimates(&estimates).unwrap();
+        assert_eq!(fees.fastest_fee, 21);
+        assert_eq!(fees.half_hour_fee, 10);
+        assert_eq!(fees.hour_fee, 6);
+        assert_eq!(fees.economy_fee, 2);
+        assert_eq!(fees.minimum_fee, 1);
+
+        // Missing targets take the estimate of the next longer one, or of the longest one.
+        let estimates = BTreeMap::from([(2, 8.0), (25, 3.0)]);
+        let fees = recommended_fees_from_estimates(&estimates).unwrap();
+        assert_eq!(fees.fastest_fee, 8);
+        assert_eq!(fees.half_hour_fee, 3);
+        assert_eq!(fees.economy_fee, 3);
+        assert_eq!(fees.minimum_fee, 3);
+
+        // Estimates below 1 sat/vbyte are raised to it.
+        let estimates = BTreeMap::from([(1, 0.5)]);
+        assert_eq!(
+            recommended_fees_from_estimates(&estimates)
+                .unwrap()
+                .fastest_fee,
+            1
+        );
+
+        assert!(recommended_fees_from_estimates(&BTreeMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_fee_estimates() {
+        let fees = RecommendedFees {
+            fastest_fee: 20,
+            half_hour_fee: 10,
+            hour_fee: 5,
+            economy_fee: 2,
+            minimum_fee: 1,
+        };
+        assert_eq!(
+            fee_estimates(&fees),
+            FeeEstimates {
+                high_sat_per_vbyte: 20,
+                normal_sat_per_vbyte: 5,
+                low_sat_per_vbyte: 2,
+            }
+        );
+
+        // Out of order estimates and the minimum fee are smoothed out.
+        let fees = RecommendedFees {
+            fastest_fee: 3,
+            half_hour_fee: 4,
+            hour_fee: 4,
+            economy_fee: 1,
+            minimum_fee: 2,
+        };
+        assert_eq!(
+            fee_estimates(&fees),
+            FeeEstimates {
+                high_sat_per_vbyte: 4,
+                normal_sat_per_vbyte: 4,
+                low_sat_per_vbyte: 2,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_synced() {
+        let chain_service = MockChainService {
+            tip: 100,
+            ..Default::default()
+        };
+        let height = Arc::new(AtomicU32::new(90));
+        let catch_up = {
+            let height = Arc::clone(&height);
+            tokio::spawn(async move {
+                sleep(Duration::from_millis(600)).await;
+                height.store(100, Ordering::SeqCst);
+            })
+        };
+
+        wait_until_synced(
+            &chain_service,
+            || height.load(Ordering::SeqCst),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        catch_up.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_wait_until_synced_timeout() {
+        let chain_service = MockChainService {
+            tip: 100,
+            ..Default::default()
+        };
+
+        let res = wait_until_synced(&chain_service, || 90, Duration::from_secs(1)).await;
+        assert!(matches!(res, Err(NodeError::ServiceConnectivity(_))));
+    }
+
+    #[tokio::test]
+    async fn test_probe() {
+        let status = probe(async {
+            sleep(Duration::from_millis(100)).await;
+            Ok::<_, NodeError>(())
+        })
+        .await;
+        assert!(status.reachable);
+        assert!(status.latency_ms >= 100);
+        assert_eq!(status.error, None);
+
+        let status = probe(async { Err(NodeError::generic("Connection refused")) }).await;
+        assert!(!status.reachable);
+        assert_eq!(status.error.as_deref(), Some("Connection refused"));
+    }
+
+    #[tokio::test]
+    async fn test_check_rgs_unreachable() {
+        // Nothing listens on the port.
+        let res = check_rgs("http://127.0.0.1:1/v2", 0, Duration::from_secs(5)).await;
+        assert!(matches!(res, Err(NodeError::ServiceConnectivity(_))));
+    }
+}
@@ -116,6 +116,8 @@
 //! * [BreezServices::list_fiat_currencies] to get the supported fiat currencies
 //! * [BreezServices::fetch_fiat_rates] to get the current exchange rates
 //! * [BreezServices::recommended_fees] for the recommended mempool fees
+//! * [BreezServices::chain_tip], [BreezServices::onchain_tx_status] and
+//!   [BreezServices::address_transactions] to follow onchain deposits
 //!
 //! ### G. Connecting to an LSP
 //!
@@ -164,6 +166,7 @@ pub mod binding;
 mod breez_services;
 mod chain;
 pub mod error;
+mod export;
 #[rustfmt::skip]
 mod node_api; // flutter_rust_bridge_codegen: has to be defined before greenlight; greenlight::node_api
 mod ldk;
@@ -189,10 +192,10 @@ mod test_utils;
 
 pub use breez_services::{
     mnemonic_to_seed, BackupFailedData, BreezEvent, BreezServices, CheckMessageRequest,
-    CheckMessageResponse, EventListener, InvoicePaidDetails, LogStream, PaymentFailedData,
-    SignMessageRequest, SignMessageResponse,
+    CheckMessageResponse, EventListener, InvoicePaidDetails, LogStream, LogStreamFilter,
+    PaymentFailedData, SignMessageRequest, SignMessageResponse,
 };
-pub use chain::RecommendedFees;
+pub use chain::{OnchainTx, RecommendedFees, TxStatus, Vin, Vout};
 pub use lsp::LspInformation;
 pub use models::*;
 pub use sdk_common::prelude::*;
@@ -1,6 +1,6 @@
 use crate::models::{OpeningFeeParams, OpeningFeeParamsMenu};
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use serde::{Deserialize, Serialize};
 
 /// Details of supported LSP
@@ -33,6 +33,17 @@ pub struct LspInformation {
     pub min_htlc_msat: i64,
     pub lsp_pubkey: Vec<u8>,
     pub opening_fee_params_list: OpeningFeeParamsMenu,
+
+    /// The capacity the LSP adds to a JIT channel on top of the received amount, in parts per
+    /// million of the amount
+    pub channel_over_provisioning_ppm: u32,
+
+    /// The number of blocks the LSP keeps a JIT channel open at least
+    pub min_channel_lifetime: u32,
+
+    /// The largest `to_self_delay`, in blocks, the LSP accepts to wait for its funds on a
+    /// unilateral close of a JIT channel
+    pub max_client_to_self_delay: u32,
 }
 
 impl LspInformation {
@@ -56,6 +67,32 @@ impl LspInformation {
             .last()
             .ok_or_else(|| anyhow!("Dynamic fees menu contains no values"))
     }
+
+    /// Verifies that the given opening fee params were issued by this LSP and are still valid
+    /// for the expiry range.
+    ///
+    /// The promise is opaque to the client, so it is checked by requiring the params to match
+    /// an entry of the menu the LSP currently advertises.
+    pub(crate) fn verify_opening_fee_params(
+        &self,
+        params: &OpeningFeeParams,
+        expiry: u32,
+    ) -> Result<()> {
+        ensure!(
+            params.valid_for(expiry)?,
+            "Opening fee params expire at {}",
+            params.valid_until
+        );
+        ensure!(
+            self.opening_fee_params_list
+                .values
+                .iter()
+                .any(|ofp| ofp == params),
+            "Opening fee params promise was not issued by LSP {}",
+            self.pubkey
+        );
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -95,6 +132,9 @@ mod tests {
             opening_fee_params_list: OpeningFeeParamsMenu {
                 values: tested_fees,
             },
+            channel_over_provisioning_ppm: 0,
+            min_channel_lifetime: 144,
+            max_client_to_self_delay: 2016,
         };
 
         for expiry in 1..3 {
@@ -115,4 +155,78 @@ mod tests {
 
         Ok(())
     }
+
+    fn test_lsp_info(values: Vec<OpeningFeeParams>) -> LspInformation {
+        LspInformation {
+            id: "id".to_string(),
+            name: "test lsp".to_string(),
+            widget_url: "".to_string(),
+            pubkey: "pubkey".to_string(),
+            host: "localhost".to_string(),
+            base_fee_msat: 1,
+            fee_rate: 1.0,
+            time_lock_delta: 32,
+            min_htlc_msat: 1000,
+            lsp_pubkey: hex::decode("A0").unwrap(),
+            opening_fee_params_list: OpeningFeeParamsMenu { values },
+            channel_over_provisioning_ppm: 0,
+            min_channel_lifetime: 144,
+            max_client_to_self_delay: 2016,
+        }
+    }
+
+    fn test_opening_fee_params(valid_until: chrono::DateTime<Utc>) -> OpeningFeeParams {
+        OpeningFeeParams {
+            min_msat: 1,
+            proportional: 1,
+            valid_until: valid_until.to_rfc3339(),
+            max_idle_time: 1,
+            max_client_to_self_delay: 1,
+            promise: "promise".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_verify_opening_fee_params() -> Result<()> {
+        let params = test_opening_fee_params(Utc::now() + Duration::hours(1));
+        let lsp_info = test_lsp_info(vec![params.clone()]);
+        lsp_info.verify_opening_fee_params(&params, 60)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_opening_fee_params_expired() {
+        let params = test_opening_fee_params(Utc::now() - Duration::hours(1));
+        let lsp_info = test_lsp_info(vec![params.clone()]);
+        let err = lsp_info.verify_opening_fee_params(&params, 60).unwrap_err();
+        assert!(err.to_string().starts_with("Opening fee params expire at"));
+
+        // Params that expire before the invoice does are rejected as well
+        let params = test_opening_fee_params(Utc::now() + Duration::seconds(30));
+        let lsp_info = test_lsp_info(vec![params.clone()]);
+        assert!(lsp_info.verify_opening_fee_params(&params, 60).is_err());
+    }
+
+    #[test]
+    fn test_verify_opening_fee_params_promise_mismatch() {
+        let params = test_opening_fee_params(Utc::now() + Duration::hours(1));
+        let lsp_info = test_lsp_info(vec![params.clone()]);
+
+        let forged = OpeningFeeParams {
+            promise: "forged promise".to_string(),
+            ..params.clone()
+        };
+        let err = lsp_info.verify_opening_fee_params(&forged, 60).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Opening fee params promise was not issued by LSP pubkey"
+        );
+
+        // A valid promise can't be reused with tampered fees
+        let tampered = OpeningFeeParams {
+            proportional: 0,
+            ..params
+        };
+        assert!(lsp_info.verify_opening_fee_params(&tampered, 60).is_err());
+    }
 }
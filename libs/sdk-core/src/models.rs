@@ -27,6 +27,10 @@ use crate::swap_out::error::{ReverseSwapError, ReverseSwapResult};
 
 pub const SWAP_PAYMENT_FEE_EXPIRY_SECONDS: u32 = 60 * 60 * 24 * 2; // 2 days
 pub const INVOICE_PAYMENT_FEE_EXPIRY_SECONDS: u32 = 60 * 60; // 60 minutes
+/// The TLV type conventionally used for text messages sent along keysend payments.
+pub const KEYSEND_MESSAGE_TLV_TYPE: u64 = 34349334;
+/// The maximum length in bytes of [SendSpontaneousPaymentRequest::comment].
+pub const MAX_KEYSEND_COMMENT_LEN: usize = 1000;
 
 /// Different types of supported payments
 #[derive(
@@ -462,6 +466,49 @@ pub struct Config {
     pub maxfee_percent: f64,
     /// Maps to the CLN `exemptfee` config when paying invoices (`lightning-pay`)
     pub exemptfee_msat: u64,
+    /// Maximum number of times a failed payment is re-attempted when the failure is transient
+    pub payment_retries: u32,
+    /// Duration of the lease on the remote lock of the node storage, in seconds.
+    /// The lease is refreshed halfway through, so another instance can take over
+    /// within this duration if the app is killed.
+    pub remote_lock_lease_secs: u32,
+    /// Interval between attempts to refresh the remote lock after a failed refresh, in seconds
+    pub remote_lock_retry_secs: u32,
+    /// Maximum time to wait for a connection to a peer to be established, in seconds
+    pub connect_timeout_secs: u32,
+    /// Whether to negotiate anchor channels. If enabled, the LSP is trusted to open anchor
+    /// channels without requiring an on-chain reserve from the node. Disable it when the
+    /// LSP or other peers don't support anchor outputs
+    pub anchor_channels_enabled: bool,
+    /// Whether to adopt node state from the remote storage that is older than the state previously
+    /// synced by this device. Only enable it to recover from a botched multi-device setup, as an
+    /// older channel state may lead to a loss of funds
+    pub allow_state_regression: bool,
+    /// The lowest fee rate, in sat/vbyte, accepted for on-chain transactions.
+    /// The minimum fee reported by the chain service is enforced too, when it is higher.
+    pub min_sat_per_vbyte: u32,
+    /// The highest fee rate, in sat/vbyte, accepted for on-chain transactions.
+    pub max_sat_per_vbyte: u32,
+    /// Maximum number of times a failed sync with the chain is re-attempted, with an
+    /// exponentially growing delay of up to 30 seconds between the attempts. At most 10
+    /// re-attempts are made, whatever the configured number
+    pub sync_retries: u32,
+    /// The maximum number of channels opened by the LSP on the fly to receive a payment.
+    /// A receive that would need another channel fails once the limit is reached, 0 disables it
+    pub max_jit_channels: u32,
+    /// The number of blocks within which the sweeps of closed channel funds, e.g. after a force
+    /// close, should confirm. Their fee rate is picked from the recommended fees for this target
+    pub sweep_confirmation_target: u32,
+    /// The largest value, in bytes, written to the local and remote stores of the node state.
+    /// Larger writes are rejected, guarding the storage against runaway values
+    pub max_store_value_bytes: u32,
+    /// Identifies the client in the `User-Agent` header of the requests to VSS, defaulting to
+    /// the SDK name and version. Must be printable ASCII of at most 128 characters.
+    pub vss_client_id: Option<String>,
+    /// Whether the files of the local cache of the node state are only accessible by the user
+    /// running the SDK. Otherwise they are created with the default permissions of the process,
+    /// which may let other users read them. Only has an effect on unix.
+    pub restrict_file_permissions: bool,
 }
 
 impl Config {
@@ -483,6 +530,20 @@ impl Config {
             api_key: Some(api_key),
             maxfee_percent: 1.0,
             exemptfee_msat: 20000,
+            payment_retries: 2,
+            remote_lock_lease_secs: 60,
+            remote_lock_retry_secs: 5,
+            connect_timeout_secs: 10,
+            anchor_channels_enabled: true,
+            allow_state_regression: false,
+            min_sat_per_vbyte: 1,
+            max_sat_per_vbyte: 500,
+            sync_retries: 2,
+            max_jit_channels: 0,
+            sweep_confirmation_target: 6,
+            max_store_value_bytes: 67108864,
+            vss_client_id: None,
+            restrict_file_permissions: true,
         }
     }
 
@@ -504,6 +565,20 @@ impl Config {
             api_key: Some(api_key),
             maxfee_percent: 0.5,
             exemptfee_msat: 20000,
+            payment_retries: 2,
+            remote_lock_lease_secs: 60,
+            remote_lock_retry_secs: 5,
+            connect_timeout_secs: 10,
+            anchor_channels_enabled: true,
+            allow_state_regression: false,
+            min_sat_per_vbyte: 1,
+            max_sat_per_vbyte: 500,
+            sync_retries: 2,
+            max_jit_channels: 0,
+            sweep_confirmation_target: 6,
+            max_store_value_bytes: 67108864,
+            vss_client_id: None,
+            restrict_file_permissions: true,
         }
     }
 
@@ -526,8 +601,28 @@ impl Config {
             api_key: Some(api_key),
             maxfee_percent: 0.5,
             exemptfee_msat: 20000,
+            payment_retries: 2,
+            remote_lock_lease_secs: 60,
+            remote_lock_retry_secs: 5,
+            connect_timeout_secs: 10,
+            anchor_channels_enabled: true,
+            allow_state_regression: false,
+            min_sat_per_vbyte: 1,
+            max_sat_per_vbyte: 500,
+            sync_retries: 2,
+            max_jit_channels: 0,
+            sweep_confirmation_target: 6,
+            max_store_value_bytes: 67108864,
+            vss_client_id: None,
+            restrict_file_permissions: true,
         }
     }
+
+    /// The routing fee limit of a payment of `amount_msat`, when no limit is given.
+    pub(crate) fn max_routing_fee_msat(&self, amount_msat: u64) -> u64 {
+        let max_fee_msat = (amount_msat as f64 * self.maxfee_percent / 100.0) as u64;
+        max_fee_msat.max(self.exemptfee_msat)
+    }
 }
 
 /// Indicates the different kinds of supported environments for [crate::BreezServices].
@@ -626,6 +721,10 @@ pub struct NodeState {
 
     /// Total receivable on all available channels
     pub total_inbound_liquidity_msats: u64,
+
+    /// Set when the last sync with the chain failed, in which case the balances may be stale.
+    #[serde(default)]
+    pub sync_warning: Option<String>,
 }
 
 /// The status of a payment
@@ -653,6 +752,15 @@ pub struct Payment {
     pub metadata: Option<String>,
 }
 
+/// The format of the payments exported with [crate::BreezServices::export_payments].
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PaymentExportFormat {
+    /// One row per payment, with amounts in millisatoshis and times in UTC.
+    Csv,
+    /// The list of [Payment]s.
+    Json,
+}
+
 /// Represents a payments external information.
 #[derive(Default)]
 pub(crate) struct PaymentExternalInfo {
@@ -747,6 +855,13 @@ pub struct LnPaymentDetails {
 
     /// Only set for [PaymentType::Sent] payments that were sent in the context of a reverse swap
     pub reverse_swap_info: Option<ReverseSwapInfo>,
+
+    /// Only set for [PaymentType::Received] payments that carried custom TLV records
+    pub custom_tlvs: Option<Vec<TlvEntry>>,
+
+    /// Only set for [PaymentType::Received] payments that opened a channel, this is the
+    /// invoice that triggered the channel open
+    pub open_channel_bolt11: Option<String>,
 }
 
 /// Represents the funds that were on the user side of the channel at the time it was closed.
@@ -757,6 +872,10 @@ pub struct ClosedChannelPaymentDetails {
     pub short_channel_id: Option<String>,
     /// Can be empty for older closed channels.
     pub closing_txid: Option<String>,
+    pub counterparty_node_id: String,
+    pub capacity_sat: u64,
+    /// Whether the channel was force closed, as opposed to closed cooperatively.
+    pub is_force_close: bool,
 }
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -794,8 +913,20 @@ pub struct ReceivePaymentRequest {
     pub use_description_hash: Option<bool>,
     /// if specified, set the time the invoice is valid for, in seconds.
     pub expiry: Option<u32>,
-    /// if specified, sets the min_final_cltv_expiry for the invoice
+    /// if specified, the minimum accepted min_final_cltv_expiry_delta of the invoice.
+    /// The delta is not set on the invoice, the node picks it and receiving fails if it is
+    /// smaller. Must be between 18 and 2016 blocks.
     pub cltv: Option<u32>,
+    /// If set to true, the LSP is asked to notify the registered webhook of the payment of
+    /// this invoice, so that it can be received while the app is offline.
+    /// See [crate::BreezServices::register_webhook].
+    pub register_notifications: Option<bool>,
+    /// If set to true, a description longer than the BOLT 11 limit is truncated instead of
+    /// rejected.
+    pub truncate_description: Option<bool>,
+    /// If set to true, the receive fails with [crate::error::ReceivePaymentError::OpenChannelNeeded]
+    /// instead of creating an invoice that opens a new channel and charges its opening fee.
+    pub no_open_channel: Option<bool>,
 }
 
 /// Represents a receive payment response.
@@ -820,10 +951,39 @@ pub struct SendPaymentRequest {
     pub bolt11: String,
     /// The amount to pay in millisatoshis. Should only be set when `bolt11` is a zero-amount invoice.
     pub amount_msat: Option<u64>,
+    /// The maximum routing fee to pay in millisatoshis. Defaults to [Config::maxfee_percent] of
+    /// the amount, but at least [Config::exemptfee_msat].
+    pub max_fee_msat: Option<u64>,
+}
+
+/// Represents a request to check that a bolt11 invoice can be paid.
+#[derive(Clone, Debug, Default)]
+pub struct PrepareSendPaymentRequest {
+    /// The bolt11 invoice
+    pub bolt11: String,
+    /// The amount to pay in millisatoshis. Should only be set when `bolt11` is a zero-amount invoice.
+    pub amount_msat: Option<u64>,
+    /// If set, probes are sent to the payee to check that a route exists.
+    pub probe: Option<bool>,
+}
+
+/// Returned when calling [crate::breez_services::BreezServices::prepare_send_payment].
+#[derive(Clone, Debug, Serialize)]
+pub struct PrepareSendPaymentResponse {
+    /// The parsed invoice
+    pub invoice: LNInvoice,
+    /// The amount to pay in millisatoshis
+    pub amount_msat: u64,
+    /// The routing fee limit of the payment, which is its fee in the worst case
+    pub max_fee_msat: u64,
+    /// Whether a route to the payee was found, if probed
+    pub route_found: Option<bool>,
+    /// Whether the amount can be sent from the channels and no probe failed to find a route
+    pub is_payable: bool,
 }
 
 /// Represents a TLV entry for a keysend payment.
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TlvEntry {
     /// The type field for the TLV
     pub field_number: u64,
@@ -840,6 +1000,29 @@ pub struct SendSpontaneousPaymentRequest {
     pub amount_msat: u64,
     // Optional extra TLVs
     pub extra_tlvs: Option<Vec<TlvEntry>>,
+    /// An optional note for the receiver, sent in the [KEYSEND_MESSAGE_TLV_TYPE] TLV
+    pub comment: Option<String>,
+}
+
+/// Represents a request to pay a node given by its address.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PayNodeAddressRequest {
+    /// The address of the node in the `node_id@host:port` format
+    pub address: String,
+    /// The amount in millisatoshis for this payment
+    pub amount_msat: u64,
+    /// If set, a channel of this capacity is opened to the node instead of routing the payment,
+    /// and the amount is pushed to the node in the channel
+    pub open_channel_sat: Option<u64>,
+}
+
+/// Represents a pay node address response.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum PayNodeAddressResponse {
+    /// The amount was paid using a spontaneous payment
+    Paid { payment: Payment },
+    /// A channel was opened to the node, pushing the amount to it
+    ChannelOpened,
 }
 
 /// Represents a send payment response.
@@ -876,6 +1059,87 @@ pub struct ServiceHealthCheckResponse {
     pub status: HealthCheckStatus,
 }
 
+/// The outcome of checking the VSS server with [crate::BreezServices::check_vss].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum VssCheckResult {
+    /// A throwaway key was written, read back and deleted.
+    Ok,
+    /// The server could not be reached.
+    ConnectivityFailure { err: String },
+    /// The server rejected the credentials.
+    AuthFailure { err: String },
+    /// The server responded, but the check failed for another reason.
+    UnexpectedFailure { err: String },
+}
+
+/// The fees of the completed payments over a time range, see
+/// [crate::BreezServices::fee_summary].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeeSummary {
+    pub total_fee_msat: u64,
+    /// Fees paid to route the sent payments.
+    pub routing_fee_msat: u64,
+    /// Fees paid to the LSP to open channels for the received payments.
+    pub lsp_fee_msat: u64,
+}
+
+/// The on-chain fee rates, in sat/vbyte, for transactions to confirm within a number of
+/// blocks, see [crate::BreezServices::fee_estimates]. The rates never increase from `high`
+/// to `low`.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimates {
+    /// The fee rate to confirm in the next block.
+    pub high_sat_per_vbyte: u64,
+    /// The fee rate to confirm within 6 blocks.
+    pub normal_sat_per_vbyte: u64,
+    /// The fee rate to confirm within a day.
+    pub low_sat_per_vbyte: u64,
+}
+
+/// The size of the routing graph of the node, see [crate::BreezServices::graph_status].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct GraphStatus {
+    pub node_count: u64,
+    pub channel_count: u64,
+    /// The time of the last Rapid Gossip Sync snapshot applied to the graph, or `None` if the
+    /// graph was not synced yet.
+    pub latest_rgs_snapshot_timestamp: Option<u64>,
+}
+
+/// Whether an external dependency of the node could be reached, see
+/// [crate::BreezServices::connectivity_check].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DependencyStatus {
+    pub reachable: bool,
+    /// The time taken to probe the dependency, whether it was reachable or not.
+    pub latency_ms: u64,
+    /// The reason the dependency was not reachable.
+    pub error: Option<String>,
+}
+
+/// The reachability of the external dependencies of the node, see
+/// [crate::BreezServices::connectivity_check].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConnectivityCheckResult {
+    /// The VSS server the node state is backed up to.
+    pub vss: DependencyStatus,
+    /// The chain source the node syncs with.
+    pub chain_source: DependencyStatus,
+    /// The Rapid Gossip Sync server the routing graph is synced from.
+    pub rgs: DependencyStatus,
+    /// The LSP peer.
+    pub lsp: DependencyStatus,
+}
+
+/// The outcome of [crate::BreezServices::resync_from_remote].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ResyncResult {
+    /// The number of keys dropped from the local cache, including the ones not yet synced.
+    pub discarded_keys: u32,
+    /// The number of keys downloaded from the remote storage.
+    pub downloaded_keys: u32,
+}
+
 /// Trait covering support-related functionality
 #[tonic::async_trait]
 pub trait SupportAPI: Send + Sync {
@@ -915,6 +1179,126 @@ pub struct OpenChannelFeeResponse {
     pub fee_params: OpeningFeeParams,
 }
 
+/// Estimate of the inbound liquidity needed to receive an amount in a single payment.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LiquidityEstimate {
+    /// The maximum amount currently receivable in a single payment without a new channel.
+    pub current_inbound_msat: u64,
+    /// The inbound liquidity a new channel has to provide, or zero if no new channel is needed.
+    ///
+    /// A single payment cannot be split between the existing channels and a new one,
+    /// so a new channel has to accommodate the whole amount.
+    pub required_new_inbound_msat: u64,
+    /// The fee for opening a new channel, if one is needed.
+    pub opening_fee_msat: Option<u64>,
+    /// The fee params used for the estimate, if a new channel is needed.
+    pub opening_fee_params: Option<OpeningFeeParams>,
+}
+
+/// Represents a response of [crate::BreezServices::preopen_channel].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PreopenChannelResponse {
+    /// The invoice which makes the LSP open the channel once it is paid
+    pub ln_invoice: LNInvoice,
+    /// The fee for opening the channel, deduced from the invoice amount
+    pub opening_fee_msat: u64,
+    /// The size of the channel the LSP opens, including the over-provisioning
+    pub channel_size_msat: u64,
+}
+
+/// A channel of the node.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelInfo {
+    /// The hex encoded id of the channel.
+    pub channel_id: String,
+    /// The public key of the channel counterparty.
+    pub counterparty_node_id: String,
+    /// The id of the funding transaction, if it was already created.
+    pub funding_txid: Option<String>,
+    /// The capacity of the channel.
+    pub capacity_sat: u64,
+    /// The amount which can currently be sent over the channel.
+    pub outbound_capacity_msat: u64,
+    /// The amount which can currently be received over the channel.
+    pub inbound_capacity_msat: u64,
+    /// Indicates whether the funding transaction reached the required confirmations.
+    pub is_channel_ready: bool,
+    /// Indicates whether the channel can be used to send and receive payments.
+    pub is_usable: bool,
+    /// The number of confirmations of the funding transaction, if it was broadcast.
+    pub confirmations: Option<u32>,
+    /// The number of confirmations the funding transaction needs for the channel to be ready,
+    /// as required by the channel acceptor, if known yet.
+    pub confirmations_required: Option<u32>,
+}
+
+impl ChannelInfo {
+    /// Returns the number of blocks left before the funding transaction has the required
+    /// confirmations, or `None` if that is not known yet.
+    pub fn blocks_until_ready(&self) -> Option<u32> {
+        let required = self.confirmations_required?;
+        Some(required.saturating_sub(self.confirmations.unwrap_or(0)))
+    }
+}
+
+/// The progress of the funds of a closed channel towards being spendable on-chain.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingCloseStatus {
+    /// The hex encoded id of the closed channel.
+    pub channel_id: String,
+    /// The amount still to be claimed from the channel.
+    pub amount_sat: u64,
+    /// The current height of the chain.
+    pub block_height: u32,
+    /// The height at which all the funds become spendable, known once the transactions
+    /// claiming them are confirmed.
+    pub spendable_height: Option<u32>,
+}
+
+impl PendingCloseStatus {
+    /// The number of blocks to be mined before all the funds become spendable.
+    pub fn blocks_remaining(&self) -> Option<u32> {
+        self.spendable_height
+            .map(|height| height.saturating_sub(self.block_height))
+    }
+}
+
+/// The stage of a sweep of funds from a closed channel to the on-chain wallet.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PendingSweepStage {
+    /// The sweeping transaction is yet to be broadcast.
+    PendingBroadcast,
+    /// The sweeping transaction was broadcast and awaits its first confirmation.
+    BroadcastAwaitingConfirmation,
+    /// The sweeping transaction confirmed, but not deep enough yet to be spendable.
+    AwaitingThresholdConfirmations,
+}
+
+/// Funds from a closed channel which are being swept to the on-chain wallet.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingSweep {
+    /// The hex encoded id of the closed channel, if known.
+    pub channel_id: Option<String>,
+    /// The amount being swept.
+    pub amount_sat: u64,
+    pub stage: PendingSweepStage,
+    /// The id of the latest sweeping transaction, once it was broadcast.
+    pub txid: Option<String>,
+}
+
+/// A peer known to the node.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PeerInfo {
+    /// The public key of the peer.
+    pub node_id: String,
+    /// The network address of the peer.
+    pub address: String,
+    /// Indicates whether the node is currently connected to the peer.
+    pub is_connected: bool,
+    /// Indicates whether the peer is reconnected to on restart.
+    pub is_persisted: bool,
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct ReceiveOnchainRequest {
     pub opening_fee_params: Option<OpeningFeeParams>,
@@ -1492,8 +1876,8 @@ mod tests {
     use crate::models::sanitize::Sanitize;
     use crate::test_utils::{get_test_ofp, get_test_ofp_48h, rand_string, rand_vec_u8};
     use crate::{
-        FullReverseSwapInfo, OpeningFeeParams, PaymentPath, PaymentPathEdge, ReverseSwapInfoCached,
-        ReverseSwapStatus, SwapInfo,
+        ChannelInfo, FullReverseSwapInfo, OpeningFeeParams, PaymentPath, PaymentPathEdge,
+        ReverseSwapInfoCached, ReverseSwapStatus, SwapInfo,
     };
 
     #[test]
@@ -1712,4 +2096,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_channel_blocks_until_ready() {
+        let mut channel = ChannelInfo {
+            channel_id: "01".repeat(32),
+            counterparty_node_id: String::new(),
+            funding_txid: None,
+            capacity_sat: 100_000,
+            outbound_capacity_msat: 0,
+            inbound_capacity_msat: 0,
+            is_channel_ready: false,
+            is_usable: false,
+            confirmations: None,
+            confirmations_required: None,
+        };
+        assert_eq!(channel.blocks_until_ready(), None);
+
+        channel.confirmations_required = Some(3);
+        assert_eq!(channel.blocks_until_ready(), Some(3));
+        channel.confirmations = Some(1);
+        assert_eq!(channel.blocks_until_ready(), Some(2));
+        channel.confirmations = Some(5);
+        assert_eq!(channel.blocks_until_ready(), Some(0));
+    }
 }
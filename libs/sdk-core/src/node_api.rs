@@ -1,11 +1,13 @@
 use std::collections::HashSet;
 use std::pin::Pin;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use anyhow::Result;
 use serde_json::Value;
 use tokio::sync::mpsc;
 use tokio_stream::Stream;
+use tokio_util::sync::CancellationToken;
 
 use sdk_common::prelude::*;
 
@@ -13,9 +15,9 @@ use crate::{
     bitcoin::bip32::{ChildNumber, Xpriv},
     error::ReceivePaymentError,
     persist::error::PersistError,
-    CustomMessage, LnUrlAuthError, MaxChannelAmount, NodeState, Payment, PaymentDetails,
-    LnPaymentDetails, PaymentResponse, PaymentStatus, PaymentType,
-    PrepareRedeemOnchainFundsRequest, PrepareRedeemOnchainFundsResponse, RouteHintHop,
+    ChannelInfo, ConnectivityCheckResult, CustomMessage, FeeEstimates, FeeSummary, GraphStatus, LiquidityEstimate, LnUrlAuthError, MaxChannelAmount, NodeState, Payment, PaymentDetails, PeerInfo,
+    LnPaymentDetails, PaymentResponse, PaymentStatus, PaymentType, PendingCloseStatus, PendingSweep,
+    PrepareRedeemOnchainFundsRequest, PrepareRedeemOnchainFundsResponse, ResyncResult, RouteHintHop,
     TlvEntry,
 };
 
@@ -26,6 +28,9 @@ pub enum NodeError {
     #[error("{0}")]
     Generic(String),
 
+    #[error("{0}")]
+    ConnectTimeout(String),
+
     #[error(transparent)]
     InvalidInvoice(#[from] InvoiceError),
 
@@ -38,18 +43,30 @@ pub enum NodeError {
     #[error("{0}")]
     InvoicePreimageAlreadyExists(String),
 
+    #[error("{0}")]
+    NodeLocked(String),
+
     #[error("{0}")]
     PaymentFailed(String),
 
     #[error("{0}")]
     PaymentTimeout(String),
 
+    #[error("{0}")]
+    Cancelled(String),
+
     #[error(transparent)]
     Persistance(#[from] PersistError),
 
     #[error("{0}")]
     RestoreOnly(String),
 
+    #[error("{0}")]
+    StateRegression(String),
+
+    #[error("{0}")]
+    SeedMismatch(String),
+
     #[error("{0}")]
     RouteTooExpensive(String),
 
@@ -62,6 +79,18 @@ pub enum NodeError {
     #[error("{0}")]
     InsufficientFunds(String),
 
+    #[error("{0}")]
+    InvalidAmount(String),
+
+    #[error("{0}")]
+    LspUnavailable(String),
+
+    #[error("{0}")]
+    LspRequestRejected(String),
+
+    #[error("{0}")]
+    LspFeeTooHigh(String),
+
     #[error("invoice already paid")]
     InvoiceAlreadyPaid,
 }
@@ -106,6 +135,9 @@ pub struct CreateInvoiceRequest {
     pub preimage: Option<Vec<u8>>,
 	pub opening_fee_msat: Option<u64>,
     pub expiry: u32,
+    /// The smallest min_final_cltv_expiry_delta accepted for the invoice. The node picks the
+    /// delta itself, the invoice is only checked against this.
+    pub min_accepted_cltv: Option<u32>,
 }
 
 pub struct FetchBolt11Result {
@@ -119,8 +151,70 @@ pub struct IncomingPayment {
     pub preimage: Vec<u8>,
     pub amount_msat: u64,
     pub bolt11: String,
+    pub custom_tlvs: Option<Vec<TlvEntry>>,
+    pub open_channel_bolt11: Option<String>,
+}
+
+/// Changes of the exclusive remote lock on the storage of the node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemoteLockEvent {
+    /// The lock is held by this instance.
+    Acquired,
+    /// The lock could not be refreshed, but is still held by this instance.
+    RefreshFailed,
+    /// The lock expired or was taken over by another instance.
+    Lost,
+}
+
+/// Progress of a splice of one of the channels of the node.
+///
+/// The amount is positive for splice-ins, negative for splice-outs and zero for splices
+/// initiated by the counterparty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SpliceEvent {
+    /// The splice transaction was negotiated and broadcast.
+    Pending {
+        channel_id: String,
+        amount_sat: i64,
+        funding_txid: String,
+    },
+    /// The splice transaction was confirmed and the channel uses the new funding output.
+    Completed { channel_id: String, amount_sat: i64 },
+    /// The splice was aborted, the channel keeps using the previous funding output.
+    Failed {
+        channel_id: String,
+        amount_sat: i64,
+        reason: String,
+    },
+}
+
+/// A reorganization of the chain, in which the previous tip of the node is no longer
+/// part of the best chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReorgEvent {
+    pub old_tip_height: u32,
+    pub old_tip_hash: String,
+    pub new_tip_height: u32,
+    pub new_tip_hash: String,
+}
+
+/// Progress of the restore of the node state from the remote storage, as when the node is
+/// started on a new device.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RestoreEvent {
+    /// The download of the node state started.
+    DownloadStarted,
+    /// The node state was downloaded.
+    DownloadCompleted,
+    /// The first sync of the restored node with the chain started.
+    ChainSyncStarted,
+    /// The first sync of the restored node with the chain completed.
+    ChainSyncCompleted,
 }
 
+/// Receives the [RestoreEvent]s, some of which are emitted before the node is built.
+pub type RestoreEventListener = Arc<dyn Fn(RestoreEvent) + Send + Sync>;
+
 impl TryFrom<IncomingPayment> for Payment {
     type Error = NodeError;
 
@@ -151,6 +245,8 @@ impl TryFrom<IncomingPayment> for Payment {
                     lnurl_withdraw_endpoint: None,
                     swap_info: None,
                     reverse_swap_info: None,
+                    custom_tlvs: p.custom_tlvs,
+                    open_channel_bolt11: p.open_channel_bolt11,
                 },
             },
             metadata: None,
@@ -164,18 +260,59 @@ impl TryFrom<IncomingPayment> for Payment {
 pub trait NodeAPI: Send + Sync {
     async fn configure_node(&self, close_to_address: Option<String>) -> NodeResult<()>;
     async fn delete_invoice(&self, bolt11: String) -> NodeResult<()>;
-    fn max_receivable_single_payment_msat(&self) -> Result<u64, ReceivePaymentError>;
+    /// Cancels the invoice with the given payment hash if it is still awaiting payment, so that
+    /// payments for it are rejected. Unknown hashes are ignored.
+    async fn cancel_jit_invoice(&self, payment_hash: String) -> NodeResult<()>;
+    /// The maximum amount receivable in a single payment without opening a new channel.
+    ///
+    /// With `mpp` the payer may split the payment across all the channels, otherwise
+    /// the payment has to fit into the channel with the most inbound liquidity.
+    fn max_receivable_single_payment_msat(&self, mpp: bool) -> Result<u64, ReceivePaymentError>;
+    /// Estimates the inbound liquidity and the opening fee needed to receive
+    /// `target_receivable_msat` in a single payment.
+    async fn estimate_liquidity_for(
+        &self,
+        target_receivable_msat: u64,
+    ) -> NodeResult<LiquidityEstimate>;
     async fn create_invoice(&self, req: CreateInvoiceRequest) -> NodeResult<String>;
     /// Fetches an existing BOLT11 invoice from the node
     async fn fetch_bolt11(&self, payment_hash: Vec<u8>) -> NodeResult<Option<FetchBolt11Result>>;
     async fn get_node_state(&self) -> NodeState;
+    /// The status of the payment with the given hash, looked up in the node directly.
+    ///
+    /// An invoice awaiting payment is reported as pending, `None` is returned if the node knows
+    /// neither a payment nor an invoice with the hash.
+    async fn payment_status(&self, payment_hash: String) -> NodeResult<Option<PaymentStatus>>;
     async fn list_payments(&self) -> NodeResult<Vec<Payment>>;
+    /// Prunes the SDK-side data of the payments with one of the given statuses, which were
+    /// last updated before `older_than_ts`. Returns the ids of the pruned payments.
+    ///
+    /// Pruned payments are no longer returned by [NodeAPI::list_payments].
+    async fn prune_payments(
+        &self,
+        older_than_ts: u64,
+        statuses: Vec<PaymentStatus>,
+    ) -> NodeResult<Vec<String>>;
     /// As per the `pb::PayRequest` docs, `amount_msat` is only needed when the invoice doesn't specify an amount
+    ///
+    /// The routing fee is limited to `max_fee_msat` if set, otherwise to the limit derived from the config.
+    ///
+    /// Once `cancel` is cancelled, stops waiting for the outcome and fails with [NodeError::Cancelled].
+    /// A payment which was already dispatched is not aborted.
     async fn send_payment(
         &self,
         bolt11: String,
         amount_msat: Option<u64>,
+        max_fee_msat: Option<u64>,
+        cancel: CancellationToken,
     ) -> NodeResult<Payment>;
+    /// Probes for a route to pay the invoice with a routing fee of at most `max_fee_msat`.
+    async fn probe_payment(
+        &self,
+        bolt11: String,
+        amount_msat: Option<u64>,
+        max_fee_msat: u64,
+    ) -> NodeResult<bool>;
     async fn send_spontaneous_payment(
         &self,
         node_id: String,
@@ -206,11 +343,90 @@ pub trait NodeAPI: Send + Sync {
         req: PrepareRedeemOnchainFundsRequest,
     ) -> NodeResult<PrepareRedeemOnchainFundsResponse>;
     async fn start(&self, shutdown: mpsc::Receiver<()>);
+    /// Waits until the node has synced to the tip of the chain, failing after `timeout`.
+    async fn wait_until_synced(&self, timeout: Duration) -> NodeResult<()>;
     async fn connect_peer(&self, node_id: String, addr: String) -> NodeResult<()>;
-    async fn close_all_channels(&self) -> NodeResult<()>;
+    /// Opens a channel of `amount_sat` to the given node, pushing `push_msat` to it.
+    async fn open_channel(
+        &self,
+        node_id: String,
+        addr: String,
+        amount_sat: u64,
+        push_msat: u64,
+    ) -> NodeResult<()>;
+    /// Connects to the configured LSP if not connected and keeps reconnecting to it, returning
+    /// the node id of the LSP.
+    async fn ensure_lsp_connected(&self) -> NodeResult<String>;
+    /// Reads the app data stored under the key, if any.
+    async fn app_kv_get(&self, key: String) -> NodeResult<Option<Vec<u8>>>;
+    /// Stores app data under the key, alongside the node state and synced the same way.
+    async fn app_kv_set(&self, key: String, value: Vec<u8>) -> NodeResult<()>;
+    async fn app_kv_delete(&self, key: String) -> NodeResult<()>;
+    async fn disconnect_peer(&self, node_id: String) -> NodeResult<()>;
+    async fn list_peers(&self) -> NodeResult<Vec<PeerInfo>>;
+    async fn list_channels(&self) -> NodeResult<Vec<ChannelInfo>>;
+    /// Adds `amount_sat` from the on-chain wallet to the channel with the given id.
+    async fn splice_in(&self, channel_id: String, amount_sat: u64) -> NodeResult<()>;
+    /// Moves `amount_sat` from the channel with the given id to `to_address`.
+    async fn splice_out(
+        &self,
+        channel_id: String,
+        amount_sat: u64,
+        to_address: String,
+    ) -> NodeResult<()>;
+    async fn new_onchain_address(&self) -> NodeResult<String>;
+    async fn close_all_channels(
+        &self,
+        close_to_address: Option<String>,
+        force: bool,
+    ) -> NodeResult<()>;
+    /// Closes the channel with the given id, force-closing it if `force` is set. If a
+    /// `close_to_address` is given, the funds of the channel are sent to it once they are
+    /// claimed on-chain, even if that happens after a restart.
+    async fn close_channel(
+        &self,
+        channel_id: String,
+        close_to_address: Option<String>,
+        force: bool,
+    ) -> NodeResult<()>;
+    /// Sums the fees of the completed payments made from `from_ts` (inclusive) to `to_ts`
+    /// (exclusive), both in epoch seconds.
+    async fn fee_summary(&self, from_ts: i64, to_ts: i64) -> NodeResult<FeeSummary>;
+    /// Force-closes the channel with the given id, provided that it is not ready yet.
+    async fn abort_channel_open(&self, channel_id: String) -> NodeResult<()>;
+    /// Returns the status of the funds of the closed channel with the given id, or `None`
+    /// if there are no funds left to claim from it.
+    async fn pending_close_status(
+        &self,
+        channel_id: String,
+    ) -> NodeResult<Option<PendingCloseStatus>>;
+    /// Lists the funds from closed channels which are being swept to the on-chain wallet.
+    async fn list_pending_sweeps(&self) -> NodeResult<Vec<PendingSweep>>;
+    /// Broadcasts again the unconfirmed transactions of the node, which may have been dropped
+    /// from the mempools, returning the ids of those rebroadcast.
+    async fn rebroadcast_pending(&self) -> NodeResult<Vec<String>>;
+    /// Returns the number of nodes and channels in the routing graph.
+    async fn graph_status(&self) -> NodeResult<GraphStatus>;
+    /// Returns the current on-chain fee rates for a few confirmation targets.
+    async fn fee_estimates(&self) -> NodeResult<FeeEstimates>;
+    /// Probes the external dependencies of the node, reporting whether each one is reachable.
+    async fn connectivity_check(&self) -> NodeResult<ConnectivityCheckResult>;
+    /// Discards the local cache of the node state and downloads it again from the remote
+    /// storage, dropping the local changes not yet synced.
+    async fn resync_from_remote(&self) -> NodeResult<ResyncResult>;
     async fn stream_incoming_payments(
         &self,
     ) -> NodeResult<Pin<Box<dyn Stream<Item = IncomingPayment> + Send>>>;
+    async fn stream_remote_lock_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = RemoteLockEvent> + Send>>>;
+    async fn stream_splice_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = SpliceEvent> + Send>>>;
+    /// Streams the reorganizations of the chain observed by the node.
+    async fn stream_reorg_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = ReorgEvent> + Send>>>;
     async fn generate_diagnostic_data(&self) -> NodeResult<Value>;
     async fn sign_message(&self, message: &str) -> NodeResult<String>;
     async fn check_message(&self, message: &str, pubkey: &str, signature: &str)
@@ -223,6 +439,23 @@ pub trait NodeAPI: Send + Sync {
     /// Gets the private key at the path specified
     async fn derive_bip32_key(&self, path: Vec<ChildNumber>) -> NodeResult<Xpriv>;
 
+    /// Gets the private key at the path specified, for the given network instead of the
+    /// network of the node
+    async fn derive_bip32_key_for_network(
+        &self,
+        path: Vec<ChildNumber>,
+        network: Network,
+    ) -> NodeResult<Xpriv>;
+
     /// Get peers with whom we have an open channel
     async fn get_open_peers(&self) -> NodeResult<HashSet<Vec<u8>>>;
 }
+
+/// Splits a node address in the `node_id@host:port` format into the node id and the host.
+pub(crate) fn split_node_address(address: &str) -> NodeResult<(&str, &str)> {
+    address.split_once('@').ok_or_else(|| {
+        NodeError::Generic(format!(
+            "Invalid node address {address}, does not contain @"
+        ))
+    })
+}
@@ -1,9 +1,11 @@
 use std::sync::Arc;
 
+use sdk_common::prelude::Network;
+
 use crate::backup::BackupTransport;
-use crate::ldk::{Ldk, LdkBackupTransport};
-use crate::models::{Config, LspAPI};
-use crate::node_api::{NodeAPI, NodeResult};
+use crate::ldk::{self, Ldk, LdkBackupTransport};
+use crate::models::{Config, LspAPI, VssCheckResult};
+use crate::node_api::{NodeAPI, NodeResult, RestoreEventListener};
 use crate::persist::db::SqliteStorage;
 
 pub struct NodeImpls {
@@ -18,9 +20,10 @@ pub async fn build_node(
     seed: Vec<u8>,
     restore_only: Option<bool>,
     persister: Arc<SqliteStorage>,
+    restore_event_listener: Option<RestoreEventListener>,
 ) -> NodeResult<NodeImpls> {
     let backup_transport = Arc::new(LdkBackupTransport::new(&config, &seed)?);
-    let ldk = Ldk::build(config, &seed, restore_only).await?;
+    let ldk = Ldk::build(config, &seed, restore_only, restore_event_listener).await?;
     let ldk = Arc::new(ldk);
     let lsp: Option<Arc<dyn LspAPI>> = Some(ldk.clone());
     Ok(NodeImpls {
@@ -29,3 +32,19 @@ pub async fn build_node(
         lsp,
     })
 }
+
+pub fn recovery_fingerprint(network: Network, seed: &[u8]) -> NodeResult<String> {
+    ldk::recovery_fingerprint(network, seed)
+}
+
+pub async fn check_vss(config: &Config, seed: &[u8]) -> NodeResult<VssCheckResult> {
+    ldk::check_vss(config, seed).await
+}
+
+pub async fn migrate_vss(config: &Config, new_config: &Config, seed: &[u8]) -> NodeResult<()> {
+    ldk::migrate_vss(config, new_config, seed).await
+}
+
+pub fn reset_local_cache(config: &Config) -> NodeResult<()> {
+    ldk::reset_local_cache(&config.working_dir)
+}
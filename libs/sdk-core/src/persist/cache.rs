@@ -2,6 +2,7 @@ use super::{db::SqliteStorage, error::PersistResult};
 
 const KEY_LAST_BACKUP_TIME: &str = "last_backup_time";
 const KEY_WEBHOOK_URL: &str = "webhook_url";
+const KEY_PREFIX_INVOICE_WEBHOOK_URL: &str = "invoice_webhook_url_";
 const KEY_MEMPOOLSPACE_BASE_URLS: &str = "mempoolspace_base_urls";
 
 impl SqliteStorage {
@@ -52,6 +53,26 @@ impl SqliteStorage {
         self.get_cached_item(KEY_WEBHOOK_URL)
     }
 
+    /// Stores the webhook url registered for the payment notifications of an invoice.
+    pub fn set_invoice_webhook_url(
+        &self,
+        payment_hash: &str,
+        webhook_url: String,
+    ) -> PersistResult<()> {
+        self.update_cached_item(
+            &format!("{KEY_PREFIX_INVOICE_WEBHOOK_URL}{payment_hash}"),
+            webhook_url,
+        )
+    }
+
+    pub fn remove_invoice_webhook_url(&self, payment_hash: &str) -> PersistResult<()> {
+        self.delete_cached_item(&format!("{KEY_PREFIX_INVOICE_WEBHOOK_URL}{payment_hash}"))
+    }
+
+    pub fn get_invoice_webhook_url(&self, payment_hash: &str) -> PersistResult<Option<String>> {
+        self.get_cached_item(&format!("{KEY_PREFIX_INVOICE_WEBHOOK_URL}{payment_hash}"))
+    }
+
     pub fn set_mempoolspace_base_urls(
         &self,
         mempool_space_endpoints: Vec<String>,
@@ -87,6 +87,25 @@ impl SqliteStorage {
         Ok(())
     }
 
+    /// Deletes the given payments along with their external info and metadata
+    pub fn delete_payments(&self, payment_ids: &[String]) -> PersistResult<()> {
+        let mut con = self.get_connection()?;
+        let tx = con.transaction()?;
+        for payment_id in payment_ids {
+            tx.execute("DELETE FROM payments WHERE id = ?1", [payment_id])?;
+            tx.execute(
+                "DELETE FROM sync.payments_external_info WHERE payment_id = ?1",
+                [payment_id],
+            )?;
+            tx.execute(
+                "DELETE FROM sync.payments_metadata WHERE payment_id = ?1",
+                [payment_id],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
     /// Inserts metadata associated with this payment
     pub fn insert_payment_external_info(
         &self,
@@ -561,6 +580,8 @@ mod test {
                         lnurl_withdraw_endpoint: None,
                         swap_info: None,
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -589,6 +610,8 @@ mod test {
                         lnurl_withdraw_endpoint: Some(lnurl_withdraw_url.to_string()),
                         swap_info: None,
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -617,6 +640,8 @@ mod test {
                         lnurl_withdraw_endpoint: None,
                         swap_info: Some(swap_info.clone()),
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -645,6 +670,8 @@ mod test {
                         lnurl_withdraw_endpoint: None,
                         swap_info: None,
                         reverse_swap_info: Some(rev_swap_info.clone()),
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -673,6 +700,8 @@ mod test {
                         lnurl_withdraw_endpoint: None,
                         swap_info: None,
                         reverse_swap_info: None,
+                        custom_tlvs: None,
+                        open_channel_bolt11: None,
                     },
                 },
                 metadata: None,
@@ -702,6 +731,8 @@ mod test {
                     lnurl_withdraw_endpoint: None,
                     swap_info: None,
                     reverse_swap_info: None,
+                    custom_tlvs: None,
+                    open_channel_bolt11: None,
                 },
             },
             metadata: None,
@@ -886,4 +917,57 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_delete_payments() -> PersistResult<(), Box<dyn std::error::Error>> {
+        use crate::models::{Payment, PaymentDetails};
+        use crate::persist::test_utils;
+
+        let payment = |id: &str, payment_time: i64| Payment {
+            id: id.to_string(),
+            payment_type: PaymentType::Received,
+            payment_time,
+            amount_msat: 100,
+            fee_msat: 0,
+            status: PaymentStatus::Complete,
+            error: None,
+            description: None,
+            details: PaymentDetails::Ln {
+                data: Default::default(),
+            },
+            metadata: None,
+        };
+        let old = payment("old", 1000);
+        let recent = payment("recent", 2000);
+
+        let storage = SqliteStorage::new(test_utils::create_test_sql_dir());
+        storage.init()?;
+        storage.insert_or_update_payments(&[old.clone(), recent.clone()], false)?;
+        for p in [&old, &recent] {
+            storage.insert_payment_external_info(
+                &p.id,
+                PaymentExternalInfo {
+                    ln_address: Some("test@ln.address.com".to_string()),
+                    ..Default::default()
+                },
+            )?;
+            storage.set_payment_external_metadata(p.id.clone(), r#"{"a":1}"#.to_string())?;
+        }
+
+        storage.delete_payments(&[old.id.clone()])?;
+        assert!(storage.get_payment_by_hash(&old.id)?.is_none());
+        let retrieved = storage.get_payment_by_hash(&recent.id)?.unwrap();
+        assert!(retrieved.metadata.is_some());
+
+        // The external info and metadata of the deleted payment are gone as well.
+        storage.insert_or_update_payments(&[old.clone()], false)?;
+        let retrieved = storage.get_payment_by_hash(&old.id)?.unwrap();
+        assert_eq!(retrieved.metadata, None);
+        match retrieved.details {
+            PaymentDetails::Ln { data } => assert_eq!(data.ln_address, None),
+            _ => panic!("Expected LN payment details"),
+        }
+
+        Ok(())
+    }
 }
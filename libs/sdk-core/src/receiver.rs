@@ -4,12 +4,26 @@ use sdk_common::ensure_sdk;
 use sdk_common::invoice::parse_invoice;
 
 use crate::error::ReceivePaymentError;
+use crate::lsp::LspInformation;
 use crate::models::{
     LspAPI, OpeningFeeParams, ReceivePaymentRequest, ReceivePaymentResponse,
     INVOICE_PAYMENT_FEE_EXPIRY_SECONDS,
 };
 use crate::node_api::{CreateInvoiceRequest, NodeAPI};
 
+/// Invoices created by the node advertise basic MPP, so payers can split a payment
+/// across all the channels instead of fitting it into a single one.
+const INVOICE_SUPPORTS_MPP: bool = true;
+
+/// The bounds of a requested `min_final_cltv_expiry_delta`, from the BOLT 11 default up to
+/// roughly two weeks of blocks.
+const MIN_FINAL_CLTV_EXPIRY_DELTA: u32 = 18;
+const MAX_FINAL_CLTV_EXPIRY_DELTA: u32 = 2016;
+
+/// The maximum length in bytes of a BOLT 11 description, which has to fit in a tagged field
+/// of at most 1023 5-bit words.
+const MAX_DESCRIPTION_LEN: usize = 639;
+
 #[cfg_attr(test, mockall::automock)]
 #[tonic::async_trait]
 pub trait Receiver: Send + Sync {
@@ -23,17 +37,24 @@ pub trait Receiver: Send + Sync {
 pub(crate) struct PaymentReceiver {
     node_api: Arc<dyn NodeAPI>,
     lsp_api: Arc<dyn LspAPI>,
+    /// The maximum number of channels opened by the LSP to receive payments, 0 for no limit.
+    max_jit_channels: u32,
 }
 
 impl PaymentReceiver {
-    pub(crate) fn new(node_api: Arc<dyn NodeAPI>, lsp_api: Arc<dyn LspAPI>) -> Self {
-        Self { node_api, lsp_api }
+    pub(crate) fn new(
+        node_api: Arc<dyn NodeAPI>,
+        lsp_api: Arc<dyn LspAPI>,
+        max_jit_channels: u32,
+    ) -> Self {
+        Self {
+            node_api,
+            lsp_api,
+            max_jit_channels,
+        }
     }
 
-    async fn load_default_opening_fee_params(
-        &self,
-        expiry: u32,
-    ) -> Result<OpeningFeeParams, ReceivePaymentError> {
+    async fn load_lsp(&self) -> Result<LspInformation, ReceivePaymentError> {
         let node_pubkey = self.node_api.node_id().await?;
         self.lsp_api
             .list_lsps(node_pubkey)
@@ -43,17 +64,87 @@ impl PaymentReceiver {
             .next()
             .ok_or_else(|| ReceivePaymentError::Generic {
                 err: "Empty LSP list".to_string(),
-            })?
-            .cheapest_open_channel_fee(expiry)
+            })
+    }
+
+    /// Returns the requested opening fee params if the LSP still honors them, otherwise
+    /// falls back to the cheapest params currently offered by the LSP.
+    async fn load_opening_fee_params(
+        &self,
+        requested: Option<OpeningFeeParams>,
+        expiry: u32,
+    ) -> Result<OpeningFeeParams, ReceivePaymentError> {
+        let lsp = self.load_lsp().await?;
+        if let Some(requested) = requested {
+            match lsp.verify_opening_fee_params(&requested, expiry) {
+                Ok(()) => return Ok(requested),
+                Err(e) => warn!("Ignoring requested opening fee params: {e}"),
+            }
+        }
+        lsp.cheapest_open_channel_fee(expiry)
             .cloned()
             .map_err(Into::into)
     }
+
+    /// Fails if the amount is below the minimum HTLC forwarded by the LSP, as payments reach
+    /// the node through it and a smaller one could never be received.
+    async fn ensure_above_min_htlc(&self, amount_msat: u64) -> Result<(), ReceivePaymentError> {
+        let node_pubkey = self.node_api.node_id().await?;
+        let lsps = self
+            .lsp_api
+            .list_lsps(node_pubkey)
+            .await
+            .map_err(|e| ReceivePaymentError::Generic { err: e.to_string() })?;
+        // Without an LSP there is no forwarding minimum to check against.
+        let Some(lsp) = lsps.first() else {
+            return Ok(());
+        };
+        let min_htlc_msat = u64::try_from(lsp.min_htlc_msat).unwrap_or_default();
+        ensure_sdk!(
+            amount_msat >= min_htlc_msat,
+            ReceivePaymentError::InvalidAmount {
+                err: format!(
+                    "Amount {amount_msat} msat is below the minimum HTLC of {min_htlc_msat} msat forwarded by the LSP"
+                )
+            }
+        );
+        Ok(())
+    }
+
+    /// Fails if the channels already opened by the LSP reached the configured maximum, so
+    /// that no further opening fee is paid.
+    async fn ensure_jit_channel_allowed(&self) -> Result<(), ReceivePaymentError> {
+        if self.max_jit_channels == 0 {
+            return Ok(());
+        }
+        let lsp = self.load_lsp().await?;
+        let jit_channels = self
+            .node_api
+            .list_channels()
+            .await?
+            .into_iter()
+            .filter(|c| c.counterparty_node_id == lsp.pubkey)
+            .count();
+        ensure_sdk!(
+            jit_channels < self.max_jit_channels as usize,
+            ReceivePaymentError::JitChannelLimitReached {
+                err: format!(
+                    "Receiving the amount needs a new channel, but {jit_channels} channels were already opened by the LSP out of the maximum {}",
+                    self.max_jit_channels
+                )
+            }
+        );
+        Ok(())
+    }
 }
 
 #[tonic::async_trait]
 impl Receiver for PaymentReceiver {
     fn open_channel_needed(&self, amount_msat: u64) -> Result<bool, ReceivePaymentError> {
-        Ok(self.node_api.max_receivable_single_payment_msat()? < amount_msat)
+        let max_receivable_msat = self
+            .node_api
+            .max_receivable_single_payment_msat(INVOICE_SUPPORTS_MPP)?;
+        Ok(max_receivable_msat < amount_msat)
     }
 
     async fn receive_payment(
@@ -74,21 +165,54 @@ impl Receiver for PaymentReceiver {
             opening_fee_params: requested_opening_fee_params,
             use_description_hash,
             expiry,
-            cltv: _,
+            cltv,
+            register_notifications: _,
+            truncate_description,
+            no_open_channel,
         } = req;
+        let no_open_channel = no_open_channel.unwrap_or(false);
+        // Only the hash of the description is included in the invoice otherwise.
+        let description = match use_description_hash.unwrap_or(false) {
+            true => description,
+            false => validate_description(description, truncate_description.unwrap_or(false))?,
+        };
+        if let Some(cltv) = cltv {
+            ensure_sdk!(
+                (MIN_FINAL_CLTV_EXPIRY_DELTA..=MAX_FINAL_CLTV_EXPIRY_DELTA).contains(&cltv),
+                ReceivePaymentError::Generic {
+                    err: format!(
+                        "Invalid min_final_cltv_expiry_delta {cltv}, must be between {MIN_FINAL_CLTV_EXPIRY_DELTA} and {MAX_FINAL_CLTV_EXPIRY_DELTA}"
+                    )
+                }
+            );
+        }
+
+        self.ensure_above_min_htlc(amount_msat).await?;
 
         let expiry = expiry.unwrap_or(INVOICE_PAYMENT_FEE_EXPIRY_SECONDS);
+        // The existing inbound capacity is always preferred, a channel is only opened when
+        // the amount cannot be received otherwise.
         let open_channel_needed = self.open_channel_needed(amount_msat)?;
+        if open_channel_needed && !no_open_channel {
+            self.ensure_jit_channel_allowed().await?;
+        }
 
         let opening_fee_params = match (open_channel_needed, requested_opening_fee_params) {
-            (true, Some(opening_fee_params)) => Some(opening_fee_params),
-            (true, None) => Some(self.load_default_opening_fee_params(expiry).await?),
+            (true, requested) => Some(self.load_opening_fee_params(requested, expiry).await?),
             (false, _) => None,
         };
 
         let opening_fee_msat = opening_fee_params
             .as_ref()
             .map(|p| p.get_channel_fees_msat_for(amount_msat));
+        if let (true, Some(opening_fee_msat)) = (no_open_channel, opening_fee_msat) {
+            return Err(ReceivePaymentError::OpenChannelNeeded {
+                err: format!(
+                    "Receiving {amount_msat} msat needs a new channel with an opening fee of {opening_fee_msat} msat"
+                ),
+                opening_fee_msat,
+            });
+        }
         if let Some(opening_fee_msat) = opening_fee_msat {
             ensure_sdk!(
                 amount_msat >= opening_fee_msat + 1000,
@@ -109,6 +233,7 @@ impl Receiver for PaymentReceiver {
                 preimage,
                 opening_fee_msat,
                 expiry,
+                min_accepted_cltv: cltv,
             })
             .await?;
 
@@ -119,3 +244,233 @@ impl Receiver for PaymentReceiver {
         })
     }
 }
+
+/// Checks that the description fits in the invoice, truncating it on a char boundary if
+/// `truncate` is set.
+fn validate_description(
+    mut description: String,
+    truncate: bool,
+) -> Result<String, ReceivePaymentError> {
+    if description.len() <= MAX_DESCRIPTION_LEN {
+        return Ok(description);
+    }
+    ensure_sdk!(
+        truncate,
+        ReceivePaymentError::InvalidDescription {
+            err: format!(
+                "Description is {} bytes long, but the maximum is {MAX_DESCRIPTION_LEN} bytes",
+                description.len()
+            )
+        }
+    );
+    let mut len = MAX_DESCRIPTION_LEN;
+    while !description.is_char_boundary(len) {
+        len -= 1;
+    }
+    description.truncate(len);
+    Ok(description)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use anyhow::Result;
+
+    use crate::breez_services::tests::get_dummy_node_state;
+    use crate::error::ReceivePaymentError;
+    use crate::models::{ChannelInfo, NodeState, ReceivePaymentRequest};
+    use crate::node_api::NodeError;
+    use crate::receiver::{PaymentReceiver, Receiver};
+    use crate::test_utils::{MockBreezServer, MockNodeAPI};
+
+    #[tokio::test]
+    async fn test_receive_payment_max_jit_channels() -> Result<()> {
+        let lsp = MockBreezServer {};
+        let mut node_api = MockNodeAPI::new(NodeState {
+            max_receivable_msat: 10_000_000,
+            ..get_dummy_node_state()
+        });
+        node_api.set_channels(vec![ChannelInfo {
+            channel_id: "01".repeat(32),
+            counterparty_node_id: lsp.lsp_pub_key(),
+            funding_txid: None,
+            capacity_sat: 20_000,
+            outbound_capacity_msat: 10_000_000,
+            inbound_capacity_msat: 10_000_000,
+            is_channel_ready: true,
+            is_usable: true,
+            confirmations: Some(6),
+            confirmations_required: Some(6),
+        }]);
+        let receiver = PaymentReceiver::new(Arc::new(node_api), Arc::new(lsp), 1);
+
+        // Small receives reuse the capacity of the channel opened by the first one.
+        for amount_msat in [1_000_000, 2_000_000] {
+            let res = receiver
+                .receive_payment(ReceivePaymentRequest {
+                    amount_msat,
+                    description: "small".to_string(),
+                    ..Default::default()
+                })
+                .await?;
+            assert!(res.opening_fee_params.is_none());
+            assert!(res.opening_fee_msat.is_none());
+        }
+
+        let res = receiver
+            .receive_payment(ReceivePaymentRequest {
+                amount_msat: 20_000_000,
+                description: "large".to_string(),
+                ..Default::default()
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(ReceivePaymentError::JitChannelLimitReached { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_payment_no_open_channel() -> Result<()> {
+        let node_api = MockNodeAPI::new(NodeState {
+            max_receivable_msat: 10_000_000,
+            ..get_dummy_node_state()
+        });
+        let receiver = PaymentReceiver::new(Arc::new(node_api), Arc::new(MockBreezServer {}), 0);
+
+        // The existing inbound capacity is enough.
+        let res = receiver
+            .receive_payment(ReceivePaymentRequest {
+                amount_msat: 1_000_000,
+                description: "small".to_string(),
+                no_open_channel: Some(true),
+                ..Default::default()
+            })
+            .await?;
+        assert!(res.opening_fee_msat.is_none());
+
+        let req = ReceivePaymentRequest {
+            amount_msat: 20_000_000,
+            description: "large".to_string(),
+            ..Default::default()
+        };
+        let expected_fee_msat = receiver
+            .receive_payment(req.clone())
+            .await?
+            .opening_fee_msat
+            .unwrap();
+        let res = receiver
+            .receive_payment(ReceivePaymentRequest {
+                no_open_channel: Some(true),
+                ..req
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(ReceivePaymentError::OpenChannelNeeded { opening_fee_msat, .. })
+                if opening_fee_msat == expected_fee_msat
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_payment_below_min_htlc() -> Result<()> {
+        let node_api = MockNodeAPI::new(NodeState {
+            max_receivable_msat: 10_000_000,
+            ..get_dummy_node_state()
+        });
+        let receiver = PaymentReceiver::new(Arc::new(node_api), Arc::new(MockBreezServer {}), 0);
+        let req = ReceivePaymentRequest {
+            amount_msat: 1_000,
+            description: "dust".to_string(),
+            ..Default::default()
+        };
+
+        // The mock LSP forwards HTLCs of at least 1000 msat.
+        receiver.receive_payment(req.clone()).await?;
+        let res = receiver
+            .receive_payment(ReceivePaymentRequest {
+                amount_msat: 999,
+                ..req
+            })
+            .await;
+        assert!(matches!(
+            res,
+            Err(ReceivePaymentError::InvalidAmount { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_payment_reused_preimage() -> Result<()> {
+        let node_api = MockNodeAPI::new(NodeState {
+            max_receivable_msat: 10_000_000,
+            ..get_dummy_node_state()
+        });
+        let receiver = PaymentReceiver::new(Arc::new(node_api), Arc::new(MockBreezServer {}), 0);
+        let req = ReceivePaymentRequest {
+            amount_msat: 1_000_000,
+            description: "reused".to_string(),
+            preimage: Some(vec![1; 32]),
+            ..Default::default()
+        };
+
+        receiver.receive_payment(req.clone()).await?;
+        let res = receiver.receive_payment(req).await;
+        assert!(matches!(
+            res,
+            Err(ReceivePaymentError::InvoicePreimageAlreadyExists { .. })
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_receive_payment_lsp_rejection() -> Result<()> {
+        let cases = [
+            (
+                ldk_node::NodeError::LiquiditySourceUnavailable,
+                "LspUnavailable",
+            ),
+            (
+                ldk_node::NodeError::LiquidityRequestFailed,
+                "LspRequestRejected",
+            ),
+            (ldk_node::NodeError::LiquidityFeeTooHigh, "LspFeeTooHigh"),
+            (ldk_node::NodeError::InvalidAmount, "InvalidAmount"),
+        ];
+        for (node_err, expected) in cases {
+            let mut node_api = MockNodeAPI::new(NodeState {
+                max_receivable_msat: 10_000_000,
+                ..get_dummy_node_state()
+            });
+            node_api.set_on_create_invoice(Box::new(move |_| Err(NodeError::from(node_err))));
+            let receiver =
+                PaymentReceiver::new(Arc::new(node_api), Arc::new(MockBreezServer {}), 0);
+
+            let err = receiver
+                .receive_payment(ReceivePaymentRequest {
+                    amount_msat: 1_000_000,
+                    description: "rejected".to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap_err();
+            let variant = match err {
+                ReceivePaymentError::LspUnavailable { .. } => "LspUnavailable",
+                ReceivePaymentError::LspRequestRejected { .. } => "LspRequestRejected",
+                ReceivePaymentError::LspFeeTooHigh { .. } => "LspFeeTooHigh",
+                ReceivePaymentError::InvalidAmount { .. } => "InvalidAmount",
+                _ => panic!("Unexpected error for {node_err:?}: {err:?}"),
+            };
+            assert_eq!(variant, expected);
+        }
+
+        Ok(())
+    }
+}
@@ -227,7 +227,8 @@ impl From<ReceivePaymentError> for GetPaymentRequestError {
             ReceivePaymentError::InvoicePreimageAlreadyExists { err: _ } => {
                 GetPaymentRequestError::InvoiceAlreadyExists
             }
-            ReceivePaymentError::ServiceConnectivity { err } => {
+            ReceivePaymentError::ServiceConnectivity { err }
+            | ReceivePaymentError::LspUnavailable { err } => {
                 GetPaymentRequestError::ServiceConnectivity(err)
             }
             _ => GetPaymentRequestError::Generic(e.to_string()),
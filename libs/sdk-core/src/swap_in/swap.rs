@@ -992,6 +992,9 @@ impl BTCReceiveSwap {
                 opening_fee_params,
                 preimage: Some(swap_info.preimage.clone()),
                 use_description_hash: None,
+                register_notifications: None,
+                truncate_description: None,
+                no_open_channel: None,
             })
             .await;
         match receive_resp {
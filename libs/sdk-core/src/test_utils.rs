@@ -9,6 +9,7 @@ use rand::distributions::uniform::{SampleRange, SampleUniform};
 use rand::distributions::{Alphanumeric, DistString, Standard};
 use rand::rngs::OsRng;
 use rand::{random, Rng};
+use sdk_common::ensure_sdk;
 use sdk_common::grpc::{
     self, CreateSwapResponse, PaySwapResponse, RefundSwapResponse, SwapParameters,
 };
@@ -19,6 +20,7 @@ use tokio::sync::{mpsc, Mutex};
 use tokio::time::sleep;
 use tokio_stream::Stream;
 use tokio_stream::StreamExt;
+use tokio_util::sync::CancellationToken;
 use tonic::Status;
 
 use crate::backup::{BackupState, BackupTransport};
@@ -33,7 +35,7 @@ use crate::bitcoin::secp256k1::{Message, PublicKey, Secp256k1};
 use crate::bitcoin::taproot::{TaprootBuilder, TaprootSpendInfo};
 use crate::bitcoin::{key::XOnlyPublicKey, Address, Network, ScriptBuf, Sequence};
 use crate::buy::BuyBitcoinApi;
-use crate::chain::{ChainService, OnchainTx, RecommendedFees};
+use crate::chain::{ChainService, OnchainTx, Outspend, RecommendedFees, TxStatus};
 use crate::error::{ReceivePaymentError, SdkError, SdkResult};
 use crate::invoice::{InvoiceError, InvoiceResult};
 use crate::lightning::bitcoin::hashes as ldk_hashes;
@@ -41,11 +43,14 @@ use crate::lightning::bitcoin::secp256k1 as ldk_secp256k1;
 use crate::lightning_invoice::{Currency, InvoiceBuilder, PaymentSecret, RawBolt11Invoice};
 use crate::lsp::LspInformation;
 use crate::models::{
-    LnPaymentDetails, LspAPI, NodeState, Payment, PaymentDetails, PaymentStatus, PaymentType,
-    ReverseSwapServiceAPI, SwapperAPI, TlvEntry,
+    ChannelInfo, ConnectivityCheckResult, DependencyStatus, FeeEstimates, FeeSummary, GraphStatus,
+    LiquidityEstimate, LnPaymentDetails, LspAPI, NodeState, Payment, PaymentDetails, PaymentStatus,
+    PaymentType, PeerInfo, PendingCloseStatus, PendingSweep, ResyncResult, ReverseSwapServiceAPI,
+    SwapperAPI, TlvEntry,
 };
 use crate::node_api::{
     CreateInvoiceRequest, FetchBolt11Result, IncomingPayment, NodeAPI, NodeError, NodeResult,
+    RemoteLockEvent, ReorgEvent, SpliceEvent,
 };
 use crate::swap_in::TaprootSwapperAPI;
 use crate::swap_out::boltzswap::{BoltzApiCreateReverseSwapResponse, BoltzApiReverseSwapStatus};
@@ -216,6 +221,53 @@ impl ChainService for MockChainService {
         Ok(self.tip)
     }
 
+    async fn block_hash(&self, height: u32) -> SdkResult<String> {
+        Err(SdkError::Generic {
+            err: format!("Block at height {height} not found"),
+        })
+    }
+
+    async fn transaction_status(&self, txid: String) -> SdkResult<TxStatus> {
+        Ok(self
+            .address_to_transactions
+            .values()
+            .flatten()
+            .find(|tx| tx.txid == txid)
+            .map(|tx| tx.status.clone())
+            .unwrap_or_default())
+    }
+
+    async fn transaction_outspends(&self, txid: String) -> SdkResult<Vec<Outspend>> {
+        let txs: Vec<&OnchainTx> = self.address_to_transactions.values().flatten().collect();
+        let Some(tx) = txs.iter().find(|tx| tx.txid == txid) else {
+            return Ok(Vec::new());
+        };
+        Ok((0..tx.vout.len() as u32)
+            .map(|vout| {
+                txs.iter()
+                    .find_map(|spending_tx| {
+                        let vin = spending_tx
+                            .vin
+                            .iter()
+                            .position(|vin| vin.txid == txid && vin.vout == vout)?;
+                        Some(Outspend {
+                            spent: true,
+                            txid: Some(spending_tx.txid.clone()),
+                            vin: Some(vin as u32),
+                            status: Some(spending_tx.status.clone()),
+                        })
+                    })
+                    .unwrap_or_default()
+            })
+            .collect())
+    }
+
+    async fn transaction_hex(&self, txid: String) -> SdkResult<Vec<u8>> {
+        Err(SdkError::Generic {
+            err: format!("Transaction {txid} not found"),
+        })
+    }
+
     async fn broadcast_transaction(&self, _tx: Vec<u8>) -> SdkResult<String> {
         let mut array = [0; 32];
         rand::thread_rng().fill(&mut array);
@@ -253,6 +305,12 @@ pub struct MockNodeAPI {
     /// added test payments.
     cloud_payments: Mutex<Vec<Payment>>,
     node_state: NodeState,
+    channels: Vec<ChannelInfo>,
+    /// Payment hashes of the created invoices, to reject reusing a preimage like the node does.
+    invoice_hashes: Mutex<HashSet<String>>,
+    /// Whether sent payments never complete, only returning once they are cancelled.
+    payments_in_flight: bool,
+    on_create_invoice: Box<dyn Fn(&CreateInvoiceRequest) -> NodeResult<()> + Sync + Send>,
     on_send_custom_message: Box<dyn Fn(CustomMessage) -> NodeResult<()> + Sync + Send>,
     on_stream_custom_messages: Mutex<mpsc::Receiver<CustomMessage>>,
 }
@@ -267,12 +325,33 @@ impl NodeAPI for MockNodeAPI {
         Ok(())
     }
 
-    fn max_receivable_single_payment_msat(&self) -> Result<u64, ReceivePaymentError> {
+    async fn cancel_jit_invoice(&self, _payment_hash: String) -> NodeResult<()> {
+        Ok(())
+    }
+
+    fn max_receivable_single_payment_msat(&self, _mpp: bool) -> Result<u64, ReceivePaymentError> {
         Ok(self.node_state.max_receivable_msat)
     }
 
+    async fn estimate_liquidity_for(
+        &self,
+        _target_receivable_msat: u64,
+    ) -> NodeResult<LiquidityEstimate> {
+        Err(NodeError::Generic("Not implemented".to_string()))
+    }
+
     async fn create_invoice(&self, req: CreateInvoiceRequest) -> NodeResult<String> {
+        (self.on_create_invoice)(&req)?;
         let invoice = create_invoice(req.description, req.amount_msat, vec![], req.preimage);
+        ensure_sdk!(
+            self.invoice_hashes
+                .lock()
+                .await
+                .insert(invoice.payment_hash.clone()),
+            NodeError::InvoicePreimageAlreadyExists(
+                "An invoice was already created with this preimage".to_string()
+            )
+        );
         Ok(invoice.bolt11)
     }
 
@@ -280,6 +359,10 @@ impl NodeAPI for MockNodeAPI {
         self.node_state.clone()
     }
 
+    async fn payment_status(&self, _payment_hash: String) -> NodeResult<Option<PaymentStatus>> {
+        Ok(None)
+    }
+
     async fn list_payments(&self) -> NodeResult<Vec<Payment>> {
         Ok(self
             .cloud_payments
@@ -291,15 +374,44 @@ impl NodeAPI for MockNodeAPI {
             .collect())
     }
 
+    async fn prune_payments(
+        &self,
+        _older_than_ts: u64,
+        _statuses: Vec<PaymentStatus>,
+    ) -> NodeResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
     async fn send_pay(&self, _bolt11: String, _max_hops: u32) -> NodeResult<PaymentResponse> {
         Err(NodeError::Generic("Not implemented".to_string()))
     }
 
-    async fn send_payment(&self, bolt11: String, _amount_msat: Option<u64>) -> NodeResult<Payment> {
+    async fn send_payment(
+        &self,
+        bolt11: String,
+        _amount_msat: Option<u64>,
+        _max_fee_msat: Option<u64>,
+        cancel: CancellationToken,
+    ) -> NodeResult<Payment> {
+        if self.payments_in_flight {
+            cancel.cancelled().await;
+            return Err(NodeError::Cancelled(
+                "Stopped waiting for payment".to_string(),
+            ));
+        }
         let payment = self.add_dummy_payment_for(bolt11, None, None).await?;
         Ok(payment)
     }
 
+    async fn probe_payment(
+        &self,
+        _bolt11: String,
+        _amount_msat: Option<u64>,
+        _max_fee_msat: u64,
+    ) -> NodeResult<bool> {
+        Ok(true)
+    }
+
     async fn send_spontaneous_payment(
         &self,
         _node_id: String,
@@ -331,10 +443,69 @@ impl NodeAPI for MockNodeAPI {
 
     async fn start(&self, _shutdown: mpsc::Receiver<()>) {}
 
+    async fn wait_until_synced(&self, _timeout: Duration) -> NodeResult<()> {
+        Ok(())
+    }
+
     async fn connect_peer(&self, _node_id: String, _addr: String) -> NodeResult<()> {
         Ok(())
     }
 
+    async fn open_channel(
+        &self,
+        _node_id: String,
+        _addr: String,
+        _amount_sat: u64,
+        _push_msat: u64,
+    ) -> NodeResult<()> {
+        Ok(())
+    }
+
+    async fn ensure_lsp_connected(&self) -> NodeResult<String> {
+        Ok(MockBreezServer {}.lsp_pub_key())
+    }
+
+    async fn app_kv_get(&self, _key: String) -> NodeResult<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    async fn app_kv_set(&self, _key: String, _value: Vec<u8>) -> NodeResult<()> {
+        Ok(())
+    }
+
+    async fn app_kv_delete(&self, _key: String) -> NodeResult<()> {
+        Ok(())
+    }
+
+    async fn disconnect_peer(&self, _node_id: String) -> NodeResult<()> {
+        Ok(())
+    }
+
+    async fn list_peers(&self) -> NodeResult<Vec<PeerInfo>> {
+        Ok(Vec::new())
+    }
+
+    async fn list_channels(&self) -> NodeResult<Vec<ChannelInfo>> {
+        Ok(self.channels.clone())
+    }
+
+    async fn splice_in(&self, _channel_id: String, _amount_sat: u64) -> NodeResult<()> {
+        Err(NodeError::Generic("Not implemented".to_string()))
+    }
+
+    async fn splice_out(
+        &self,
+        _channel_id: String,
+        _amount_sat: u64,
+        _to_address: String,
+    ) -> NodeResult<()> {
+        Err(NodeError::Generic("Not implemented".to_string()))
+    }
+
+    async fn new_onchain_address(&self) -> NodeResult<String> {
+        Err(NodeError::Generic("Not implemented".to_string()))
+    }
+
     async fn sign_message(&self, _message: &str) -> NodeResult<String> {
         Ok("".to_string())
     }
@@ -348,15 +519,99 @@ impl NodeAPI for MockNodeAPI {
         Ok(true)
     }
 
-    async fn close_all_channels(&self) -> NodeResult<()> {
+    async fn close_all_channels(
+        &self,
+        _close_to_address: Option<String>,
+        _force: bool,
+    ) -> NodeResult<()> {
         Ok(())
     }
+
+    async fn close_channel(
+        &self,
+        _channel_id: String,
+        _close_to_address: Option<String>,
+        _force: bool,
+    ) -> NodeResult<()> {
+        Ok(())
+    }
+
+    async fn fee_summary(&self, _from_ts: i64, _to_ts: i64) -> NodeResult<FeeSummary> {
+        Ok(FeeSummary::default())
+    }
+
+    async fn abort_channel_open(&self, _channel_id: String) -> NodeResult<()> {
+        Ok(())
+    }
+
+    async fn pending_close_status(
+        &self,
+        _channel_id: String,
+    ) -> NodeResult<Option<PendingCloseStatus>> {
+        Ok(None)
+    }
+
+    async fn list_pending_sweeps(&self) -> NodeResult<Vec<PendingSweep>> {
+        Ok(Vec::new())
+    }
+
+    async fn rebroadcast_pending(&self) -> NodeResult<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    async fn graph_status(&self) -> NodeResult<GraphStatus> {
+        Ok(GraphStatus::default())
+    }
+
+    async fn fee_estimates(&self) -> NodeResult<FeeEstimates> {
+        Ok(FeeEstimates::default())
+    }
+
+    async fn connectivity_check(&self) -> NodeResult<ConnectivityCheckResult> {
+        let reachable = DependencyStatus {
+            reachable: true,
+            latency_ms: 0,
+            error: None,
+        };
+        Ok(ConnectivityCheckResult {
+            vss: reachable.clone(),
+            chain_source: reachable.clone(),
+            rgs: reachable.clone(),
+            lsp: reachable,
+        })
+    }
+
+    async fn resync_from_remote(&self) -> NodeResult<ResyncResult> {
+        Ok(ResyncResult {
+            discarded_keys: 0,
+            downloaded_keys: 0,
+        })
+    }
+
     async fn stream_incoming_payments(
         &self,
     ) -> NodeResult<Pin<Box<dyn Stream<Item = IncomingPayment> + Send>>> {
         Err(NodeError::Generic("Not implemented".to_string()))
     }
 
+    async fn stream_remote_lock_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = RemoteLockEvent> + Send>>> {
+        Ok(Box::pin(tokio_stream::empty()))
+    }
+
+    async fn stream_splice_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = SpliceEvent> + Send>>> {
+        Ok(Box::pin(tokio_stream::empty()))
+    }
+
+    async fn stream_reorg_events(
+        &self,
+    ) -> NodeResult<Pin<Box<dyn Stream<Item = ReorgEvent> + Send>>> {
+        Ok(Box::pin(tokio_stream::empty()))
+    }
+
     async fn generate_diagnostic_data(&self) -> NodeResult<Value> {
         Ok(json!({}))
     }
@@ -374,6 +629,15 @@ impl NodeAPI for MockNodeAPI {
         Ok(Xpriv::new_master(Network::Bitcoin, &[])?)
     }
 
+    async fn derive_bip32_key_for_network(
+        &self,
+        _path: Vec<ChildNumber>,
+        network: sdk_common::prelude::Network,
+    ) -> NodeResult<Xpriv> {
+        let network: Network = network.into();
+        Ok(Xpriv::new_master(network, &[])?)
+    }
+
     async fn send_custom_message(&self, message: CustomMessage) -> NodeResult<()> {
         (self.on_send_custom_message)(message)
     }
@@ -404,6 +668,10 @@ impl MockNodeAPI {
         Self {
             cloud_payments: Mutex::new(Vec::new()),
             node_state,
+            channels: Vec::new(),
+            invoice_hashes: Mutex::new(HashSet::new()),
+            payments_in_flight: false,
+            on_create_invoice: Box::new(|_| Ok(())),
             on_send_custom_message: Box::new(|_| Ok(())),
             on_stream_custom_messages: {
                 let (_, rx) = mpsc::channel(1);
@@ -472,6 +740,8 @@ impl MockNodeAPI {
                     lnurl_withdraw_endpoint: None,
                     swap_info: None,
                     reverse_swap_info: None,
+                    custom_tlvs: None,
+                    open_channel_bolt11: None,
                 },
             },
             metadata: None,
@@ -503,6 +773,21 @@ impl MockNodeAPI {
         }
     }
 
+    pub fn set_channels(&mut self, channels: Vec<ChannelInfo>) {
+        self.channels = channels;
+    }
+
+    pub fn set_payments_in_flight(&mut self, payments_in_flight: bool) {
+        self.payments_in_flight = payments_in_flight;
+    }
+
+    pub fn set_on_create_invoice(
+        &mut self,
+        f: Box<dyn Fn(&CreateInvoiceRequest) -> NodeResult<()> + Sync + Send>,
+    ) {
+        self.on_create_invoice = f;
+    }
+
     pub fn set_on_send_custom_message(
         &mut self,
         f: Box<dyn Fn(CustomMessage) -> NodeResult<()> + Sync + Send>,
@@ -546,6 +831,9 @@ impl LspAPI for MockBreezServer {
             opening_fee_params_list: OpeningFeeParamsMenu::try_from(vec![get_test_ofp_48h(
                 10, 12,
             )])?,
+            channel_over_provisioning_ppm: 0,
+            min_channel_lifetime: 144,
+            max_client_to_self_delay: 2016,
         }])
     }
 
@@ -717,6 +1005,27 @@ pub fn create_invoice(
     parse_invoice(&sign_invoice(raw_invoice)).unwrap()
 }
 
+/// Creates an invoice for the given network, created at `timestamp` and valid for an hour.
+pub(crate) fn create_invoice_for(
+    currency: Currency,
+    timestamp: SystemTime,
+    amount_msat: u64,
+) -> LNInvoice {
+    let payment_hash = ldk_hashes::sha256::Hash::from_slice(&rand_vec_u8(32))
+        .expect("hash has a fixed 32-byte size");
+    let raw_invoice = InvoiceBuilder::new(currency)
+        .description("test".to_string())
+        .payment_hash(payment_hash)
+        .timestamp(timestamp)
+        .amount_milli_satoshis(amount_msat)
+        .expiry_time(Duration::new(3600, 0))
+        .payment_secret(PaymentSecret(rand::thread_rng().gen::<[u8; 32]>()))
+        .min_final_cltv_expiry_delta(32)
+        .build_raw()
+        .unwrap();
+    parse_invoice(&sign_invoice(raw_invoice)).unwrap()
+}
+
 fn sign_invoice(invoice: RawBolt11Invoice) -> String {
     let secp = ldk_secp256k1::Secp256k1::new();
     let (secret_key, _) = secp.generate_keypair(&mut OsRng);
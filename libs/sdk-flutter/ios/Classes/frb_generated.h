@@ -159,6 +159,8 @@ typedef struct wire_cst_ln_payment_details {
   struct wire_cst_list_prim_u_8_strict *lnurl_withdraw_endpoint;
   struct wire_cst_swap_info *swap_info;
   struct wire_cst_reverse_swap_info *reverse_swap_info;
+  struct wire_cst_list_tlv_entry *custom_tlvs;
+  struct wire_cst_list_prim_u_8_strict *open_channel_bolt11;
 } wire_cst_ln_payment_details;
 
 typedef struct wire_cst_PaymentDetails_Ln {
@@ -170,6 +172,9 @@ typedef struct wire_cst_closed_channel_payment_details {
   struct wire_cst_list_prim_u_8_strict *funding_txid;
   struct wire_cst_list_prim_u_8_strict *short_channel_id;
   struct wire_cst_list_prim_u_8_strict *closing_txid;
+  struct wire_cst_list_prim_u_8_strict *counterparty_node_id;
+  uint64_t capacity_sat;
+  bool is_force_close;
 } wire_cst_closed_channel_payment_details;
 
 typedef struct wire_cst_PaymentDetails_ClosedChannel {
@@ -278,6 +283,30 @@ typedef struct wire_cst_BreezEvent_SwapUpdated {
   struct wire_cst_swap_info *details;
 } wire_cst_BreezEvent_SwapUpdated;
 
+typedef struct wire_cst_BreezEvent_SplicePending {
+  struct wire_cst_list_prim_u_8_strict *channel_id;
+  int64_t amount_sat;
+  struct wire_cst_list_prim_u_8_strict *funding_txid;
+} wire_cst_BreezEvent_SplicePending;
+
+typedef struct wire_cst_BreezEvent_SpliceCompleted {
+  struct wire_cst_list_prim_u_8_strict *channel_id;
+  int64_t amount_sat;
+} wire_cst_BreezEvent_SpliceCompleted;
+
+typedef struct wire_cst_BreezEvent_SpliceFailed {
+  struct wire_cst_list_prim_u_8_strict *channel_id;
+  int64_t amount_sat;
+  struct wire_cst_list_prim_u_8_strict *reason;
+} wire_cst_BreezEvent_SpliceFailed;
+
+typedef struct wire_cst_BreezEvent_ChainReorg {
+  uint32_t old_tip_height;
+  struct wire_cst_list_prim_u_8_strict *old_tip_hash;
+  uint32_t new_tip_height;
+  struct wire_cst_list_prim_u_8_strict *new_tip_hash;
+} wire_cst_BreezEvent_ChainReorg;
+
 typedef union BreezEventKind {
   struct wire_cst_BreezEvent_NewBlock NewBlock;
   struct wire_cst_BreezEvent_InvoicePaid InvoicePaid;
@@ -286,6 +315,10 @@ typedef union BreezEventKind {
   struct wire_cst_BreezEvent_BackupFailed BackupFailed;
   struct wire_cst_BreezEvent_ReverseSwapUpdated ReverseSwapUpdated;
   struct wire_cst_BreezEvent_SwapUpdated SwapUpdated;
+  struct wire_cst_BreezEvent_SplicePending SplicePending;
+  struct wire_cst_BreezEvent_SpliceCompleted SpliceCompleted;
+  struct wire_cst_BreezEvent_SpliceFailed SpliceFailed;
+  struct wire_cst_BreezEvent_ChainReorg ChainReorg;
 } BreezEventKind;
 
 typedef struct wire_cst_breez_event {
@@ -323,6 +356,20 @@ typedef struct wire_cst_config {
   struct wire_cst_list_prim_u_8_strict *api_key;
   double maxfee_percent;
   uint64_t exemptfee_msat;
+  uint32_t payment_retries;
+  uint32_t remote_lock_lease_secs;
+  uint32_t remote_lock_retry_secs;
+  uint32_t connect_timeout_secs;
+  bool anchor_channels_enabled;
+  bool allow_state_regression;
+  uint32_t min_sat_per_vbyte;
+  uint32_t max_sat_per_vbyte;
+  uint32_t sync_retries;
+  uint32_t max_jit_channels;
+  uint32_t sweep_confirmation_target;
+  uint32_t max_store_value_bytes;
+  struct wire_cst_list_prim_u_8_strict *vss_client_id;
+  bool restrict_file_permissions;
 } wire_cst_config;
 
 typedef struct wire_cst_connect_request {
@@ -465,6 +512,9 @@ typedef struct wire_cst_receive_payment_request {
   bool *use_description_hash;
   uint32_t *expiry;
   uint32_t *cltv;
+  bool *register_notifications;
+  bool *truncate_description;
+  bool *no_open_channel;
 } wire_cst_receive_payment_request;
 
 typedef struct wire_cst_redeem_onchain_funds_request {
@@ -500,6 +550,7 @@ typedef struct wire_cst_report_issue_request {
 typedef struct wire_cst_send_payment_request {
   struct wire_cst_list_prim_u_8_strict *bolt11;
   uint64_t *amount_msat;
+  uint64_t *max_fee_msat;
 } wire_cst_send_payment_request;
 
 typedef struct wire_cst_tlv_entry {
@@ -516,6 +567,7 @@ typedef struct wire_cst_send_spontaneous_payment_request {
   struct wire_cst_list_prim_u_8_strict *node_id;
   uint64_t amount_msat;
   struct wire_cst_list_tlv_entry *extra_tlvs;
+  struct wire_cst_list_prim_u_8_strict *comment;
 } wire_cst_send_spontaneous_payment_request;
 
 typedef struct wire_cst_sign_message_request {
@@ -612,6 +664,9 @@ typedef struct wire_cst_lsp_information {
   int64_t min_htlc_msat;
   struct wire_cst_list_prim_u_8_strict *lsp_pubkey;
   struct wire_cst_opening_fee_params_menu opening_fee_params_list;
+  uint32_t channel_over_provisioning_ppm;
+  uint32_t min_channel_lifetime;
+  uint32_t max_client_to_self_delay;
 } wire_cst_lsp_information;
 
 typedef struct wire_cst_symbol {
@@ -852,6 +907,7 @@ typedef struct wire_cst_node_state {
   struct wire_cst_list_String *connected_peers;
   uint64_t max_receivable_single_payment_amount_msat;
   uint64_t total_inbound_liquidity_msats;
+  struct wire_cst_list_prim_u_8_strict *sync_warning;
 } wire_cst_node_state;
 
 typedef struct wire_cst_onchain_payment_limits_response {
@@ -939,7 +995,8 @@ void frbgen_breez_sdk_wire__crate__binding__breez_events_stream(int64_t port_,
                                                                 struct wire_cst_list_prim_u_8_strict *s);
 
 void frbgen_breez_sdk_wire__crate__binding__breez_log_stream(int64_t port_,
-                                                             struct wire_cst_list_prim_u_8_strict *s);
+                                                             struct wire_cst_list_prim_u_8_strict *s,
+                                                             struct wire_cst_list_prim_u_8_strict *filter);
 
 void frbgen_breez_sdk_wire__crate__binding__buy_bitcoin(int64_t port_,
                                                         struct wire_cst_buy_bitcoin_request *req);
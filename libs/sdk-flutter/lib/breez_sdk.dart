@@ -513,6 +513,20 @@ extension SDKConfig on Config {
     String? apiKey,
     double? maxfeePercent,
     BigInt? exemptfeeMsat,
+    int? paymentRetries,
+    int? remoteLockLeaseSecs,
+    int? remoteLockRetrySecs,
+    int? connectTimeoutSecs,
+    bool? anchorChannelsEnabled,
+    bool? allowStateRegression,
+    int? minSatPerVbyte,
+    int? maxSatPerVbyte,
+    int? syncRetries,
+    int? maxJitChannels,
+    int? sweepConfirmationTarget,
+    int? maxStoreValueBytes,
+    String? vssClientId,
+    bool? restrictFilePermissions,
   }) {
     return Config(
       breezserver: breezserver ?? this.breezserver,
@@ -528,6 +542,20 @@ extension SDKConfig on Config {
       apiKey: apiKey ?? this.apiKey,
       maxfeePercent: maxfeePercent ?? this.maxfeePercent,
       exemptfeeMsat: exemptfeeMsat ?? this.exemptfeeMsat,
+      paymentRetries: paymentRetries ?? this.paymentRetries,
+      remoteLockLeaseSecs: remoteLockLeaseSecs ?? this.remoteLockLeaseSecs,
+      remoteLockRetrySecs: remoteLockRetrySecs ?? this.remoteLockRetrySecs,
+      connectTimeoutSecs: connectTimeoutSecs ?? this.connectTimeoutSecs,
+      anchorChannelsEnabled: anchorChannelsEnabled ?? this.anchorChannelsEnabled,
+      allowStateRegression: allowStateRegression ?? this.allowStateRegression,
+      minSatPerVbyte: minSatPerVbyte ?? this.minSatPerVbyte,
+      maxSatPerVbyte: maxSatPerVbyte ?? this.maxSatPerVbyte,
+      syncRetries: syncRetries ?? this.syncRetries,
+      maxJitChannels: maxJitChannels ?? this.maxJitChannels,
+      sweepConfirmationTarget: sweepConfirmationTarget ?? this.sweepConfirmationTarget,
+      maxStoreValueBytes: maxStoreValueBytes ?? this.maxStoreValueBytes,
+      vssClientId: vssClientId ?? this.vssClientId,
+      restrictFilePermissions: restrictFilePermissions ?? this.restrictFilePermissions,
     );
   }
 }
@@ -65,7 +65,10 @@ Future<ServiceHealthCheckResponse> serviceHealthCheck({required String apiKey})
 Stream<BreezEvent> breezEventsStream() => BreezSdkBindings.instance.api.crateBindingBreezEventsStream();
 
 /// If used, this must be called before `connect`. It can only be called once.
-Stream<LogEntry> breezLogStream() => BreezSdkBindings.instance.api.crateBindingBreezLogStream();
+///
+/// The records can be filtered with `env_logger` directives, see [LogStreamFilter].
+Stream<LogEntry> breezLogStream({String? filter}) =>
+    BreezSdkBindings.instance.api.crateBindingBreezLogStream(filter: filter);
 
 /// See [BreezServices::list_lsps]
 Future<List<LspInformation>> listLsps() => BreezSdkBindings.instance.api.crateBindingListLsps();
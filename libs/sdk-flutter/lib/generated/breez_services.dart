@@ -60,6 +60,59 @@ sealed class BreezEvent with _$BreezEvent {
   /// Indicates that a swap has been updated which may also
   /// include a status change
   const factory BreezEvent.swapUpdated({required SwapInfo details}) = BreezEvent_SwapUpdated;
+
+  /// Indicates that this instance holds the exclusive lock on the node storage
+  const factory BreezEvent.remoteLockAcquired() = BreezEvent_RemoteLockAcquired;
+
+  /// Indicates that the exclusive lock on the node storage could not be refreshed
+  const factory BreezEvent.remoteLockRefreshFailed() = BreezEvent_RemoteLockRefreshFailed;
+
+  /// Indicates that the exclusive lock on the node storage was lost, which means
+  /// that the node may be running on another device
+  const factory BreezEvent.remoteLockLost() = BreezEvent_RemoteLockLost;
+
+  /// Indicates that a splice of a channel was negotiated and its transaction broadcast.
+  /// The amount is positive for splice-ins and negative for splice-outs
+  const factory BreezEvent.splicePending({
+    required String channelId,
+    required PlatformInt64 amountSat,
+    required String fundingTxid,
+  }) = BreezEvent_SplicePending;
+
+  /// Indicates that a splice of a channel was confirmed and the channel is usable again
+  const factory BreezEvent.spliceCompleted({
+    required String channelId,
+    required PlatformInt64 amountSat,
+  }) = BreezEvent_SpliceCompleted;
+
+  /// Indicates that a splice of a channel failed and the channel was left unchanged
+  const factory BreezEvent.spliceFailed({
+    required String channelId,
+    required PlatformInt64 amountSat,
+    required String reason,
+  }) = BreezEvent_SpliceFailed;
+
+  /// Indicates that the download of the node state from the remote storage started, as when
+  /// the node is restored on a new device
+  const factory BreezEvent.restoreDownloadStarted() = BreezEvent_RestoreDownloadStarted;
+
+  /// Indicates that the node state was downloaded from the remote storage
+  const factory BreezEvent.restoreDownloadCompleted() = BreezEvent_RestoreDownloadCompleted;
+
+  /// Indicates that the first sync of the restored node with the chain started
+  const factory BreezEvent.restoreChainSyncStarted() = BreezEvent_RestoreChainSyncStarted;
+
+  /// Indicates that the first sync of the restored node with the chain completed
+  const factory BreezEvent.restoreChainSyncCompleted() = BreezEvent_RestoreChainSyncCompleted;
+
+  /// Indicates that the previous tip of the chain was replaced by a block of another branch.
+  /// The transactions confirmed in the replaced blocks may be unconfirmed again
+  const factory BreezEvent.chainReorg({
+    required int oldTipHeight,
+    required String oldTipHash,
+    required int newTipHeight,
+    required String newTipHash,
+  }) = BreezEvent_ChainReorg;
 }
 
 /// Request to check a message was signed by a specific node id.
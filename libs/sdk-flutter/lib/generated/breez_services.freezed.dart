@@ -598,6 +598,502 @@ as SwapInfo,
 }
 
 
+}
+
+/// @nodoc
+
+
+class BreezEvent_RemoteLockAcquired extends BreezEvent {
+  const BreezEvent_RemoteLockAcquired(): super._();
+  
+
+
+
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_RemoteLockAcquired);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'BreezEvent.remoteLockAcquired()';
+}
+
+
+}
+
+
+
+
+/// @nodoc
+
+
+class BreezEvent_RemoteLockRefreshFailed extends BreezEvent {
+  const BreezEvent_RemoteLockRefreshFailed(): super._();
+  
+
+
+
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_RemoteLockRefreshFailed);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'BreezEvent.remoteLockRefreshFailed()';
+}
+
+
+}
+
+
+
+
+/// @nodoc
+
+
+class BreezEvent_RemoteLockLost extends BreezEvent {
+  const BreezEvent_RemoteLockLost(): super._();
+  
+
+
+
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_RemoteLockLost);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'BreezEvent.remoteLockLost()';
+}
+
+
+}
+
+/// @nodoc
+
+
+class BreezEvent_SplicePending extends BreezEvent {
+  const BreezEvent_SplicePending({required this.channelId, required this.amountSat, required this.fundingTxid}): super._();
+  
+
+ final  String channelId;
+ final  PlatformInt64 amountSat;
+ final  String fundingTxid;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$BreezEvent_SplicePendingCopyWith<BreezEvent_SplicePending> get copyWith => _$BreezEvent_SplicePendingCopyWithImpl<BreezEvent_SplicePending>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_SplicePending&&(identical(other.channelId, channelId) || other.channelId == channelId)&&(identical(other.amountSat, amountSat) || other.amountSat == amountSat)&&(identical(other.fundingTxid, fundingTxid) || other.fundingTxid == fundingTxid));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,channelId,amountSat,fundingTxid);
+
+@override
+String toString() {
+  return 'BreezEvent.splicePending(channelId: $channelId, amountSat: $amountSat, fundingTxid: $fundingTxid)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $BreezEvent_SplicePendingCopyWith<$Res> implements $BreezEventCopyWith<$Res> {
+  factory $BreezEvent_SplicePendingCopyWith(BreezEvent_SplicePending value, $Res Function(BreezEvent_SplicePending) _then) = _$BreezEvent_SplicePendingCopyWithImpl;
+@useResult
+$Res call({
+ String channelId, PlatformInt64 amountSat, String fundingTxid
+});
+
+
+
+
+}
+/// @nodoc
+class _$BreezEvent_SplicePendingCopyWithImpl<$Res>
+    implements $BreezEvent_SplicePendingCopyWith<$Res> {
+  _$BreezEvent_SplicePendingCopyWithImpl(this._self, this._then);
+
+  final BreezEvent_SplicePending _self;
+  final $Res Function(BreezEvent_SplicePending) _then;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? channelId = null,Object? amountSat = null,Object? fundingTxid = null,}) {
+  return _then(BreezEvent_SplicePending(
+channelId: null == channelId ? _self.channelId : channelId // ignore: cast_nullable_to_non_nullable
+as String,amountSat: null == amountSat ? _self.amountSat : amountSat // ignore: cast_nullable_to_non_nullable
+as PlatformInt64,fundingTxid: null == fundingTxid ? _self.fundingTxid : fundingTxid // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class BreezEvent_SpliceCompleted extends BreezEvent {
+  const BreezEvent_SpliceCompleted({required this.channelId, required this.amountSat}): super._();
+  
+
+ final  String channelId;
+ final  PlatformInt64 amountSat;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$BreezEvent_SpliceCompletedCopyWith<BreezEvent_SpliceCompleted> get copyWith => _$BreezEvent_SpliceCompletedCopyWithImpl<BreezEvent_SpliceCompleted>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_SpliceCompleted&&(identical(other.channelId, channelId) || other.channelId == channelId)&&(identical(other.amountSat, amountSat) || other.amountSat == amountSat));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,channelId,amountSat);
+
+@override
+String toString() {
+  return 'BreezEvent.spliceCompleted(channelId: $channelId, amountSat: $amountSat)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $BreezEvent_SpliceCompletedCopyWith<$Res> implements $BreezEventCopyWith<$Res> {
+  factory $BreezEvent_SpliceCompletedCopyWith(BreezEvent_SpliceCompleted value, $Res Function(BreezEvent_SpliceCompleted) _then) = _$BreezEvent_SpliceCompletedCopyWithImpl;
+@useResult
+$Res call({
+ String channelId, PlatformInt64 amountSat
+});
+
+
+
+
+}
+/// @nodoc
+class _$BreezEvent_SpliceCompletedCopyWithImpl<$Res>
+    implements $BreezEvent_SpliceCompletedCopyWith<$Res> {
+  _$BreezEvent_SpliceCompletedCopyWithImpl(this._self, this._then);
+
+  final BreezEvent_SpliceCompleted _self;
+  final $Res Function(BreezEvent_SpliceCompleted) _then;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? channelId = null,Object? amountSat = null,}) {
+  return _then(BreezEvent_SpliceCompleted(
+channelId: null == channelId ? _self.channelId : channelId // ignore: cast_nullable_to_non_nullable
+as String,amountSat: null == amountSat ? _self.amountSat : amountSat // ignore: cast_nullable_to_non_nullable
+as PlatformInt64,
+  ));
+}
+
+
+}
+
+/// @nodoc
+
+
+class BreezEvent_SpliceFailed extends BreezEvent {
+  const BreezEvent_SpliceFailed({required this.channelId, required this.amountSat, required this.reason}): super._();
+  
+
+ final  String channelId;
+ final  PlatformInt64 amountSat;
+ final  String reason;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$BreezEvent_SpliceFailedCopyWith<BreezEvent_SpliceFailed> get copyWith => _$BreezEvent_SpliceFailedCopyWithImpl<BreezEvent_SpliceFailed>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_SpliceFailed&&(identical(other.channelId, channelId) || other.channelId == channelId)&&(identical(other.amountSat, amountSat) || other.amountSat == amountSat)&&(identical(other.reason, reason) || other.reason == reason));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,channelId,amountSat,reason);
+
+@override
+String toString() {
+  return 'BreezEvent.spliceFailed(channelId: $channelId, amountSat: $amountSat, reason: $reason)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $BreezEvent_SpliceFailedCopyWith<$Res> implements $BreezEventCopyWith<$Res> {
+  factory $BreezEvent_SpliceFailedCopyWith(BreezEvent_SpliceFailed value, $Res Function(BreezEvent_SpliceFailed) _then) = _$BreezEvent_SpliceFailedCopyWithImpl;
+@useResult
+$Res call({
+ String channelId, PlatformInt64 amountSat, String reason
+});
+
+
+
+
+}
+/// @nodoc
+class _$BreezEvent_SpliceFailedCopyWithImpl<$Res>
+    implements $BreezEvent_SpliceFailedCopyWith<$Res> {
+  _$BreezEvent_SpliceFailedCopyWithImpl(this._self, this._then);
+
+  final BreezEvent_SpliceFailed _self;
+  final $Res Function(BreezEvent_SpliceFailed) _then;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? channelId = null,Object? amountSat = null,Object? reason = null,}) {
+  return _then(BreezEvent_SpliceFailed(
+channelId: null == channelId ? _self.channelId : channelId // ignore: cast_nullable_to_non_nullable
+as String,amountSat: null == amountSat ? _self.amountSat : amountSat // ignore: cast_nullable_to_non_nullable
+as PlatformInt64,reason: null == reason ? _self.reason : reason // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
+}
+
+
+/// @nodoc
+
+
+class BreezEvent_RestoreDownloadStarted extends BreezEvent {
+  const BreezEvent_RestoreDownloadStarted(): super._();
+
+
+
+
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_RestoreDownloadStarted);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'BreezEvent.restoreDownloadStarted()';
+}
+
+
+}
+
+
+
+/// @nodoc
+
+
+class BreezEvent_RestoreDownloadCompleted extends BreezEvent {
+  const BreezEvent_RestoreDownloadCompleted(): super._();
+
+
+
+
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_RestoreDownloadCompleted);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'BreezEvent.restoreDownloadCompleted()';
+}
+
+
+}
+
+
+
+/// @nodoc
+
+
+class BreezEvent_RestoreChainSyncStarted extends BreezEvent {
+  const BreezEvent_RestoreChainSyncStarted(): super._();
+
+
+
+
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_RestoreChainSyncStarted);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'BreezEvent.restoreChainSyncStarted()';
+}
+
+
+}
+
+
+
+/// @nodoc
+
+
+class BreezEvent_RestoreChainSyncCompleted extends BreezEvent {
+  const BreezEvent_RestoreChainSyncCompleted(): super._();
+
+
+
+
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_RestoreChainSyncCompleted);
+}
+
+
+@override
+int get hashCode => runtimeType.hashCode;
+
+@override
+String toString() {
+  return 'BreezEvent.restoreChainSyncCompleted()';
+}
+
+
+}
+
+/// @nodoc
+
+
+class BreezEvent_ChainReorg extends BreezEvent {
+  const BreezEvent_ChainReorg({required this.oldTipHeight, required this.oldTipHash, required this.newTipHeight, required this.newTipHash}): super._();
+  
+
+ final  int oldTipHeight;
+ final  String oldTipHash;
+ final  int newTipHeight;
+ final  String newTipHash;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@JsonKey(includeFromJson: false, includeToJson: false)
+@pragma('vm:prefer-inline')
+$BreezEvent_ChainReorgCopyWith<BreezEvent_ChainReorg> get copyWith => _$BreezEvent_ChainReorgCopyWithImpl<BreezEvent_ChainReorg>(this, _$identity);
+
+
+
+@override
+bool operator ==(Object other) {
+  return identical(this, other) || (other.runtimeType == runtimeType&&other is BreezEvent_ChainReorg&&(identical(other.oldTipHeight, oldTipHeight) || other.oldTipHeight == oldTipHeight)&&(identical(other.oldTipHash, oldTipHash) || other.oldTipHash == oldTipHash)&&(identical(other.newTipHeight, newTipHeight) || other.newTipHeight == newTipHeight)&&(identical(other.newTipHash, newTipHash) || other.newTipHash == newTipHash));
+}
+
+
+@override
+int get hashCode => Object.hash(runtimeType,oldTipHeight,oldTipHash,newTipHeight,newTipHash);
+
+@override
+String toString() {
+  return 'BreezEvent.chainReorg(oldTipHeight: $oldTipHeight, oldTipHash: $oldTipHash, newTipHeight: $newTipHeight, newTipHash: $newTipHash)';
+}
+
+
+}
+
+/// @nodoc
+abstract mixin class $BreezEvent_ChainReorgCopyWith<$Res> implements $BreezEventCopyWith<$Res> {
+  factory $BreezEvent_ChainReorgCopyWith(BreezEvent_ChainReorg value, $Res Function(BreezEvent_ChainReorg) _then) = _$BreezEvent_ChainReorgCopyWithImpl;
+@useResult
+$Res call({
+ int oldTipHeight, String oldTipHash, int newTipHeight, String newTipHash
+});
+
+
+
+
+}
+/// @nodoc
+class _$BreezEvent_ChainReorgCopyWithImpl<$Res>
+    implements $BreezEvent_ChainReorgCopyWith<$Res> {
+  _$BreezEvent_ChainReorgCopyWithImpl(this._self, this._then);
+
+  final BreezEvent_ChainReorg _self;
+  final $Res Function(BreezEvent_ChainReorg) _then;
+
+/// Create a copy of BreezEvent
+/// with the given fields replaced by the non-null parameter values.
+@pragma('vm:prefer-inline') $Res call({Object? oldTipHeight = null,Object? oldTipHash = null,Object? newTipHeight = null,Object? newTipHash = null,}) {
+  return _then(BreezEvent_ChainReorg(
+oldTipHeight: null == oldTipHeight ? _self.oldTipHeight : oldTipHeight // ignore: cast_nullable_to_non_nullable
+as int,oldTipHash: null == oldTipHash ? _self.oldTipHash : oldTipHash // ignore: cast_nullable_to_non_nullable
+as String,newTipHeight: null == newTipHeight ? _self.newTipHeight : newTipHeight // ignore: cast_nullable_to_non_nullable
+as int,newTipHash: null == newTipHash ? _self.newTipHash : newTipHash // ignore: cast_nullable_to_non_nullable
+as String,
+  ));
+}
+
+
 }
 
 // dart format on
@@ -82,7 +82,7 @@ abstract class BreezSdkBindingsApi extends BaseApi {
 
   Stream<BreezEvent> crateBindingBreezEventsStream();
 
-  Stream<LogEntry> crateBindingBreezLogStream();
+  Stream<LogEntry> crateBindingBreezLogStream({String? filter});
 
   Future<BuyBitcoinResponse> crateBindingBuyBitcoin({required BuyBitcoinRequest req});
 
@@ -292,18 +292,19 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       const TaskConstMeta(debugName: "breez_events_stream", argNames: ["s"]);
 
   @override
-  Stream<LogEntry> crateBindingBreezLogStream() {
+  Stream<LogEntry> crateBindingBreezLogStream({String? filter}) {
     final s = RustStreamSink<LogEntry>();
     unawaited(
       handler.executeNormal(
         NormalTask(
           callFfi: (port_) {
             var arg0 = cst_encode_StreamSink_log_entry_Dco(s);
-            return wire.wire__crate__binding__breez_log_stream(port_, arg0);
+            var arg1 = cst_encode_opt_String(filter);
+            return wire.wire__crate__binding__breez_log_stream(port_, arg0, arg1);
           },
           codec: DcoCodec(decodeSuccessData: dco_decode_unit, decodeErrorData: dco_decode_AnyhowException),
           constMeta: kCrateBindingBreezLogStreamConstMeta,
-          argValues: [s],
+          argValues: [s, filter],
           apiImpl: this,
         ),
       ),
@@ -312,7 +313,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   }
 
   TaskConstMeta get kCrateBindingBreezLogStreamConstMeta =>
-      const TaskConstMeta(debugName: "breez_log_stream", argNames: ["s"]);
+      const TaskConstMeta(debugName: "breez_log_stream", argNames: ["s", "filter"]);
 
   @override
   Future<BuyBitcoinResponse> crateBindingBuyBitcoin({required BuyBitcoinRequest req}) {
@@ -1918,6 +1919,44 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
         return BreezEvent_ReverseSwapUpdated(details: dco_decode_box_autoadd_reverse_swap_info(raw[1]));
       case 9:
         return BreezEvent_SwapUpdated(details: dco_decode_box_autoadd_swap_info(raw[1]));
+      case 10:
+        return BreezEvent_RemoteLockAcquired();
+      case 11:
+        return BreezEvent_RemoteLockRefreshFailed();
+      case 12:
+        return BreezEvent_RemoteLockLost();
+      case 13:
+        return BreezEvent_SplicePending(
+          channelId: dco_decode_String(raw[1]),
+          amountSat: dco_decode_i_64(raw[2]),
+          fundingTxid: dco_decode_String(raw[3]),
+        );
+      case 14:
+        return BreezEvent_SpliceCompleted(
+          channelId: dco_decode_String(raw[1]),
+          amountSat: dco_decode_i_64(raw[2]),
+        );
+      case 15:
+        return BreezEvent_SpliceFailed(
+          channelId: dco_decode_String(raw[1]),
+          amountSat: dco_decode_i_64(raw[2]),
+          reason: dco_decode_String(raw[3]),
+        );
+      case 16:
+        return BreezEvent_RestoreDownloadStarted();
+      case 17:
+        return BreezEvent_RestoreDownloadCompleted();
+      case 18:
+        return BreezEvent_RestoreChainSyncStarted();
+      case 19:
+        return BreezEvent_RestoreChainSyncCompleted();
+      case 20:
+        return BreezEvent_ChainReorg(
+          oldTipHeight: dco_decode_u_32(raw[1]),
+          oldTipHash: dco_decode_String(raw[2]),
+          newTipHeight: dco_decode_u_32(raw[3]),
+          newTipHash: dco_decode_String(raw[4]),
+        );
       default:
         throw Exception("unreachable");
     }
@@ -1982,12 +2021,15 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   ClosedChannelPaymentDetails dco_decode_closed_channel_payment_details(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 4) throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
+    if (arr.length != 7) throw Exception('unexpected arr length: expect 7 but see ${arr.length}');
     return ClosedChannelPaymentDetails(
       state: dco_decode_channel_state(arr[0]),
       fundingTxid: dco_decode_String(arr[1]),
       shortChannelId: dco_decode_opt_String(arr[2]),
       closingTxid: dco_decode_opt_String(arr[3]),
+      counterpartyNodeId: dco_decode_String(arr[4]),
+      capacitySat: dco_decode_u_64(arr[5]),
+      isForceClose: dco_decode_bool(arr[6]),
     );
   }
 
@@ -1995,7 +2037,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   Config dco_decode_config(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 13) throw Exception('unexpected arr length: expect 13 but see ${arr.length}');
+    if (arr.length != 27) throw Exception('unexpected arr length: expect 27 but see ${arr.length}');
     return Config(
       breezserver: dco_decode_String(arr[0]),
       chainnotifierUrl: dco_decode_String(arr[1]),
@@ -2010,6 +2052,20 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       apiKey: dco_decode_opt_String(arr[10]),
       maxfeePercent: dco_decode_f_64(arr[11]),
       exemptfeeMsat: dco_decode_u_64(arr[12]),
+      paymentRetries: dco_decode_u_32(arr[13]),
+      remoteLockLeaseSecs: dco_decode_u_32(arr[14]),
+      remoteLockRetrySecs: dco_decode_u_32(arr[15]),
+      connectTimeoutSecs: dco_decode_u_32(arr[16]),
+      anchorChannelsEnabled: dco_decode_bool(arr[17]),
+      allowStateRegression: dco_decode_bool(arr[18]),
+      minSatPerVbyte: dco_decode_u_32(arr[19]),
+      maxSatPerVbyte: dco_decode_u_32(arr[20]),
+      syncRetries: dco_decode_u_32(arr[21]),
+      maxJitChannels: dco_decode_u_32(arr[22]),
+      sweepConfirmationTarget: dco_decode_u_32(arr[23]),
+      maxStoreValueBytes: dco_decode_u_32(arr[24]),
+      vssClientId: dco_decode_opt_String(arr[25]),
+      restrictFilePermissions: dco_decode_bool(arr[26]),
     );
   }
 
@@ -2326,7 +2382,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   LnPaymentDetails dco_decode_ln_payment_details(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 13) throw Exception('unexpected arr length: expect 13 but see ${arr.length}');
+    if (arr.length != 15) throw Exception('unexpected arr length: expect 15 but see ${arr.length}');
     return LnPaymentDetails(
       paymentHash: dco_decode_String(arr[0]),
       destinationPubkey: dco_decode_String(arr[1]),
@@ -2341,6 +2397,8 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       lnurlWithdrawEndpoint: dco_decode_opt_String(arr[10]),
       swapInfo: dco_decode_opt_box_autoadd_swap_info(arr[11]),
       reverseSwapInfo: dco_decode_opt_box_autoadd_reverse_swap_info(arr[12]),
+      customTlvs: dco_decode_opt_list_tlv_entry(arr[13]),
+      openChannelBolt11: dco_decode_opt_String(arr[14]),
     );
   }
 
@@ -2525,7 +2583,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   LspInformation dco_decode_lsp_information(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 11) throw Exception('unexpected arr length: expect 11 but see ${arr.length}');
+    if (arr.length != 14) throw Exception('unexpected arr length: expect 14 but see ${arr.length}');
     return LspInformation(
       id: dco_decode_String(arr[0]),
       name: dco_decode_String(arr[1]),
@@ -2538,6 +2596,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       minHtlcMsat: dco_decode_i_64(arr[8]),
       lspPubkey: dco_decode_list_prim_u_8_strict(arr[9]),
       openingFeeParamsList: dco_decode_opening_fee_params_menu(arr[10]),
+      channelOverProvisioningPpm: dco_decode_u_32(arr[11]),
+      minChannelLifetime: dco_decode_u_32(arr[12]),
+      maxClientToSelfDelay: dco_decode_u_32(arr[13]),
     );
   }
 
@@ -2567,7 +2628,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   NodeState dco_decode_node_state(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 13) throw Exception('unexpected arr length: expect 13 but see ${arr.length}');
+    if (arr.length != 14) throw Exception('unexpected arr length: expect 14 but see ${arr.length}');
     return NodeState(
       id: dco_decode_String(arr[0]),
       blockHeight: dco_decode_u_32(arr[1]),
@@ -2582,6 +2643,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       connectedPeers: dco_decode_list_String(arr[10]),
       maxReceivableSinglePaymentAmountMsat: dco_decode_u_64(arr[11]),
       totalInboundLiquidityMsats: dco_decode_u_64(arr[12]),
+      syncWarning: dco_decode_opt_String(arr[13]),
     );
   }
 
@@ -2939,7 +3001,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   ReceivePaymentRequest dco_decode_receive_payment_request(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 7) throw Exception('unexpected arr length: expect 7 but see ${arr.length}');
+    if (arr.length != 10) throw Exception('unexpected arr length: expect 10 but see ${arr.length}');
     return ReceivePaymentRequest(
       amountMsat: dco_decode_u_64(arr[0]),
       description: dco_decode_String(arr[1]),
@@ -2948,6 +3010,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       useDescriptionHash: dco_decode_opt_box_autoadd_bool(arr[4]),
       expiry: dco_decode_opt_box_autoadd_u_32(arr[5]),
       cltv: dco_decode_opt_box_autoadd_u_32(arr[6]),
+      registerNotifications: dco_decode_opt_box_autoadd_bool(arr[7]),
+      truncateDescription: dco_decode_opt_box_autoadd_bool(arr[8]),
+      noOpenChannel: dco_decode_opt_box_autoadd_bool(arr[9]),
     );
   }
 
@@ -3117,10 +3182,11 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   SendPaymentRequest dco_decode_send_payment_request(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 2) throw Exception('unexpected arr length: expect 2 but see ${arr.length}');
+    if (arr.length != 3) throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
     return SendPaymentRequest(
       bolt11: dco_decode_String(arr[0]),
       amountMsat: dco_decode_opt_box_autoadd_u_64(arr[1]),
+      maxFeeMsat: dco_decode_opt_box_autoadd_u_64(arr[2]),
     );
   }
 
@@ -3136,11 +3202,12 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
   SendSpontaneousPaymentRequest dco_decode_send_spontaneous_payment_request(dynamic raw) {
     // Codec=Dco (DartCObject based), see doc to use other codecs
     final arr = raw as List<dynamic>;
-    if (arr.length != 3) throw Exception('unexpected arr length: expect 3 but see ${arr.length}');
+    if (arr.length != 4) throw Exception('unexpected arr length: expect 4 but see ${arr.length}');
     return SendSpontaneousPaymentRequest(
       nodeId: dco_decode_String(arr[0]),
       amountMsat: dco_decode_u_64(arr[1]),
       extraTlvs: dco_decode_opt_list_tlv_entry(arr[2]),
+      comment: dco_decode_opt_String(arr[3]),
     );
   }
 
@@ -3829,6 +3896,53 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       case 9:
         var var_details = sse_decode_box_autoadd_swap_info(deserializer);
         return BreezEvent_SwapUpdated(details: var_details);
+      case 10:
+        return BreezEvent_RemoteLockAcquired();
+      case 11:
+        return BreezEvent_RemoteLockRefreshFailed();
+      case 12:
+        return BreezEvent_RemoteLockLost();
+      case 13:
+        var var_channelId = sse_decode_String(deserializer);
+        var var_amountSat = sse_decode_i_64(deserializer);
+        var var_fundingTxid = sse_decode_String(deserializer);
+        return BreezEvent_SplicePending(
+          channelId: var_channelId,
+          amountSat: var_amountSat,
+          fundingTxid: var_fundingTxid,
+        );
+      case 14:
+        var var_channelId = sse_decode_String(deserializer);
+        var var_amountSat = sse_decode_i_64(deserializer);
+        return BreezEvent_SpliceCompleted(channelId: var_channelId, amountSat: var_amountSat);
+      case 15:
+        var var_channelId = sse_decode_String(deserializer);
+        var var_amountSat = sse_decode_i_64(deserializer);
+        var var_reason = sse_decode_String(deserializer);
+        return BreezEvent_SpliceFailed(
+          channelId: var_channelId,
+          amountSat: var_amountSat,
+          reason: var_reason,
+        );
+      case 16:
+        return BreezEvent_RestoreDownloadStarted();
+      case 17:
+        return BreezEvent_RestoreDownloadCompleted();
+      case 18:
+        return BreezEvent_RestoreChainSyncStarted();
+      case 19:
+        return BreezEvent_RestoreChainSyncCompleted();
+      case 20:
+        var var_oldTipHeight = sse_decode_u_32(deserializer);
+        var var_oldTipHash = sse_decode_String(deserializer);
+        var var_newTipHeight = sse_decode_u_32(deserializer);
+        var var_newTipHash = sse_decode_String(deserializer);
+        return BreezEvent_ChainReorg(
+          oldTipHeight: var_oldTipHeight,
+          oldTipHash: var_oldTipHash,
+          newTipHeight: var_newTipHeight,
+          newTipHash: var_newTipHash,
+        );
       default:
         throw UnimplementedError('');
     }
@@ -3892,11 +4006,17 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     var var_fundingTxid = sse_decode_String(deserializer);
     var var_shortChannelId = sse_decode_opt_String(deserializer);
     var var_closingTxid = sse_decode_opt_String(deserializer);
+    var var_counterpartyNodeId = sse_decode_String(deserializer);
+    var var_capacitySat = sse_decode_u_64(deserializer);
+    var var_isForceClose = sse_decode_bool(deserializer);
     return ClosedChannelPaymentDetails(
       state: var_state,
       fundingTxid: var_fundingTxid,
       shortChannelId: var_shortChannelId,
       closingTxid: var_closingTxid,
+      counterpartyNodeId: var_counterpartyNodeId,
+      capacitySat: var_capacitySat,
+      isForceClose: var_isForceClose,
     );
   }
 
@@ -3916,6 +4036,20 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     var var_apiKey = sse_decode_opt_String(deserializer);
     var var_maxfeePercent = sse_decode_f_64(deserializer);
     var var_exemptfeeMsat = sse_decode_u_64(deserializer);
+    var var_paymentRetries = sse_decode_u_32(deserializer);
+    var var_remoteLockLeaseSecs = sse_decode_u_32(deserializer);
+    var var_remoteLockRetrySecs = sse_decode_u_32(deserializer);
+    var var_connectTimeoutSecs = sse_decode_u_32(deserializer);
+    var var_anchorChannelsEnabled = sse_decode_bool(deserializer);
+    var var_allowStateRegression = sse_decode_bool(deserializer);
+    var var_minSatPerVbyte = sse_decode_u_32(deserializer);
+    var var_maxSatPerVbyte = sse_decode_u_32(deserializer);
+    var var_syncRetries = sse_decode_u_32(deserializer);
+    var var_maxJitChannels = sse_decode_u_32(deserializer);
+    var var_sweepConfirmationTarget = sse_decode_u_32(deserializer);
+    var var_maxStoreValueBytes = sse_decode_u_32(deserializer);
+    var var_vssClientId = sse_decode_opt_String(deserializer);
+    var var_restrictFilePermissions = sse_decode_bool(deserializer);
     return Config(
       breezserver: var_breezserver,
       chainnotifierUrl: var_chainnotifierUrl,
@@ -3930,6 +4064,20 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       apiKey: var_apiKey,
       maxfeePercent: var_maxfeePercent,
       exemptfeeMsat: var_exemptfeeMsat,
+      paymentRetries: var_paymentRetries,
+      remoteLockLeaseSecs: var_remoteLockLeaseSecs,
+      remoteLockRetrySecs: var_remoteLockRetrySecs,
+      connectTimeoutSecs: var_connectTimeoutSecs,
+      anchorChannelsEnabled: var_anchorChannelsEnabled,
+      allowStateRegression: var_allowStateRegression,
+      minSatPerVbyte: var_minSatPerVbyte,
+      maxSatPerVbyte: var_maxSatPerVbyte,
+      syncRetries: var_syncRetries,
+      maxJitChannels: var_maxJitChannels,
+      sweepConfirmationTarget: var_sweepConfirmationTarget,
+      maxStoreValueBytes: var_maxStoreValueBytes,
+      vssClientId: var_vssClientId,
+      restrictFilePermissions: var_restrictFilePermissions,
     );
   }
 
@@ -4397,6 +4545,8 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     var var_lnurlWithdrawEndpoint = sse_decode_opt_String(deserializer);
     var var_swapInfo = sse_decode_opt_box_autoadd_swap_info(deserializer);
     var var_reverseSwapInfo = sse_decode_opt_box_autoadd_reverse_swap_info(deserializer);
+    var var_customTlvs = sse_decode_opt_list_tlv_entry(deserializer);
+    var var_openChannelBolt11 = sse_decode_opt_String(deserializer);
     return LnPaymentDetails(
       paymentHash: var_paymentHash,
       destinationPubkey: var_destinationPubkey,
@@ -4411,6 +4561,8 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       lnurlWithdrawEndpoint: var_lnurlWithdrawEndpoint,
       swapInfo: var_swapInfo,
       reverseSwapInfo: var_reverseSwapInfo,
+      customTlvs: var_customTlvs,
+      openChannelBolt11: var_openChannelBolt11,
     );
   }
 
@@ -4617,6 +4769,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     var var_minHtlcMsat = sse_decode_i_64(deserializer);
     var var_lspPubkey = sse_decode_list_prim_u_8_strict(deserializer);
     var var_openingFeeParamsList = sse_decode_opening_fee_params_menu(deserializer);
+    var var_channelOverProvisioningPpm = sse_decode_u_32(deserializer);
+    var var_minChannelLifetime = sse_decode_u_32(deserializer);
+    var var_maxClientToSelfDelay = sse_decode_u_32(deserializer);
     return LspInformation(
       id: var_id,
       name: var_name,
@@ -4629,6 +4784,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       minHtlcMsat: var_minHtlcMsat,
       lspPubkey: var_lspPubkey,
       openingFeeParamsList: var_openingFeeParamsList,
+      channelOverProvisioningPpm: var_channelOverProvisioningPpm,
+      minChannelLifetime: var_minChannelLifetime,
+      maxClientToSelfDelay: var_maxClientToSelfDelay,
     );
   }
 
@@ -4670,6 +4828,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     var var_connectedPeers = sse_decode_list_String(deserializer);
     var var_maxReceivableSinglePaymentAmountMsat = sse_decode_u_64(deserializer);
     var var_totalInboundLiquidityMsats = sse_decode_u_64(deserializer);
+    var var_syncWarning = sse_decode_opt_String(deserializer);
     return NodeState(
       id: var_id,
       blockHeight: var_blockHeight,
@@ -4684,6 +4843,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       connectedPeers: var_connectedPeers,
       maxReceivableSinglePaymentAmountMsat: var_maxReceivableSinglePaymentAmountMsat,
       totalInboundLiquidityMsats: var_totalInboundLiquidityMsats,
+      syncWarning: var_syncWarning,
     );
   }
 
@@ -5153,6 +5313,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     var var_useDescriptionHash = sse_decode_opt_box_autoadd_bool(deserializer);
     var var_expiry = sse_decode_opt_box_autoadd_u_32(deserializer);
     var var_cltv = sse_decode_opt_box_autoadd_u_32(deserializer);
+    var var_registerNotifications = sse_decode_opt_box_autoadd_bool(deserializer);
+    var var_truncateDescription = sse_decode_opt_box_autoadd_bool(deserializer);
+    var var_noOpenChannel = sse_decode_opt_box_autoadd_bool(deserializer);
     return ReceivePaymentRequest(
       amountMsat: var_amountMsat,
       description: var_description,
@@ -5161,6 +5324,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       useDescriptionHash: var_useDescriptionHash,
       expiry: var_expiry,
       cltv: var_cltv,
+      registerNotifications: var_registerNotifications,
+      truncateDescription: var_truncateDescription,
+      noOpenChannel: var_noOpenChannel,
     );
   }
 
@@ -5341,7 +5507,12 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     // Codec=Sse (Serialization based), see doc to use other codecs
     var var_bolt11 = sse_decode_String(deserializer);
     var var_amountMsat = sse_decode_opt_box_autoadd_u_64(deserializer);
-    return SendPaymentRequest(bolt11: var_bolt11, amountMsat: var_amountMsat);
+    var var_maxFeeMsat = sse_decode_opt_box_autoadd_u_64(deserializer);
+    return SendPaymentRequest(
+      bolt11: var_bolt11,
+      amountMsat: var_amountMsat,
+      maxFeeMsat: var_maxFeeMsat,
+    );
   }
 
   @protected
@@ -5357,10 +5528,12 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     var var_nodeId = sse_decode_String(deserializer);
     var var_amountMsat = sse_decode_u_64(deserializer);
     var var_extraTlvs = sse_decode_opt_list_tlv_entry(deserializer);
+    var var_comment = sse_decode_opt_String(deserializer);
     return SendSpontaneousPaymentRequest(
       nodeId: var_nodeId,
       amountMsat: var_amountMsat,
       extraTlvs: var_extraTlvs,
+      comment: var_comment,
     );
   }
 
@@ -6193,6 +6366,53 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
       case BreezEvent_SwapUpdated(details: final details):
         sse_encode_i_32(9, serializer);
         sse_encode_box_autoadd_swap_info(details, serializer);
+      case BreezEvent_RemoteLockAcquired():
+        sse_encode_i_32(10, serializer);
+      case BreezEvent_RemoteLockRefreshFailed():
+        sse_encode_i_32(11, serializer);
+      case BreezEvent_RemoteLockLost():
+        sse_encode_i_32(12, serializer);
+      case BreezEvent_SplicePending(
+        channelId: final channelId,
+        amountSat: final amountSat,
+        fundingTxid: final fundingTxid,
+      ):
+        sse_encode_i_32(13, serializer);
+        sse_encode_String(channelId, serializer);
+        sse_encode_i_64(amountSat, serializer);
+        sse_encode_String(fundingTxid, serializer);
+      case BreezEvent_SpliceCompleted(channelId: final channelId, amountSat: final amountSat):
+        sse_encode_i_32(14, serializer);
+        sse_encode_String(channelId, serializer);
+        sse_encode_i_64(amountSat, serializer);
+      case BreezEvent_SpliceFailed(
+        channelId: final channelId,
+        amountSat: final amountSat,
+        reason: final reason,
+      ):
+        sse_encode_i_32(15, serializer);
+        sse_encode_String(channelId, serializer);
+        sse_encode_i_64(amountSat, serializer);
+        sse_encode_String(reason, serializer);
+      case BreezEvent_RestoreDownloadStarted():
+        sse_encode_i_32(16, serializer);
+      case BreezEvent_RestoreDownloadCompleted():
+        sse_encode_i_32(17, serializer);
+      case BreezEvent_RestoreChainSyncStarted():
+        sse_encode_i_32(18, serializer);
+      case BreezEvent_RestoreChainSyncCompleted():
+        sse_encode_i_32(19, serializer);
+      case BreezEvent_ChainReorg(
+        oldTipHeight: final oldTipHeight,
+        oldTipHash: final oldTipHash,
+        newTipHeight: final newTipHeight,
+        newTipHash: final newTipHash,
+      ):
+        sse_encode_i_32(20, serializer);
+        sse_encode_u_32(oldTipHeight, serializer);
+        sse_encode_String(oldTipHash, serializer);
+        sse_encode_u_32(newTipHeight, serializer);
+        sse_encode_String(newTipHash, serializer);
     }
   }
 
@@ -6244,6 +6464,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     sse_encode_String(self.fundingTxid, serializer);
     sse_encode_opt_String(self.shortChannelId, serializer);
     sse_encode_opt_String(self.closingTxid, serializer);
+    sse_encode_String(self.counterpartyNodeId, serializer);
+    sse_encode_u_64(self.capacitySat, serializer);
+    sse_encode_bool(self.isForceClose, serializer);
   }
 
   @protected
@@ -6262,6 +6485,20 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     sse_encode_opt_String(self.apiKey, serializer);
     sse_encode_f_64(self.maxfeePercent, serializer);
     sse_encode_u_64(self.exemptfeeMsat, serializer);
+    sse_encode_u_32(self.paymentRetries, serializer);
+    sse_encode_u_32(self.remoteLockLeaseSecs, serializer);
+    sse_encode_u_32(self.remoteLockRetrySecs, serializer);
+    sse_encode_u_32(self.connectTimeoutSecs, serializer);
+    sse_encode_bool(self.anchorChannelsEnabled, serializer);
+    sse_encode_bool(self.allowStateRegression, serializer);
+    sse_encode_u_32(self.minSatPerVbyte, serializer);
+    sse_encode_u_32(self.maxSatPerVbyte, serializer);
+    sse_encode_u_32(self.syncRetries, serializer);
+    sse_encode_u_32(self.maxJitChannels, serializer);
+    sse_encode_u_32(self.sweepConfirmationTarget, serializer);
+    sse_encode_u_32(self.maxStoreValueBytes, serializer);
+    sse_encode_opt_String(self.vssClientId, serializer);
+    sse_encode_bool(self.restrictFilePermissions, serializer);
   }
 
   @protected
@@ -6617,6 +6854,8 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     sse_encode_opt_String(self.lnurlWithdrawEndpoint, serializer);
     sse_encode_opt_box_autoadd_swap_info(self.swapInfo, serializer);
     sse_encode_opt_box_autoadd_reverse_swap_info(self.reverseSwapInfo, serializer);
+    sse_encode_opt_list_tlv_entry(self.customTlvs, serializer);
+    sse_encode_opt_String(self.openChannelBolt11, serializer);
   }
 
   @protected
@@ -6776,6 +7015,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     sse_encode_i_64(self.minHtlcMsat, serializer);
     sse_encode_list_prim_u_8_strict(self.lspPubkey, serializer);
     sse_encode_opening_fee_params_menu(self.openingFeeParamsList, serializer);
+    sse_encode_u_32(self.channelOverProvisioningPpm, serializer);
+    sse_encode_u_32(self.minChannelLifetime, serializer);
+    sse_encode_u_32(self.maxClientToSelfDelay, serializer);
   }
 
   @protected
@@ -6813,6 +7055,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     sse_encode_list_String(self.connectedPeers, serializer);
     sse_encode_u_64(self.maxReceivableSinglePaymentAmountMsat, serializer);
     sse_encode_u_64(self.totalInboundLiquidityMsats, serializer);
+    sse_encode_opt_String(self.syncWarning, serializer);
   }
 
   @protected
@@ -7212,6 +7455,9 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     sse_encode_opt_box_autoadd_bool(self.useDescriptionHash, serializer);
     sse_encode_opt_box_autoadd_u_32(self.expiry, serializer);
     sse_encode_opt_box_autoadd_u_32(self.cltv, serializer);
+    sse_encode_opt_box_autoadd_bool(self.registerNotifications, serializer);
+    sse_encode_opt_box_autoadd_bool(self.truncateDescription, serializer);
+    sse_encode_opt_box_autoadd_bool(self.noOpenChannel, serializer);
   }
 
   @protected
@@ -7336,6 +7582,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     // Codec=Sse (Serialization based), see doc to use other codecs
     sse_encode_String(self.bolt11, serializer);
     sse_encode_opt_box_autoadd_u_64(self.amountMsat, serializer);
+    sse_encode_opt_box_autoadd_u_64(self.maxFeeMsat, serializer);
   }
 
   @protected
@@ -7353,6 +7600,7 @@ class BreezSdkBindingsApiImpl extends BreezSdkBindingsApiImplPlatform implements
     sse_encode_String(self.nodeId, serializer);
     sse_encode_u_64(self.amountMsat, serializer);
     sse_encode_opt_list_tlv_entry(self.extraTlvs, serializer);
+    sse_encode_opt_String(self.comment, serializer);
   }
 
   @protected
@@ -2728,6 +2728,74 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
       wireObj.kind.SwapUpdated.details = pre_details;
       return;
     }
+    if (apiObj is BreezEvent_RemoteLockAcquired) {
+      wireObj.tag = 10;
+      return;
+    }
+    if (apiObj is BreezEvent_RemoteLockRefreshFailed) {
+      wireObj.tag = 11;
+      return;
+    }
+    if (apiObj is BreezEvent_RemoteLockLost) {
+      wireObj.tag = 12;
+      return;
+    }
+    if (apiObj is BreezEvent_SplicePending) {
+      var pre_channel_id = cst_encode_String(apiObj.channelId);
+      var pre_amount_sat = cst_encode_i_64(apiObj.amountSat);
+      var pre_funding_txid = cst_encode_String(apiObj.fundingTxid);
+      wireObj.tag = 13;
+      wireObj.kind.SplicePending.channel_id = pre_channel_id;
+      wireObj.kind.SplicePending.amount_sat = pre_amount_sat;
+      wireObj.kind.SplicePending.funding_txid = pre_funding_txid;
+      return;
+    }
+    if (apiObj is BreezEvent_SpliceCompleted) {
+      var pre_channel_id = cst_encode_String(apiObj.channelId);
+      var pre_amount_sat = cst_encode_i_64(apiObj.amountSat);
+      wireObj.tag = 14;
+      wireObj.kind.SpliceCompleted.channel_id = pre_channel_id;
+      wireObj.kind.SpliceCompleted.amount_sat = pre_amount_sat;
+      return;
+    }
+    if (apiObj is BreezEvent_SpliceFailed) {
+      var pre_channel_id = cst_encode_String(apiObj.channelId);
+      var pre_amount_sat = cst_encode_i_64(apiObj.amountSat);
+      var pre_reason = cst_encode_String(apiObj.reason);
+      wireObj.tag = 15;
+      wireObj.kind.SpliceFailed.channel_id = pre_channel_id;
+      wireObj.kind.SpliceFailed.amount_sat = pre_amount_sat;
+      wireObj.kind.SpliceFailed.reason = pre_reason;
+      return;
+    }
+    if (apiObj is BreezEvent_RestoreDownloadStarted) {
+      wireObj.tag = 16;
+      return;
+    }
+    if (apiObj is BreezEvent_RestoreDownloadCompleted) {
+      wireObj.tag = 17;
+      return;
+    }
+    if (apiObj is BreezEvent_RestoreChainSyncStarted) {
+      wireObj.tag = 18;
+      return;
+    }
+    if (apiObj is BreezEvent_RestoreChainSyncCompleted) {
+      wireObj.tag = 19;
+      return;
+    }
+    if (apiObj is BreezEvent_ChainReorg) {
+      var pre_old_tip_height = cst_encode_u_32(apiObj.oldTipHeight);
+      var pre_old_tip_hash = cst_encode_String(apiObj.oldTipHash);
+      var pre_new_tip_height = cst_encode_u_32(apiObj.newTipHeight);
+      var pre_new_tip_hash = cst_encode_String(apiObj.newTipHash);
+      wireObj.tag = 20;
+      wireObj.kind.ChainReorg.old_tip_height = pre_old_tip_height;
+      wireObj.kind.ChainReorg.old_tip_hash = pre_old_tip_hash;
+      wireObj.kind.ChainReorg.new_tip_height = pre_new_tip_height;
+      wireObj.kind.ChainReorg.new_tip_hash = pre_new_tip_hash;
+      return;
+    }
   }
 
   @protected
@@ -2776,6 +2844,9 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
     wireObj.funding_txid = cst_encode_String(apiObj.fundingTxid);
     wireObj.short_channel_id = cst_encode_opt_String(apiObj.shortChannelId);
     wireObj.closing_txid = cst_encode_opt_String(apiObj.closingTxid);
+    wireObj.counterparty_node_id = cst_encode_String(apiObj.counterpartyNodeId);
+    wireObj.capacity_sat = cst_encode_u_64(apiObj.capacitySat);
+    wireObj.is_force_close = cst_encode_bool(apiObj.isForceClose);
   }
 
   @protected
@@ -2793,6 +2864,20 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
     wireObj.api_key = cst_encode_opt_String(apiObj.apiKey);
     wireObj.maxfee_percent = cst_encode_f_64(apiObj.maxfeePercent);
     wireObj.exemptfee_msat = cst_encode_u_64(apiObj.exemptfeeMsat);
+    wireObj.payment_retries = cst_encode_u_32(apiObj.paymentRetries);
+    wireObj.remote_lock_lease_secs = cst_encode_u_32(apiObj.remoteLockLeaseSecs);
+    wireObj.remote_lock_retry_secs = cst_encode_u_32(apiObj.remoteLockRetrySecs);
+    wireObj.connect_timeout_secs = cst_encode_u_32(apiObj.connectTimeoutSecs);
+    wireObj.anchor_channels_enabled = cst_encode_bool(apiObj.anchorChannelsEnabled);
+    wireObj.allow_state_regression = cst_encode_bool(apiObj.allowStateRegression);
+    wireObj.min_sat_per_vbyte = cst_encode_u_32(apiObj.minSatPerVbyte);
+    wireObj.max_sat_per_vbyte = cst_encode_u_32(apiObj.maxSatPerVbyte);
+    wireObj.sync_retries = cst_encode_u_32(apiObj.syncRetries);
+    wireObj.max_jit_channels = cst_encode_u_32(apiObj.maxJitChannels);
+    wireObj.sweep_confirmation_target = cst_encode_u_32(apiObj.sweepConfirmationTarget);
+    wireObj.max_store_value_bytes = cst_encode_u_32(apiObj.maxStoreValueBytes);
+    wireObj.vss_client_id = cst_encode_opt_String(apiObj.vssClientId);
+    wireObj.restrict_file_permissions = cst_encode_bool(apiObj.restrictFilePermissions);
   }
 
   @protected
@@ -2975,6 +3060,8 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
     wireObj.lnurl_withdraw_endpoint = cst_encode_opt_String(apiObj.lnurlWithdrawEndpoint);
     wireObj.swap_info = cst_encode_opt_box_autoadd_swap_info(apiObj.swapInfo);
     wireObj.reverse_swap_info = cst_encode_opt_box_autoadd_reverse_swap_info(apiObj.reverseSwapInfo);
+    wireObj.custom_tlvs = cst_encode_opt_list_tlv_entry(apiObj.customTlvs);
+    wireObj.open_channel_bolt11 = cst_encode_opt_String(apiObj.openChannelBolt11);
   }
 
   @protected
@@ -3165,6 +3252,9 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
       apiObj.openingFeeParamsList,
       wireObj.opening_fee_params_list,
     );
+    wireObj.channel_over_provisioning_ppm = cst_encode_u_32(apiObj.channelOverProvisioningPpm);
+    wireObj.min_channel_lifetime = cst_encode_u_32(apiObj.minChannelLifetime);
+    wireObj.max_client_to_self_delay = cst_encode_u_32(apiObj.maxClientToSelfDelay);
   }
 
   @protected
@@ -3198,6 +3288,7 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
       apiObj.maxReceivableSinglePaymentAmountMsat,
     );
     wireObj.total_inbound_liquidity_msats = cst_encode_u_64(apiObj.totalInboundLiquidityMsats);
+    wireObj.sync_warning = cst_encode_opt_String(apiObj.syncWarning);
   }
 
   @protected
@@ -3391,6 +3482,9 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
     wireObj.use_description_hash = cst_encode_opt_box_autoadd_bool(apiObj.useDescriptionHash);
     wireObj.expiry = cst_encode_opt_box_autoadd_u_32(apiObj.expiry);
     wireObj.cltv = cst_encode_opt_box_autoadd_u_32(apiObj.cltv);
+    wireObj.register_notifications = cst_encode_opt_box_autoadd_bool(apiObj.registerNotifications);
+    wireObj.truncate_description = cst_encode_opt_box_autoadd_bool(apiObj.truncateDescription);
+    wireObj.no_open_channel = cst_encode_opt_box_autoadd_bool(apiObj.noOpenChannel);
   }
 
   @protected
@@ -3520,6 +3614,7 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
   ) {
     wireObj.bolt11 = cst_encode_String(apiObj.bolt11);
     wireObj.amount_msat = cst_encode_opt_box_autoadd_u_64(apiObj.amountMsat);
+    wireObj.max_fee_msat = cst_encode_opt_box_autoadd_u_64(apiObj.maxFeeMsat);
   }
 
   @protected
@@ -3538,6 +3633,7 @@ abstract class BreezSdkBindingsApiImplPlatform extends BaseApiImpl<BreezSdkBindi
     wireObj.node_id = cst_encode_String(apiObj.nodeId);
     wireObj.amount_msat = cst_encode_u_64(apiObj.amountMsat);
     wireObj.extra_tlvs = cst_encode_opt_list_tlv_entry(apiObj.extraTlvs);
+    wireObj.comment = cst_encode_opt_String(apiObj.comment);
   }
 
   @protected
@@ -4512,16 +4608,28 @@ class BreezSdkBindingsWire implements BaseWire {
   late final _wire__crate__binding__breez_events_stream = _wire__crate__binding__breez_events_streamPtr
       .asFunction<void Function(int, ffi.Pointer<wire_cst_list_prim_u_8_strict>)>();
 
-  void wire__crate__binding__breez_log_stream(int port_, ffi.Pointer<wire_cst_list_prim_u_8_strict> s) {
-    return _wire__crate__binding__breez_log_stream(port_, s);
+  void wire__crate__binding__breez_log_stream(
+    int port_,
+    ffi.Pointer<wire_cst_list_prim_u_8_strict> s,
+    ffi.Pointer<wire_cst_list_prim_u_8_strict> filter,
+  ) {
+    return _wire__crate__binding__breez_log_stream(port_, s, filter);
   }
 
   late final _wire__crate__binding__breez_log_streamPtr =
-      _lookup<ffi.NativeFunction<ffi.Void Function(ffi.Int64, ffi.Pointer<wire_cst_list_prim_u_8_strict>)>>(
-        'frbgen_breez_sdk_wire__crate__binding__breez_log_stream',
-      );
+      _lookup<
+        ffi.NativeFunction<
+          ffi.Void Function(
+            ffi.Int64,
+            ffi.Pointer<wire_cst_list_prim_u_8_strict>,
+            ffi.Pointer<wire_cst_list_prim_u_8_strict>,
+          )
+        >
+      >('frbgen_breez_sdk_wire__crate__binding__breez_log_stream');
   late final _wire__crate__binding__breez_log_stream = _wire__crate__binding__breez_log_streamPtr
-      .asFunction<void Function(int, ffi.Pointer<wire_cst_list_prim_u_8_strict>)>();
+      .asFunction<
+        void Function(int, ffi.Pointer<wire_cst_list_prim_u_8_strict>, ffi.Pointer<wire_cst_list_prim_u_8_strict>)
+      >();
 
   void wire__crate__binding__buy_bitcoin(int port_, ffi.Pointer<wire_cst_buy_bitcoin_request> req) {
     return _wire__crate__binding__buy_bitcoin(port_, req);
@@ -6248,6 +6356,10 @@ final class wire_cst_ln_payment_details extends ffi.Struct {
   external ffi.Pointer<wire_cst_swap_info> swap_info;
 
   external ffi.Pointer<wire_cst_reverse_swap_info> reverse_swap_info;
+
+  external ffi.Pointer<wire_cst_list_tlv_entry> custom_tlvs;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> open_channel_bolt11;
 }
 
 final class wire_cst_PaymentDetails_Ln extends ffi.Struct {
@@ -6263,6 +6375,14 @@ final class wire_cst_closed_channel_payment_details extends ffi.Struct {
   external ffi.Pointer<wire_cst_list_prim_u_8_strict> short_channel_id;
 
   external ffi.Pointer<wire_cst_list_prim_u_8_strict> closing_txid;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> counterparty_node_id;
+
+  @ffi.Uint64()
+  external int capacity_sat;
+
+  @ffi.Bool()
+  external bool is_force_close;
 }
 
 final class wire_cst_PaymentDetails_ClosedChannel extends ffi.Struct {
@@ -6420,6 +6540,43 @@ final class wire_cst_BreezEvent_SwapUpdated extends ffi.Struct {
   external ffi.Pointer<wire_cst_swap_info> details;
 }
 
+final class wire_cst_BreezEvent_SplicePending extends ffi.Struct {
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> channel_id;
+
+  @ffi.Int64()
+  external int amount_sat;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> funding_txid;
+}
+
+final class wire_cst_BreezEvent_SpliceCompleted extends ffi.Struct {
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> channel_id;
+
+  @ffi.Int64()
+  external int amount_sat;
+}
+
+final class wire_cst_BreezEvent_SpliceFailed extends ffi.Struct {
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> channel_id;
+
+  @ffi.Int64()
+  external int amount_sat;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> reason;
+}
+
+final class wire_cst_BreezEvent_ChainReorg extends ffi.Struct {
+  @ffi.Uint32()
+  external int old_tip_height;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> old_tip_hash;
+
+  @ffi.Uint32()
+  external int new_tip_height;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> new_tip_hash;
+}
+
 final class BreezEventKind extends ffi.Union {
   external wire_cst_BreezEvent_NewBlock NewBlock;
 
@@ -6434,6 +6591,14 @@ final class BreezEventKind extends ffi.Union {
   external wire_cst_BreezEvent_ReverseSwapUpdated ReverseSwapUpdated;
 
   external wire_cst_BreezEvent_SwapUpdated SwapUpdated;
+
+  external wire_cst_BreezEvent_SplicePending SplicePending;
+
+  external wire_cst_BreezEvent_SpliceCompleted SpliceCompleted;
+
+  external wire_cst_BreezEvent_SpliceFailed SpliceFailed;
+
+  external wire_cst_BreezEvent_ChainReorg ChainReorg;
 }
 
 final class wire_cst_breez_event extends ffi.Struct {
@@ -6494,6 +6659,47 @@ final class wire_cst_config extends ffi.Struct {
 
   @ffi.Uint64()
   external int exemptfee_msat;
+
+  @ffi.Uint32()
+  external int payment_retries;
+
+  @ffi.Uint32()
+  external int remote_lock_lease_secs;
+
+  @ffi.Uint32()
+  external int remote_lock_retry_secs;
+
+  @ffi.Uint32()
+  external int connect_timeout_secs;
+
+  @ffi.Bool()
+  external bool anchor_channels_enabled;
+
+  @ffi.Bool()
+  external bool allow_state_regression;
+
+  @ffi.Uint32()
+  external int min_sat_per_vbyte;
+
+  @ffi.Uint32()
+  external int max_sat_per_vbyte;
+
+  @ffi.Uint32()
+  external int sync_retries;
+
+  @ffi.Uint32()
+  external int max_jit_channels;
+
+  @ffi.Uint32()
+  external int sweep_confirmation_target;
+
+  @ffi.Uint32()
+  external int max_store_value_bytes;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> vss_client_id;
+
+  @ffi.Bool()
+  external bool restrict_file_permissions;
 }
 
 final class wire_cst_connect_request extends ffi.Struct {
@@ -6717,6 +6923,12 @@ final class wire_cst_receive_payment_request extends ffi.Struct {
   external ffi.Pointer<ffi.Uint32> expiry;
 
   external ffi.Pointer<ffi.Uint32> cltv;
+
+  external ffi.Pointer<ffi.Bool> register_notifications;
+
+  external ffi.Pointer<ffi.Bool> truncate_description;
+
+  external ffi.Pointer<ffi.Bool> no_open_channel;
 }
 
 final class wire_cst_redeem_onchain_funds_request extends ffi.Struct {
@@ -6762,6 +6974,8 @@ final class wire_cst_send_payment_request extends ffi.Struct {
   external ffi.Pointer<wire_cst_list_prim_u_8_strict> bolt11;
 
   external ffi.Pointer<ffi.Uint64> amount_msat;
+
+  external ffi.Pointer<ffi.Uint64> max_fee_msat;
 }
 
 final class wire_cst_tlv_entry extends ffi.Struct {
@@ -6785,6 +6999,8 @@ final class wire_cst_send_spontaneous_payment_request extends ffi.Struct {
   external int amount_msat;
 
   external ffi.Pointer<wire_cst_list_tlv_entry> extra_tlvs;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> comment;
 }
 
 final class wire_cst_sign_message_request extends ffi.Struct {
@@ -6919,6 +7135,15 @@ final class wire_cst_lsp_information extends ffi.Struct {
   external ffi.Pointer<wire_cst_list_prim_u_8_strict> lsp_pubkey;
 
   external wire_cst_opening_fee_params_menu opening_fee_params_list;
+
+  @ffi.Uint32()
+  external int channel_over_provisioning_ppm;
+
+  @ffi.Uint32()
+  external int min_channel_lifetime;
+
+  @ffi.Uint32()
+  external int max_client_to_self_delay;
 }
 
 final class wire_cst_symbol extends ffi.Struct {
@@ -7249,6 +7474,8 @@ final class wire_cst_node_state extends ffi.Struct {
 
   @ffi.Uint64()
   external int total_inbound_liquidity_msats;
+
+  external ffi.Pointer<wire_cst_list_prim_u_8_strict> sync_warning;
 }
 
 final class wire_cst_onchain_payment_limits_response extends ffi.Struct {
@@ -37,6 +37,17 @@ class LspInformation {
   final Uint8List lspPubkey;
   final OpeningFeeParamsMenu openingFeeParamsList;
 
+  /// The capacity the LSP adds to a JIT channel on top of the received amount, in parts per
+  /// million of the amount
+  final int channelOverProvisioningPpm;
+
+  /// The number of blocks the LSP keeps a JIT channel open at least
+  final int minChannelLifetime;
+
+  /// The largest `to_self_delay`, in blocks, the LSP accepts to wait for its funds on a
+  /// unilateral close of a JIT channel
+  final int maxClientToSelfDelay;
+
   const LspInformation({
     required this.id,
     required this.name,
@@ -49,6 +60,9 @@ class LspInformation {
     required this.minHtlcMsat,
     required this.lspPubkey,
     required this.openingFeeParamsList,
+    required this.channelOverProvisioningPpm,
+    required this.minChannelLifetime,
+    required this.maxClientToSelfDelay,
   });
 
   @override
@@ -63,7 +77,10 @@ class LspInformation {
       timeLockDelta.hashCode ^
       minHtlcMsat.hashCode ^
       lspPubkey.hashCode ^
-      openingFeeParamsList.hashCode;
+      openingFeeParamsList.hashCode ^
+      channelOverProvisioningPpm.hashCode ^
+      minChannelLifetime.hashCode ^
+      maxClientToSelfDelay.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -80,5 +97,8 @@ class LspInformation {
           timeLockDelta == other.timeLockDelta &&
           minHtlcMsat == other.minHtlcMsat &&
           lspPubkey == other.lspPubkey &&
-          openingFeeParamsList == other.openingFeeParamsList;
+          openingFeeParamsList == other.openingFeeParamsList &&
+          channelOverProvisioningPpm == other.channelOverProvisioningPpm &&
+          minChannelLifetime == other.minChannelLifetime &&
+          maxClientToSelfDelay == other.maxClientToSelfDelay;
 }
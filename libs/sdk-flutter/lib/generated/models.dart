@@ -85,16 +85,31 @@ class ClosedChannelPaymentDetails {
 
   /// Can be empty for older closed channels.
   final String? closingTxid;
+  final String counterpartyNodeId;
+  final BigInt capacitySat;
+
+  /// Whether the channel was force closed, as opposed to closed cooperatively.
+  final bool isForceClose;
 
   const ClosedChannelPaymentDetails({
     required this.state,
     required this.fundingTxid,
     this.shortChannelId,
     this.closingTxid,
+    required this.counterpartyNodeId,
+    required this.capacitySat,
+    required this.isForceClose,
   });
 
   @override
-  int get hashCode => state.hashCode ^ fundingTxid.hashCode ^ shortChannelId.hashCode ^ closingTxid.hashCode;
+  int get hashCode =>
+      state.hashCode ^
+      fundingTxid.hashCode ^
+      shortChannelId.hashCode ^
+      closingTxid.hashCode ^
+      counterpartyNodeId.hashCode ^
+      capacitySat.hashCode ^
+      isForceClose.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -104,7 +119,10 @@ class ClosedChannelPaymentDetails {
           state == other.state &&
           fundingTxid == other.fundingTxid &&
           shortChannelId == other.shortChannelId &&
-          closingTxid == other.closingTxid;
+          closingTxid == other.closingTxid &&
+          counterpartyNodeId == other.counterpartyNodeId &&
+          capacitySat == other.capacitySat &&
+          isForceClose == other.isForceClose;
 }
 
 /// Configuration for the Breez Services
@@ -142,6 +160,62 @@ class Config {
   /// Maps to the CLN `exemptfee` config when paying invoices (`lightning-pay`)
   final BigInt exemptfeeMsat;
 
+  /// Maximum number of times a failed payment is re-attempted when the failure is transient
+  final int paymentRetries;
+
+  /// Duration of the lease on the remote lock of the node storage, in seconds.
+  /// The lease is refreshed halfway through, so another instance can take over
+  /// within this duration if the app is killed.
+  final int remoteLockLeaseSecs;
+
+  /// Interval between attempts to refresh the remote lock after a failed refresh, in seconds
+  final int remoteLockRetrySecs;
+
+  /// Maximum time to wait for a connection to a peer to be established, in seconds
+  final int connectTimeoutSecs;
+
+  /// Whether to open anchor channels. If enabled, the LSP is trusted to
+  /// provide the fee bumping reserve, so no on-chain funds are required
+  final bool anchorChannelsEnabled;
+
+  /// Whether to adopt node state from the remote storage that is older than the state previously
+  /// synced by this device. Only enable it to recover from a botched multi-device setup, as an
+  /// older channel state may lead to a loss of funds
+  final bool allowStateRegression;
+
+  /// The lowest fee rate, in sat/vbyte, accepted for on-chain transactions.
+  /// The minimum fee reported by the chain service is enforced too, when it is higher.
+  final int minSatPerVbyte;
+
+  /// The highest fee rate, in sat/vbyte, accepted for on-chain transactions.
+  final int maxSatPerVbyte;
+
+  /// Maximum number of times a failed sync with the chain is re-attempted, with an
+  /// exponentially growing delay of up to 30 seconds between the attempts. At most 10
+  /// re-attempts are made, whatever the configured number
+  final int syncRetries;
+
+  /// The maximum number of channels opened by the LSP on the fly to receive a payment.
+  /// A receive that would need another channel fails once the limit is reached, 0 disables it
+  final int maxJitChannels;
+
+  /// The number of blocks within which the sweeps of closed channel funds, e.g. after a force
+  /// close, should confirm. Their fee rate is picked from the recommended fees for this target
+  final int sweepConfirmationTarget;
+
+  /// The largest value, in bytes, written to the local and remote stores of the node state.
+  /// Larger writes are rejected, guarding the storage against runaway values
+  final int maxStoreValueBytes;
+
+  /// Identifies the client in the `User-Agent` header of the requests to VSS, defaulting to
+  /// the SDK name and version. Must be printable ASCII of at most 128 characters.
+  final String? vssClientId;
+
+  /// Whether the files of the local cache of the node state are only accessible by the user
+  /// running the SDK. Otherwise they are created with the default permissions of the process,
+  /// which may let other users read them. Only has an effect on unix.
+  final bool restrictFilePermissions;
+
   const Config({
     required this.breezserver,
     required this.chainnotifierUrl,
@@ -156,6 +230,20 @@ class Config {
     this.apiKey,
     required this.maxfeePercent,
     required this.exemptfeeMsat,
+    required this.paymentRetries,
+    required this.remoteLockLeaseSecs,
+    required this.remoteLockRetrySecs,
+    required this.connectTimeoutSecs,
+    required this.anchorChannelsEnabled,
+    required this.allowStateRegression,
+    required this.minSatPerVbyte,
+    required this.maxSatPerVbyte,
+    required this.syncRetries,
+    required this.maxJitChannels,
+    required this.sweepConfirmationTarget,
+    required this.maxStoreValueBytes,
+    this.vssClientId,
+    required this.restrictFilePermissions,
   });
 
   @override
@@ -172,7 +260,21 @@ class Config {
       paymentTimeoutSec.hashCode ^
       apiKey.hashCode ^
       maxfeePercent.hashCode ^
-      exemptfeeMsat.hashCode;
+      exemptfeeMsat.hashCode ^
+      paymentRetries.hashCode ^
+      remoteLockLeaseSecs.hashCode ^
+      remoteLockRetrySecs.hashCode ^
+      connectTimeoutSecs.hashCode ^
+      anchorChannelsEnabled.hashCode ^
+      allowStateRegression.hashCode ^
+      minSatPerVbyte.hashCode ^
+      maxSatPerVbyte.hashCode ^
+      syncRetries.hashCode ^
+      maxJitChannels.hashCode ^
+      sweepConfirmationTarget.hashCode ^
+      maxStoreValueBytes.hashCode ^
+      vssClientId.hashCode ^
+      restrictFilePermissions.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -191,7 +293,21 @@ class Config {
           paymentTimeoutSec == other.paymentTimeoutSec &&
           apiKey == other.apiKey &&
           maxfeePercent == other.maxfeePercent &&
-          exemptfeeMsat == other.exemptfeeMsat;
+          exemptfeeMsat == other.exemptfeeMsat &&
+          paymentRetries == other.paymentRetries &&
+          remoteLockLeaseSecs == other.remoteLockLeaseSecs &&
+          remoteLockRetrySecs == other.remoteLockRetrySecs &&
+          connectTimeoutSecs == other.connectTimeoutSecs &&
+          anchorChannelsEnabled == other.anchorChannelsEnabled &&
+          allowStateRegression == other.allowStateRegression &&
+          minSatPerVbyte == other.minSatPerVbyte &&
+          maxSatPerVbyte == other.maxSatPerVbyte &&
+          syncRetries == other.syncRetries &&
+          maxJitChannels == other.maxJitChannels &&
+          sweepConfirmationTarget == other.sweepConfirmationTarget &&
+          maxStoreValueBytes == other.maxStoreValueBytes &&
+          vssClientId == other.vssClientId &&
+          restrictFilePermissions == other.restrictFilePermissions;
 }
 
 /// Represents a configure node request.
@@ -350,6 +466,13 @@ class LnPaymentDetails {
   /// Only set for [PaymentType::Sent] payments that were sent in the context of a reverse swap
   final ReverseSwapInfo? reverseSwapInfo;
 
+  /// Only set for [PaymentType::Received] payments that carried custom TLV records
+  final List<TlvEntry>? customTlvs;
+
+  /// Only set for [PaymentType::Received] payments that opened a channel, this is the
+  /// invoice that triggered the channel open
+  final String? openChannelBolt11;
+
   const LnPaymentDetails({
     required this.paymentHash,
     required this.destinationPubkey,
@@ -364,6 +487,8 @@ class LnPaymentDetails {
     this.lnurlWithdrawEndpoint,
     this.swapInfo,
     this.reverseSwapInfo,
+    this.customTlvs,
+    this.openChannelBolt11,
   });
 
   @override
@@ -380,7 +505,9 @@ class LnPaymentDetails {
       lnurlMetadata.hashCode ^
       lnurlWithdrawEndpoint.hashCode ^
       swapInfo.hashCode ^
-      reverseSwapInfo.hashCode;
+      reverseSwapInfo.hashCode ^
+      customTlvs.hashCode ^
+      openChannelBolt11.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -399,7 +526,9 @@ class LnPaymentDetails {
           lnurlMetadata == other.lnurlMetadata &&
           lnurlWithdrawEndpoint == other.lnurlWithdrawEndpoint &&
           swapInfo == other.swapInfo &&
-          reverseSwapInfo == other.reverseSwapInfo;
+          reverseSwapInfo == other.reverseSwapInfo &&
+          customTlvs == other.customTlvs &&
+          openChannelBolt11 == other.openChannelBolt11;
 }
 
 /// Internal SDK log entry
@@ -465,6 +594,9 @@ class NodeState {
   /// Total receivable on all available channels
   final BigInt totalInboundLiquidityMsats;
 
+  /// Set when the last sync with the chain failed, in which case the balances may be stale.
+  final String? syncWarning;
+
   const NodeState({
     required this.id,
     required this.blockHeight,
@@ -479,6 +611,7 @@ class NodeState {
     required this.connectedPeers,
     required this.maxReceivableSinglePaymentAmountMsat,
     required this.totalInboundLiquidityMsats,
+    this.syncWarning,
   });
 
   @override
@@ -495,7 +628,8 @@ class NodeState {
       maxChanReserveMsats.hashCode ^
       connectedPeers.hashCode ^
       maxReceivableSinglePaymentAmountMsat.hashCode ^
-      totalInboundLiquidityMsats.hashCode;
+      totalInboundLiquidityMsats.hashCode ^
+      syncWarning.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -514,7 +648,8 @@ class NodeState {
           maxChanReserveMsats == other.maxChanReserveMsats &&
           connectedPeers == other.connectedPeers &&
           maxReceivableSinglePaymentAmountMsat == other.maxReceivableSinglePaymentAmountMsat &&
-          totalInboundLiquidityMsats == other.totalInboundLiquidityMsats;
+          totalInboundLiquidityMsats == other.totalInboundLiquidityMsats &&
+          syncWarning == other.syncWarning;
 }
 
 class OnchainPaymentLimitsResponse {
@@ -958,9 +1093,24 @@ class ReceivePaymentRequest {
   /// if specified, set the time the invoice is valid for, in seconds.
   final int? expiry;
 
-  /// if specified, sets the min_final_cltv_expiry for the invoice
+  /// if specified, the minimum accepted min_final_cltv_expiry_delta of the invoice.
+  /// The delta is not set on the invoice, the node picks it and receiving fails if it is
+  /// smaller. Must be between 18 and 2016 blocks.
   final int? cltv;
 
+  /// If set to true, the LSP is asked to notify the registered webhook of the payment of
+  /// this invoice, so that it can be received while the app is offline.
+  /// See [crate::BreezServices::register_webhook].
+  final bool? registerNotifications;
+
+  /// If set to true, a description longer than the BOLT 11 limit is truncated instead of
+  /// rejected.
+  final bool? truncateDescription;
+
+  /// If set to true, the receive fails with [crate::error::ReceivePaymentError::OpenChannelNeeded]
+  /// instead of creating an invoice that opens a new channel and charges its opening fee.
+  final bool? noOpenChannel;
+
   const ReceivePaymentRequest({
     required this.amountMsat,
     required this.description,
@@ -969,6 +1119,9 @@ class ReceivePaymentRequest {
     this.useDescriptionHash,
     this.expiry,
     this.cltv,
+    this.registerNotifications,
+    this.truncateDescription,
+    this.noOpenChannel,
   });
 
   @override
@@ -979,7 +1132,10 @@ class ReceivePaymentRequest {
       openingFeeParams.hashCode ^
       useDescriptionHash.hashCode ^
       expiry.hashCode ^
-      cltv.hashCode;
+      cltv.hashCode ^
+      registerNotifications.hashCode ^
+      truncateDescription.hashCode ^
+      noOpenChannel.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -992,7 +1148,10 @@ class ReceivePaymentRequest {
           openingFeeParams == other.openingFeeParams &&
           useDescriptionHash == other.useDescriptionHash &&
           expiry == other.expiry &&
-          cltv == other.cltv;
+          cltv == other.cltv &&
+          registerNotifications == other.registerNotifications &&
+          truncateDescription == other.truncateDescription &&
+          noOpenChannel == other.noOpenChannel;
 }
 
 /// Represents a receive payment response.
@@ -1286,10 +1445,14 @@ class SendPaymentRequest {
   /// The amount to pay in millisatoshis. Should only be set when `bolt11` is a zero-amount invoice.
   final BigInt? amountMsat;
 
-  const SendPaymentRequest({required this.bolt11, this.amountMsat});
+  /// The maximum routing fee to pay in millisatoshis. Defaults to [Config::maxfee_percent] of
+  /// the amount, but at least [Config::exemptfee_msat].
+  final BigInt? maxFeeMsat;
+
+  const SendPaymentRequest({required this.bolt11, this.amountMsat, this.maxFeeMsat});
 
   @override
-  int get hashCode => bolt11.hashCode ^ amountMsat.hashCode;
+  int get hashCode => bolt11.hashCode ^ amountMsat.hashCode ^ maxFeeMsat.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -1297,7 +1460,8 @@ class SendPaymentRequest {
       other is SendPaymentRequest &&
           runtimeType == other.runtimeType &&
           bolt11 == other.bolt11 &&
-          amountMsat == other.amountMsat;
+          amountMsat == other.amountMsat &&
+          maxFeeMsat == other.maxFeeMsat;
 }
 
 /// Represents a send payment response.
@@ -1324,10 +1488,18 @@ class SendSpontaneousPaymentRequest {
   final BigInt amountMsat;
   final List<TlvEntry>? extraTlvs;
 
-  const SendSpontaneousPaymentRequest({required this.nodeId, required this.amountMsat, this.extraTlvs});
+  /// An optional note for the receiver, sent in the [KEYSEND_MESSAGE_TLV_TYPE] TLV
+  final String? comment;
+
+  const SendSpontaneousPaymentRequest({
+    required this.nodeId,
+    required this.amountMsat,
+    this.extraTlvs,
+    this.comment,
+  });
 
   @override
-  int get hashCode => nodeId.hashCode ^ amountMsat.hashCode ^ extraTlvs.hashCode;
+  int get hashCode => nodeId.hashCode ^ amountMsat.hashCode ^ extraTlvs.hashCode ^ comment.hashCode;
 
   @override
   bool operator ==(Object other) =>
@@ -1336,7 +1508,8 @@ class SendSpontaneousPaymentRequest {
           runtimeType == other.runtimeType &&
           nodeId == other.nodeId &&
           amountMsat == other.amountMsat &&
-          extraTlvs == other.extraTlvs;
+          extraTlvs == other.extraTlvs &&
+          comment == other.comment;
 }
 
 /// Represents a service health check response.
@@ -14,7 +14,7 @@ use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 use tracing::info;
 
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, EnvironmentId};
+use crate::environment::{ApiCredentials, EnvironmentId, Service};
 
 const BITCOIND_VERSION: &str = "v28.0";
 const BITCOIND_DOCKER_IMAGE: &str = "lncm/bitcoind";
@@ -41,6 +41,12 @@ struct RpcResponse<T> {
     error: Option<Value>,
 }
 
+#[derive(Deserialize, Debug)]
+struct WalletTransaction {
+    /// Negative if the transaction conflicts with a confirmed one.
+    confirmations: i64,
+}
+
 impl Bitcoind {
     pub async fn new(environment_id: &EnvironmentId) -> Result<Self> {
         let container = GenericImage::new(BITCOIND_DOCKER_IMAGE, BITCOIND_VERSION)
@@ -51,6 +57,7 @@ impl Bitcoind {
                 "init message: Done loading",
             )))
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Bitcoind))
             .with_log_consumer(LogConsumer::new("bitcoind"))
             .with_cmd([
                 "-regtest",
@@ -146,7 +153,7 @@ impl Bitcoind {
         Ok(())
     }
 
-    async fn get_new_address(&self) -> Result<Address> {
+    pub async fn get_new_address(&self) -> Result<Address> {
         self.rpc_call::<String>("getnewaddress", &[json!("mining"), json!("bech32")])
             .await?
             .parse::<Address<NetworkUnchecked>>()?
@@ -160,6 +167,17 @@ impl Bitcoind {
             .await
     }
 
+    pub async fn best_block_hash(&self) -> Result<String> {
+        self.rpc_call::<String>("getbestblockhash", &[]).await
+    }
+
+    /// Marks the block and its descendants as invalid, making the chain go back to its parent
+    /// until a longer chain is mined.
+    pub async fn invalidate_block(&self, block_hash: &str) -> Result<()> {
+        self.rpc_call::<()>("invalidateblock", &[json!(block_hash)])
+            .await
+    }
+
     pub async fn fund_address(&self, address: &Address, amount: Amount) -> Result<Txid> {
         let amount = amount.to_string_in(Denomination::Bitcoin);
         self.rpc_call::<String>(
@@ -171,6 +189,37 @@ impl Bitcoind {
         .map_err(anyhow::Error::msg)
     }
 
+    /// Sends `amount` to `address` and mines `confirmations` blocks on top of the transaction.
+    pub async fn fund_and_confirm(
+        &self,
+        address: &Address,
+        amount: Amount,
+        confirmations: u64,
+    ) -> Result<Txid> {
+        let txid = self.fund_address(address, amount).await?;
+        self.generate_blocks(confirmations).await?;
+        let actual = self.get_confirmations(&txid).await?;
+        ensure!(
+            actual >= confirmations as i64,
+            "Transaction {txid} has {actual} confirmations after mining {confirmations} blocks"
+        );
+        Ok(txid)
+    }
+
+    async fn get_confirmations(&self, txid: &Txid) -> Result<i64> {
+        let tx = self
+            .rpc_call::<WalletTransaction>("gettransaction", &[json!(txid.to_string())])
+            .await?;
+        Ok(tx.confirmations)
+    }
+
+    pub async fn get_received_by_address(&self, address: &Address) -> Result<Amount> {
+        let amount = self
+            .rpc_call::<f64>("getreceivedbyaddress", &[json!(address.to_string())])
+            .await?;
+        Amount::from_btc(amount).map_err(anyhow::Error::msg)
+    }
+
     async fn rpc_call<T: for<'de> Deserialize<'de>>(
         &self,
         method: &str,
@@ -201,6 +250,10 @@ impl Bitcoind {
         match (response.result, response.error) {
             (Some(result), None) => Ok(result),
             (None, Some(error)) => bail!("RPC error: {error:?}"),
+            // Methods without a result, as `invalidateblock`, return null.
+            (None, None) => {
+                serde_json::from_value(Value::Null).map_err(|_| anyhow!("Invalid RPC response"))
+            }
             _ => bail!("Invalid RPC response"),
         }
     }
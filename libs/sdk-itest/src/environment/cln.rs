@@ -10,7 +10,7 @@ use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 
 use crate::environment::container::ContainerExt;
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, Cert, EnvironmentId};
+use crate::environment::{ApiCredentials, Cert, EnvironmentId, Service};
 
 const CA_PEM_FILE: &str = "/data/.lightning/regtest/ca.pem";
 const CLIENT_CERT_FILE: &str = "/data/.lightning/regtest/client.pem";
@@ -41,6 +41,7 @@ impl Cln {
             ))
             .with_network(environment_id.network_name())
             .with_hostname(CLN_HOSTNAME)
+            .with_startup_timeout(environment_id.startup_timeout(Service::Cln))
             .with_log_consumer(LogConsumer::new("cln"))
             .with_cmd([
                 "--network=regtest",
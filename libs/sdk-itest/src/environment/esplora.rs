@@ -5,7 +5,7 @@ use testcontainers::runners::AsyncRunner;
 use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, EnvironmentId};
+use crate::environment::{ApiCredentials, EnvironmentId, Service};
 
 const IMAGE_NAME: &str = "ghcr.io/vulpemventures/electrs";
 const IMAGE_TAG: &str = "a808b51d0d9301fa82390b985c57551966001f9b";
@@ -29,6 +29,7 @@ impl Esplora {
                     .with_expected_status_code(200u16),
             )))
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Esplora))
             .with_log_consumer(LogConsumer::new("esplora"))
             .with_cmd([
                 "-vvvv",
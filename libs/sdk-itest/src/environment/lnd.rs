@@ -1,26 +1,34 @@
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use anyhow::{Result, bail};
+use bitcoin::hashes::{Hash, sha256};
 use bitcoin::secp256k1::PublicKey;
 use bitcoin::{Address, Amount, Network};
+use breez_sdk_core::RouteHint;
+use rand::Rng;
 use testcontainers::core::WaitFor;
 use testcontainers::runners::AsyncRunner;
 use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 use tokio::sync::Mutex;
 use tonic_lnd::Client;
+use tonic_lnd::lnrpc::payment::PaymentStatus;
 use tonic_lnd::lnrpc::{
-    AddressType, ConnectPeerRequest, GetInfoRequest, Invoice, LightningAddress,
-    ListChannelsRequest, NewAddressRequest, OpenChannelRequest, SendRequest,
+    AddressType, ConnectPeerRequest, FeatureBit, GetInfoRequest, HopHint, Invoice,
+    LightningAddress, ListChannelsRequest, NewAddressRequest, OpenChannelRequest, PaymentHash,
+    SendRequest,
 };
+use tonic_lnd::routerrpc::SendPaymentRequest;
 
 use crate::environment::container::ContainerExt;
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, EnvironmentId};
+use crate::environment::{ApiCredentials, EnvironmentId, Service};
 
 const IMAGE_NAME: &str = "lightninglabs/lnd";
 const IMAGE_TAG: &str = "v0.19.3-beta";
 const LIGHTNING_PORT: u16 = 9735;
 const RPC_PORT: u16 = 10009;
+const KEYSEND_PREIMAGE_TLV_TYPE: u64 = 5482373484;
 
 pub struct Lnd {
     pub lightning_api: ApiCredentials,
@@ -40,6 +48,7 @@ impl Lnd {
             .with_exposed_port(RPC_PORT.into())
             .with_wait_for(WaitFor::message_on_stdout("Server listening on"))
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Lnd))
             .with_log_consumer(LogConsumer::new("lnd"))
             .with_cmd([
                 "--bitcoin.regtest",
@@ -191,6 +200,30 @@ impl Lnd {
         Ok(())
     }
 
+    /// Pays the invoice allowing the payment to be split into up to `max_parts` parts.
+    pub async fn pay_mpp(&self, payment_request: String, max_parts: u32) -> Result<()> {
+        let mut client = self.client.lock().await;
+        let mut updates = client
+            .router()
+            .send_payment_v2(SendPaymentRequest {
+                payment_request,
+                max_parts,
+                timeout_seconds: 60,
+                fee_limit_sat: 100,
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+        while let Some(payment) = updates.message().await? {
+            match payment.status() {
+                PaymentStatus::Succeeded => return Ok(()),
+                PaymentStatus::Failed => bail!("Payment failed: {:?}", payment.failure_reason()),
+                _ => continue,
+            }
+        }
+        bail!("Payment status stream ended unexpectedly")
+    }
+
     pub async fn receive(&self, amount: &Amount) -> Result<String> {
         let mut client = self.client.lock().await;
         let resp = client
@@ -203,4 +236,78 @@ impl Lnd {
             .await?;
         Ok(resp.into_inner().payment_request)
     }
+
+    pub async fn keysend(
+        &self,
+        dest: &str,
+        amount: &Amount,
+        route_hints: &[RouteHint],
+        mut custom_records: HashMap<u64, Vec<u8>>,
+    ) -> Result<()> {
+        let preimage = rand::rng().random::<[u8; 32]>();
+        let payment_hash = sha256::Hash::hash(&preimage);
+        custom_records.insert(KEYSEND_PREIMAGE_TLV_TYPE, preimage.to_vec());
+        let mut lnd_route_hints = Vec::new();
+        for route_hint in route_hints {
+            let hop_hints = route_hint
+                .to_ldk_hint()?
+                .0
+                .into_iter()
+                .map(|hop| HopHint {
+                    node_id: hop.src_node_id.to_string(),
+                    chan_id: hop.short_channel_id,
+                    fee_base_msat: hop.fees.base_msat,
+                    fee_proportional_millionths: hop.fees.proportional_millionths,
+                    cltv_expiry_delta: hop.cltv_expiry_delta.into(),
+                })
+                .collect();
+            lnd_route_hints.push(tonic_lnd::lnrpc::RouteHint { hop_hints });
+        }
+
+        let mut client = self.client.lock().await;
+        let mut updates = client
+            .router()
+            .send_payment_v2(SendPaymentRequest {
+                dest: hex::decode(dest)?,
+                amt_msat: (amount.to_sat() * 1000) as i64,
+                payment_hash: payment_hash.to_byte_array().to_vec(),
+                dest_custom_records: custom_records,
+                dest_features: vec![FeatureBit::TlvOnionReq as i32],
+                route_hints: lnd_route_hints,
+                timeout_seconds: 60,
+                fee_limit_sat: 100,
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+        while let Some(payment) = updates.message().await? {
+            match payment.status() {
+                PaymentStatus::Succeeded => return Ok(()),
+                PaymentStatus::Failed => bail!("Keysend failed: {:?}", payment.failure_reason()),
+                _ => continue,
+            }
+        }
+        bail!("Keysend status stream ended unexpectedly")
+    }
+
+    /// Returns the custom records received with the HTLCs of the payment with the given hash.
+    pub async fn received_custom_records(
+        &self,
+        payment_hash: &str,
+    ) -> Result<HashMap<u64, Vec<u8>>> {
+        let mut client = self.client.lock().await;
+        let invoice = client
+            .lightning()
+            .lookup_invoice(PaymentHash {
+                r_hash: hex::decode(payment_hash)?,
+                ..Default::default()
+            })
+            .await?
+            .into_inner();
+        Ok(invoice
+            .htlcs
+            .into_iter()
+            .flat_map(|htlc| htlc.custom_records)
+            .collect())
+    }
 }
@@ -11,7 +11,7 @@ use testcontainers::runners::AsyncRunner;
 use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, EnvironmentId};
+use crate::environment::{ApiCredentials, EnvironmentId, Service};
 
 const LIGHTNING_PORT: u16 = 9735;
 const RPC_PORT: u16 = 9736;
@@ -42,6 +42,7 @@ impl Lsp {
                     .with_expected_status_code(200u16),
             )))
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Lsp))
             .with_log_consumer(LogConsumer::new("lsps2-server"))
             .with_env_var("ESPLORA_URL", esplora_api.endpoint())
             .with_env_var("LISTENING_ADDRESS", format!("0.0.0.0:{LIGHTNING_PORT}"))
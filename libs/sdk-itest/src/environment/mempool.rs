@@ -8,7 +8,7 @@ use testcontainers_modules::mariadb::Mariadb;
 use tokio::try_join;
 
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, EnvironmentId};
+use crate::environment::{ApiCredentials, EnvironmentId, Service};
 
 const IMAGE_NAME: &str = "mempool/backend";
 const IMAGE_TAG: &str = "v3.0.1";
@@ -32,6 +32,7 @@ impl Mempool {
             .with_env_var("MARIADB_PASSWORD", "mempool")
             .with_env_var("MARIADB_USER", "mempool")
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Mempool))
             .with_log_consumer(LogConsumer::new("mempool-db"))
             .start()
             .map_err(anyhow::Error::msg);
@@ -47,6 +48,7 @@ impl Mempool {
                     .with_expected_status_code(200u16),
             )))
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Mempool))
             .with_log_consumer(LogConsumer::new("mempool"))
             .with_env_var("CORE_RPC_HOST", bitcoind_api.host.clone())
             .with_env_var("CORE_RPC_PASSWORD", bitcoind_api.password.clone())
@@ -9,11 +9,13 @@ mod mempool;
 mod rgs;
 mod vss;
 
+use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 
 use anyhow::Result;
-use bitcoin::Amount;
 use bitcoin::secp256k1::PublicKey;
+use bitcoin::{Address, Amount, Txid};
 use bitcoind::Bitcoind;
 use cln::Cln;
 use esplora::Esplora;
@@ -31,10 +33,55 @@ use vss::Vss;
 
 use crate::wait_for;
 
+/// The default time given to the container of a service to become ready, as in testcontainers.
+const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The services of the environment, each running in its own containers.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Service {
+    Bitcoind,
+    Esplora,
+    Mempool,
+    Vss,
+    Lsp,
+    Lnd,
+    Cln,
+    Rgs,
+}
+
+impl Service {
+    const ALL: [Service; 8] = [
+        Self::Bitcoind,
+        Self::Esplora,
+        Self::Mempool,
+        Self::Vss,
+        Self::Lsp,
+        Self::Lnd,
+        Self::Cln,
+        Self::Rgs,
+    ];
+
+    fn default_startup_timeout(self) -> Duration {
+        match self {
+            // Waiting for the second channel update takes time...
+            Self::Rgs => Duration::from_secs(180),
+            _ => DEFAULT_STARTUP_TIMEOUT,
+        }
+    }
+
+    /// The environment variable overriding the startup timeout of the service, in seconds,
+    /// e.g. `ITEST_RGS_STARTUP_TIMEOUT_SECS`.
+    fn startup_timeout_var(self) -> String {
+        let name = format!("{self:?}").to_uppercase();
+        format!("ITEST_{name}_STARTUP_TIMEOUT_SECS")
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct EnvironmentId {
     id: String,
     working_dir: PathBuf,
+    startup_timeouts: HashMap<Service, Duration>,
 }
 
 impl Default for EnvironmentId {
@@ -49,7 +96,35 @@ impl EnvironmentId {
         let id = hex::encode(id.to_le_bytes());
         let mut working_dir = testdir!();
         working_dir.push(id.clone());
-        Self { id, working_dir }
+        let startup_timeouts = Service::ALL
+            .into_iter()
+            .filter_map(|service| {
+                let var = service.startup_timeout_var();
+                let secs = std::env::var(&var).ok()?;
+                let secs = secs
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid {var}: {secs}"));
+                Some((service, Duration::from_secs(secs)))
+            })
+            .collect();
+        Self {
+            id,
+            working_dir,
+            startup_timeouts,
+        }
+    }
+
+    /// Overrides the time given to the containers of `service` to become ready.
+    pub fn with_startup_timeout(mut self, service: Service, timeout: Duration) -> Self {
+        self.startup_timeouts.insert(service, timeout);
+        self
+    }
+
+    pub fn startup_timeout(&self, service: Service) -> Duration {
+        self.startup_timeouts
+            .get(&service)
+            .copied()
+            .unwrap_or_else(|| service.default_startup_timeout())
     }
 
     pub fn network_name(&self) -> String {
@@ -118,7 +193,6 @@ impl ApiCredentials {
     }
 }
 
-#[derive(Default)]
 pub struct Environment {
     environmnet_id: EnvironmentId,
     bitcoind: OnceCell<Bitcoind>,
@@ -133,12 +207,82 @@ pub struct Environment {
     rgs: OnceCell<Rgs>,
 }
 
+impl Default for Environment {
+    fn default() -> Self {
+        Self::new(EnvironmentId::new())
+    }
+}
+
 impl Environment {
+    pub fn new(environment_id: EnvironmentId) -> Self {
+        Self {
+            environmnet_id: environment_id,
+            bitcoind: OnceCell::new(),
+            esplora: OnceCell::new(),
+            mempool: OnceCell::new(),
+            vss: OnceCell::new(),
+            lsp: OnceCell::new(),
+            lnd: OnceCell::new(),
+            cln: OnceCell::new(),
+            channel: OnceCell::new(),
+            cln_channel: OnceCell::new(),
+            rgs: OnceCell::new(),
+        }
+    }
+
     #[instrument(skip(self))]
     pub async fn bitcoind_api(&self) -> Result<&ApiCredentials> {
         Ok(&self.bitcoind().await?.api)
     }
 
+    #[instrument(skip(self))]
+    pub async fn bitcoind_new_address(&self) -> Result<Address> {
+        self.bitcoind().await?.get_new_address().await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn bitcoind_received_by_address(&self, address: &Address) -> Result<Amount> {
+        self.bitcoind()
+            .await?
+            .get_received_by_address(address)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn bitcoind_fund_address(&self, address: &Address, amount: Amount) -> Result<Txid> {
+        self.bitcoind().await?.fund_address(address, amount).await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn bitcoind_fund_and_confirm(
+        &self,
+        address: &Address,
+        amount: Amount,
+        confirmations: u64,
+    ) -> Result<Txid> {
+        self.bitcoind()
+            .await?
+            .fund_and_confirm(address, amount, confirmations)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    pub async fn generate_blocks(&self, count: u64) -> Result<()> {
+        self.bitcoind().await?.generate_blocks(count).await?;
+        Ok(())
+    }
+
+    /// Replaces the current tip with a block of another branch, leaving the chain one block
+    /// longer.
+    #[instrument(skip(self))]
+    pub async fn reorg_tip(&self) -> Result<()> {
+        let bitcoind = self.bitcoind().await?;
+        let tip = bitcoind.best_block_hash().await?;
+        bitcoind.invalidate_block(&tip).await?;
+        bitcoind.generate_blocks(2).await?;
+        Ok(())
+    }
+
     #[instrument(skip(self))]
     async fn bitcoind_rest_api(&self) -> Result<&ApiCredentials> {
         Ok(&self.bitcoind().await?.rest_api)
@@ -277,12 +421,11 @@ impl Environment {
 
         let amount = Amount::ONE_BTC;
         let address = lsp.get_new_address().await?;
-        bitcoind.fund_address(&address, amount).await?;
+        bitcoind.fund_and_confirm(&address, amount, 1).await?;
 
         let address = lnd.get_new_address().await?;
-        bitcoind.fund_address(&address, amount).await?;
+        bitcoind.fund_and_confirm(&address, amount, 1).await?;
 
-        bitcoind.generate_blocks(1).await?;
         info!("Waiting for LSP to see on-chain funds...");
         wait_for!(lsp.get_balance(true).await?.spendable_onchain_sats >= amount.to_sat());
 
@@ -307,8 +450,7 @@ impl Environment {
 
         let amount = Amount::ONE_BTC;
         let address = cln.get_new_address().await?;
-        bitcoind.fund_address(&address, amount).await?;
-        bitcoind.generate_blocks(1).await?;
+        bitcoind.fund_and_confirm(&address, amount, 1).await?;
         info!("Waiting for CLN to see on-chain funds...");
         wait_for!(cln.spendable_onchain_sats().await? >= amount.to_sat());
 
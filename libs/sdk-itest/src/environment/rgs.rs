@@ -1,5 +1,3 @@
-use std::time::Duration;
-
 use anyhow::Result;
 use futures::TryFutureExt;
 use testcontainers::core::wait::LogWaitStrategy;
@@ -10,7 +8,7 @@ use testcontainers_modules::postgres::Postgres;
 use tokio::try_join;
 
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, EnvironmentId, Lnd};
+use crate::environment::{ApiCredentials, EnvironmentId, Lnd, Service};
 
 const IMAGE_NAME: &str = "rgs";
 const IMAGE_TAG: &str = "latest";
@@ -33,6 +31,7 @@ impl Rgs {
         let postgres = Postgres::default()
             .with_tag("16")
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Rgs))
             .with_log_consumer(LogConsumer::new("rgs-postgres"))
             .start()
             .map_err(anyhow::Error::msg);
@@ -59,7 +58,7 @@ impl Rgs {
             .with_env_var("RAPID_GOSSIP_SYNC_SERVER_DB_PASSWORD", "postgres")
             .with_env_var("RAPID_GOSSIP_SYNC_SERVER_DB_USER", "postgres")
             .with_env_var("RAPID_GOSSIP_SYNC_SERVER_NETWORK", "regtest")
-            .with_startup_timeout(Duration::from_secs(180)) // Wating for the second channel update takes time...
+            .with_startup_timeout(environment_id.startup_timeout(Service::Rgs))
             .start()
             .await?;
 
@@ -69,6 +68,7 @@ impl Rgs {
         let nginx = GenericImage::new("nginx", "latest")
             .with_exposed_port(RPC_PORT.into())
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Rgs))
             .with_log_consumer(LogConsumer::new("rgs-nginx"))
             .with_copy_to("/etc/nginx/conf.d/default.conf", nginx_config.to_vec())
             .with_mount(rgs_data)
@@ -6,7 +6,7 @@ use testcontainers::{ContainerAsync, GenericImage, ImageExt};
 use testcontainers_modules::postgres::Postgres;
 
 use crate::environment::log::LogConsumer;
-use crate::environment::{ApiCredentials, EnvironmentId};
+use crate::environment::{ApiCredentials, EnvironmentId, Service};
 
 const IMAGE_NAME: &str = "vss";
 const IMAGE_TAG: &str = "latest";
@@ -25,6 +25,7 @@ impl Vss {
             .with_init_sql(init_sql.to_vec())
             .with_tag("16")
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Vss))
             .with_log_consumer(LogConsumer::new("vss-postgres"))
             .start()
             .await?;
@@ -38,6 +39,7 @@ impl Vss {
                     .with_expected_status_code(400u16),
             )))
             .with_network(environment_id.network_name())
+            .with_startup_timeout(environment_id.startup_timeout(Service::Vss))
             .with_log_consumer(LogConsumer::new("vss-server"))
             .with_env_var("VSS_BIND_ADDRESS", format!("0.0.0.0:{RPC_PORT}"))
             .with_env_var("VSS_PSQL_ADDRESS", format!("{postgres_host}:5432"))
@@ -1,17 +1,21 @@
 mod event_listener;
 
+use std::collections::HashMap;
+use std::str::FromStr;
 use std::time::Duration;
 
-use bitcoin::Amount;
+use bitcoin::{Address, Amount};
 use breez_sdk_core::error::{ConnectError, SendPaymentError};
 use breez_sdk_core::{
-    BreezEvent, BreezServices, Config, ConnectRequest, ListPaymentsRequest, LnPaymentDetails,
-    PaymentDetails, PaymentStatus, PaymentType, ReceivePaymentRequest, SendPaymentRequest,
-    SendSpontaneousPaymentRequest,
+    BreezEvent, BreezServices, Config, ConnectRequest, KEYSEND_MESSAGE_TLV_TYPE,
+    ListPaymentsRequest, LnPaymentDetails, PayNodeAddressRequest, PayNodeAddressResponse,
+    PaymentDetails, PaymentStatus, PaymentType, PaymentTypeFilter, PendingSweepStage,
+    PrepareSendPaymentRequest, ReceivePaymentRequest, SendPaymentRequest,
+    SendSpontaneousPaymentRequest, TlvEntry, VssCheckResult,
 };
 use rand::Rng;
 use rstest::*;
-use sdk_itest::environment::Environment;
+use sdk_itest::environment::{Environment, EnvironmentId, Service};
 use sdk_itest::wait_for;
 use testdir::testdir;
 use tokio::sync::mpsc;
@@ -22,6 +26,7 @@ use tracing::info;
 use crate::event_listener::EventListenerImpl;
 
 const SECOND: Duration = Duration::from_secs(1);
+const PODCAST_TLV_TYPE: u64 = 7629169;
 const UNPAYABLE_BOLT11: &str = "lnbcrt10u1p5h5g5kpp5asutj0mvuxr7g5asar2cu0l0mreyxp6a88mmerjuzk5r64zqpyxsdq9f38ygcqzzsxq97zvuqsp5hagpy8n954f86y7ca3kx5alr36a9nr4md6cyzfz9anmkf33nv63q9qxpqysgqrnjfrk9j6q6zl7alg287mhf8qfj5wawk6kk7n7rkgx82zd9y50sy8w4edmsetqatfpv5ezjkv7wxse2p7m63ax6mt7gkllwr3jmw0mcp9urhh9";
 
 #[ignore = "Manual test for testing the environment itself"]
@@ -32,6 +37,33 @@ async fn test_environment() {
     Environment::default().cln_with_channel().await.unwrap();
 }
 
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_environment_startup_timeout() {
+    let environment_id =
+        EnvironmentId::new().with_startup_timeout(Service::Bitcoind, Duration::from_millis(1));
+    let env = Environment::new(environment_id);
+    // Well below the default startup timeout.
+    let res = tokio::time::timeout(30 * SECOND, env.bitcoind_api()).await;
+    assert!(matches!(res, Ok(Err(_))));
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_environment_fund_and_confirm() {
+    let env = Environment::default();
+    let address = env.bitcoind_new_address().await.unwrap();
+    let amount = Amount::from_sat(100_000);
+    env.bitcoind_fund_and_confirm(&address, amount, 3)
+        .await
+        .unwrap();
+    // Only confirmed transactions are counted.
+    let received = env.bitcoind_received_by_address(&address).await.unwrap();
+    assert_eq!(received, amount);
+}
+
 #[rstest]
 #[tokio::test(flavor = "multi_thread")]
 #[test_log::test]
@@ -113,6 +145,11 @@ async fn test_node_receive_payments() {
     ));
     let balance_msat = services.node_info().await.channels_balance_msat;
     assert_eq!(balance_msat, huge_amount_msat - opening_fee_msat);
+    let (lsp_id, lsp_address) = config.lsps2_address.split_once('@').unwrap();
+    let peers = services.list_peers().await.unwrap();
+    let lsp_peer = peers.iter().find(|p| p.node_id == lsp_id).unwrap();
+    assert_eq!(lsp_peer.address, lsp_address);
+    assert!(lsp_peer.is_connected);
     let payments = services.list_payments(Default::default()).await.unwrap();
     assert_eq!(payments.len(), 1);
     let payment = payments.first().cloned().unwrap();
@@ -123,6 +160,7 @@ async fn test_node_receive_payments() {
     if let PaymentDetails::Ln { data } = &payment.details {
         assert_eq!(data.bolt11, invoice.bolt11);
         assert_eq!(data.destination_pubkey, node_pubkey);
+        assert_eq!(data.open_channel_bolt11, Some(invoice.bolt11.clone()));
     } else {
         panic!("Expected LN payment details");
     }
@@ -160,6 +198,7 @@ async fn test_node_receive_payments() {
     if let PaymentDetails::Ln { data } = &payment.details {
         assert_eq!(data.bolt11, invoice.bolt11);
         assert_eq!(data.destination_pubkey, node_pubkey);
+        assert_eq!(data.open_channel_bolt11, None);
     } else {
         panic!("Expected LN payment details");
     }
@@ -175,6 +214,7 @@ async fn test_node_receive_payments() {
                 .send_payment(SendPaymentRequest {
                     bolt11: UNPAYABLE_BOLT11.to_string(),
                     amount_msat: None,
+                    max_fee_msat: None,
                 })
                 .await
         })
@@ -239,6 +279,7 @@ async fn test_node_receive_payments() {
                 .send_payment(SendPaymentRequest {
                     bolt11,
                     amount_msat: None,
+                    max_fee_msat: None,
                 })
                 .await
         })
@@ -300,6 +341,7 @@ async fn test_node_receive_payments() {
         .send_payment(SendPaymentRequest {
             bolt11: bolt11.clone(),
             amount_msat: Some(amount.to_msat()),
+            max_fee_msat: None,
         })
         .await
         .unwrap()
@@ -334,7 +376,7 @@ async fn test_node_receive_payments() {
     // Ensure that the next payment does not occur at the same time (down to the second).
     sleep(SECOND).await;
 
-    // Sending spontaneous payment.
+    // Sending spontaneous payment with a comment.
     let lnd_id = lnd.get_id().await.unwrap();
     let amount = Amount::from_sat(1200);
     let payment = services
@@ -342,6 +384,7 @@ async fn test_node_receive_payments() {
             node_id: lnd_id,
             amount_msat: amount.to_msat(),
             extra_tlvs: None,
+            comment: Some("Thanks!".to_string()),
         })
         .await
         .unwrap()
@@ -354,6 +397,17 @@ async fn test_node_receive_payments() {
             data: LnPaymentDetails { keysend: true, .. }
         }
     ));
+    let PaymentDetails::Ln { data } = &payment.details else {
+        panic!("Expected LN payment details");
+    };
+    let custom_records = lnd
+        .received_custom_records(&data.payment_hash)
+        .await
+        .unwrap();
+    assert_eq!(
+        custom_records.get(&KEYSEND_MESSAGE_TLV_TYPE),
+        Some(&b"Thanks!".to_vec())
+    );
     info!("Waiting for BreezEvent::PaymentSucceed...");
     wait_for!(matches!(
         events.recv().await,
@@ -379,6 +433,275 @@ async fn test_node_receive_payments() {
         panic!("Expected LN payment details");
     }
 
+    // Ensure that the next payment does not occur at the same time (down to the second).
+    sleep(SECOND).await;
+
+    // Paying a node by its node_id@host address.
+    let address = format!(
+        "{}@{}",
+        lnd.get_id().await.unwrap(),
+        lnd.lightning_api.external_address()
+    );
+    let amount = Amount::from_sat(1300);
+    let res = services
+        .pay_node_address(PayNodeAddressRequest {
+            address,
+            amount_msat: amount.to_msat(),
+            open_channel_sat: None,
+        })
+        .await
+        .unwrap();
+    let PayNodeAddressResponse::Paid { payment } = res else {
+        panic!("Expected a paid response");
+    };
+    assert_eq!(payment.amount_msat, amount.to_msat());
+    assert!(matches!(
+        payment.details,
+        PaymentDetails::Ln {
+            data: LnPaymentDetails { keysend: true, .. }
+        }
+    ));
+    info!("Waiting for BreezEvent::PaymentSucceed...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::PaymentSucceed { .. })
+    ));
+
+    // Ensure that the next payment does not occur at the same time (down to the second).
+    sleep(SECOND).await;
+
+    // Receiving a keysend payment with a custom TLV record.
+    let route_hints = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat: small_amount_msat,
+            description: "Route hints".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+        .ln_invoice
+        .routing_hints;
+    let amount = Amount::from_sat(100);
+    let tlv = TlvEntry {
+        field_number: PODCAST_TLV_TYPE,
+        value: b"Sent from LND".to_vec(),
+    };
+    lnd.keysend(
+        &node_pubkey,
+        &amount,
+        &route_hints,
+        HashMap::from([(tlv.field_number, tlv.value.clone())]),
+    )
+    .await
+    .unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+    services.sync().await.unwrap();
+    let payments = services.list_payments(Default::default()).await.unwrap();
+    let payment = payments
+        .into_iter()
+        .find(|p| {
+            p.payment_type == PaymentType::Received
+                && matches!(
+                    p.details,
+                    PaymentDetails::Ln {
+                        data: LnPaymentDetails { keysend: true, .. }
+                    }
+                )
+        })
+        .unwrap();
+    assert_eq!(payment.amount_msat, amount.to_msat());
+    if let PaymentDetails::Ln { data } = &payment.details {
+        assert_eq!(data.custom_tlvs, Some(vec![tlv]));
+    } else {
+        panic!("Expected LN payment details");
+    }
+
+    // Paying with a routing fee limit below the fee of the only route through the LSP.
+    let amount = Amount::from_sat(1200);
+    let bolt11 = lnd.receive(&amount).await.unwrap();
+    let res = services
+        .send_payment(SendPaymentRequest {
+            bolt11: bolt11.clone(),
+            amount_msat: None,
+            max_fee_msat: Some(999),
+        })
+        .await;
+    assert!(matches!(
+        res,
+        Err(SendPaymentError::RouteTooExpensive { .. })
+    ));
+
+    // Ensure that the next payment does not occur at the same time (down to the second).
+    sleep(SECOND).await;
+
+    // Retrying with a higher limit succeeds.
+    let payment = services
+        .send_payment(SendPaymentRequest {
+            bolt11,
+            amount_msat: None,
+            max_fee_msat: Some(1000),
+        })
+        .await
+        .unwrap()
+        .payment;
+    assert_eq!(payment.status, PaymentStatus::Complete);
+    assert_eq!(payment.fee_msat, 1000);
+
+    // Splicing on-chain funds into the channel.
+    let address = services.new_onchain_address().await.unwrap();
+    let address = Address::from_str(&address).unwrap().assume_checked();
+    let funding_txid = env
+        .bitcoind_fund_address(&address, Amount::from_sat(100_000))
+        .await
+        .unwrap()
+        .to_string();
+    let tip_before_funding = services.chain_tip().await.unwrap();
+    assert!(tip_before_funding > 0);
+    info!("Waiting for the on-chain funds to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await.onchain_balance_msat > 0
+    });
+    info!("Waiting for mempool to index the funding transaction...");
+    wait_for!(
+        services
+            .onchain_tx_status(funding_txid.clone())
+            .await
+            .is_ok_and(|status| status.confirmed)
+    );
+    let status = services
+        .onchain_tx_status(funding_txid.clone())
+        .await
+        .unwrap();
+    let tip = services.chain_tip().await.unwrap();
+    assert!(status.block_height.unwrap() > tip_before_funding);
+    assert!(tip >= status.block_height.unwrap());
+    let address_txs = services
+        .address_transactions(address.to_string())
+        .await
+        .unwrap();
+    assert!(address_txs.iter().any(|tx| tx.txid == funding_txid));
+    let channel = services.list_channels().await.unwrap().pop().unwrap();
+    let splice_amount = Amount::from_sat(50_000);
+    services
+        .splice_in(channel.channel_id.clone(), splice_amount.to_sat())
+        .await
+        .unwrap();
+    info!("Waiting for BreezEvent::SplicePending...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::SplicePending { channel_id, amount_sat, .. })
+            if channel_id == channel.channel_id && amount_sat == splice_amount.to_sat() as i64
+    ));
+    info!("Waiting for BreezEvent::SpliceCompleted...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        std::iter::from_fn(|| events.try_recv().ok())
+            .any(|e| matches!(e, BreezEvent::SpliceCompleted { .. }))
+    });
+    let channels = services.list_channels().await.unwrap();
+    assert!(
+        channels
+            .iter()
+            .any(|c| c.capacity_sat == channel.capacity_sat + splice_amount.to_sat())
+    );
+
+    // Splicing funds out of the channel to an external address.
+    let splice_out_address = env.bitcoind_new_address().await.unwrap();
+    let splice_out_amount = Amount::from_sat(10_000);
+    services
+        .splice_out(
+            channel.channel_id.clone(),
+            splice_out_amount.to_sat(),
+            splice_out_address.to_string(),
+        )
+        .await
+        .unwrap();
+    info!("Waiting for the spliced out funds to arrive...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        env.bitcoind_received_by_address(&splice_out_address)
+            .await
+            .unwrap()
+            == splice_out_amount
+    });
+    let channels = services.list_channels().await.unwrap();
+    assert!(channels.iter().any(|c| c.channel_id == channel.channel_id));
+
+    // Closing all channels cooperatively to an external address.
+    let close_to_address = env.bitcoind_new_address().await.unwrap();
+    services
+        .close_all_channels(Some(close_to_address.to_string()), false)
+        .await
+        .unwrap();
+    info!("Waiting for the closing transaction to confirm...");
+    let mut blocks_remaining = None;
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        blocks_remaining = services
+            .pending_close_status(channel.channel_id.clone())
+            .await
+            .unwrap()
+            .and_then(|status| status.blocks_remaining());
+        blocks_remaining.is_some_and(|blocks| blocks > 1)
+    });
+    env.generate_blocks(1).await.unwrap();
+    info!("Waiting for the confirmation countdown to decrease...");
+    wait_for!(
+        services
+            .pending_close_status(channel.channel_id.clone())
+            .await
+            .unwrap()
+            .and_then(|status| status.blocks_remaining())
+            < blocks_remaining
+    );
+    info!("Waiting for the funds to be swept to the close-to address...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await;
+        env.bitcoind_received_by_address(&close_to_address)
+            .await
+            .unwrap()
+            > Amount::ZERO
+    });
+
+    // The closed channel is reported as a payment along with its closing transaction.
+    services.sync().await.unwrap();
+    let payments = services
+        .list_payments(ListPaymentsRequest {
+            filters: Some(vec![PaymentTypeFilter::ClosedChannel]),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let payment = payments
+        .iter()
+        .find(|p| p.id == channel.channel_id)
+        .unwrap();
+    assert_eq!(payment.payment_type, PaymentType::ClosedChannel);
+    let PaymentDetails::ClosedChannel { data } = &payment.details else {
+        panic!("Unexpected payment details: {:?}", payment.details);
+    };
+    assert!(data.closing_txid.is_some());
+    assert_eq!(data.counterparty_node_id, channel.counterparty_node_id);
+    assert!(!data.is_force_close);
+
+    let node_state = services.node_info().await;
+    assert_eq!(node_state.channels_balance_msat, 0);
+    assert_eq!(node_state.onchain_balance_msat, 0);
+
+    // Disconnecting from the LSP.
+    let (lsp_id, _) = config.lsps2_address.split_once('@').unwrap();
+    assert!(node_state.connected_peers.iter().any(|p| p == lsp_id));
+    services.disconnect_peer(lsp_id.to_string()).await.unwrap();
+    let node_state = services.node_info().await;
+    assert!(!node_state.connected_peers.iter().any(|p| p == lsp_id));
+    assert!(services.disconnect_peer(lsp_id.to_string()).await.is_err());
+
     services.disconnect().await.unwrap();
     drop(services);
     assert!(events.is_closed());
@@ -400,12 +723,1676 @@ async fn test_node_receive_payments() {
     assert!(events.is_closed());
 }
 
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_send_open_amount_fee() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat: 10_000_000,
+            description: "JIT".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    lnd.pay(response.ln_invoice.bolt11).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+
+    // Paying an open amount invoice, the routing fee is paid on top of the given amount.
+    let balance_before_msat = services.node_info().await.channels_balance_msat;
+    let bolt11 = lnd.receive(&Amount::ZERO).await.unwrap();
+    let amount = Amount::from_sat(1100);
+    let payment = services
+        .send_payment(SendPaymentRequest {
+            bolt11,
+            amount_msat: Some(amount.to_msat()),
+            max_fee_msat: None,
+        })
+        .await
+        .unwrap()
+        .payment;
+    assert_eq!(payment.amount_msat, amount.to_msat());
+    assert!(payment.fee_msat > 0);
+    info!("Waiting for BreezEvent::PaymentSucceed...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::PaymentSucceed { .. })
+    ));
+    let balance_after_msat = services.node_info().await.channels_balance_msat;
+    assert_eq!(
+        balance_before_msat - balance_after_msat,
+        payment.amount_msat + payment.fee_msat
+    );
+    let payments = services.list_payments(Default::default()).await.unwrap();
+    let listed = payments.first().unwrap();
+    assert_eq!(listed.amount_msat, payment.amount_msat);
+    assert_eq!(listed.fee_msat, payment.fee_msat);
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_probe_not_listed() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat: 10_000_000,
+            description: "JIT".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    lnd.pay(response.ln_invoice.bolt11).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+    let payments = services.list_payments(Default::default()).await.unwrap();
+    assert_eq!(payments.len(), 1);
+
+    let bolt11 = lnd.receive(&Amount::from_sat(1000)).await.unwrap();
+    let response = services
+        .prepare_send_payment(PrepareSendPaymentRequest {
+            bolt11,
+            amount_msat: None,
+            probe: Some(true),
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.route_found, Some(true));
+    // Letting the probes complete.
+    sleep(SECOND).await;
+    services.sync().await.unwrap();
+    let probed_payments = services.list_payments(Default::default()).await.unwrap();
+    assert_eq!(probed_payments, payments);
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_lsp_routing_hints() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat: 10_000_000,
+            description: "JIT".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    lnd.pay(response.ln_invoice.bolt11).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+
+    let lsp_info = services.lsp_info().await.unwrap();
+    info!("Waiting for a routing hint through the LSP channel...");
+    let mut hops = Vec::new();
+    wait_for!({
+        let response = services
+            .receive_payment(ReceivePaymentRequest {
+                amount_msat: 1_000_000,
+                description: "Hint".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.opening_fee_msat, None);
+        hops = response
+            .ln_invoice
+            .routing_hints
+            .into_iter()
+            .flat_map(|hint| hint.hops)
+            .filter(|hop| hop.src_node_id == lsp_info.pubkey)
+            .collect();
+        !hops.is_empty()
+    });
+    // The LSP info reflects the parameters announced by the LSP for the channel.
+    let lsp_info = services.lsp_info().await.unwrap();
+    for hop in hops {
+        assert_eq!(i64::from(hop.fees_base_msat), lsp_info.base_fee_msat);
+        assert_eq!(
+            f64::from(hop.fees_proportional_millionths) / 1_000_000.0,
+            lsp_info.fee_rate
+        );
+        assert_eq!(hop.cltv_expiry_delta, u64::from(lsp_info.time_lock_delta));
+    }
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_receive_mpp() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // Opening two channels with JIT payments exceeding the inbound liquidity.
+    let mut inbound_msats = Vec::new();
+    let mut amount_msat = 10_000_000;
+    for _ in 0..2 {
+        let response = services
+            .receive_payment(ReceivePaymentRequest {
+                amount_msat,
+                description: "JIT".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert!(response.opening_fee_msat.is_some());
+        lnd.pay(response.ln_invoice.bolt11).await.unwrap();
+        info!("Waiting for BreezEvent::InvoicePaid...");
+        wait_for!(matches!(
+            events.recv().await,
+            Some(BreezEvent::InvoicePaid { .. })
+        ));
+        let total_inbound_msat = services.node_info().await.total_inbound_liquidity_msats;
+        inbound_msats.push(total_inbound_msat - inbound_msats.iter().sum::<u64>());
+        amount_msat = total_inbound_msat + 1_000_000;
+    }
+
+    // Receiving a payment larger than the inbound liquidity of either channel,
+    // but smaller than their sum, without a new channel.
+    let largest_inbound_msat = *inbound_msats.iter().max().unwrap();
+    let smallest_inbound_msat = *inbound_msats.iter().min().unwrap();
+    let amount_msat = largest_inbound_msat + smallest_inbound_msat / 2;
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat,
+            description: "MPP".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(response.opening_fee_msat, None);
+    lnd.pay_mpp(response.ln_invoice.bolt11, 16).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+    let payments = services.list_payments(Default::default()).await.unwrap();
+    let payment = payments.first().cloned().unwrap();
+    assert_eq!(payment.amount_msat, amount_msat);
+    assert_eq!(payment.fee_msat, 0);
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_receive_after_lsp_disconnect() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+    let (lsp_id, _) = config.lsps2_address.split_once('@').unwrap();
+    let lsp_id = lsp_id.to_string();
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    info!("Waiting for BreezEvent::Synced...");
+    wait_for!(matches!(events.recv().await, Some(BreezEvent::Synced)));
+
+    // The LSP is reconnected before the JIT receive instead of failing it.
+    services.disconnect_peer(lsp_id.clone()).await.unwrap();
+    let node_state = services.node_info().await;
+    assert!(!node_state.connected_peers.contains(&lsp_id));
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat: 10_000_000,
+            description: "JIT after disconnect".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(response.opening_fee_msat.is_some());
+    let node_state = services.node_info().await;
+    assert!(node_state.connected_peers.contains(&lsp_id));
+
+    lnd.pay(response.ln_invoice.bolt11).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_ensure_lsp_connected() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+    let (lsp_id, _) = config.lsps2_address.split_once('@').unwrap();
+    let lsp_id = lsp_id.to_string();
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    info!("Waiting for BreezEvent::Synced...");
+    wait_for!(matches!(events.recv().await, Some(BreezEvent::Synced)));
+
+    // Losing the connection, as when the device goes offline.
+    services.disconnect_peer(lsp_id.clone()).await.unwrap();
+    let node_state = services.node_info().await;
+    assert!(!node_state.connected_peers.contains(&lsp_id));
+
+    assert_eq!(services.ensure_lsp_connected().await.unwrap(), lsp_id);
+    let node_state = services.node_info().await;
+    assert!(node_state.connected_peers.contains(&lsp_id));
+    // Calling it again while connected is fine.
+    assert_eq!(services.ensure_lsp_connected().await.unwrap(), lsp_id);
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_cancel_jit_invoice() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    info!("Waiting for BreezEvent::Synced...");
+    wait_for!(matches!(events.recv().await, Some(BreezEvent::Synced)));
+
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat: 10_000_000,
+            description: "Cancelled JIT".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert!(response.opening_fee_msat.is_some());
+    let payment_hash = response.ln_invoice.payment_hash;
+    services
+        .cancel_jit_invoice(payment_hash.clone())
+        .await
+        .unwrap();
+    // Cancelling again, like cancelling an unknown invoice, does nothing.
+    services
+        .cancel_jit_invoice(payment_hash.clone())
+        .await
+        .unwrap();
+
+    // The payment is no longer expected, so it is rejected.
+    let res = lnd.pay(response.ln_invoice.bolt11).await;
+    assert!(res.is_err());
+    let payments = services.list_payments(Default::default()).await.unwrap();
+    assert!(payments.is_empty());
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_receive_register_notifications() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    info!("Waiting for BreezEvent::Synced...");
+    wait_for!(matches!(events.recv().await, Some(BreezEvent::Synced)));
+
+    // The LSP does not offer payment notifications, so no webhook can be registered.
+    let res = services
+        .register_webhook("https://example.com/webhook".to_string())
+        .await;
+    assert!(res.is_err());
+
+    let req = ReceivePaymentRequest {
+        amount_msat: 10_000_000,
+        description: "Notified".to_string(),
+        preimage: Some(rand::rng().random::<[u8; 32]>().to_vec()),
+        register_notifications: Some(true),
+        ..Default::default()
+    };
+    let res = services.receive_payment(req.clone()).await;
+    assert!(res.is_err());
+
+    // No invoice was created for the preimage, so it can still be used.
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            register_notifications: None,
+            ..req
+        })
+        .await
+        .unwrap();
+    services
+        .delete_invoice(response.ln_invoice.bolt11)
+        .await
+        .unwrap();
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_payment_status() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    let unknown_hash = "00".repeat(32);
+    assert_eq!(services.payment_status(unknown_hash).await.unwrap(), None);
+
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat: 10_000_000,
+            description: "Polled".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let payment_hash = response.ln_invoice.payment_hash;
+    assert_eq!(
+        services.payment_status(payment_hash.clone()).await.unwrap(),
+        Some(PaymentStatus::Pending)
+    );
+
+    lnd.pay(response.ln_invoice.bolt11).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+    assert_eq!(
+        services.payment_status(payment_hash).await.unwrap(),
+        Some(PaymentStatus::Complete)
+    );
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_receive_after_restart() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let seed = rand::rng().random::<[u8; 64]>().to_vec();
+    let req = ConnectRequest {
+        config: config.clone(),
+        seed: seed.clone(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // Creating an invoice and restarting the node before it is paid.
+    let amount_msat = 10_000_000;
+    let response = services
+        .receive_payment(ReceivePaymentRequest {
+            amount_msat,
+            description: "Restart".to_string(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    let opening_fee_msat = response.opening_fee_msat.unwrap_or_default();
+    let invoice = response.ln_invoice;
+    services.disconnect().await.unwrap();
+    drop(services);
+
+    info!("Restarting the node");
+    let req = ConnectRequest {
+        config: config.clone(),
+        seed: seed.clone(),
+        restore_only: Some(true),
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    lnd.pay(invoice.bolt11.clone()).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    let details = loop {
+        match events.recv().await {
+            Some(BreezEvent::InvoicePaid { details }) => break details,
+            Some(_) => continue,
+            None => panic!("Event listener closed"),
+        }
+    };
+    assert_eq!(details.payment_hash, invoice.payment_hash);
+    assert_eq!(details.bolt11, invoice.bolt11);
+    let payment = details.payment.unwrap();
+    assert_eq!(payment.payment_type, PaymentType::Received);
+    assert_eq!(payment.amount_msat, amount_msat - opening_fee_msat);
+    assert_eq!(payment.description.unwrap(), "Restart");
+    services.disconnect().await.unwrap();
+    drop(services);
+
+    info!("Restarting the node with an unreachable chain source");
+    config.esplora_url = "http://127.0.0.1:1".to_string();
+    let req = ConnectRequest {
+        config,
+        seed,
+        restore_only: Some(true),
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    let node_state = services.node_info().await;
+    assert!(node_state.sync_warning.is_some());
+    let payments = services.list_payments(Default::default()).await.unwrap();
+    assert!(
+        payments
+            .iter()
+            .any(|p| p.id == payment.id && p.status == PaymentStatus::Complete)
+    );
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_app_data() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().join("first").to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let seed = rand::rng().random::<[u8; 64]>().to_vec();
+    let req = ConnectRequest {
+        config: config.clone(),
+        seed: seed.clone(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    let key = "last_notification_id".to_string();
+    assert_eq!(services.app_kv_get(key.clone()).await.unwrap(), None);
+    services
+        .app_kv_set(key.clone(), b"42".to_vec())
+        .await
+        .unwrap();
+    services
+        .app_kv_set("to_delete".to_string(), b"value".to_vec())
+        .await
+        .unwrap();
+    services
+        .app_kv_delete("to_delete".to_string())
+        .await
+        .unwrap();
+    assert!(
+        services
+            .app_kv_set("too_large".to_string(), vec![0; 8 * 1024 + 1])
+            .await
+            .is_err()
+    );
+    services.disconnect().await.unwrap();
+    drop(services);
+
+    info!("Restoring the node in a new working dir from VSS");
+    config.working_dir = testdir!().join("restored").to_string_lossy().to_string();
+    let req = ConnectRequest {
+        config,
+        seed,
+        restore_only: Some(true),
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    assert_eq!(
+        services.app_kv_get(key).await.unwrap(),
+        Some(b"42".to_vec())
+    );
+    assert_eq!(
+        services.app_kv_get("to_delete".to_string()).await.unwrap(),
+        None
+    );
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_chain_reorg() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    info!("Waiting for BreezEvent::Synced...");
+    wait_for!(matches!(events.recv().await, Some(BreezEvent::Synced)));
+    // Give the node a poll of its tip before replacing it.
+    sleep(15 * SECOND).await;
+
+    env.reorg_tip().await.unwrap();
+    info!("Waiting for BreezEvent::ChainReorg...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::ChainReorg { .. })
+    ));
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_connectivity_check() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let connect = |config: Config, working_dir: &str| {
+        let mut config = config;
+        config.working_dir = working_dir.to_string();
+        let req = ConnectRequest {
+            config,
+            seed: rand::rng().random::<[u8; 64]>().to_vec(),
+            restore_only: None,
+        };
+        async move {
+            let (tx, mut events) = mpsc::channel(100);
+            let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+                .await
+                .unwrap();
+            info!("Waiting for BreezEvent::Synced...");
+            wait_for!(matches!(events.recv().await, Some(BreezEvent::Synced)));
+            services
+        }
+    };
+    let test_dir = testdir!();
+
+    let services = connect(
+        config.clone(),
+        &test_dir.join("reachable").to_string_lossy(),
+    )
+    .await;
+    let result = services.connectivity_check().await.unwrap();
+    for (name, status) in [
+        ("vss", &result.vss),
+        ("chain source", &result.chain_source),
+        ("rgs", &result.rgs),
+        ("lsp", &result.lsp),
+    ] {
+        assert!(
+            status.reachable,
+            "{name} is unreachable: {:?}",
+            status.error
+        );
+        assert_eq!(status.error, None);
+    }
+    services.disconnect().await.unwrap();
+
+    // Nothing listens on the port.
+    config.rgs_url = "http://127.0.0.1:1/v2".to_string();
+    let services = connect(config, &test_dir.join("dead_rgs").to_string_lossy()).await;
+    let result = services.connectivity_check().await.unwrap();
+    assert!(!result.rgs.reachable);
+    assert!(result.rgs.error.is_some());
+    assert!(result.vss.reachable);
+    assert!(result.chain_source.reachable);
+    assert!(result.lsp.reachable);
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_preopen_channel() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let seed = rand::rng().random::<[u8; 64]>().to_vec();
+    let req = ConnectRequest {
+        config,
+        seed,
+        restore_only: None,
+    };
+    let (tx, mut events) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    assert_eq!(services.node_info().await.total_inbound_liquidity_msats, 0);
+
+    let inbound_msat = 50_000_000;
+    let response = services.preopen_channel(inbound_msat).await.unwrap();
+    // The LSP over-provisions the channel by the whole forwarded amount.
+    assert_eq!(response.channel_size_msat, 2 * inbound_msat);
+    lnd.pay(response.ln_invoice.bolt11).await.unwrap();
+    info!("Waiting for BreezEvent::InvoicePaid...");
+    wait_for!(matches!(
+        events.recv().await,
+        Some(BreezEvent::InvoicePaid { .. })
+    ));
+
+    let channels = services.list_channels().await.unwrap();
+    assert_eq!(channels.len(), 1);
+    assert_eq!(channels[0].capacity_sat * 1000, response.channel_size_msat);
+    // The reserve of the LSP and the commitment fee it pays cannot be received.
+    let node_state = services.node_info().await;
+    assert!(node_state.total_inbound_liquidity_msats >= inbound_msat * 9 / 10);
+    assert!(node_state.total_inbound_liquidity_msats < inbound_msat);
+    assert_eq!(node_state.channels_balance_msat, inbound_msat);
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_lsp_channel_terms() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let seed = rand::rng().random::<[u8; 64]>().to_vec();
+    let req = ConnectRequest {
+        config,
+        seed,
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // The values configured in lsps2-server/src/main.rs.
+    let lsp_info = services.lsp_info().await.unwrap();
+    assert_eq!(lsp_info.channel_over_provisioning_ppm, 1_000_000);
+    assert_eq!(lsp_info.min_channel_lifetime, 100_000);
+    assert_eq!(lsp_info.max_client_to_self_delay, 10_000);
+    for params in lsp_info.opening_fee_params_list.values {
+        assert_eq!(
+            params.max_client_to_self_delay,
+            lsp_info.max_client_to_self_delay
+        );
+    }
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_abort_channel_open() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // Funding the on-chain wallet.
+    let address = services.new_onchain_address().await.unwrap();
+    let address = Address::from_str(&address).unwrap().assume_checked();
+    let funding_amount = Amount::from_sat(100_000);
+    env.bitcoind_fund_address(&address, funding_amount)
+        .await
+        .unwrap();
+    info!("Waiting for the on-chain funds to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await.onchain_balance_msat > 0
+    });
+
+    // Opening a channel and aborting it before the funding transaction confirms.
+    let address = format!(
+        "{}@{}",
+        lnd.get_id().await.unwrap(),
+        lnd.lightning_api.external_address()
+    );
+    let res = services
+        .pay_node_address(PayNodeAddressRequest {
+            address,
+            amount_msat: 0,
+            open_channel_sat: Some(50_000),
+        })
+        .await
+        .unwrap();
+    assert!(matches!(res, PayNodeAddressResponse::ChannelOpened));
+    info!("Waiting for the channel to be pending...");
+    wait_for!(
+        services
+            .list_pending_channels()
+            .await
+            .unwrap()
+            .iter()
+            .any(|c| c.funding_txid.is_some())
+    );
+    let channel = services
+        .list_pending_channels()
+        .await
+        .unwrap()
+        .pop()
+        .unwrap();
+    services
+        .abort_channel_open(channel.channel_id.clone())
+        .await
+        .unwrap();
+
+    info!("Waiting for the funds to return to the on-chain wallet...");
+    wait_for!({
+        env.generate_blocks(10).await.unwrap();
+        services.list_channels().await.unwrap().is_empty()
+            && services.node_info().await.onchain_balance_msat > funding_amount.to_msat() * 9 / 10
+    });
+    assert!(services.list_pending_channels().await.unwrap().is_empty());
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_close_channels_to_addresses() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // Funding the on-chain wallet.
+    let address = services.new_onchain_address().await.unwrap();
+    let address = Address::from_str(&address).unwrap().assume_checked();
+    env.bitcoind_fund_address(&address, Amount::from_sat(200_000))
+        .await
+        .unwrap();
+    info!("Waiting for the on-chain funds to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await.onchain_balance_msat > 0
+    });
+
+    // Opening two channels of different capacities.
+    let address = format!(
+        "{}@{}",
+        lnd.get_id().await.unwrap(),
+        lnd.lightning_api.external_address()
+    );
+    for capacity_sat in [60_000, 40_000] {
+        let res = services
+            .pay_node_address(PayNodeAddressRequest {
+                address: address.clone(),
+                amount_msat: 0,
+                open_channel_sat: Some(capacity_sat),
+            })
+            .await
+            .unwrap();
+        assert!(matches!(res, PayNodeAddressResponse::ChannelOpened));
+    }
+    info!("Waiting for the channels to be ready...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        let channels = services.list_channels().await.unwrap();
+        channels.len() == 2 && channels.iter().all(|c| c.is_usable)
+    });
+
+    // Closing each channel to its own address.
+    let mut close_to_addresses = Vec::new();
+    for channel in services.list_channels().await.unwrap() {
+        let close_to_address = env.bitcoind_new_address().await.unwrap();
+        services
+            .close_channel(
+                channel.channel_id.clone(),
+                Some(close_to_address.to_string()),
+                false,
+            )
+            .await
+            .unwrap();
+        close_to_addresses.push((close_to_address, Amount::from_sat(channel.capacity_sat)));
+    }
+    info!("Waiting for the funds to be swept to the close-to addresses...");
+    for (close_to_address, capacity) in close_to_addresses {
+        wait_for!({
+            env.generate_blocks(1).await.unwrap();
+            services.node_info().await;
+            env.bitcoind_received_by_address(&close_to_address)
+                .await
+                .unwrap()
+                > Amount::ZERO
+        });
+        // The funds of the channel less the fees of the closing and sweeping transactions.
+        let received = env
+            .bitcoind_received_by_address(&close_to_address)
+            .await
+            .unwrap();
+        assert!(received <= capacity);
+        assert!(received.to_sat() > capacity.to_sat() * 9 / 10);
+    }
+    assert!(services.list_channels().await.unwrap().is_empty());
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_rebroadcast_pending() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let seed = rand::rng().random::<[u8; 64]>().to_vec();
+    let req = ConnectRequest {
+        config: config.clone(),
+        seed: seed.clone(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // Funding the on-chain wallet.
+    let address = services.new_onchain_address().await.unwrap();
+    let address = Address::from_str(&address).unwrap().assume_checked();
+    env.bitcoind_fund_address(&address, Amount::from_sat(100_000))
+        .await
+        .unwrap();
+    info!("Waiting for the on-chain funds to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await.onchain_balance_msat > 0
+    });
+    assert!(services.rebroadcast_pending().await.unwrap().is_empty());
+
+    // Opening a channel, whose funding transaction is left unconfirmed.
+    let address = format!(
+        "{}@{}",
+        lnd.get_id().await.unwrap(),
+        lnd.lightning_api.external_address()
+    );
+    let res = services
+        .pay_node_address(PayNodeAddressRequest {
+            address,
+            amount_msat: 0,
+            open_channel_sat: Some(50_000),
+        })
+        .await
+        .unwrap();
+    assert!(matches!(res, PayNodeAddressResponse::ChannelOpened));
+    let mut txids = Vec::new();
+    info!("Waiting for the funding transaction to be broadcast...");
+    wait_for!({
+        services.sync().await.unwrap();
+        txids = services.rebroadcast_pending().await.unwrap();
+        !txids.is_empty()
+    });
+    services.disconnect().await.unwrap();
+    drop(services);
+
+    info!("Restarting the node");
+    let req = ConnectRequest {
+        config,
+        seed,
+        restore_only: Some(true),
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    assert_eq!(services.rebroadcast_pending().await.unwrap(), txids);
+
+    info!("Waiting for the rebroadcast transaction to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        let channels = services.list_channels().await.unwrap();
+        channels.len() == 1 && channels[0].is_usable
+    });
+    services.sync().await.unwrap();
+    assert!(services.rebroadcast_pending().await.unwrap().is_empty());
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_graph_status() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, _lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd_with_channel(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    // Nothing listens there, so the graph is never synced.
+    config.rgs_url = "http://127.0.0.1:1/v2".to_string();
+    config.lsps2_address = lsp;
+
+    let seed = rand::rng().random::<[u8; 64]>().to_vec();
+    let req = ConnectRequest {
+        config: config.clone(),
+        seed: seed.clone(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    let status = services.graph_status().await.unwrap();
+    assert_eq!(status.node_count, 0);
+    assert_eq!(status.channel_count, 0);
+    assert_eq!(status.latest_rgs_snapshot_timestamp, None);
+    services.disconnect().await.unwrap();
+    drop(services);
+
+    info!("Restarting the node with RGS");
+    config.rgs_url = rgs.external_endpoint();
+    let req = ConnectRequest {
+        config,
+        seed,
+        restore_only: Some(true),
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+    info!("Waiting for the graph to be synced...");
+    wait_for!(
+        services
+            .graph_status()
+            .await
+            .unwrap()
+            .latest_rgs_snapshot_timestamp
+            .is_some()
+    );
+    let status = services.graph_status().await.unwrap();
+    assert!(status.node_count > 0);
+    assert!(status.channel_count > 0);
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_fee_estimates() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    let estimates = services.fee_estimates().await.unwrap();
+    assert!(estimates.low_sat_per_vbyte > 0);
+    assert!(estimates.normal_sat_per_vbyte >= estimates.low_sat_per_vbyte);
+    assert!(estimates.high_sat_per_vbyte >= estimates.normal_sat_per_vbyte);
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_pending_channel_confirmations() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // Funding the on-chain wallet.
+    let address = services.new_onchain_address().await.unwrap();
+    let address = Address::from_str(&address).unwrap().assume_checked();
+    env.bitcoind_fund_address(&address, Amount::from_sat(100_000))
+        .await
+        .unwrap();
+    info!("Waiting for the on-chain funds to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await.onchain_balance_msat > 0
+    });
+
+    let address = format!(
+        "{}@{}",
+        lnd.get_id().await.unwrap(),
+        lnd.lightning_api.external_address()
+    );
+    let res = services
+        .pay_node_address(PayNodeAddressRequest {
+            address,
+            amount_msat: 0,
+            open_channel_sat: Some(50_000),
+        })
+        .await
+        .unwrap();
+    assert!(matches!(res, PayNodeAddressResponse::ChannelOpened));
+    info!("Waiting for the funding transaction to be broadcast...");
+    wait_for!({
+        services.sync().await.unwrap();
+        !services.rebroadcast_pending().await.unwrap().is_empty()
+    });
+    let channels = services.list_pending_channels().await.unwrap();
+    assert_eq!(channels.len(), 1);
+    let required = channels[0].confirmations_required.unwrap();
+    assert!(required > 1);
+    assert_eq!(channels[0].blocks_until_ready(), Some(required));
+
+    // Counting down the confirmations required by the channel acceptor.
+    for confirmations in 1..required {
+        env.generate_blocks(1).await.unwrap();
+        let mut channels = Vec::new();
+        wait_for!({
+            services.sync().await.unwrap();
+            channels = services.list_pending_channels().await.unwrap();
+            channels[0].confirmations == Some(confirmations)
+        });
+        assert_eq!(
+            channels[0].blocks_until_ready(),
+            Some(required - confirmations)
+        );
+    }
+    env.generate_blocks(1).await.unwrap();
+    info!("Waiting for the channel to be ready...");
+    wait_for!({
+        services.sync().await.unwrap();
+        services.list_pending_channels().await.unwrap().is_empty()
+    });
+    let channels = services.list_channels().await.unwrap();
+    assert_eq!(channels[0].blocks_until_ready(), Some(0));
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_node_force_close_pending_sweeps() {
+    let env = Environment::default();
+    let (esplora, mempool, vss, lsp, lnd, rgs) = try_join!(
+        env.esplora_api(),
+        env.mempool_api(),
+        env.vss_api(),
+        env.lsp_external_address(),
+        env.lnd(),
+        env.rgs()
+    )
+    .unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.working_dir = testdir!().to_string_lossy().to_string();
+    config.mempoolspace_url = Some(mempool.external_endpoint());
+    config.esplora_url = esplora.external_endpoint();
+    config.vss_url = vss.external_endpoint();
+    config.rgs_url = rgs.external_endpoint();
+    config.lsps2_address = lsp;
+
+    let req = ConnectRequest {
+        config,
+        seed: rand::rng().random::<[u8; 64]>().to_vec(),
+        restore_only: None,
+    };
+    let (tx, _) = mpsc::channel(100);
+    let services = BreezServices::connect(req, Box::new(EventListenerImpl::new(tx)))
+        .await
+        .unwrap();
+
+    // Funding the on-chain wallet.
+    let address = services.new_onchain_address().await.unwrap();
+    let address = Address::from_str(&address).unwrap().assume_checked();
+    env.bitcoind_fund_address(&address, Amount::from_sat(100_000))
+        .await
+        .unwrap();
+    info!("Waiting for the on-chain funds to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await.onchain_balance_msat > 0
+    });
+
+    // Opening a channel and force-closing it, which leaves the funds to be swept.
+    let address = format!(
+        "{}@{}",
+        lnd.get_id().await.unwrap(),
+        lnd.lightning_api.external_address()
+    );
+    let res = services
+        .pay_node_address(PayNodeAddressRequest {
+            address,
+            amount_msat: 0,
+            open_channel_sat: Some(50_000),
+        })
+        .await
+        .unwrap();
+    assert!(matches!(res, PayNodeAddressResponse::ChannelOpened));
+    info!("Waiting for the channel to be ready...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services
+            .list_channels()
+            .await
+            .unwrap()
+            .iter()
+            .any(|c| c.is_usable)
+    });
+    let channel = services.list_channels().await.unwrap().pop().unwrap();
+    assert!(services.list_pending_sweeps().await.unwrap().is_empty());
+    services
+        .close_channel(channel.channel_id.clone(), None, true)
+        .await
+        .unwrap();
+
+    info!("Waiting for the funds of the channel to be swept...");
+    let mut sweeps = Vec::new();
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await;
+        sweeps = services.list_pending_sweeps().await.unwrap();
+        !sweeps.is_empty()
+    });
+    let sweep = sweeps.pop().unwrap();
+    assert!(sweeps.is_empty());
+    assert_eq!(sweep.channel_id, Some(channel.channel_id.clone()));
+    assert!(sweep.amount_sat > 0);
+    assert!(sweep.amount_sat < channel.capacity_sat);
+    info!("Waiting for the sweeping transaction to confirm...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await;
+        services
+            .list_pending_sweeps()
+            .await
+            .unwrap()
+            .iter()
+            .any(|s| {
+                s.stage == PendingSweepStage::AwaitingThresholdConfirmations && s.txid.is_some()
+            })
+    });
+    info!("Waiting for the swept funds to be spendable...");
+    wait_for!({
+        env.generate_blocks(1).await.unwrap();
+        services.node_info().await;
+        services.list_pending_sweeps().await.unwrap().is_empty()
+    });
+    assert!(services.node_info().await.onchain_balance_msat > 0);
+
+    services.disconnect().await.unwrap();
+}
+
+#[rstest]
+#[tokio::test(flavor = "multi_thread")]
+#[test_log::test]
+async fn test_check_vss() {
+    let env = Environment::default();
+    let vss = env.vss_api().await.unwrap();
+
+    let mut config = Config::regtest(String::new());
+    config.vss_url = vss.external_endpoint();
+    let seed = rand::rng().random::<[u8; 64]>().to_vec();
+    let result = BreezServices::check_vss(config.clone(), seed.clone())
+        .await
+        .unwrap();
+    assert_eq!(result, VssCheckResult::Ok);
+
+    config.vss_url = "http://127.0.0.1:1".to_string();
+    let result = BreezServices::check_vss(config, seed).await.unwrap();
+    assert!(matches!(result, VssCheckResult::ConnectivityFailure { .. }));
+}
+
 trait Msats {
     fn to_msat(&self) -> u64;
 }
 
 impl Msats for Amount {
     fn to_msat(&self) -> u64 {
-        self.to_sat() * 1000
+        self.to_sat()
+            .checked_mul(1000)
+            .expect("Amount overflows u64 in msat")
     }
 }
@@ -293,6 +293,9 @@ fun asClosedChannelPaymentDetails(closedChannelPaymentDetails: ReadableMap): Clo
             arrayOf(
                 "state",
                 "fundingTxid",
+                "counterpartyNodeId",
+                "capacitySat",
+                "isForceClose",
             ),
         )
     ) {
@@ -320,7 +323,10 @@ fun asClosedChannelPaymentDetails(closedChannelPaymentDetails: ReadableMap): Clo
         } else {
             null
         }
-    return ClosedChannelPaymentDetails(state, fundingTxid, shortChannelId, closingTxid)
+    val counterpartyNodeId = closedChannelPaymentDetails.getString("counterpartyNodeId")!!
+    val capacitySat = closedChannelPaymentDetails.getDouble("capacitySat").toULong()
+    val isForceClose = closedChannelPaymentDetails.getBoolean("isForceClose")
+    return ClosedChannelPaymentDetails(state, fundingTxid, shortChannelId, closingTxid, counterpartyNodeId, capacitySat, isForceClose)
 }
 
 fun readableMapOf(closedChannelPaymentDetails: ClosedChannelPaymentDetails): ReadableMap =
@@ -329,6 +335,9 @@ fun readableMapOf(closedChannelPaymentDetails: ClosedChannelPaymentDetails): Rea
         "fundingTxid" to closedChannelPaymentDetails.fundingTxid,
         "shortChannelId" to closedChannelPaymentDetails.shortChannelId,
         "closingTxid" to closedChannelPaymentDetails.closingTxid,
+        "counterpartyNodeId" to closedChannelPaymentDetails.counterpartyNodeId,
+        "capacitySat" to closedChannelPaymentDetails.capacitySat,
+        "isForceClose" to closedChannelPaymentDetails.isForceClose,
     )
 
 fun asClosedChannelPaymentDetailsList(arr: ReadableArray): List<ClosedChannelPaymentDetails> {
@@ -357,6 +366,19 @@ fun asConfig(config: ReadableMap): Config? {
                 "paymentTimeoutSec",
                 "maxfeePercent",
                 "exemptfeeMsat",
+                "paymentRetries",
+                "remoteLockLeaseSecs",
+                "remoteLockRetrySecs",
+                "connectTimeoutSecs",
+                "anchorChannelsEnabled",
+                "allowStateRegression",
+                "minSatPerVbyte",
+                "maxSatPerVbyte",
+                "syncRetries",
+                "maxJitChannels",
+                "sweepConfirmationTarget",
+                "maxStoreValueBytes",
+                "restrictFilePermissions",
             ),
         )
     ) {
@@ -375,6 +397,20 @@ fun asConfig(config: ReadableMap): Config? {
     val apiKey = if (hasNonNullKey(config, "apiKey")) config.getString("apiKey") else null
     val maxfeePercent = config.getDouble("maxfeePercent")
     val exemptfeeMsat = config.getDouble("exemptfeeMsat").toULong()
+    val paymentRetries = config.getInt("paymentRetries").toUInt()
+    val remoteLockLeaseSecs = config.getInt("remoteLockLeaseSecs").toUInt()
+    val remoteLockRetrySecs = config.getInt("remoteLockRetrySecs").toUInt()
+    val connectTimeoutSecs = config.getInt("connectTimeoutSecs").toUInt()
+    val anchorChannelsEnabled = config.getBoolean("anchorChannelsEnabled")
+    val allowStateRegression = config.getBoolean("allowStateRegression")
+    val minSatPerVbyte = config.getInt("minSatPerVbyte").toUInt()
+    val maxSatPerVbyte = config.getInt("maxSatPerVbyte").toUInt()
+    val syncRetries = config.getInt("syncRetries").toUInt()
+    val maxJitChannels = config.getInt("maxJitChannels").toUInt()
+    val sweepConfirmationTarget = config.getInt("sweepConfirmationTarget").toUInt()
+    val maxStoreValueBytes = config.getInt("maxStoreValueBytes").toUInt()
+    val vssClientId = if (hasNonNullKey(config, "vssClientId")) config.getString("vssClientId") else null
+    val restrictFilePermissions = config.getBoolean("restrictFilePermissions")
     return Config(
         breezserver,
         chainnotifierUrl,
@@ -389,6 +425,20 @@ fun asConfig(config: ReadableMap): Config? {
         apiKey,
         maxfeePercent,
         exemptfeeMsat,
+        paymentRetries,
+        remoteLockLeaseSecs,
+        remoteLockRetrySecs,
+        connectTimeoutSecs,
+        anchorChannelsEnabled,
+        allowStateRegression,
+        minSatPerVbyte,
+        maxSatPerVbyte,
+        syncRetries,
+        maxJitChannels,
+        sweepConfirmationTarget,
+        maxStoreValueBytes,
+        vssClientId,
+        restrictFilePermissions,
     )
 }
 
@@ -407,6 +457,20 @@ fun readableMapOf(config: Config): ReadableMap =
         "apiKey" to config.apiKey,
         "maxfeePercent" to config.maxfeePercent,
         "exemptfeeMsat" to config.exemptfeeMsat,
+        "paymentRetries" to config.paymentRetries,
+        "remoteLockLeaseSecs" to config.remoteLockLeaseSecs,
+        "remoteLockRetrySecs" to config.remoteLockRetrySecs,
+        "connectTimeoutSecs" to config.connectTimeoutSecs,
+        "anchorChannelsEnabled" to config.anchorChannelsEnabled,
+        "allowStateRegression" to config.allowStateRegression,
+        "minSatPerVbyte" to config.minSatPerVbyte,
+        "maxSatPerVbyte" to config.maxSatPerVbyte,
+        "syncRetries" to config.syncRetries,
+        "maxJitChannels" to config.maxJitChannels,
+        "sweepConfirmationTarget" to config.sweepConfirmationTarget,
+        "maxStoreValueBytes" to config.maxStoreValueBytes,
+        "vssClientId" to config.vssClientId,
+        "restrictFilePermissions" to config.restrictFilePermissions,
     )
 
 fun asConfigList(arr: ReadableArray): List<Config> {
@@ -912,6 +976,16 @@ fun asLnPaymentDetails(lnPaymentDetails: ReadableMap): LnPaymentDetails? {
         } else {
             null
         }
+    val customTlvs =
+        if (hasNonNullKey(lnPaymentDetails, "customTlvs")) {
+            lnPaymentDetails.getArray("customTlvs")?.let {
+                asTlvEntryList(it)
+            }
+        } else {
+            null
+        }
+    val openChannelBolt11 =
+        if (hasNonNullKey(lnPaymentDetails, "openChannelBolt11")) lnPaymentDetails.getString("openChannelBolt11") else null
     return LnPaymentDetails(
         paymentHash,
         destinationPubkey,
@@ -926,6 +1000,8 @@ fun asLnPaymentDetails(lnPaymentDetails: ReadableMap): LnPaymentDetails? {
         lnurlWithdrawEndpoint,
         swapInfo,
         reverseSwapInfo,
+        customTlvs,
+        openChannelBolt11,
     )
 }
 
@@ -944,6 +1020,8 @@ fun readableMapOf(lnPaymentDetails: LnPaymentDetails): ReadableMap =
         "lnurlWithdrawEndpoint" to lnPaymentDetails.lnurlWithdrawEndpoint,
         "swapInfo" to lnPaymentDetails.swapInfo?.let { readableMapOf(it) },
         "reverseSwapInfo" to lnPaymentDetails.reverseSwapInfo?.let { readableMapOf(it) },
+        "customTlvs" to lnPaymentDetails.customTlvs?.let { readableArrayOf(it) },
+        "openChannelBolt11" to lnPaymentDetails.openChannelBolt11,
     )
 
 fun asLnPaymentDetailsList(arr: ReadableArray): List<LnPaymentDetails> {
@@ -1430,6 +1508,9 @@ fun asLspInformation(lspInformation: ReadableMap): LspInformation? {
                 "minHtlcMsat",
                 "lspPubkey",
                 "openingFeeParamsList",
+                "channelOverProvisioningPpm",
+                "minChannelLifetime",
+                "maxClientToSelfDelay",
             ),
         )
     ) {
@@ -1446,6 +1527,9 @@ fun asLspInformation(lspInformation: ReadableMap): LspInformation? {
     val minHtlcMsat = lspInformation.getDouble("minHtlcMsat").toLong()
     val lspPubkey = lspInformation.getArray("lspPubkey")?.let { asUByteList(it) }!!
     val openingFeeParamsList = lspInformation.getMap("openingFeeParamsList")?.let { asOpeningFeeParamsMenu(it) }!!
+    val channelOverProvisioningPpm = lspInformation.getInt("channelOverProvisioningPpm").toUInt()
+    val minChannelLifetime = lspInformation.getInt("minChannelLifetime").toUInt()
+    val maxClientToSelfDelay = lspInformation.getInt("maxClientToSelfDelay").toUInt()
     return LspInformation(
         id,
         name,
@@ -1458,6 +1542,9 @@ fun asLspInformation(lspInformation: ReadableMap): LspInformation? {
         minHtlcMsat,
         lspPubkey,
         openingFeeParamsList,
+        channelOverProvisioningPpm,
+        minChannelLifetime,
+        maxClientToSelfDelay,
     )
 }
 
@@ -1474,6 +1561,9 @@ fun readableMapOf(lspInformation: LspInformation): ReadableMap =
         "minHtlcMsat" to lspInformation.minHtlcMsat,
         "lspPubkey" to readableArrayOf(lspInformation.lspPubkey),
         "openingFeeParamsList" to readableMapOf(lspInformation.openingFeeParamsList),
+        "channelOverProvisioningPpm" to lspInformation.channelOverProvisioningPpm,
+        "minChannelLifetime" to lspInformation.minChannelLifetime,
+        "maxClientToSelfDelay" to lspInformation.maxClientToSelfDelay,
     )
 
 fun asLspInformationList(arr: ReadableArray): List<LspInformation> {
@@ -1618,6 +1708,7 @@ fun asNodeState(nodeState: ReadableMap): NodeState? {
     val connectedPeers = nodeState.getArray("connectedPeers")?.let { asStringList(it) }!!
     val maxReceivableSinglePaymentAmountMsat = nodeState.getDouble("maxReceivableSinglePaymentAmountMsat").toULong()
     val totalInboundLiquidityMsats = nodeState.getDouble("totalInboundLiquidityMsats").toULong()
+    val syncWarning = if (hasNonNullKey(nodeState, "syncWarning")) nodeState.getString("syncWarning") else null
     return NodeState(
         id,
         blockHeight,
@@ -1632,6 +1723,7 @@ fun asNodeState(nodeState: ReadableMap): NodeState? {
         connectedPeers,
         maxReceivableSinglePaymentAmountMsat,
         totalInboundLiquidityMsats,
+        syncWarning,
     )
 }
 
@@ -1650,6 +1742,7 @@ fun readableMapOf(nodeState: NodeState): ReadableMap =
         "connectedPeers" to readableArrayOf(nodeState.connectedPeers),
         "maxReceivableSinglePaymentAmountMsat" to nodeState.maxReceivableSinglePaymentAmountMsat,
         "totalInboundLiquidityMsats" to nodeState.totalInboundLiquidityMsats,
+        "syncWarning" to nodeState.syncWarning,
     )
 
 fun asNodeStateList(arr: ReadableArray): List<NodeState> {
@@ -2335,7 +2428,48 @@ fun asReceivePaymentRequest(receivePaymentRequest: ReadableMap): ReceivePaymentR
         }
     val expiry = if (hasNonNullKey(receivePaymentRequest, "expiry")) receivePaymentRequest.getInt("expiry").toUInt() else null
     val cltv = if (hasNonNullKey(receivePaymentRequest, "cltv")) receivePaymentRequest.getInt("cltv").toUInt() else null
-    return ReceivePaymentRequest(amountMsat, description, preimage, openingFeeParams, useDescriptionHash, expiry, cltv)
+    val registerNotifications =
+        if (hasNonNullKey(
+                receivePaymentRequest,
+                "registerNotifications",
+            )
+        ) {
+            receivePaymentRequest.getBoolean("registerNotifications")
+        } else {
+            null
+        }
+    val truncateDescription =
+        if (hasNonNullKey(
+                receivePaymentRequest,
+                "truncateDescription",
+            )
+        ) {
+            receivePaymentRequest.getBoolean("truncateDescription")
+        } else {
+            null
+        }
+    val noOpenChannel =
+        if (hasNonNullKey(
+                receivePaymentRequest,
+                "noOpenChannel",
+            )
+        ) {
+            receivePaymentRequest.getBoolean("noOpenChannel")
+        } else {
+            null
+        }
+    return ReceivePaymentRequest(
+        amountMsat,
+        description,
+        preimage,
+        openingFeeParams,
+        useDescriptionHash,
+        expiry,
+        cltv,
+        registerNotifications,
+        truncateDescription,
+        noOpenChannel,
+    )
 }
 
 fun readableMapOf(receivePaymentRequest: ReceivePaymentRequest): ReadableMap =
@@ -2347,6 +2481,9 @@ fun readableMapOf(receivePaymentRequest: ReceivePaymentRequest): ReadableMap =
         "useDescriptionHash" to receivePaymentRequest.useDescriptionHash,
         "expiry" to receivePaymentRequest.expiry,
         "cltv" to receivePaymentRequest.cltv,
+        "registerNotifications" to receivePaymentRequest.registerNotifications,
+        "truncateDescription" to receivePaymentRequest.truncateDescription,
+        "noOpenChannel" to receivePaymentRequest.noOpenChannel,
     )
 
 fun asReceivePaymentRequestList(arr: ReadableArray): List<ReceivePaymentRequest> {
@@ -2853,13 +2990,15 @@ fun asSendPaymentRequest(sendPaymentRequest: ReadableMap): SendPaymentRequest? {
     }
     val bolt11 = sendPaymentRequest.getString("bolt11")!!
     val amountMsat = if (hasNonNullKey(sendPaymentRequest, "amountMsat")) sendPaymentRequest.getDouble("amountMsat").toULong() else null
-    return SendPaymentRequest(bolt11, amountMsat)
+    val maxFeeMsat = if (hasNonNullKey(sendPaymentRequest, "maxFeeMsat")) sendPaymentRequest.getDouble("maxFeeMsat").toULong() else null
+    return SendPaymentRequest(bolt11, amountMsat, maxFeeMsat)
 }
 
 fun readableMapOf(sendPaymentRequest: SendPaymentRequest): ReadableMap =
     readableMapOf(
         "bolt11" to sendPaymentRequest.bolt11,
         "amountMsat" to sendPaymentRequest.amountMsat,
+        "maxFeeMsat" to sendPaymentRequest.maxFeeMsat,
     )
 
 fun asSendPaymentRequestList(arr: ReadableArray): List<SendPaymentRequest> {
@@ -2928,7 +3067,8 @@ fun asSendSpontaneousPaymentRequest(sendSpontaneousPaymentRequest: ReadableMap):
         } else {
             null
         }
-    return SendSpontaneousPaymentRequest(nodeId, amountMsat, extraTlvs)
+    val comment = if (hasNonNullKey(sendSpontaneousPaymentRequest, "comment")) sendSpontaneousPaymentRequest.getString("comment") else null
+    return SendSpontaneousPaymentRequest(nodeId, amountMsat, extraTlvs, comment)
 }
 
 fun readableMapOf(sendSpontaneousPaymentRequest: SendSpontaneousPaymentRequest): ReadableMap =
@@ -2936,6 +3076,7 @@ fun readableMapOf(sendSpontaneousPaymentRequest: SendSpontaneousPaymentRequest):
         "nodeId" to sendSpontaneousPaymentRequest.nodeId,
         "amountMsat" to sendSpontaneousPaymentRequest.amountMsat,
         "extraTlvs" to sendSpontaneousPaymentRequest.extraTlvs?.let { readableArrayOf(it) },
+        "comment" to sendSpontaneousPaymentRequest.comment,
     )
 
 fun asSendSpontaneousPaymentRequestList(arr: ReadableArray): List<SendSpontaneousPaymentRequest> {
@@ -3502,6 +3643,51 @@ fun asBreezEvent(breezEvent: ReadableMap): BreezEvent? {
         val details = breezEvent.getMap("details")?.let { asSwapInfo(it) }!!
         return BreezEvent.SwapUpdated(details)
     }
+    if (type == "remoteLockAcquired") {
+        return BreezEvent.RemoteLockAcquired
+    }
+    if (type == "remoteLockRefreshFailed") {
+        return BreezEvent.RemoteLockRefreshFailed
+    }
+    if (type == "remoteLockLost") {
+        return BreezEvent.RemoteLockLost
+    }
+    if (type == "splicePending") {
+        val channelId = breezEvent.getString("channelId")!!
+        val amountSat = breezEvent.getDouble("amountSat").toLong()
+        val fundingTxid = breezEvent.getString("fundingTxid")!!
+        return BreezEvent.SplicePending(channelId, amountSat, fundingTxid)
+    }
+    if (type == "spliceCompleted") {
+        val channelId = breezEvent.getString("channelId")!!
+        val amountSat = breezEvent.getDouble("amountSat").toLong()
+        return BreezEvent.SpliceCompleted(channelId, amountSat)
+    }
+    if (type == "spliceFailed") {
+        val channelId = breezEvent.getString("channelId")!!
+        val amountSat = breezEvent.getDouble("amountSat").toLong()
+        val reason = breezEvent.getString("reason")!!
+        return BreezEvent.SpliceFailed(channelId, amountSat, reason)
+    }
+    if (type == "restoreDownloadStarted") {
+        return BreezEvent.RestoreDownloadStarted
+    }
+    if (type == "restoreDownloadCompleted") {
+        return BreezEvent.RestoreDownloadCompleted
+    }
+    if (type == "restoreChainSyncStarted") {
+        return BreezEvent.RestoreChainSyncStarted
+    }
+    if (type == "restoreChainSyncCompleted") {
+        return BreezEvent.RestoreChainSyncCompleted
+    }
+    if (type == "chainReorg") {
+        val oldTipHeight = breezEvent.getInt("oldTipHeight").toUInt()
+        val oldTipHash = breezEvent.getString("oldTipHash")!!
+        val newTipHeight = breezEvent.getInt("newTipHeight").toUInt()
+        val newTipHash = breezEvent.getString("newTipHash")!!
+        return BreezEvent.ChainReorg(oldTipHeight, oldTipHash, newTipHeight, newTipHash)
+    }
     return null
 }
 
@@ -3554,6 +3740,62 @@ fun readableMapOf(breezEvent: BreezEvent): ReadableMap? {
             pushToMap(map, "type", "swapUpdated")
             pushToMap(map, "details", readableMapOf(breezEvent.details))
         }
+
+        is BreezEvent.RemoteLockAcquired -> {
+            pushToMap(map, "type", "remoteLockAcquired")
+        }
+
+        is BreezEvent.RemoteLockRefreshFailed -> {
+            pushToMap(map, "type", "remoteLockRefreshFailed")
+        }
+
+        is BreezEvent.RemoteLockLost -> {
+            pushToMap(map, "type", "remoteLockLost")
+        }
+
+        is BreezEvent.SplicePending -> {
+            pushToMap(map, "type", "splicePending")
+            pushToMap(map, "channelId", breezEvent.channelId)
+            pushToMap(map, "amountSat", breezEvent.amountSat)
+            pushToMap(map, "fundingTxid", breezEvent.fundingTxid)
+        }
+
+        is BreezEvent.SpliceCompleted -> {
+            pushToMap(map, "type", "spliceCompleted")
+            pushToMap(map, "channelId", breezEvent.channelId)
+            pushToMap(map, "amountSat", breezEvent.amountSat)
+        }
+
+        is BreezEvent.SpliceFailed -> {
+            pushToMap(map, "type", "spliceFailed")
+            pushToMap(map, "channelId", breezEvent.channelId)
+            pushToMap(map, "amountSat", breezEvent.amountSat)
+            pushToMap(map, "reason", breezEvent.reason)
+        }
+
+        is BreezEvent.RestoreDownloadStarted -> {
+            pushToMap(map, "type", "restoreDownloadStarted")
+        }
+
+        is BreezEvent.RestoreDownloadCompleted -> {
+            pushToMap(map, "type", "restoreDownloadCompleted")
+        }
+
+        is BreezEvent.RestoreChainSyncStarted -> {
+            pushToMap(map, "type", "restoreChainSyncStarted")
+        }
+
+        is BreezEvent.RestoreChainSyncCompleted -> {
+            pushToMap(map, "type", "restoreChainSyncCompleted")
+        }
+
+        is BreezEvent.ChainReorg -> {
+            pushToMap(map, "type", "chainReorg")
+            pushToMap(map, "oldTipHeight", breezEvent.oldTipHeight)
+            pushToMap(map, "oldTipHash", breezEvent.oldTipHash)
+            pushToMap(map, "newTipHeight", breezEvent.newTipHeight)
+            pushToMap(map, "newTipHash", breezEvent.newTipHash)
+        }
     }
     return map
 }
@@ -345,7 +345,17 @@ enum BreezSDKMapper {
             closingTxid = closingTxidTmp
         }
 
-        return ClosedChannelPaymentDetails(state: state, fundingTxid: fundingTxid, shortChannelId: shortChannelId, closingTxid: closingTxid)
+        guard let counterpartyNodeId = closedChannelPaymentDetails["counterpartyNodeId"] as? String else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "counterpartyNodeId", typeName: "ClosedChannelPaymentDetails"))
+        }
+        guard let capacitySat = closedChannelPaymentDetails["capacitySat"] as? UInt64 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "capacitySat", typeName: "ClosedChannelPaymentDetails"))
+        }
+        guard let isForceClose = closedChannelPaymentDetails["isForceClose"] as? Bool else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "isForceClose", typeName: "ClosedChannelPaymentDetails"))
+        }
+
+        return ClosedChannelPaymentDetails(state: state, fundingTxid: fundingTxid, shortChannelId: shortChannelId, closingTxid: closingTxid, counterpartyNodeId: counterpartyNodeId, capacitySat: capacitySat, isForceClose: isForceClose)
     }
 
     static func dictionaryOf(closedChannelPaymentDetails: ClosedChannelPaymentDetails) -> [String: Any?] {
@@ -354,6 +364,9 @@ enum BreezSDKMapper {
             "fundingTxid": closedChannelPaymentDetails.fundingTxid,
             "shortChannelId": closedChannelPaymentDetails.shortChannelId == nil ? nil : closedChannelPaymentDetails.shortChannelId,
             "closingTxid": closedChannelPaymentDetails.closingTxid == nil ? nil : closedChannelPaymentDetails.closingTxid,
+            "counterpartyNodeId": closedChannelPaymentDetails.counterpartyNodeId,
+            "capacitySat": closedChannelPaymentDetails.capacitySat,
+            "isForceClose": closedChannelPaymentDetails.isForceClose,
         ]
     }
 
@@ -424,8 +437,54 @@ enum BreezSDKMapper {
         guard let exemptfeeMsat = config["exemptfeeMsat"] as? UInt64 else {
             throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "exemptfeeMsat", typeName: "Config"))
         }
+        guard let paymentRetries = config["paymentRetries"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "paymentRetries", typeName: "Config"))
+        }
+        guard let remoteLockLeaseSecs = config["remoteLockLeaseSecs"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "remoteLockLeaseSecs", typeName: "Config"))
+        }
+        guard let remoteLockRetrySecs = config["remoteLockRetrySecs"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "remoteLockRetrySecs", typeName: "Config"))
+        }
+        guard let connectTimeoutSecs = config["connectTimeoutSecs"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "connectTimeoutSecs", typeName: "Config"))
+        }
+        guard let anchorChannelsEnabled = config["anchorChannelsEnabled"] as? Bool else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "anchorChannelsEnabled", typeName: "Config"))
+        }
+        guard let allowStateRegression = config["allowStateRegression"] as? Bool else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "allowStateRegression", typeName: "Config"))
+        }
+        guard let minSatPerVbyte = config["minSatPerVbyte"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "minSatPerVbyte", typeName: "Config"))
+        }
+        guard let maxSatPerVbyte = config["maxSatPerVbyte"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "maxSatPerVbyte", typeName: "Config"))
+        }
+        guard let syncRetries = config["syncRetries"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "syncRetries", typeName: "Config"))
+        }
+        guard let maxJitChannels = config["maxJitChannels"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "maxJitChannels", typeName: "Config"))
+        }
+        guard let sweepConfirmationTarget = config["sweepConfirmationTarget"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "sweepConfirmationTarget", typeName: "Config"))
+        }
+        guard let maxStoreValueBytes = config["maxStoreValueBytes"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "maxStoreValueBytes", typeName: "Config"))
+        }
+        var vssClientId: String?
+        if hasNonNilKey(data: config, key: "vssClientId") {
+            guard let vssClientIdTmp = config["vssClientId"] as? String else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "vssClientId"))
+            }
+            vssClientId = vssClientIdTmp
+        }
+        guard let restrictFilePermissions = config["restrictFilePermissions"] as? Bool else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "restrictFilePermissions", typeName: "Config"))
+        }
 
-        return Config(breezserver: breezserver, chainnotifierUrl: chainnotifierUrl, mempoolspaceUrl: mempoolspaceUrl, esploraUrl: esploraUrl, vssUrl: vssUrl, rgsUrl: rgsUrl, lsps2Address: lsps2Address, workingDir: workingDir, network: network, paymentTimeoutSec: paymentTimeoutSec, apiKey: apiKey, maxfeePercent: maxfeePercent, exemptfeeMsat: exemptfeeMsat)
+        return Config(breezserver: breezserver, chainnotifierUrl: chainnotifierUrl, mempoolspaceUrl: mempoolspaceUrl, esploraUrl: esploraUrl, vssUrl: vssUrl, rgsUrl: rgsUrl, lsps2Address: lsps2Address, workingDir: workingDir, network: network, paymentTimeoutSec: paymentTimeoutSec, apiKey: apiKey, maxfeePercent: maxfeePercent, exemptfeeMsat: exemptfeeMsat, paymentRetries: paymentRetries, remoteLockLeaseSecs: remoteLockLeaseSecs, remoteLockRetrySecs: remoteLockRetrySecs, connectTimeoutSecs: connectTimeoutSecs, anchorChannelsEnabled: anchorChannelsEnabled, allowStateRegression: allowStateRegression, minSatPerVbyte: minSatPerVbyte, maxSatPerVbyte: maxSatPerVbyte, syncRetries: syncRetries, maxJitChannels: maxJitChannels, sweepConfirmationTarget: sweepConfirmationTarget, maxStoreValueBytes: maxStoreValueBytes, vssClientId: vssClientId, restrictFilePermissions: restrictFilePermissions)
     }
 
     static func dictionaryOf(config: Config) -> [String: Any?] {
@@ -443,6 +502,20 @@ enum BreezSDKMapper {
             "apiKey": config.apiKey == nil ? nil : config.apiKey,
             "maxfeePercent": config.maxfeePercent,
             "exemptfeeMsat": config.exemptfeeMsat,
+            "paymentRetries": config.paymentRetries,
+            "remoteLockLeaseSecs": config.remoteLockLeaseSecs,
+            "remoteLockRetrySecs": config.remoteLockRetrySecs,
+            "connectTimeoutSecs": config.connectTimeoutSecs,
+            "anchorChannelsEnabled": config.anchorChannelsEnabled,
+            "allowStateRegression": config.allowStateRegression,
+            "minSatPerVbyte": config.minSatPerVbyte,
+            "maxSatPerVbyte": config.maxSatPerVbyte,
+            "syncRetries": config.syncRetries,
+            "maxJitChannels": config.maxJitChannels,
+            "sweepConfirmationTarget": config.sweepConfirmationTarget,
+            "maxStoreValueBytes": config.maxStoreValueBytes,
+            "vssClientId": config.vssClientId == nil ? nil : config.vssClientId,
+            "restrictFilePermissions": config.restrictFilePermissions,
         ]
     }
 
@@ -1096,7 +1169,20 @@ enum BreezSDKMapper {
             reverseSwapInfo = try asReverseSwapInfo(reverseSwapInfo: reverseSwapInfoTmp)
         }
 
-        return LnPaymentDetails(paymentHash: paymentHash, destinationPubkey: destinationPubkey, paymentPreimage: paymentPreimage, keysend: keysend, bolt11: bolt11, lnurlSuccessAction: lnurlSuccessAction, lnurlPayDomain: lnurlPayDomain, lnurlPayComment: lnurlPayComment, lnurlMetadata: lnurlMetadata, lnAddress: lnAddress, lnurlWithdrawEndpoint: lnurlWithdrawEndpoint, swapInfo: swapInfo, reverseSwapInfo: reverseSwapInfo)
+        var customTlvs: [TlvEntry]?
+        if let customTlvsTmp = lnPaymentDetails["customTlvs"] as? [[String: Any?]] {
+            customTlvs = try asTlvEntryList(arr: customTlvsTmp)
+        }
+
+        var openChannelBolt11: String?
+        if hasNonNilKey(data: lnPaymentDetails, key: "openChannelBolt11") {
+            guard let openChannelBolt11Tmp = lnPaymentDetails["openChannelBolt11"] as? String else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "openChannelBolt11"))
+            }
+            openChannelBolt11 = openChannelBolt11Tmp
+        }
+
+        return LnPaymentDetails(paymentHash: paymentHash, destinationPubkey: destinationPubkey, paymentPreimage: paymentPreimage, keysend: keysend, bolt11: bolt11, lnurlSuccessAction: lnurlSuccessAction, lnurlPayDomain: lnurlPayDomain, lnurlPayComment: lnurlPayComment, lnurlMetadata: lnurlMetadata, lnAddress: lnAddress, lnurlWithdrawEndpoint: lnurlWithdrawEndpoint, swapInfo: swapInfo, reverseSwapInfo: reverseSwapInfo, customTlvs: customTlvs, openChannelBolt11: openChannelBolt11)
     }
 
     static func dictionaryOf(lnPaymentDetails: LnPaymentDetails) -> [String: Any?] {
@@ -1114,6 +1200,8 @@ enum BreezSDKMapper {
             "lnurlWithdrawEndpoint": lnPaymentDetails.lnurlWithdrawEndpoint == nil ? nil : lnPaymentDetails.lnurlWithdrawEndpoint,
             "swapInfo": lnPaymentDetails.swapInfo == nil ? nil : dictionaryOf(swapInfo: lnPaymentDetails.swapInfo!),
             "reverseSwapInfo": lnPaymentDetails.reverseSwapInfo == nil ? nil : dictionaryOf(reverseSwapInfo: lnPaymentDetails.reverseSwapInfo!),
+            "customTlvs": lnPaymentDetails.customTlvs == nil ? nil : arrayOf(tlvEntryList: lnPaymentDetails.customTlvs!),
+            "openChannelBolt11": lnPaymentDetails.openChannelBolt11 == nil ? nil : lnPaymentDetails.openChannelBolt11,
         ]
     }
 
@@ -1690,8 +1778,17 @@ enum BreezSDKMapper {
             throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "openingFeeParamsList", typeName: "LspInformation"))
         }
         let openingFeeParamsList = try asOpeningFeeParamsMenu(openingFeeParamsMenu: openingFeeParamsListTmp)
+        guard let channelOverProvisioningPpm = lspInformation["channelOverProvisioningPpm"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "channelOverProvisioningPpm", typeName: "LspInformation"))
+        }
+        guard let minChannelLifetime = lspInformation["minChannelLifetime"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "minChannelLifetime", typeName: "LspInformation"))
+        }
+        guard let maxClientToSelfDelay = lspInformation["maxClientToSelfDelay"] as? UInt32 else {
+            throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "maxClientToSelfDelay", typeName: "LspInformation"))
+        }
 
-        return LspInformation(id: id, name: name, widgetUrl: widgetUrl, pubkey: pubkey, host: host, baseFeeMsat: baseFeeMsat, feeRate: feeRate, timeLockDelta: timeLockDelta, minHtlcMsat: minHtlcMsat, lspPubkey: lspPubkey, openingFeeParamsList: openingFeeParamsList)
+        return LspInformation(id: id, name: name, widgetUrl: widgetUrl, pubkey: pubkey, host: host, baseFeeMsat: baseFeeMsat, feeRate: feeRate, timeLockDelta: timeLockDelta, minHtlcMsat: minHtlcMsat, lspPubkey: lspPubkey, openingFeeParamsList: openingFeeParamsList, channelOverProvisioningPpm: channelOverProvisioningPpm, minChannelLifetime: minChannelLifetime, maxClientToSelfDelay: maxClientToSelfDelay)
     }
 
     static func dictionaryOf(lspInformation: LspInformation) -> [String: Any?] {
@@ -1707,6 +1804,9 @@ enum BreezSDKMapper {
             "minHtlcMsat": lspInformation.minHtlcMsat,
             "lspPubkey": lspInformation.lspPubkey,
             "openingFeeParamsList": dictionaryOf(openingFeeParamsMenu: lspInformation.openingFeeParamsList),
+            "channelOverProvisioningPpm": lspInformation.channelOverProvisioningPpm,
+            "minChannelLifetime": lspInformation.minChannelLifetime,
+            "maxClientToSelfDelay": lspInformation.maxClientToSelfDelay,
         ]
     }
 
@@ -1870,8 +1970,15 @@ enum BreezSDKMapper {
         guard let totalInboundLiquidityMsats = nodeState["totalInboundLiquidityMsats"] as? UInt64 else {
             throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "totalInboundLiquidityMsats", typeName: "NodeState"))
         }
+        var syncWarning: String?
+        if hasNonNilKey(data: nodeState, key: "syncWarning") {
+            guard let syncWarningTmp = nodeState["syncWarning"] as? String else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "syncWarning"))
+            }
+            syncWarning = syncWarningTmp
+        }
 
-        return NodeState(id: id, blockHeight: blockHeight, channelsBalanceMsat: channelsBalanceMsat, onchainBalanceMsat: onchainBalanceMsat, pendingOnchainBalanceMsat: pendingOnchainBalanceMsat, utxos: utxos, maxPayableMsat: maxPayableMsat, maxReceivableMsat: maxReceivableMsat, maxSinglePaymentAmountMsat: maxSinglePaymentAmountMsat, maxChanReserveMsats: maxChanReserveMsats, connectedPeers: connectedPeers, maxReceivableSinglePaymentAmountMsat: maxReceivableSinglePaymentAmountMsat, totalInboundLiquidityMsats: totalInboundLiquidityMsats)
+        return NodeState(id: id, blockHeight: blockHeight, channelsBalanceMsat: channelsBalanceMsat, onchainBalanceMsat: onchainBalanceMsat, pendingOnchainBalanceMsat: pendingOnchainBalanceMsat, utxos: utxos, maxPayableMsat: maxPayableMsat, maxReceivableMsat: maxReceivableMsat, maxSinglePaymentAmountMsat: maxSinglePaymentAmountMsat, maxChanReserveMsats: maxChanReserveMsats, connectedPeers: connectedPeers, maxReceivableSinglePaymentAmountMsat: maxReceivableSinglePaymentAmountMsat, totalInboundLiquidityMsats: totalInboundLiquidityMsats, syncWarning: syncWarning)
     }
 
     static func dictionaryOf(nodeState: NodeState) -> [String: Any?] {
@@ -1889,6 +1996,7 @@ enum BreezSDKMapper {
             "connectedPeers": nodeState.connectedPeers,
             "maxReceivableSinglePaymentAmountMsat": nodeState.maxReceivableSinglePaymentAmountMsat,
             "totalInboundLiquidityMsats": nodeState.totalInboundLiquidityMsats,
+            "syncWarning": nodeState.syncWarning == nil ? nil : nodeState.syncWarning,
         ]
     }
 
@@ -2662,8 +2770,29 @@ enum BreezSDKMapper {
             }
             cltv = cltvTmp
         }
+        var registerNotifications: Bool?
+        if hasNonNilKey(data: receivePaymentRequest, key: "registerNotifications") {
+            guard let registerNotificationsTmp = receivePaymentRequest["registerNotifications"] as? Bool else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "registerNotifications"))
+            }
+            registerNotifications = registerNotificationsTmp
+        }
+        var truncateDescription: Bool?
+        if hasNonNilKey(data: receivePaymentRequest, key: "truncateDescription") {
+            guard let truncateDescriptionTmp = receivePaymentRequest["truncateDescription"] as? Bool else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "truncateDescription"))
+            }
+            truncateDescription = truncateDescriptionTmp
+        }
+        var noOpenChannel: Bool?
+        if hasNonNilKey(data: receivePaymentRequest, key: "noOpenChannel") {
+            guard let noOpenChannelTmp = receivePaymentRequest["noOpenChannel"] as? Bool else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "noOpenChannel"))
+            }
+            noOpenChannel = noOpenChannelTmp
+        }
 
-        return ReceivePaymentRequest(amountMsat: amountMsat, description: description, preimage: preimage, openingFeeParams: openingFeeParams, useDescriptionHash: useDescriptionHash, expiry: expiry, cltv: cltv)
+        return ReceivePaymentRequest(amountMsat: amountMsat, description: description, preimage: preimage, openingFeeParams: openingFeeParams, useDescriptionHash: useDescriptionHash, expiry: expiry, cltv: cltv, registerNotifications: registerNotifications, truncateDescription: truncateDescription, noOpenChannel: noOpenChannel)
     }
 
     static func dictionaryOf(receivePaymentRequest: ReceivePaymentRequest) -> [String: Any?] {
@@ -2675,6 +2804,9 @@ enum BreezSDKMapper {
             "useDescriptionHash": receivePaymentRequest.useDescriptionHash == nil ? nil : receivePaymentRequest.useDescriptionHash,
             "expiry": receivePaymentRequest.expiry == nil ? nil : receivePaymentRequest.expiry,
             "cltv": receivePaymentRequest.cltv == nil ? nil : receivePaymentRequest.cltv,
+            "registerNotifications": receivePaymentRequest.registerNotifications == nil ? nil : receivePaymentRequest.registerNotifications,
+            "truncateDescription": receivePaymentRequest.truncateDescription == nil ? nil : receivePaymentRequest.truncateDescription,
+            "noOpenChannel": receivePaymentRequest.noOpenChannel == nil ? nil : receivePaymentRequest.noOpenChannel,
         ]
     }
 
@@ -3240,14 +3372,22 @@ enum BreezSDKMapper {
             }
             amountMsat = amountMsatTmp
         }
+        var maxFeeMsat: UInt64?
+        if hasNonNilKey(data: sendPaymentRequest, key: "maxFeeMsat") {
+            guard let maxFeeMsatTmp = sendPaymentRequest["maxFeeMsat"] as? UInt64 else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "maxFeeMsat"))
+            }
+            maxFeeMsat = maxFeeMsatTmp
+        }
 
-        return SendPaymentRequest(bolt11: bolt11, amountMsat: amountMsat)
+        return SendPaymentRequest(bolt11: bolt11, amountMsat: amountMsat, maxFeeMsat: maxFeeMsat)
     }
 
     static func dictionaryOf(sendPaymentRequest: SendPaymentRequest) -> [String: Any?] {
         return [
             "bolt11": sendPaymentRequest.bolt11,
             "amountMsat": sendPaymentRequest.amountMsat == nil ? nil : sendPaymentRequest.amountMsat,
+            "maxFeeMsat": sendPaymentRequest.maxFeeMsat == nil ? nil : sendPaymentRequest.maxFeeMsat,
         ]
     }
 
@@ -3312,7 +3452,15 @@ enum BreezSDKMapper {
             extraTlvs = try asTlvEntryList(arr: extraTlvsTmp)
         }
 
-        return SendSpontaneousPaymentRequest(nodeId: nodeId, amountMsat: amountMsat, extraTlvs: extraTlvs)
+        var comment: String?
+        if hasNonNilKey(data: sendSpontaneousPaymentRequest, key: "comment") {
+            guard let commentTmp = sendSpontaneousPaymentRequest["comment"] as? String else {
+                throw SdkError.Generic(message: errUnexpectedValue(fieldName: "comment"))
+            }
+            comment = commentTmp
+        }
+
+        return SendSpontaneousPaymentRequest(nodeId: nodeId, amountMsat: amountMsat, extraTlvs: extraTlvs, comment: comment)
     }
 
     static func dictionaryOf(sendSpontaneousPaymentRequest: SendSpontaneousPaymentRequest) -> [String: Any?] {
@@ -3320,6 +3468,7 @@ enum BreezSDKMapper {
             "nodeId": sendSpontaneousPaymentRequest.nodeId,
             "amountMsat": sendSpontaneousPaymentRequest.amountMsat,
             "extraTlvs": sendSpontaneousPaymentRequest.extraTlvs == nil ? nil : arrayOf(tlvEntryList: sendSpontaneousPaymentRequest.extraTlvs!),
+            "comment": sendSpontaneousPaymentRequest.comment == nil ? nil : sendSpontaneousPaymentRequest.comment,
         ]
     }
 
@@ -4000,6 +4149,75 @@ enum BreezSDKMapper {
 
             return BreezEvent.swapUpdated(details: _details)
         }
+        if type == "remoteLockAcquired" {
+            return BreezEvent.remoteLockAcquired
+        }
+        if type == "remoteLockRefreshFailed" {
+            return BreezEvent.remoteLockRefreshFailed
+        }
+        if type == "remoteLockLost" {
+            return BreezEvent.remoteLockLost
+        }
+        if type == "splicePending" {
+            guard let _channelId = breezEvent["channelId"] as? String else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "channelId", typeName: "BreezEvent"))
+            }
+            guard let _amountSat = breezEvent["amountSat"] as? Int64 else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "amountSat", typeName: "BreezEvent"))
+            }
+            guard let _fundingTxid = breezEvent["fundingTxid"] as? String else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "fundingTxid", typeName: "BreezEvent"))
+            }
+            return BreezEvent.splicePending(channelId: _channelId, amountSat: _amountSat, fundingTxid: _fundingTxid)
+        }
+        if type == "spliceCompleted" {
+            guard let _channelId = breezEvent["channelId"] as? String else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "channelId", typeName: "BreezEvent"))
+            }
+            guard let _amountSat = breezEvent["amountSat"] as? Int64 else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "amountSat", typeName: "BreezEvent"))
+            }
+            return BreezEvent.spliceCompleted(channelId: _channelId, amountSat: _amountSat)
+        }
+        if type == "spliceFailed" {
+            guard let _channelId = breezEvent["channelId"] as? String else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "channelId", typeName: "BreezEvent"))
+            }
+            guard let _amountSat = breezEvent["amountSat"] as? Int64 else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "amountSat", typeName: "BreezEvent"))
+            }
+            guard let _reason = breezEvent["reason"] as? String else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "reason", typeName: "BreezEvent"))
+            }
+            return BreezEvent.spliceFailed(channelId: _channelId, amountSat: _amountSat, reason: _reason)
+        }
+        if type == "restoreDownloadStarted" {
+            return BreezEvent.restoreDownloadStarted
+        }
+        if type == "restoreDownloadCompleted" {
+            return BreezEvent.restoreDownloadCompleted
+        }
+        if type == "restoreChainSyncStarted" {
+            return BreezEvent.restoreChainSyncStarted
+        }
+        if type == "restoreChainSyncCompleted" {
+            return BreezEvent.restoreChainSyncCompleted
+        }
+        if type == "chainReorg" {
+            guard let _oldTipHeight = breezEvent["oldTipHeight"] as? UInt32 else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "oldTipHeight", typeName: "BreezEvent"))
+            }
+            guard let _oldTipHash = breezEvent["oldTipHash"] as? String else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "oldTipHash", typeName: "BreezEvent"))
+            }
+            guard let _newTipHeight = breezEvent["newTipHeight"] as? UInt32 else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "newTipHeight", typeName: "BreezEvent"))
+            }
+            guard let _newTipHash = breezEvent["newTipHash"] as? String else {
+                throw SdkError.Generic(message: errMissingMandatoryField(fieldName: "newTipHash", typeName: "BreezEvent"))
+            }
+            return BreezEvent.chainReorg(oldTipHeight: _oldTipHeight, oldTipHash: _oldTipHash, newTipHeight: _newTipHeight, newTipHash: _newTipHash)
+        }
 
         throw SdkError.Generic(message: "Unexpected type \(type) for enum BreezEvent")
     }
@@ -4076,6 +4294,81 @@ enum BreezSDKMapper {
                 "type": "swapUpdated",
                 "details": dictionaryOf(swapInfo: details),
             ]
+
+        case .remoteLockAcquired:
+            return [
+                "type": "remoteLockAcquired",
+            ]
+
+        case .remoteLockRefreshFailed:
+            return [
+                "type": "remoteLockRefreshFailed",
+            ]
+
+        case .remoteLockLost:
+            return [
+                "type": "remoteLockLost",
+            ]
+
+        case let .splicePending(
+            channelId, amountSat, fundingTxid
+        ):
+            return [
+                "type": "splicePending",
+                "channelId": channelId,
+                "amountSat": amountSat,
+                "fundingTxid": fundingTxid,
+            ]
+
+        case let .spliceCompleted(
+            channelId, amountSat
+        ):
+            return [
+                "type": "spliceCompleted",
+                "channelId": channelId,
+                "amountSat": amountSat,
+            ]
+
+        case let .spliceFailed(
+            channelId, amountSat, reason
+        ):
+            return [
+                "type": "spliceFailed",
+                "channelId": channelId,
+                "amountSat": amountSat,
+                "reason": reason,
+            ]
+
+        case .restoreDownloadStarted:
+            return [
+                "type": "restoreDownloadStarted",
+            ]
+
+        case .restoreDownloadCompleted:
+            return [
+                "type": "restoreDownloadCompleted",
+            ]
+
+        case .restoreChainSyncStarted:
+            return [
+                "type": "restoreChainSyncStarted",
+            ]
+
+        case .restoreChainSyncCompleted:
+            return [
+                "type": "restoreChainSyncCompleted",
+            ]
+
+        case let .chainReorg(
+            oldTipHeight, oldTipHash, newTipHeight, newTipHash
+        ):
+            return [
+                "type": "chainReorg",
+                "oldTipHeight": oldTipHeight,
+                "oldTipHash": oldTipHash,
+                "newTipHeight": newTipHeight,
+                "newTipHash": newTipHash,
+            ]
         }
     }
 
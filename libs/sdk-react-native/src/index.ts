@@ -67,6 +67,9 @@ export interface ClosedChannelPaymentDetails {
     fundingTxid: string
     shortChannelId?: string
     closingTxid?: string
+    counterpartyNodeId: string
+    capacitySat: number
+    isForceClose: boolean
 }
 
 export interface Config {
@@ -83,6 +86,20 @@ export interface Config {
     apiKey?: string
     maxfeePercent: number
     exemptfeeMsat: number
+    paymentRetries: number
+    remoteLockLeaseSecs: number
+    remoteLockRetrySecs: number
+    connectTimeoutSecs: number
+    anchorChannelsEnabled: boolean
+    allowStateRegression: boolean
+    minSatPerVbyte: number
+    maxSatPerVbyte: number
+    syncRetries: number
+    maxJitChannels: number
+    sweepConfirmationTarget: number
+    maxStoreValueBytes: number
+    vssClientId?: string
+    restrictFilePermissions: boolean
 }
 
 export interface ConfigureNodeRequest {
@@ -178,6 +195,8 @@ export interface LnPaymentDetails {
     lnurlWithdrawEndpoint?: string
     swapInfo?: SwapInfo
     reverseSwapInfo?: ReverseSwapInfo
+    customTlvs?: TlvEntry[]
+    openChannelBolt11?: string
 }
 
 export interface LnUrlAuthRequestData {
@@ -267,6 +286,9 @@ export interface LspInformation {
     minHtlcMsat: number
     lspPubkey: number[]
     openingFeeParamsList: OpeningFeeParamsMenu
+    channelOverProvisioningPpm: number
+    minChannelLifetime: number
+    maxClientToSelfDelay: number
 }
 
 export interface MessageSuccessActionData {
@@ -297,6 +319,7 @@ export interface NodeState {
     connectedPeers: string[]
     maxReceivableSinglePaymentAmountMsat: number
     totalInboundLiquidityMsats: number
+    syncWarning?: string
 }
 
 export interface OnchainPaymentLimitsResponse {
@@ -411,6 +434,9 @@ export interface ReceivePaymentRequest {
     useDescriptionHash?: boolean
     expiry?: number
     cltv?: number
+    registerNotifications?: boolean
+    truncateDescription?: boolean
+    noOpenChannel?: boolean
 }
 
 export interface ReceivePaymentResponse {
@@ -493,6 +519,7 @@ export interface RouteHintHop {
 export interface SendPaymentRequest {
     bolt11: string
     amountMsat?: number
+    maxFeeMsat?: number
 }
 
 export interface SendPaymentResponse {
@@ -503,6 +530,7 @@ export interface SendSpontaneousPaymentRequest {
     nodeId: string
     amountMsat: number
     extraTlvs?: TlvEntry[]
+    comment?: string
 }
 
 export interface ServiceHealthCheckResponse {
@@ -615,7 +643,18 @@ export enum BreezEventVariant {
     BACKUP_SUCCEEDED = "backupSucceeded",
     BACKUP_FAILED = "backupFailed",
     REVERSE_SWAP_UPDATED = "reverseSwapUpdated",
-    SWAP_UPDATED = "swapUpdated"
+    SWAP_UPDATED = "swapUpdated",
+    REMOTE_LOCK_ACQUIRED = "remoteLockAcquired",
+    REMOTE_LOCK_REFRESH_FAILED = "remoteLockRefreshFailed",
+    REMOTE_LOCK_LOST = "remoteLockLost",
+    SPLICE_PENDING = "splicePending",
+    SPLICE_COMPLETED = "spliceCompleted",
+    SPLICE_FAILED = "spliceFailed",
+    RESTORE_DOWNLOAD_STARTED = "restoreDownloadStarted",
+    RESTORE_DOWNLOAD_COMPLETED = "restoreDownloadCompleted",
+    RESTORE_CHAIN_SYNC_STARTED = "restoreChainSyncStarted",
+    RESTORE_CHAIN_SYNC_COMPLETED = "restoreChainSyncCompleted",
+    CHAIN_REORG = "chainReorg"
 }
 
 export type BreezEvent = {
@@ -645,6 +684,40 @@ export type BreezEvent = {
 } | {
     type: BreezEventVariant.SWAP_UPDATED,
     details: SwapInfo
+} | {
+    type: BreezEventVariant.REMOTE_LOCK_ACQUIRED
+} | {
+    type: BreezEventVariant.REMOTE_LOCK_REFRESH_FAILED
+} | {
+    type: BreezEventVariant.REMOTE_LOCK_LOST
+} | {
+    type: BreezEventVariant.SPLICE_PENDING,
+    channelId: string
+    amountSat: number
+    fundingTxid: string
+} | {
+    type: BreezEventVariant.SPLICE_COMPLETED,
+    channelId: string
+    amountSat: number
+} | {
+    type: BreezEventVariant.SPLICE_FAILED,
+    channelId: string
+    amountSat: number
+    reason: string
+} | {
+    type: BreezEventVariant.RESTORE_DOWNLOAD_STARTED
+} | {
+    type: BreezEventVariant.RESTORE_DOWNLOAD_COMPLETED
+} | {
+    type: BreezEventVariant.RESTORE_CHAIN_SYNC_STARTED
+} | {
+    type: BreezEventVariant.RESTORE_CHAIN_SYNC_COMPLETED
+} | {
+    type: BreezEventVariant.CHAIN_REORG,
+    oldTipHeight: number
+    oldTipHash: string
+    newTipHeight: number
+    newTipHash: string
 }
 
 export enum BuyBitcoinProvider {
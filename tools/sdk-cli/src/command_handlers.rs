@@ -199,6 +199,7 @@ impl CommandHandler {
             Commands::SendPayment {
                 bolt11,
                 amount_msat,
+                max_fee_msat,
             } => {
                 let start = SystemTime::now();
                 let payment = self
@@ -206,6 +207,7 @@ impl CommandHandler {
                     .send_payment(SendPaymentRequest {
                         bolt11,
                         amount_msat,
+                        max_fee_msat,
                     })
                     .await?;
                 let end = SystemTime::now();
@@ -216,6 +218,7 @@ impl CommandHandler {
             Commands::SendSpontaneousPayment {
                 node_id,
                 amount_msat,
+                comment,
             } => {
                 let start = SystemTime::now();
                 let response = self
@@ -224,6 +227,7 @@ impl CommandHandler {
                         node_id,
                         amount_msat,
                         extra_tlvs: None,
+                        comment,
                     })
                     .await?;
                 let end = SystemTime::now();
@@ -34,10 +34,19 @@ pub(crate) enum Commands {
 
         #[clap(name = "amount_msat", short = 'a', long = "amt")]
         amount_msat: Option<u64>,
+
+        #[clap(name = "max_fee_msat", short = 'f', long = "max_fee")]
+        max_fee_msat: Option<u64>,
     },
 
     /// [pay] Send a spontaneous (keysend) payment
-    SendSpontaneousPayment { node_id: String, amount_msat: u64 },
+    SendSpontaneousPayment {
+        node_id: String,
+        amount_msat: u64,
+
+        #[clap(name = "comment", short = 'c', long = "comment")]
+        comment: Option<String>,
+    },
 
     /// [pay] Generate a bolt11 invoice
     ReceivePayment {